@@ -4,7 +4,7 @@ main() {
     local cargo=cross
 
     # all features except those that don't easily work with cross such as font-loading
-    local all_features="--features std,more-image-formats,image-shrinking,rendering,software-rendering,wasm-web,networking"
+    local all_features="--features std,more-image-formats,image-shrinking,rendering,software-rendering,svg-rendering,wasm-web,networking,auto-splitting,server"
 
     if [ "$SKIP_CROSS" = "skip" ]; then
         cargo=cargo
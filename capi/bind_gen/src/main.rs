@@ -19,8 +19,8 @@ use std::path::PathBuf;
 use std::rc::Rc;
 use structopt::StructOpt;
 use syn::{
-    parse_file, FnArg, Item, ItemFn, Lit, Meta, Pat, ReturnType, Signature, Type as SynType,
-    Visibility,
+    parse_file, FnArg, GenericArgument, Item, ItemFn, Lit, Meta, Pat, PathArguments, ReturnType,
+    Signature, Type as SynType, Visibility,
 };
 
 #[derive(StructOpt)]
@@ -106,6 +106,28 @@ fn get_type(ty: &SynType) -> Type {
         SynType::Path(path) => {
             if let Some(segment) = path.path.segments.iter().last() {
                 let mut name = segment.ident.to_string();
+                if name == "Option" {
+                    // Optional function pointers are passed as plain pointers.
+                    // Other optional types have no representation in the
+                    // bindings.
+                    let is_fn_pointer = matches!(
+                        &segment.arguments,
+                        PathArguments::AngleBracketed(args)
+                            if matches!(
+                                args.args.first(),
+                                Some(GenericArgument::Type(SynType::BareFn(_)))
+                            )
+                    );
+                    if !is_fn_pointer {
+                        panic!("Only optional function pointers are supported");
+                    }
+                    return Type {
+                        kind: TypeKind::Ref,
+                        is_custom: false,
+                        is_nullable: false,
+                        name: String::from("u8"),
+                    };
+                }
                 let is_nullable = if let Some(rest) = name.strip_prefix("Nullable") {
                     name = rest.to_string();
                     true
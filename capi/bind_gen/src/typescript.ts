@@ -4,6 +4,8 @@ export type ComponentStateJson =
     { DetailedTimer: DetailedTimerComponentStateJson } |
     { Graph: GraphComponentStateJson } |
     { KeyValue: KeyValueComponentStateJson } |
+    { RunHistory: RunHistoryComponentStateJson } |
+    { SegmentDistribution: SegmentDistributionComponentStateJson } |
     { Separator: null } |
     { Splits: SplitsComponentStateJson } |
     { Text: TextComponentStateJson } |
@@ -143,7 +145,10 @@ export type FontStretch =
 
 /**
  * A Timing Method describes which form of timing is used. This can either be
- * Real Time or Game Time.
+ * Real Time, Game Time or one of the custom timing methods. A Run can give the
+ * custom timing methods names, so they can be used for additional forms of
+ * timing, like an in-game time that needs to be kept apart from a load removed
+ * Real Time.
  */
 export enum TimingMethod {
     /**
@@ -157,6 +162,14 @@ export enum TimingMethod {
      * times removed or some time provided by the game.
      */
     GameTime = 1,
+    /** The first custom timing method. */
+    Custom1 = 2,
+    /** The second custom timing method. */
+    Custom2 = 3,
+    /** The third custom timing method. */
+    Custom3 = 4,
+    /** The fourth custom timing method. */
+    Custom4 = 5,
 }
 
 /**
@@ -343,6 +356,16 @@ export interface SplitStateJson {
      * showing only a subset of segments. Each index is guaranteed to be unique.
      */
     index: number,
+    /**
+     * The color of the name as specified by the Style Rules that apply to
+     * this row. If `null` is specified, the color is taken from the layout.
+     */
+    text_color: Color | null,
+    /**
+     * The background to show behind this row as specified by the Style Rules
+     * that apply to it. If `null` is specified, the usual background is shown.
+     */
+    background: Gradient | null,
 }
 
 /** Describes the state of a single segment's column to visualize. */
@@ -463,6 +486,104 @@ export interface GraphComponentStateJson {
     height: number,
 }
 
+/**
+ * The state object describes the information to visualize for this component.
+ * All the coordinates are in the range 0..1, with the slower times at the top.
+ */
+export interface RunHistoryComponentStateJson {
+    /** The background shown behind the component. */
+    background: Gradient,
+    /** All the attempts to visualize as points. */
+    attempts: RunHistoryComponentStateAttemptJson[],
+    /**
+     * The points of the line showing the progression of the Personal Best.
+     * Connect all of them to visualize the line.
+     */
+    personal_best_line: RunHistoryComponentStatePointJson[],
+    /**
+     * The points of the line showing the progression of the Sum of Best
+     * Segments. Connect all of them to visualize the line. This is empty if the
+     * progression is not supposed to be shown.
+     */
+    sum_of_best_line: RunHistoryComponentStatePointJson[],
+    /** Contains the y coordinates of all the horizontal grid lines. */
+    horizontal_grid_lines: number[],
+    /** The color of the grid lines on the graph. */
+    grid_lines_color: Color,
+    /** The color of the points of the finished attempts. */
+    finished_color: Color,
+    /** The color of the points of the attempts that got reset. */
+    reset_color: Color,
+    /**
+     * The color of the line showing the progression of the Personal Best and of
+     * the points of the attempts that achieved a new Personal Best.
+     */
+    personal_best_color: Color,
+    /**
+     * The color of the line showing the progression of the Sum of Best
+     * Segments.
+     */
+    sum_of_best_color: Color,
+    /** The height of the graph. */
+    height: number,
+}
+
+/** Describes an attempt to visualize as a point. */
+export interface RunHistoryComponentStateAttemptJson {
+    /** The x coordinate of the point. */
+    x: number,
+    /** The y coordinate of the point. */
+    y: number,
+    /**
+     * Describes whether the attempt was finished. Otherwise the attempt got
+     * reset and the point visualizes the split time it reached.
+     */
+    is_finished: boolean,
+    /** Describes whether the attempt achieved a new Personal Best. */
+    is_personal_best: boolean,
+}
+
+/** Describes a point of one of the lines to visualize. */
+export interface RunHistoryComponentStatePointJson {
+    /** The x coordinate of the point. */
+    x: number,
+    /** The y coordinate of the point. */
+    y: number,
+}
+
+/**
+ * The state object describes the information to visualize for this component.
+ * All the coordinates are in the range 0..1, with the faster segment times on
+ * the left.
+ */
+export interface SegmentDistributionComponentStateJson {
+    /** The background shown behind the component. */
+    background: Gradient,
+    /**
+     * The heights of all the bars of the histogram from left to right. The
+     * highest bar has a height of 1. This is empty if the segment has no
+     * segment history.
+     */
+    bars: number[],
+    /**
+     * The x coordinate of the live segment time. If the live segment time is
+     * outside the range of the histogram, the marker is placed at the edge of
+     * the histogram. This is null if there is no live segment time.
+     */
+    marker: number | null,
+    /**
+     * The share of the weighted segment times that are faster than the live
+     * segment time. This is null if there is no live segment time.
+     */
+    marker_percentile: number | null,
+    /** The color of the bars of the histogram. */
+    bar_color: Color,
+    /** The color of the marker that shows where the live segment time lands. */
+    marker_color: Color,
+    /** The height of the histogram. */
+    height: number,
+}
+
 /** Describes a point on the graph to visualize. */
 export interface GraphComponentStatePointJson {
     /** The x coordinate of the point. */
@@ -627,8 +748,34 @@ export type SettingsDescriptionValueJson =
     { Hotkey: string } |
     { LayoutDirection: LayoutDirection } |
     { Font: Font | null } |
+    { Statistic: Statistic } |
+    { HistoryAxis: HistoryAxis } |
+    { StyleCondition: StyleCondition } |
     { CustomCombobox: CustomCombobox };
 
+/** Describes which statistic the Statistic Component shows. */
+export type Statistic =
+    "Mean" |
+    "Median" |
+    "StandardDeviation" |
+    "Consistency" |
+    "ResetRate" |
+    "TimeLostVsGold" |
+    "Trend";
+
+/** Describes what the horizontal axis of the Run History Component is based on. */
+export type HistoryAxis = "AttemptIndex" | "Date";
+
+/**
+ * The condition a row of the Splits Component needs to meet for a Style Rule
+ * to be applied. The threshold of `DeltaAbove` is specified in seconds and the
+ * pattern of `NameMatches` is a regular expression.
+ */
+export type StyleCondition =
+    { DeltaAbove: string } |
+    "BestSegment" |
+    { NameMatches: string };
+
 /** Describes the direction the components of a layout are laid out in. */
 export type LayoutDirection = "Vertical" | "Horizontal";
 
@@ -650,7 +797,12 @@ export type ColumnStartWith =
     "Empty" |
     "ComparisonTime" |
     "ComparisonSegmentTime" |
-    "PossibleTimeSave";
+    "PossibleTimeSave" |
+    "BestPossibleTimeSave" |
+    "AverageSegmentTime" |
+    "MedianSegmentTime" |
+    "ResetPercentage" |
+    "GoldCount";
 
 /**
  * Once a certain condition is met, which is usually being on the split or
@@ -664,7 +816,8 @@ export type ColumnUpdateWith =
     "DeltaWithFallback" |
     "SegmentTime" |
     "SegmentDelta" |
-    "SegmentDeltaWithFallback";
+    "SegmentDeltaWithFallback" |
+    "PossibleTimeSave";
 
 /** Specifies when a column's value gets updated. */
 export type ColumnUpdateTrigger =
@@ -682,7 +835,13 @@ export type AccuracyJson = "Seconds" | "Tenths" | "Hundredths" | "Milliseconds";
  * A Timing Method describes which form of timing is used. This can either be
  * Real Time or Game Time.
  */
-export type TimingMethodJson = "RealTime" | "GameTime";
+export type TimingMethodJson =
+    "RealTime" |
+    "GameTime" |
+    "Custom1" |
+    "Custom2" |
+    "Custom3" |
+    "Custom4";
 
 /**
  * A Digits Format describes how many digits of a time to always shown. The
@@ -726,6 +885,11 @@ export interface RunEditorStateJson {
      * edited.
      */
     timing_method: TimingMethodJson,
+    /**
+     * The names of the custom timing methods, ordered by their custom index.
+     * The name is empty if the custom timing method is not in use.
+     */
+    custom_timing_method_names: string[],
     /** The state of all the segments. */
     segments: RunEditorRowJson[],
     /** The names of all the custom comparisons that exist for this Run. */
@@ -765,6 +929,12 @@ export interface RunMetadataJson {
      * specified.
      */
     region_name: string,
+    /**
+     * The frame rate the game runs at. If it is specified, the times of the
+     * run are meant to be entered, shown and rounded as frames at this frame
+     * rate.
+     */
+    frame_rate: number | null,
     /**
      * Stores all the speedrun.com variables. A variable is an arbitrary key
      * value pair storing additional information about the category. An example
@@ -826,6 +996,11 @@ export interface RunEditorButtonsJson {
      * moved.
      */
     can_move_down: boolean,
+    /**
+     * Describes whether the currently selected segments can be merged into a
+     * single segment. This requires at least two segments to be selected.
+     */
+    can_merge: boolean,
 }
 
 /** Describes the current state of a segment. */
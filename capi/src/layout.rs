@@ -4,9 +4,10 @@
 use super::{get_file, output_vec, release_file, str, Json};
 use crate::component::OwnedComponent;
 use crate::layout_state::OwnedLayoutState;
-use livesplit_core::layout::{parser, LayoutSettings, LayoutState};
+use livesplit_core::layout::{parser, saver, LayoutSettings, LayoutState};
 use livesplit_core::{Layout, Timer};
 use std::io::{BufReader, Cursor};
+use std::os::raw::c_char;
 use std::slice;
 
 /// type
@@ -130,6 +131,16 @@ pub extern "C" fn Layout_settings_as_json(this: &Layout) -> Json {
     })
 }
 
+/// Saves the layout as a layout file of the original LiveSplit (*.lsl). Not
+/// everything can be represented by the original LiveSplit, so some settings
+/// may be lost.
+#[no_mangle]
+pub extern "C" fn Layout_save_as_lsl(this: &Layout) -> *const c_char {
+    output_vec(|o| {
+        saver::livesplit::save_layout(this, o).unwrap();
+    })
+}
+
 /// Adds a new component to the end of the layout.
 #[no_mangle]
 pub extern "C" fn Layout_push(this: &mut Layout, component: OwnedComponent) {
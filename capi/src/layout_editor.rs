@@ -83,58 +83,91 @@ pub extern "C" fn LayoutEditor_update_layout_state_as_json(
 }
 
 /// Selects the component with the given index in order to modify its
-/// settings. Only a single component is selected at any given time. You may
-/// not provide an invalid index.
+/// settings. All other components are unselected. The component chosen also
+/// becomes the active component. You may not provide an invalid index.
 #[no_mangle]
 pub extern "C" fn LayoutEditor_select(this: &mut LayoutEditor, index: usize) {
     this.select(index);
 }
 
+/// In addition to the components that are already selected, the component
+/// with the given index is being selected. The component chosen also becomes
+/// the active component, whose settings are shown. You may not provide an
+/// invalid index.
+#[no_mangle]
+pub extern "C" fn LayoutEditor_select_additionally(this: &mut LayoutEditor, index: usize) {
+    this.select_additionally(index);
+}
+
+/// Select all components from the currently active component to the component
+/// at the index provided. The component at the index provided becomes the new
+/// active component. You may not provide an invalid index.
+#[no_mangle]
+pub extern "C" fn LayoutEditor_select_range(this: &mut LayoutEditor, index: usize) {
+    this.select_range(index);
+}
+
+/// Unselects the component with the given index. If it's not selected or the
+/// index is out of bounds, nothing happens. The component is not unselected,
+/// when it is the only component that is selected. If the active component is
+/// unselected, the most recently selected component remaining becomes the
+/// active component.
+#[no_mangle]
+pub extern "C" fn LayoutEditor_unselect(this: &mut LayoutEditor, index: usize) {
+    this.unselect(index);
+}
+
 /// Adds the component provided to the end of the layout. The newly added
-/// component becomes the selected component.
+/// component becomes the only selected component.
 #[no_mangle]
 pub extern "C" fn LayoutEditor_add_component(this: &mut LayoutEditor, component: OwnedComponent) {
     this.add_component(*component);
 }
 
-/// Removes the currently selected component, unless there's only one
-/// component in the layout. The next component becomes the selected
-/// component. If there's none, the previous component becomes the selected
-/// component instead.
+/// Removes all the selected components, unless all of them are selected. The
+/// next not-to-be-removed component after the active component becomes the
+/// only selected component. If there's none, the previous not-to-be-removed
+/// component becomes the selected component instead.
 #[no_mangle]
 pub extern "C" fn LayoutEditor_remove_component(this: &mut LayoutEditor) {
     this.remove_component();
 }
 
-/// Moves the selected component up, unless the first component is selected.
+/// Moves all the selected components up, unless the first component is
+/// selected. The active component stays the active component.
 #[no_mangle]
 pub extern "C" fn LayoutEditor_move_component_up(this: &mut LayoutEditor) {
     this.move_component_up();
 }
 
-/// Moves the selected component down, unless the last component is
-/// selected.
+/// Moves all the selected components down, unless the last component is
+/// selected. The active component stays the active component.
 #[no_mangle]
 pub extern "C" fn LayoutEditor_move_component_down(this: &mut LayoutEditor) {
     this.move_component_down();
 }
 
-/// Moves the selected component to the index provided. You may not provide
-/// an invalid index.
+/// Moves the selected components, so that the active component ends up at the
+/// index provided. All the other selected components are moved by the same
+/// amount. If they can't be moved that far, they are moved as far as
+/// possible. You may not provide an invalid index.
 #[no_mangle]
 pub extern "C" fn LayoutEditor_move_component(this: &mut LayoutEditor, dst_index: usize) {
     this.move_component(dst_index);
 }
 
-/// Duplicates the currently selected component. The copy gets placed right
-/// after the selected component and becomes the newly selected component.
+/// Duplicates all the selected components. Each copy gets placed right after
+/// the component it is a copy of. The copies become the newly selected
+/// components.
 #[no_mangle]
 pub extern "C" fn LayoutEditor_duplicate_component(this: &mut LayoutEditor) {
     this.duplicate_component();
 }
 
-/// Sets a setting's value of the selected component by its setting index
-/// to the given value.
+/// Sets a setting's value of the active component by its setting index to
+/// the given value. The setting is also changed for all the other selected
+/// components that have a setting with the same name and the same type of
+/// value.
 ///
 /// This panics if the type of the value to be set is not compatible with
 /// the type of the setting's value. A panic can also occur if the index of
@@ -162,3 +195,31 @@ pub extern "C" fn LayoutEditor_set_general_settings_value(
 ) {
     this.set_general_settings_value(index, *value);
 }
+
+/// Checks if there is an action that can be undone.
+#[no_mangle]
+pub extern "C" fn LayoutEditor_can_undo(this: &LayoutEditor) -> bool {
+    this.can_undo()
+}
+
+/// Checks if there is an action that can be redone.
+#[no_mangle]
+pub extern "C" fn LayoutEditor_can_redo(this: &LayoutEditor) -> bool {
+    this.can_redo()
+}
+
+/// Undoes the most recent action. The Layout and the component selection are
+/// restored to the state before the action was applied. Returns <FALSE> if
+/// there is nothing to undo.
+#[no_mangle]
+pub extern "C" fn LayoutEditor_undo(this: &mut LayoutEditor) -> bool {
+    this.undo()
+}
+
+/// Redoes the most recently undone action. Any new action that is applied
+/// after undoing an action discards all the actions that could be redone.
+/// Returns <FALSE> if there is nothing to redo.
+#[no_mangle]
+pub extern "C" fn LayoutEditor_redo(this: &mut LayoutEditor) -> bool {
+    this.redo()
+}
@@ -32,25 +32,39 @@ pub extern "C" fn LayoutEditorState_component_text(
 ///
 /// The bits are as follows:
 ///
-/// * `0x04` - Can remove the current component
-/// * `0x02` - Can move the current component up
-/// * `0x01` - Can move the current component down
+/// * `0x10` - Can undo the most recent action
+/// * `0x08` - Can redo the most recently undone action
+/// * `0x04` - Can remove the selected components
+/// * `0x02` - Can move the selected components up
+/// * `0x01` - Can move the selected components down
 #[no_mangle]
 pub extern "C" fn LayoutEditorState_buttons(this: &LayoutEditorState) -> u8 {
-    (this.buttons.can_remove as u8) << 2
+    (this.buttons.can_undo as u8) << 4
+        | (this.buttons.can_redo as u8) << 3
+        | (this.buttons.can_remove as u8) << 2
         | (this.buttons.can_move_up as u8) << 1
         | this.buttons.can_move_down as u8
 }
 
-/// Returns the index of the currently selected component.
+/// Returns the index of the active component. This is the most recently
+/// selected component, whose settings are shown.
 #[no_mangle]
 pub extern "C" fn LayoutEditorState_selected_component(this: &LayoutEditorState) -> u32 {
     this.selected_component
 }
 
+/// Returns whether the component at the specified index is selected.
+#[no_mangle]
+pub extern "C" fn LayoutEditorState_is_component_selected(
+    this: &LayoutEditorState,
+    index: u32,
+) -> bool {
+    this.selected_components.contains(&index)
+}
+
 /// Returns the number of fields in the layout's settings.
 ///
-/// Set `component_settings` to true to use the selected component's settings instead.
+/// Set `component_settings` to true to use the active component's settings instead.
 #[no_mangle]
 pub extern "C" fn LayoutEditorState_field_len(
     this: &LayoutEditorState,
@@ -65,7 +79,7 @@ pub extern "C" fn LayoutEditorState_field_len(
 
 /// Returns the name of the layout's setting at the specified index.
 ///
-/// Set `component_settings` to true to use the selected component's settings instead.
+/// Set `component_settings` to true to use the active component's settings instead.
 #[no_mangle]
 pub extern "C" fn LayoutEditorState_field_text(
     this: &LayoutEditorState,
@@ -81,7 +95,7 @@ pub extern "C" fn LayoutEditorState_field_text(
 
 /// Returns the value of the layout's setting at the specified index.
 ///
-/// Set `component_settings` to true to use the selected component's settings instead.
+/// Set `component_settings` to true to use the active component's settings instead.
 #[no_mangle]
 pub extern "C" fn LayoutEditorState_field_value(
     this: &LayoutEditorState,
@@ -10,9 +10,11 @@ use crate::{output_vec, Json};
 use livesplit_core::component::{
     blank_space::State as BlankSpaceComponentState,
     detailed_timer::State as DetailedTimerComponentState, graph::State as GraphComponentState,
-    key_value::State as KeyValueComponentState, separator::State as SeparatorComponentState,
-    splits::State as SplitsComponentState, text::State as TextComponentState,
-    timer::State as TimerComponentState, title::State as TitleComponentState,
+    key_value::State as KeyValueComponentState, run_history::State as RunHistoryComponentState,
+    segment_distribution::State as SegmentDistributionComponentState,
+    separator::State as SeparatorComponentState, splits::State as SplitsComponentState,
+    text::State as TextComponentState, timer::State as TimerComponentState,
+    title::State as TitleComponentState,
 };
 use livesplit_core::layout::{ComponentState, LayoutState};
 use std::os::raw::c_char;
@@ -57,6 +59,8 @@ pub extern "C" fn LayoutState_component_type(this: &LayoutState, index: usize) -
         ComponentState::DetailedTimer(_) => "DetailedTimer\0",
         ComponentState::Graph(_) => "Graph\0",
         ComponentState::KeyValue(_) => "KeyValue\0",
+        ComponentState::RunHistory(_) => "RunHistory\0",
+        ComponentState::SegmentDistribution(_) => "SegmentDistribution\0",
         ComponentState::Separator(_) => "Separator\0",
         ComponentState::Splits(_) => "Splits\0",
         ComponentState::Text(_) => "Text\0",
@@ -115,6 +119,30 @@ pub extern "C" fn LayoutState_component_as_key_value(
     }
 }
 
+/// Gets the Run History component state at the specified index.
+#[no_mangle]
+pub extern "C" fn LayoutState_component_as_run_history(
+    this: &LayoutState,
+    index: usize,
+) -> &RunHistoryComponentState {
+    match &this.components[index] {
+        ComponentState::RunHistory(x) => x,
+        _ => panic!("wrong component state type"),
+    }
+}
+
+/// Gets the Segment Distribution component state at the specified index.
+#[no_mangle]
+pub extern "C" fn LayoutState_component_as_segment_distribution(
+    this: &LayoutState,
+    index: usize,
+) -> &SegmentDistributionComponentState {
+    match &this.components[index] {
+        ComponentState::SegmentDistribution(x) => x,
+        _ => panic!("wrong component state type"),
+    }
+}
+
 /// Gets the Separator component state at the specified index.
 #[no_mangle]
 pub extern "C" fn LayoutState_component_as_separator(
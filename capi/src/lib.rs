@@ -40,12 +40,16 @@ pub mod potential_clean_up;
 pub mod previous_segment_component;
 pub mod run;
 pub mod run_editor;
+pub mod run_history_component;
+pub mod run_history_component_state;
 pub mod run_metadata;
 pub mod run_metadata_custom_variable;
 pub mod run_metadata_custom_variables_iter;
 pub mod run_metadata_speedrun_com_variable;
 pub mod run_metadata_speedrun_com_variables_iter;
 pub mod segment;
+pub mod segment_distribution_component;
+pub mod segment_distribution_component_state;
 pub mod segment_history;
 pub mod segment_history_element;
 pub mod segment_history_iter;
@@ -57,6 +61,7 @@ pub mod shared_timer;
 pub mod software_renderer;
 pub mod splits_component;
 pub mod splits_component_state;
+pub mod statistic_component;
 pub mod sum_of_best_cleaner;
 pub mod sum_of_best_component;
 pub mod text_component;
@@ -66,6 +71,7 @@ pub mod time_span;
 pub mod timer;
 pub mod timer_component;
 pub mod timer_component_state;
+pub mod timer_event;
 pub mod timer_read_lock;
 pub mod timer_write_lock;
 pub mod title_component;
@@ -1,14 +1,14 @@
 //! A Run stores the split times for a specific game and category of a runner.
 
-use super::{get_file, output_str, output_time_span, output_vec, release_file, str};
+use super::{get_file, output_str, output_time_span, output_vec, release_file, str, Json};
 use crate::parse_run_result::OwnedParseRunResult;
 use crate::segment::OwnedSegment;
 use livesplit_core::run::{parser, saver};
-use livesplit_core::{Attempt, Run, RunMetadata, Segment, TimeSpan};
+use livesplit_core::{Attempt, Run, RunMetadata, Segment, TimeSpan, TimingMethod};
 use std::io::{BufReader, Cursor, Write};
 use std::os::raw::c_char;
 use std::path::PathBuf;
-use std::slice;
+use std::{ptr, slice};
 
 /// type
 pub type OwnedRun = Box<Run>;
@@ -256,6 +256,34 @@ pub extern "C" fn Run_save_as_lss(this: &Run) -> *const c_char {
     })
 }
 
+/// Exports the Attempt History of the Run as CSV. There is a row for every
+/// attempt, containing its index, when it started and ended, how long it was
+/// paused for and its final time for every timing method in use.
+#[no_mangle]
+pub extern "C" fn Run_attempts_as_csv(this: &Run) -> *const c_char {
+    output_vec(|o| {
+        saver::history::save_attempts_csv(this, o).unwrap();
+    })
+}
+
+/// Exports the Segment Histories of the Run as a CSV matrix for the timing
+/// method provided. There is a row for every segment and a column for every
+/// attempt, with each cell containing the segment time of the attempt.
+#[no_mangle]
+pub extern "C" fn Run_segment_history_as_csv(this: &Run, method: TimingMethod) -> *const c_char {
+    output_vec(|o| {
+        saver::history::save_segment_history_csv(this, method, o).unwrap();
+    })
+}
+
+/// Exports the Attempt History and the Segment Histories of the Run as JSON.
+#[no_mangle]
+pub extern "C" fn Run_history_as_json(this: &Run) -> Json {
+    output_vec(|o| {
+        saver::history::save_json(this, o).unwrap();
+    })
+}
+
 /// Returns the amount of custom comparisons stored in this Run.
 #[no_mangle]
 pub extern "C" fn Run_custom_comparisons_len(this: &Run) -> usize {
@@ -270,6 +298,37 @@ pub extern "C" fn Run_custom_comparison(this: &Run, index: usize) -> *const c_ch
     output_str(&this.custom_comparisons()[index])
 }
 
+/// Accesses the name the Run gave to the custom timing method provided. <NULL>
+/// is returned if the custom timing method is not in use by the Run or if the
+/// timing method provided is not a custom timing method.
+#[no_mangle]
+pub extern "C" fn Run_custom_timing_method_name(this: &Run, method: TimingMethod) -> *const c_char {
+    match this.custom_timing_method_name(method) {
+        Some(name) => output_str(name),
+        None => ptr::null(),
+    }
+}
+
+/// Returns the name to show for the timing method provided. This is the name
+/// the Run gave to a custom timing method or the default name of the timing
+/// method otherwise.
+#[no_mangle]
+pub extern "C" fn Run_timing_method_name(this: &Run, method: TimingMethod) -> *const c_char {
+    output_str(this.timing_method_name(method))
+}
+
+/// Sets the name of the custom timing method provided. An empty name marks the
+/// custom timing method as not being in use by the Run. Real Time and Game
+/// Time can't be renamed, so nothing happens for them.
+#[no_mangle]
+pub unsafe extern "C" fn Run_set_custom_timing_method_name(
+    this: &mut Run,
+    method: TimingMethod,
+    name: *const c_char,
+) {
+    this.set_custom_timing_method_name(method, str(name));
+}
+
 /// Accesses the Auto Splitter Settings that are encoded as XML.
 #[no_mangle]
 pub extern "C" fn Run_auto_splitter_settings(this: &Run) -> *const c_char {
@@ -6,7 +6,7 @@
 use super::{output_vec, str, Json};
 use crate::run::OwnedRun;
 use crate::sum_of_best_cleaner::OwnedSumOfBestCleaner;
-use livesplit_core::{Run, RunEditor, TimingMethod};
+use livesplit_core::{timing::FrameRate, Run, RunEditor, TimingMethod};
 use std::os::raw::c_char;
 use std::slice;
 
@@ -91,6 +91,18 @@ pub unsafe extern "C" fn RunEditor_set_category_name(
     this.set_category_name(str(category));
 }
 
+/// Sets the name of the custom timing method provided. An empty name marks the
+/// custom timing method as not being in use by the Run. Real Time and Game
+/// Time can't be renamed, so nothing happens for them.
+#[no_mangle]
+pub unsafe extern "C" fn RunEditor_set_custom_timing_method_name(
+    this: &mut RunEditor,
+    method: TimingMethod,
+    name: *const c_char,
+) {
+    this.set_custom_timing_method_name(method, str(name));
+}
+
 /// Parses and sets the timer offset from the string provided. The timer
 /// offset specifies the time, the timer starts at when starting a new
 /// attempt.
@@ -158,6 +170,27 @@ pub extern "C" fn RunEditor_set_emulator_usage(this: &mut RunEditor, uses_emulat
     this.set_emulator_usage(uses_emulator);
 }
 
+/// Sets the frame rate the game runs at. If it is specified, the times are
+/// entered, shown and rounded as frames at this frame rate. The times that are
+/// already stored are not modified. Returns <FALSE> if the frame rate is not a
+/// positive number, in which case nothing is changed.
+#[no_mangle]
+pub extern "C" fn RunEditor_set_frame_rate(this: &mut RunEditor, frames_per_second: f64) -> bool {
+    if let Some(frame_rate) = FrameRate::new(frames_per_second) {
+        this.set_frame_rate(Some(frame_rate));
+        true
+    } else {
+        false
+    }
+}
+
+/// Removes the frame rate, so that the times are entered and shown as regular
+/// times again.
+#[no_mangle]
+pub extern "C" fn RunEditor_clear_frame_rate(this: &mut RunEditor) {
+    this.set_frame_rate(None);
+}
+
 /// Sets the speedrun.com variable with the name specified to the value specified. A
 /// variable is an arbitrary key value pair storing additional information
 /// about the category. An example of this may be whether Amiibos are used
@@ -258,6 +291,87 @@ pub extern "C" fn RunEditor_move_segments_down(this: &mut RunEditor) {
     this.move_segments_down();
 }
 
+/// Splits the active segment into two segments. A new segment is inserted
+/// above the active segment. It takes over the proportion provided of all the
+/// active segment's times, while the active segment keeps the rest. The
+/// proportion needs to be between 0 and 1, otherwise nothing happens. The
+/// newly created segment is then the only selected segment and also the active
+/// segment.
+#[no_mangle]
+pub extern "C" fn RunEditor_split_segment(this: &mut RunEditor, proportion: f64) {
+    this.split_segment(proportion);
+}
+
+/// Merges all the segments from the first to the last selected segment into a
+/// single segment. The times of these segments are combined into the last of
+/// them, which keeps its name and icon. Nothing happens if less than two
+/// segments are selected.
+#[no_mangle]
+pub extern "C" fn RunEditor_merge_segments(this: &mut RunEditor) {
+    this.merge_segments();
+}
+
+/// Moves the segment at the source index to the destination index, while
+/// keeping as much of the Segment History as possible. The Best Segment Times
+/// and the comparisons are adjusted to the new order. Returns <FALSE> if any
+/// of the indices is out of bounds.
+#[no_mangle]
+pub extern "C" fn RunEditor_move_segment(
+    this: &mut RunEditor,
+    src_index: usize,
+    dst_index: usize,
+) -> bool {
+    let mut order = (0..this.run().len()).collect::<Vec<_>>();
+    if src_index >= order.len() || dst_index >= order.len() {
+        return false;
+    }
+    let segment = order.remove(src_index);
+    order.insert(dst_index, segment);
+    this.reorder_segments(&order)
+}
+
+/// Creates a new Segment Group that spans from the first to the last selected
+/// segment. Existing groups within these segments become part of the new
+/// group. Nothing happens if any existing group only partially overlaps with
+/// these segments.
+#[no_mangle]
+pub extern "C" fn RunEditor_create_segment_group(this: &mut RunEditor) {
+    this.create_segment_group();
+}
+
+/// Merges all the Segment Groups that overlap with the selected segments into
+/// a single group. The name and the collapsed state of the first group are
+/// kept.
+#[no_mangle]
+pub extern "C" fn RunEditor_merge_segment_groups(this: &mut RunEditor) {
+    this.merge_segment_groups();
+}
+
+/// Dissolves all the Segment Groups that any of the selected segments are part
+/// of. The segments themselves are kept.
+#[no_mangle]
+pub extern "C" fn RunEditor_dissolve_segment_groups(this: &mut RunEditor) {
+    this.dissolve_segment_groups();
+}
+
+/// Sets the name of the Segment Group that the active segment is part of. An
+/// empty name removes the name of the group, so the name of its last segment
+/// is used instead.
+#[no_mangle]
+pub unsafe extern "C" fn RunEditor_set_segment_group_name(
+    this: &mut RunEditor,
+    name: *const c_char,
+) {
+    this.set_segment_group_name(str(name));
+}
+
+/// Sets whether the Segment Group that the active segment is part of is
+/// collapsed.
+#[no_mangle]
+pub extern "C" fn RunEditor_set_segment_group_collapsed(this: &mut RunEditor, collapsed: bool) {
+    this.set_segment_group_collapsed(collapsed);
+}
+
 /// Sets the icon of the active segment.
 #[no_mangle]
 pub unsafe extern "C" fn RunEditor_active_set_icon(
@@ -432,3 +546,31 @@ pub extern "C" fn RunEditor_clean_sum_of_best(
 ) -> OwnedSumOfBestCleaner {
     Box::new(this.clean_sum_of_best())
 }
+
+/// Checks if there is an action that can be undone.
+#[no_mangle]
+pub extern "C" fn RunEditor_can_undo(this: &RunEditor) -> bool {
+    this.can_undo()
+}
+
+/// Checks if there is an action that can be redone.
+#[no_mangle]
+pub extern "C" fn RunEditor_can_redo(this: &RunEditor) -> bool {
+    this.can_redo()
+}
+
+/// Undoes the most recent action. The Run and the segment selection are
+/// restored to the state before the action was applied. Returns <FALSE> if
+/// there is nothing to undo.
+#[no_mangle]
+pub extern "C" fn RunEditor_undo(this: &mut RunEditor) -> bool {
+    this.undo()
+}
+
+/// Redoes the most recently undone action. Any new action that is applied after
+/// undoing an action discards all the actions that could be redone. Returns
+/// <FALSE> if there is nothing to redo.
+#[no_mangle]
+pub extern "C" fn RunEditor_redo(this: &mut RunEditor) -> bool {
+    this.redo()
+}
@@ -0,0 +1,55 @@
+//! The Run History Component visualizes the history of all the attempts of the
+//! Run. The final time of every finished attempt and the split time every reset
+//! attempt reached are shown as points. Additionally the progression of the
+//! Personal Best and optionally the progression of the Sum of Best Segments
+//! over the attempts are shown as lines.
+
+use super::{output_vec, Json};
+use crate::component::OwnedComponent;
+use crate::run_history_component_state::OwnedRunHistoryComponentState;
+use livesplit_core::component::run_history::Component as RunHistoryComponent;
+use livesplit_core::Timer;
+
+/// type
+pub type OwnedRunHistoryComponent = Box<RunHistoryComponent>;
+
+/// Creates a new Run History Component.
+#[no_mangle]
+pub extern "C" fn RunHistoryComponent_new() -> OwnedRunHistoryComponent {
+    Box::new(RunHistoryComponent::new())
+}
+
+/// drop
+#[no_mangle]
+pub extern "C" fn RunHistoryComponent_drop(this: OwnedRunHistoryComponent) {
+    drop(this);
+}
+
+/// Converts the component into a generic component suitable for using with a
+/// layout.
+#[no_mangle]
+pub extern "C" fn RunHistoryComponent_into_generic(
+    this: OwnedRunHistoryComponent,
+) -> OwnedComponent {
+    Box::new((*this).into())
+}
+
+/// Encodes the component's state information as JSON.
+#[no_mangle]
+pub extern "C" fn RunHistoryComponent_state_as_json(
+    this: &mut RunHistoryComponent,
+    timer: &Timer,
+) -> Json {
+    output_vec(|o| {
+        this.state(&timer.snapshot()).write_json(o).unwrap();
+    })
+}
+
+/// Calculates the component's state based on the timer provided.
+#[no_mangle]
+pub extern "C" fn RunHistoryComponent_state(
+    this: &mut RunHistoryComponent,
+    timer: &Timer,
+) -> OwnedRunHistoryComponentState {
+    Box::new(this.state(&timer.snapshot()))
+}
@@ -0,0 +1,140 @@
+//! The state object describes the information to visualize for this component.
+//! All the coordinates are in the range 0..1, with the slower times at the top.
+
+use livesplit_core::component::run_history::State as RunHistoryComponentState;
+
+/// type
+pub type OwnedRunHistoryComponentState = Box<RunHistoryComponentState>;
+
+/// drop
+#[no_mangle]
+pub extern "C" fn RunHistoryComponentState_drop(this: OwnedRunHistoryComponentState) {
+    drop(this);
+}
+
+/// Returns the amount of attempts to visualize as points.
+#[no_mangle]
+pub extern "C" fn RunHistoryComponentState_attempts_len(this: &RunHistoryComponentState) -> usize {
+    this.attempts.len()
+}
+
+/// Returns the x coordinate of the attempt specified. You may not provide an
+/// out of bounds index.
+#[no_mangle]
+pub extern "C" fn RunHistoryComponentState_attempt_x(
+    this: &RunHistoryComponentState,
+    index: usize,
+) -> f32 {
+    this.attempts[index].x
+}
+
+/// Returns the y coordinate of the attempt specified. You may not provide an
+/// out of bounds index.
+#[no_mangle]
+pub extern "C" fn RunHistoryComponentState_attempt_y(
+    this: &RunHistoryComponentState,
+    index: usize,
+) -> f32 {
+    this.attempts[index].y
+}
+
+/// Describes whether the attempt specified was finished. Otherwise the attempt
+/// got reset and the point visualizes the split time it reached. You may not
+/// provide an out of bounds index.
+#[no_mangle]
+pub extern "C" fn RunHistoryComponentState_attempt_is_finished(
+    this: &RunHistoryComponentState,
+    index: usize,
+) -> bool {
+    this.attempts[index].is_finished
+}
+
+/// Describes whether the attempt specified achieved a new Personal Best. You
+/// may not provide an out of bounds index.
+#[no_mangle]
+pub extern "C" fn RunHistoryComponentState_attempt_is_personal_best(
+    this: &RunHistoryComponentState,
+    index: usize,
+) -> bool {
+    this.attempts[index].is_personal_best
+}
+
+/// Returns the amount of points of the line showing the progression of the
+/// Personal Best.
+#[no_mangle]
+pub extern "C" fn RunHistoryComponentState_personal_best_line_len(
+    this: &RunHistoryComponentState,
+) -> usize {
+    this.personal_best_line.len()
+}
+
+/// Returns the x coordinate of the point specified of the line showing the
+/// progression of the Personal Best. You may not provide an out of bounds
+/// index.
+#[no_mangle]
+pub extern "C" fn RunHistoryComponentState_personal_best_line_x(
+    this: &RunHistoryComponentState,
+    index: usize,
+) -> f32 {
+    this.personal_best_line[index].x
+}
+
+/// Returns the y coordinate of the point specified of the line showing the
+/// progression of the Personal Best. You may not provide an out of bounds
+/// index.
+#[no_mangle]
+pub extern "C" fn RunHistoryComponentState_personal_best_line_y(
+    this: &RunHistoryComponentState,
+    index: usize,
+) -> f32 {
+    this.personal_best_line[index].y
+}
+
+/// Returns the amount of points of the line showing the progression of the Sum
+/// of Best Segments. This is 0 if the progression is not supposed to be shown.
+#[no_mangle]
+pub extern "C" fn RunHistoryComponentState_sum_of_best_line_len(
+    this: &RunHistoryComponentState,
+) -> usize {
+    this.sum_of_best_line.len()
+}
+
+/// Returns the x coordinate of the point specified of the line showing the
+/// progression of the Sum of Best Segments. You may not provide an out of
+/// bounds index.
+#[no_mangle]
+pub extern "C" fn RunHistoryComponentState_sum_of_best_line_x(
+    this: &RunHistoryComponentState,
+    index: usize,
+) -> f32 {
+    this.sum_of_best_line[index].x
+}
+
+/// Returns the y coordinate of the point specified of the line showing the
+/// progression of the Sum of Best Segments. You may not provide an out of
+/// bounds index.
+#[no_mangle]
+pub extern "C" fn RunHistoryComponentState_sum_of_best_line_y(
+    this: &RunHistoryComponentState,
+    index: usize,
+) -> f32 {
+    this.sum_of_best_line[index].y
+}
+
+/// Describes how many horizontal grid lines to visualize.
+#[no_mangle]
+pub extern "C" fn RunHistoryComponentState_horizontal_grid_lines_len(
+    this: &RunHistoryComponentState,
+) -> usize {
+    this.horizontal_grid_lines.len()
+}
+
+/// Accesses the y coordinate of the horizontal grid line specified. You may not
+/// provide an out of bounds index.
+#[no_mangle]
+pub extern "C" fn RunHistoryComponentState_horizontal_grid_line(
+    this: &RunHistoryComponentState,
+    index: usize,
+) -> f32 {
+    this.horizontal_grid_lines[index]
+}
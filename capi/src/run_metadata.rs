@@ -40,6 +40,14 @@ pub extern "C" fn RunMetadata_region_name(this: &RunMetadata) -> *const c_char {
     output_str(this.region_name())
 }
 
+/// Returns the frame rate the game runs at. If it is specified, the times of
+/// the run are meant to be entered, shown and rounded as frames at this frame
+/// rate. 0 is returned if no frame rate is specified.
+#[no_mangle]
+pub extern "C" fn RunMetadata_frame_rate(this: &RunMetadata) -> f64 {
+    this.frame_rate().map_or(0.0, |f| f.frames_per_second())
+}
+
 /// Returns an iterator iterating over all the speedrun.com variables and their
 /// values that have been specified.
 #[no_mangle]
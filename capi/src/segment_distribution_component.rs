@@ -0,0 +1,53 @@
+//! The Segment Distribution Component visualizes how the segment times of the
+//! current segment are distributed as a histogram and marks where the live
+//! segment time of the current attempt lands in that distribution.
+
+use super::{output_vec, Json};
+use crate::component::OwnedComponent;
+use crate::segment_distribution_component_state::OwnedSegmentDistributionComponentState;
+use livesplit_core::component::segment_distribution::Component as SegmentDistributionComponent;
+use livesplit_core::Timer;
+
+/// type
+pub type OwnedSegmentDistributionComponent = Box<SegmentDistributionComponent>;
+
+/// Creates a new Segment Distribution Component.
+#[no_mangle]
+pub extern "C" fn SegmentDistributionComponent_new() -> OwnedSegmentDistributionComponent {
+    Box::new(SegmentDistributionComponent::new())
+}
+
+/// drop
+#[no_mangle]
+pub extern "C" fn SegmentDistributionComponent_drop(this: OwnedSegmentDistributionComponent) {
+    drop(this);
+}
+
+/// Converts the component into a generic component suitable for using with a
+/// layout.
+#[no_mangle]
+pub extern "C" fn SegmentDistributionComponent_into_generic(
+    this: OwnedSegmentDistributionComponent,
+) -> OwnedComponent {
+    Box::new((*this).into())
+}
+
+/// Encodes the component's state information as JSON.
+#[no_mangle]
+pub extern "C" fn SegmentDistributionComponent_state_as_json(
+    this: &mut SegmentDistributionComponent,
+    timer: &Timer,
+) -> Json {
+    output_vec(|o| {
+        this.state(&timer.snapshot()).write_json(o).unwrap();
+    })
+}
+
+/// Calculates the component's state based on the timer provided.
+#[no_mangle]
+pub extern "C" fn SegmentDistributionComponent_state(
+    this: &mut SegmentDistributionComponent,
+    timer: &Timer,
+) -> OwnedSegmentDistributionComponentState {
+    Box::new(this.state(&timer.snapshot()))
+}
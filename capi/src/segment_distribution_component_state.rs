@@ -0,0 +1,62 @@
+//! The state object describes the information to visualize for this component.
+//! All the coordinates are in the range 0..1, with the faster segment times on
+//! the left.
+
+use livesplit_core::component::segment_distribution::State as SegmentDistributionComponentState;
+
+/// type
+pub type OwnedSegmentDistributionComponentState = Box<SegmentDistributionComponentState>;
+
+/// drop
+#[no_mangle]
+pub extern "C" fn SegmentDistributionComponentState_drop(
+    this: OwnedSegmentDistributionComponentState,
+) {
+    drop(this);
+}
+
+/// Returns the amount of bars of the histogram. This is 0 if the segment has no
+/// segment history.
+#[no_mangle]
+pub extern "C" fn SegmentDistributionComponentState_bars_len(
+    this: &SegmentDistributionComponentState,
+) -> usize {
+    this.bars.len()
+}
+
+/// Returns the height of the bar specified. The highest bar has a height of 1.
+/// You may not provide an out of bounds index.
+#[no_mangle]
+pub extern "C" fn SegmentDistributionComponentState_bar(
+    this: &SegmentDistributionComponentState,
+    index: usize,
+) -> f32 {
+    this.bars[index]
+}
+
+/// Returns whether there is a live segment time to mark.
+#[no_mangle]
+pub extern "C" fn SegmentDistributionComponentState_has_marker(
+    this: &SegmentDistributionComponentState,
+) -> bool {
+    this.marker.is_some()
+}
+
+/// Returns the x coordinate of the live segment time. You may not call this if
+/// there is no live segment time to mark.
+#[no_mangle]
+pub extern "C" fn SegmentDistributionComponentState_marker(
+    this: &SegmentDistributionComponentState,
+) -> f32 {
+    this.marker.unwrap()
+}
+
+/// Returns the share of the weighted segment times that are faster than the
+/// live segment time. You may not call this if there is no live segment time to
+/// mark.
+#[no_mangle]
+pub extern "C" fn SegmentDistributionComponentState_marker_percentile(
+    this: &SegmentDistributionComponentState,
+) -> f64 {
+    this.marker_percentile.unwrap()
+}
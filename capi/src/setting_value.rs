@@ -3,14 +3,20 @@
 
 use crate::{output_vec, str, Json};
 use livesplit_core::{
-    component::splits::{ColumnStartWith, ColumnUpdateTrigger, ColumnUpdateWith},
+    component::{
+        run_history::HistoryAxis,
+        splits::{
+            ColumnStartWith, ColumnUpdateTrigger, ColumnUpdateWith, NamePattern, StyleCondition,
+        },
+        statistic::Statistic,
+    },
     layout::LayoutDirection,
     settings::{
         Alignment, Color, Font, FontStretch, FontStyle, FontWeight, Gradient, ListGradient,
         Value as SettingValue,
     },
     timing::formatter::{Accuracy, DigitsFormat},
-    TimingMethod,
+    TimeSpan, TimingMethod,
 };
 use std::os::raw::c_char;
 
@@ -126,6 +132,10 @@ pub unsafe extern "C" fn SettingValue_from_optional_timing_method(
         let value = match value {
             "RealTime" => TimingMethod::RealTime,
             "GameTime" => TimingMethod::GameTime,
+            "Custom1" => TimingMethod::Custom1,
+            "Custom2" => TimingMethod::Custom2,
+            "Custom3" => TimingMethod::Custom3,
+            "Custom4" => TimingMethod::Custom4,
             _ => return None,
         };
         Some(Box::new(Some(value).into()))
@@ -243,6 +253,11 @@ pub unsafe extern "C" fn SettingValue_from_column_start_with(
         "ComparisonTime" => ColumnStartWith::ComparisonTime,
         "ComparisonSegmentTime" => ColumnStartWith::ComparisonSegmentTime,
         "PossibleTimeSave" => ColumnStartWith::PossibleTimeSave,
+        "BestPossibleTimeSave" => ColumnStartWith::BestPossibleTimeSave,
+        "AverageSegmentTime" => ColumnStartWith::AverageSegmentTime,
+        "MedianSegmentTime" => ColumnStartWith::MedianSegmentTime,
+        "ResetPercentage" => ColumnStartWith::ResetPercentage,
+        "GoldCount" => ColumnStartWith::GoldCount,
         _ => return None,
     };
     Some(Box::new(value.into()))
@@ -263,6 +278,7 @@ pub unsafe extern "C" fn SettingValue_from_column_update_with(
         "SegmentTime" => ColumnUpdateWith::SegmentTime,
         "SegmentDelta" => ColumnUpdateWith::SegmentDelta,
         "SegmentDeltaWithFallback" => ColumnUpdateWith::SegmentDeltaWithFallback,
+        "PossibleTimeSave" => ColumnUpdateWith::PossibleTimeSave,
         _ => return None,
     };
     Some(Box::new(value.into()))
@@ -299,6 +315,69 @@ pub unsafe extern "C" fn SettingValue_from_layout_direction(
     Some(Box::new(value.into()))
 }
 
+/// Creates a new setting value from the statistic name. If it doesn't match a
+/// known statistic, <NULL> is returned.
+#[no_mangle]
+pub unsafe extern "C" fn SettingValue_from_statistic(
+    value: *const c_char,
+) -> NullableOwnedSettingValue {
+    let value = str(value);
+    let value = match value {
+        "Mean" => Statistic::Mean,
+        "Median" => Statistic::Median,
+        "StandardDeviation" => Statistic::StandardDeviation,
+        "Consistency" => Statistic::Consistency,
+        "ResetRate" => Statistic::ResetRate,
+        "TimeLostVsGold" => Statistic::TimeLostVsGold,
+        "Trend" => Statistic::Trend,
+        _ => return None,
+    };
+    Some(Box::new(value.into()))
+}
+
+/// Creates a new setting value from the name of what the horizontal axis of the
+/// Run History Component is based on. If it doesn't match a known axis, <NULL>
+/// is returned.
+#[no_mangle]
+pub unsafe extern "C" fn SettingValue_from_history_axis(
+    value: *const c_char,
+) -> NullableOwnedSettingValue {
+    let value = str(value);
+    let value = match value {
+        "AttemptIndex" => HistoryAxis::AttemptIndex,
+        "Date" => HistoryAxis::Date,
+        _ => return None,
+    };
+    Some(Box::new(value.into()))
+}
+
+/// Creates a new setting value from a style condition that is met when the
+/// delta of a row is larger than the threshold provided in seconds.
+#[no_mangle]
+pub extern "C" fn SettingValue_from_delta_above_style_condition(
+    threshold: f64,
+) -> OwnedSettingValue {
+    Box::new(StyleCondition::DeltaAbove(TimeSpan::from_seconds(threshold)).into())
+}
+
+/// Creates a new setting value from a style condition that is met when the
+/// current attempt achieved a new best segment time on the row's segment.
+#[no_mangle]
+pub extern "C" fn SettingValue_from_best_segment_style_condition() -> OwnedSettingValue {
+    Box::new(StyleCondition::BestSegment.into())
+}
+
+/// Creates a new setting value from a style condition that is met when the
+/// name of a row matches the regular expression provided. If the regular
+/// expression is invalid, <NULL> is returned.
+#[no_mangle]
+pub unsafe extern "C" fn SettingValue_from_name_matches_style_condition(
+    pattern: *const c_char,
+) -> NullableOwnedSettingValue {
+    let pattern = NamePattern::new(str(pattern)).ok()?;
+    Some(Box::new(StyleCondition::NameMatches(pattern).into()))
+}
+
 /// Creates a new setting value with the type `font`.
 #[no_mangle]
 pub unsafe extern "C" fn SettingValue_from_font(
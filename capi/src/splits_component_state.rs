@@ -128,3 +128,35 @@ pub extern "C" fn SplitsComponentState_is_current_split(
 ) -> bool {
     this.splits[index].is_current_split
 }
+
+/// Describes whether the row with the specified index represents an entire
+/// Segment Group rather than a single segment. You may not provide an out of
+/// bounds index.
+#[no_mangle]
+pub extern "C" fn SplitsComponentState_is_segment_group(
+    this: &SplitsComponentState,
+    index: usize,
+) -> bool {
+    this.splits[index].is_segment_group
+}
+
+/// Describes whether the Segment Group the row with the specified index
+/// represents is collapsed. You may not provide an out of bounds index.
+#[no_mangle]
+pub extern "C" fn SplitsComponentState_is_collapsed(
+    this: &SplitsComponentState,
+    index: usize,
+) -> bool {
+    this.splits[index].is_collapsed
+}
+
+/// Describes whether the segment with the specified index is part of a Segment
+/// Group that is shown expanded. Such segments are usually shown indented. You
+/// may not provide an out of bounds index.
+#[no_mangle]
+pub extern "C" fn SplitsComponentState_is_subsplit(
+    this: &SplitsComponentState,
+    index: usize,
+) -> bool {
+    this.splits[index].is_subsplit
+}
@@ -0,0 +1,51 @@
+//! The Statistic Component is a component that shows a chosen statistic about
+//! the past attempts for the current segment, like the average segment time or
+//! how often attempts are reset during the segment.
+
+use super::{output_vec, Json};
+use crate::component::OwnedComponent;
+use crate::key_value_component_state::OwnedKeyValueComponentState;
+use livesplit_core::component::statistic::Component as StatisticComponent;
+use livesplit_core::Timer;
+
+/// type
+pub type OwnedStatisticComponent = Box<StatisticComponent>;
+
+/// Creates a new Statistic Component.
+#[no_mangle]
+pub extern "C" fn StatisticComponent_new() -> OwnedStatisticComponent {
+    Box::new(StatisticComponent::new())
+}
+
+/// drop
+#[no_mangle]
+pub extern "C" fn StatisticComponent_drop(this: OwnedStatisticComponent) {
+    drop(this);
+}
+
+/// Converts the component into a generic component suitable for using with a
+/// layout.
+#[no_mangle]
+pub extern "C" fn StatisticComponent_into_generic(this: OwnedStatisticComponent) -> OwnedComponent {
+    Box::new((*this).into())
+}
+
+/// Encodes the component's state information as JSON.
+#[no_mangle]
+pub extern "C" fn StatisticComponent_state_as_json(
+    this: &StatisticComponent,
+    timer: &Timer,
+) -> Json {
+    output_vec(|o| {
+        this.state(&timer.snapshot()).write_json(o).unwrap();
+    })
+}
+
+/// Calculates the component's state based on the timer provided.
+#[no_mangle]
+pub extern "C" fn StatisticComponent_state(
+    this: &StatisticComponent,
+    timer: &Timer,
+) -> OwnedKeyValueComponentState {
+    Box::new(this.state(&timer.snapshot()))
+}
@@ -1,10 +1,11 @@
 //! A Timer provides all the capabilities necessary for doing speedrun attempts.
 
-use super::{output_str, output_time, output_time_span, output_vec};
+use super::{output_str, output_time, output_time_span, output_vec, str};
 use crate::run::{NullableOwnedRun, OwnedRun};
 use crate::shared_timer::OwnedSharedTimer;
 use livesplit_core::run::saver;
-use livesplit_core::{Run, Time, TimeSpan, Timer, TimerPhase, TimingMethod};
+use livesplit_core::timing::TimerSubscriptionId;
+use livesplit_core::{Run, Time, TimeSpan, Timer, TimerEvent, TimerPhase, TimingMethod};
 use std::os::raw::c_char;
 use std::ptr;
 
@@ -189,6 +190,44 @@ pub extern "C" fn Timer_current_comparison(this: &Timer) -> *const c_char {
     output_str(this.current_comparison())
 }
 
+/// Adds a race comparison against the runner provided, which compares against
+/// the Personal Best of the Run provided. The comparison is called
+/// `[Race] <runner>` and its segments are mapped onto the segments of the
+/// Timer's Run by their names. Returns <FALSE> if there already is a race
+/// comparison against the runner.
+#[no_mangle]
+pub unsafe extern "C" fn Timer_add_race_comparison(
+    this: &mut Timer,
+    runner: *const c_char,
+    run: &Run,
+) -> bool {
+    this.add_race_comparison(str(runner), run).is_ok()
+}
+
+/// Updates the race comparison against the runner provided with the Personal
+/// Best of the Run provided. This can be done at any point, even in the middle
+/// of an attempt. Returns <FALSE> if there is no race comparison against the
+/// runner.
+#[no_mangle]
+pub unsafe extern "C" fn Timer_update_race_comparison(
+    this: &mut Timer,
+    runner: *const c_char,
+    run: &Run,
+) -> bool {
+    this.update_race_comparison(str(runner), run)
+}
+
+/// Removes the race comparison against the runner provided. If it is the
+/// current comparison, the Timer switches to the Personal Best. Returns
+/// <FALSE> if there is no race comparison against the runner.
+#[no_mangle]
+pub unsafe extern "C" fn Timer_remove_race_comparison(
+    this: &mut Timer,
+    runner: *const c_char,
+) -> bool {
+    this.remove_race_comparison(str(runner))
+}
+
 /// Switches the current comparison to the next comparison in the list.
 #[no_mangle]
 pub extern "C" fn Timer_switch_to_next_comparison(this: &mut Timer) {
@@ -251,6 +290,22 @@ pub extern "C" fn Timer_set_game_time(this: &mut Timer, time: &TimeSpan) {
     this.set_game_time(*time);
 }
 
+/// Sets the time of the custom timing method provided. Custom timing methods
+/// don't move forward on their own, so their time needs to be updated
+/// periodically, for example by an auto splitter. Nothing happens for Real
+/// Time and Game Time.
+#[no_mangle]
+pub extern "C" fn Timer_set_custom_time(this: &mut Timer, method: TimingMethod, time: &TimeSpan) {
+    this.set_custom_time(method, Some(*time));
+}
+
+/// Clears the time of the custom timing method provided, so that it doesn't
+/// have a time anymore. Nothing happens for Real Time and Game Time.
+#[no_mangle]
+pub extern "C" fn Timer_clear_custom_time(this: &mut Timer, method: TimingMethod) {
+    this.set_custom_time(method, None);
+}
+
 /// Accesses the loading times. Loading times are defined as Game Time - Real Time.
 #[no_mangle]
 pub extern "C" fn Timer_loading_times(this: &Timer) -> *const TimeSpan {
@@ -305,3 +360,47 @@ pub extern "C" fn Timer_print_debug(this: &Timer) {
 pub extern "C" fn Timer_current_time(this: &Timer) -> *const Time {
     output_time(this.snapshot().current_time())
 }
+
+/// The user data is only ever handed back to the callback. Whoever registers
+/// the callback is responsible for it being safe to use from the thread the
+/// Timer is modified on.
+struct UserData(*const u8);
+
+unsafe impl Send for UserData {}
+unsafe impl Sync for UserData {}
+
+/// Subscribes to the events emitted by the Timer. The callback needs to be a
+/// pointer to a function with the signature
+/// `void callback(void const* user_data, TimerEvent const* event)`. It is
+/// called for every event right after it happened, with the user data
+/// provided. The event is only valid for the duration of the callback. The
+/// callback is called while the Timer is being modified, so it must not try
+/// to lock a Shared Timer containing this Timer. Returns an ID that can be
+/// used to unsubscribe again. If the callback is <NULL>, nothing is subscribed
+/// and the maximum value of the ID type is returned instead.
+#[no_mangle]
+pub extern "C" fn Timer_subscribe(
+    this: &mut Timer,
+    // The signature is spelled out, so the binding generator can tell that
+    // this is an optional function pointer.
+    callback: Option<unsafe extern "C" fn(user_data: *const u8, event: &TimerEvent)>,
+    user_data: *const u8,
+) -> usize {
+    let callback = match callback {
+        Some(callback) => callback,
+        None => return usize::MAX,
+    };
+    let user_data = UserData(user_data);
+    let TimerSubscriptionId(id) = this.subscribe(move |event| {
+        let user_data = &user_data;
+        unsafe { callback(user_data.0, event) }
+    });
+    id
+}
+
+/// Unsubscribes the callback with the ID provided from the events emitted by
+/// the Timer. Returns <FALSE> if there was no such callback.
+#[no_mangle]
+pub extern "C" fn Timer_unsubscribe(this: &mut Timer, id: usize) -> bool {
+    this.unsubscribe(TimerSubscriptionId(id))
+}
@@ -0,0 +1,117 @@
+//! An event that is emitted by the Timer whenever something happened to it.
+//! Timer Events are passed to the callbacks registered with
+//! `Timer_subscribe` and are only valid for the duration of the callback.
+
+use super::{output_str, output_time, output_time_span};
+use crate::time_span::NullableTimeSpan;
+use livesplit_core::{Time, TimerEvent, TimingMethod};
+use std::{os::raw::c_char, ptr};
+
+/// Returns the kind of the event.
+///
+/// The kinds are as follows:
+///
+/// * `0` - Started
+/// * `1` - Split
+/// * `2` - Split Skipped
+/// * `3` - Split Undone
+/// * `4` - Reset
+/// * `5` - Paused
+/// * `6` - Resumed
+/// * `7` - Pauses Undone
+/// * `8` - Comparison Changed
+/// * `9` - Timing Method Changed
+/// * `10` - Game Time Initialized
+#[no_mangle]
+pub extern "C" fn TimerEvent_kind(this: &TimerEvent) -> u8 {
+    match this {
+        TimerEvent::Started => 0,
+        TimerEvent::Split { .. } => 1,
+        TimerEvent::SplitSkipped { .. } => 2,
+        TimerEvent::SplitUndone { .. } => 3,
+        TimerEvent::Reset { .. } => 4,
+        TimerEvent::Paused => 5,
+        TimerEvent::Resumed => 6,
+        TimerEvent::PausesUndone => 7,
+        TimerEvent::ComparisonChanged { .. } => 8,
+        TimerEvent::TimingMethodChanged { .. } => 9,
+        TimerEvent::GameTimeInitialized => 10,
+    }
+}
+
+/// Returns the index of the segment that was split, skipped or undone. For
+/// all other events this returns 0.
+#[no_mangle]
+pub extern "C" fn TimerEvent_segment_index(this: &TimerEvent) -> usize {
+    match *this {
+        TimerEvent::Split { segment_index, .. }
+        | TimerEvent::SplitSkipped { segment_index }
+        | TimerEvent::SplitUndone { segment_index } => segment_index,
+        _ => 0,
+    }
+}
+
+/// Returns the split time that was stored by a Split event. For all other
+/// events this returns an empty time.
+#[no_mangle]
+pub extern "C" fn TimerEvent_split_time(this: &TimerEvent) -> *const Time {
+    match *this {
+        TimerEvent::Split { split_time, .. } => output_time(split_time),
+        _ => output_time(Time::default()),
+    }
+}
+
+/// Returns the difference between the split time and the current
+/// comparison's time of a Split event. This returns <NULL> if there is no
+/// such difference or if the event is not a Split event.
+#[no_mangle]
+pub extern "C" fn TimerEvent_delta(this: &TimerEvent) -> *const NullableTimeSpan {
+    match *this {
+        TimerEvent::Split {
+            delta: Some(delta), ..
+        } => output_time_span(delta),
+        _ => ptr::null(),
+    }
+}
+
+/// Returns whether a Split event stored a new best segment. For all other
+/// events this returns <FALSE>.
+#[no_mangle]
+pub extern "C" fn TimerEvent_is_best_segment(this: &TimerEvent) -> bool {
+    match *this {
+        TimerEvent::Split {
+            is_best_segment, ..
+        } => is_best_segment,
+        _ => false,
+    }
+}
+
+/// Returns whether the attempt's information was stored in the Run's history
+/// when it was reset. For all other events this returns <FALSE>.
+#[no_mangle]
+pub extern "C" fn TimerEvent_was_saved(this: &TimerEvent) -> bool {
+    match *this {
+        TimerEvent::Reset { saved } => saved,
+        _ => false,
+    }
+}
+
+/// Returns the name of the new comparison of a Comparison Changed event. For
+/// all other events this returns an empty string.
+#[no_mangle]
+pub extern "C" fn TimerEvent_comparison(this: &TimerEvent) -> *const c_char {
+    match this {
+        TimerEvent::ComparisonChanged { comparison } => output_str(comparison),
+        _ => output_str(""),
+    }
+}
+
+/// Returns the new timing method of a Timing Method Changed event. For all
+/// other events this returns the Real Time timing method.
+#[no_mangle]
+pub extern "C" fn TimerEvent_timing_method(this: &TimerEvent) -> TimingMethod {
+    match *this {
+        TimerEvent::TimingMethodChanged { method } => method,
+        _ => TimingMethod::RealTime,
+    }
+}
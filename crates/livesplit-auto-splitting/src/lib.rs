@@ -0,0 +1,99 @@
+//! livesplit-auto-splitting is a library that provides a runtime for running
+//! auto splitters that can control a speedrun timer. These auto splitters are
+//! provided as WebAssembly modules and run in a sandbox. The only way for them
+//! to interact with the outside world is through the functions provided by the
+//! runtime, which allow attaching to processes, reading their memory and
+//! controlling the timer.
+//!
+//! # Writing an auto splitter
+//!
+//! An auto splitter needs to export its `memory` and a function called
+//! `update` that takes no arguments and returns nothing. The runtime calls this
+//! function on every tick. All of the following functions are imported from
+//! the `env` module:
+//!
+//! ```ignore
+//! /// Gets the state that the timer currently is in. The state is 0 if there
+//! /// is no attempt in progress, 1 if an attempt is running, 2 if it is paused
+//! /// and 3 if it has ended.
+//! fn timer_get_state() -> i32;
+//! /// Starts the timer.
+//! fn timer_start();
+//! /// Splits the current segment.
+//! fn timer_split();
+//! /// Resets the timer.
+//! fn timer_reset();
+//! /// Sets the game time in seconds.
+//! fn timer_set_game_time(seconds: f64);
+//! /// Pauses the game time. This does not pause the timer, only the automatic
+//! /// flow of time for the game time.
+//! fn timer_pause_game_time();
+//! /// Resumes the game time. This does not resume the timer, only the
+//! /// automatic flow of time for the game time.
+//! fn timer_resume_game_time();
+//!
+//! /// Attaches to a process based on its name. Returns 0 if the process can't
+//! /// be found.
+//! fn process_attach(name_ptr: *const u8, name_len: usize) -> u64;
+//! /// Detaches from a process.
+//! fn process_detach(process: u64);
+//! /// Checks whether a process is still open. Returns 0 if it is not.
+//! fn process_is_open(process: u64) -> i32;
+//! /// Reads memory from a process at the address given into the buffer.
+//! /// Returns 0 if the memory couldn't be read.
+//! fn process_read(process: u64, address: u64, buf_ptr: *mut u8, buf_len: usize) -> i32;
+//!
+//! /// Sets the tick rate of the runtime in ticks per second.
+//! fn runtime_set_tick_rate(ticks_per_second: f64);
+//! ```
+//!
+//! Reading the memory of other processes is currently only supported on
+//! Linux, where it is done through `/proc/<pid>/mem`. On all other platforms
+//! attaching to a process always fails.
+
+#![warn(
+    clippy::complexity,
+    clippy::correctness,
+    clippy::perf,
+    clippy::style,
+    missing_docs,
+    rust_2018_idioms
+)]
+
+mod process;
+mod runtime;
+
+pub use self::runtime::{CreationError, RunError, Runtime};
+
+/// The state that a timer is in.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum TimerState {
+    /// There is no attempt in progress.
+    NotRunning,
+    /// An attempt is running.
+    Running,
+    /// The attempt is paused.
+    Paused,
+    /// The attempt has ended, but didn't get reset yet.
+    Ended,
+}
+
+/// A timer that can be controlled by an auto splitter.
+pub trait Timer {
+    /// Returns the state that the timer is in.
+    fn state(&self) -> TimerState;
+    /// Starts the timer.
+    fn start(&mut self);
+    /// Splits the current segment.
+    fn split(&mut self);
+    /// Resets the timer.
+    fn reset(&mut self);
+    /// Sets the game time in seconds.
+    fn set_game_time(&mut self, seconds: f64);
+    /// Pauses the game time. This does not pause the timer, only the automatic
+    /// flow of time for the game time.
+    fn pause_game_time(&mut self);
+    /// Resumes the game time. This does not resume the timer, only the
+    /// automatic flow of time for the game time.
+    fn resume_game_time(&mut self);
+}
@@ -0,0 +1,58 @@
+use std::{
+    fs::{self, File},
+    io,
+    os::unix::fs::FileExt,
+    path::{Path, PathBuf},
+};
+
+/// A process that the auto splitter is attached to. Its memory is read through
+/// `/proc/<pid>/mem`.
+#[derive(Debug)]
+pub struct Process {
+    path: PathBuf,
+    memory: File,
+}
+
+impl Process {
+    /// Attaches to the first process that has the name provided. The name is
+    /// either compared to the process's command name or to the file name of its
+    /// executable.
+    pub fn with_name(name: &str) -> io::Result<Self> {
+        for entry in fs::read_dir("/proc")? {
+            let path = entry?.path();
+            let is_pid = matches!(
+                path.file_name().and_then(|n| n.to_str()),
+                Some(n) if n.bytes().all(|b| b.is_ascii_digit())
+            );
+            if is_pid && has_name(&path, name) {
+                let memory = File::open(path.join("mem"))?;
+                return Ok(Self { path, memory });
+            }
+        }
+        Err(io::ErrorKind::NotFound.into())
+    }
+
+    /// Checks whether the process is still running.
+    pub fn is_open(&self) -> bool {
+        self.path.exists()
+    }
+
+    /// Reads the memory at the address provided into the buffer.
+    pub fn read(&self, address: u64, buf: &mut [u8]) -> io::Result<()> {
+        self.memory.read_exact_at(buf, address)
+    }
+}
+
+fn has_name(path: &Path, name: &str) -> bool {
+    // The command name is truncated to 15 bytes, so the executable's file name
+    // is checked as well.
+    if let Ok(comm) = fs::read_to_string(path.join("comm")) {
+        if comm.trim_end_matches('\n') == name {
+            return true;
+        }
+    }
+    matches!(
+        fs::read_link(path.join("exe")),
+        Ok(exe) if exe.file_name() == Some(name.as_ref())
+    )
+}
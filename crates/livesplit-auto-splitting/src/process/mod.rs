@@ -0,0 +1,9 @@
+cfg_if::cfg_if! {
+    if #[cfg(target_os = "linux")] {
+        mod linux;
+        pub use self::linux::*;
+    } else {
+        mod other;
+        pub use self::other::*;
+    }
+}
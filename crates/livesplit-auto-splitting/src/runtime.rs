@@ -0,0 +1,233 @@
+use crate::{process::Process, Timer, TimerState};
+use snafu::{OptionExt, ResultExt, Snafu};
+use std::{str, time::Duration};
+use wasmi::{Caller, Engine, Error, Extern, Linker, Memory, Module, Store, TypedFunc};
+
+/// The Error type for auto splitters that couldn't be loaded.
+#[derive(Debug, Snafu)]
+pub enum CreationError {
+    /// The WebAssembly module couldn't be loaded.
+    LoadModule {
+        /// The underlying error.
+        source: Error,
+    },
+    /// The WebAssembly module couldn't be instantiated. This happens if the
+    /// module imports functions that the runtime doesn't provide.
+    Instantiate {
+        /// The underlying error.
+        source: Error,
+    },
+    /// The WebAssembly module doesn't export an `update` function with the
+    /// correct signature.
+    MissingUpdateFunction {
+        /// The underlying error.
+        source: Error,
+    },
+    /// The WebAssembly module doesn't export its memory.
+    MissingMemory,
+}
+
+/// The Error type for auto splitters that failed while running.
+#[derive(Debug, Snafu)]
+pub enum RunError {
+    /// The `update` function of the auto splitter trapped.
+    Trapped {
+        /// The underlying error.
+        source: Error,
+    },
+}
+
+struct Context<T> {
+    timer: T,
+    processes: Vec<Option<Process>>,
+    tick_rate: Duration,
+}
+
+impl<T> Context<T> {
+    fn process(&self, handle: u64) -> Result<&Process, Error> {
+        handle
+            .checked_sub(1)
+            .and_then(|index| self.processes.get(index as usize)?.as_ref())
+            .ok_or_else(|| Error::new("Invalid process handle"))
+    }
+}
+
+/// An auto splitter that is loaded and ready to be run. The auto splitter
+/// controls the timer provided to it.
+pub struct Runtime<T> {
+    store: Store<Context<T>>,
+    update: TypedFunc<(), ()>,
+}
+
+impl<T: Timer + 'static> Runtime<T> {
+    /// Loads the auto splitter from the WebAssembly module provided.
+    pub fn new(module: &[u8], timer: T) -> Result<Self, CreationError> {
+        let engine = Engine::default();
+        let module = Module::new(&engine, module).context(LoadModule)?;
+        let mut store = Store::new(
+            &engine,
+            Context {
+                timer,
+                processes: Vec::new(),
+                tick_rate: Duration::from_secs(1) / 120,
+            },
+        );
+
+        let instance = linker(&engine)
+            .and_then(|linker| linker.instantiate_and_start(&mut store, &module))
+            .context(Instantiate)?;
+        instance
+            .get_memory(&store, "memory")
+            .context(MissingMemory)?;
+        let update = instance
+            .get_typed_func(&store, "update")
+            .context(MissingUpdateFunction)?;
+
+        Ok(Self { store, update })
+    }
+
+    /// Runs the `update` function of the auto splitter once. This is supposed
+    /// to be called once per tick.
+    pub fn step(&mut self) -> Result<(), RunError> {
+        self.update.call(&mut self.store, ()).context(Trapped)
+    }
+
+    /// Returns the duration to wait until the next tick. This can be changed
+    /// by the auto splitter.
+    pub fn tick_rate(&self) -> Duration {
+        self.store.data().tick_rate
+    }
+}
+
+fn memory<T>(caller: &Caller<'_, Context<T>>) -> Result<Memory, Error> {
+    caller
+        .get_export("memory")
+        .and_then(Extern::into_memory)
+        .ok_or_else(|| Error::new("The module doesn't export its memory"))
+}
+
+fn slice_mut(memory: &mut [u8], ptr: u32, len: u32) -> Result<&mut [u8], Error> {
+    let start = ptr as usize;
+    memory
+        .get_mut(start..start + len as usize)
+        .ok_or_else(|| Error::new("Out of bounds memory access"))
+}
+
+fn linker<T: Timer + 'static>(engine: &Engine) -> Result<Linker<Context<T>>, Error> {
+    let mut linker = Linker::new(engine);
+
+    linker
+        .func_wrap(
+            "env",
+            "timer_get_state",
+            |caller: Caller<'_, Context<T>>| -> u32 {
+                match caller.data().timer.state() {
+                    TimerState::NotRunning => 0,
+                    TimerState::Running => 1,
+                    TimerState::Paused => 2,
+                    TimerState::Ended => 3,
+                }
+            },
+        )?
+        .func_wrap(
+            "env",
+            "timer_start",
+            |mut caller: Caller<'_, Context<T>>| caller.data_mut().timer.start(),
+        )?
+        .func_wrap(
+            "env",
+            "timer_split",
+            |mut caller: Caller<'_, Context<T>>| caller.data_mut().timer.split(),
+        )?
+        .func_wrap(
+            "env",
+            "timer_reset",
+            |mut caller: Caller<'_, Context<T>>| caller.data_mut().timer.reset(),
+        )?
+        .func_wrap(
+            "env",
+            "timer_set_game_time",
+            |mut caller: Caller<'_, Context<T>>, seconds: f64| {
+                caller.data_mut().timer.set_game_time(seconds)
+            },
+        )?
+        .func_wrap(
+            "env",
+            "timer_pause_game_time",
+            |mut caller: Caller<'_, Context<T>>| caller.data_mut().timer.pause_game_time(),
+        )?
+        .func_wrap(
+            "env",
+            "timer_resume_game_time",
+            |mut caller: Caller<'_, Context<T>>| caller.data_mut().timer.resume_game_time(),
+        )?
+        .func_wrap(
+            "env",
+            "process_attach",
+            |mut caller: Caller<'_, Context<T>>, ptr: u32, len: u32| -> Result<u64, Error> {
+                let (memory, context) = memory(&caller)?.data_and_store_mut(&mut caller);
+                let name = str::from_utf8(slice_mut(memory, ptr, len)?)
+                    .map_err(|_| Error::new("The process name is not valid UTF-8"))?;
+                Ok(match Process::with_name(name) {
+                    Ok(process) => {
+                        // Slots of detached processes are reused.
+                        let index = match context.processes.iter().position(Option::is_none) {
+                            Some(index) => {
+                                context.processes[index] = Some(process);
+                                index
+                            }
+                            None => {
+                                context.processes.push(Some(process));
+                                context.processes.len() - 1
+                            }
+                        };
+                        index as u64 + 1
+                    }
+                    Err(_) => 0,
+                })
+            },
+        )?
+        .func_wrap(
+            "env",
+            "process_detach",
+            |mut caller: Caller<'_, Context<T>>, process: u64| -> Result<(), Error> {
+                let context = caller.data_mut();
+                context.process(process)?;
+                context.processes[process as usize - 1] = None;
+                Ok(())
+            },
+        )?
+        .func_wrap(
+            "env",
+            "process_is_open",
+            |caller: Caller<'_, Context<T>>, process: u64| -> Result<u32, Error> {
+                Ok(caller.data().process(process)?.is_open() as u32)
+            },
+        )?
+        .func_wrap(
+            "env",
+            "process_read",
+            |mut caller: Caller<'_, Context<T>>,
+             process: u64,
+             address: u64,
+             buf_ptr: u32,
+             buf_len: u32|
+             -> Result<u32, Error> {
+                let (memory, context) = memory(&caller)?.data_and_store_mut(&mut caller);
+                let buf = slice_mut(memory, buf_ptr, buf_len)?;
+                Ok(context.process(process)?.read(address, buf).is_ok() as u32)
+            },
+        )?
+        .func_wrap(
+            "env",
+            "runtime_set_tick_rate",
+            |mut caller: Caller<'_, Context<T>>, ticks_per_second: f64| -> Result<(), Error> {
+                let tick_rate = Duration::try_from_secs_f64(ticks_per_second.recip())
+                    .map_err(|_| Error::new("The tick rate needs to be positive"))?;
+                caller.data_mut().tick_rate = tick_rate;
+                Ok(())
+            },
+        )?;
+
+    Ok(linker)
+}
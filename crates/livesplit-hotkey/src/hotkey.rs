@@ -0,0 +1,160 @@
+use crate::KeyCode;
+use core::{
+    fmt,
+    ops::{BitOr, BitOrAssign},
+    str::FromStr,
+};
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+
+/// A set of modifier keys that need to be held down for a hotkey to trigger.
+#[derive(Debug, Default, Eq, PartialEq, Hash, Copy, Clone)]
+pub struct Modifiers(u8);
+
+impl Modifiers {
+    /// Either of the Shift keys.
+    pub const SHIFT: Self = Self(1 << 0);
+    /// Either of the Control keys.
+    pub const CONTROL: Self = Self(1 << 1);
+    /// Either of the Alt keys. This is the Option key on macOS.
+    pub const ALT: Self = Self(1 << 2);
+    /// Either of the Meta keys. This is the Windows key on Windows and the
+    /// Command key on macOS.
+    pub const META: Self = Self(1 << 3);
+
+    const NAMES: [(Self, &'static str); 4] = [
+        (Self::CONTROL, "Control"),
+        (Self::ALT, "Alt"),
+        (Self::SHIFT, "Shift"),
+        (Self::META, "Meta"),
+    ];
+
+    /// Creates an empty set of modifiers.
+    pub const fn empty() -> Self {
+        Self(0)
+    }
+
+    /// Returns whether no modifiers are in the set.
+    pub const fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+
+    /// Returns whether all the modifiers of the other set are in this set.
+    pub const fn contains(self, other: Self) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    /// Adds all the modifiers of the other set to this set.
+    pub fn insert(&mut self, other: Self) {
+        self.0 |= other.0;
+    }
+
+    /// Adds the modifiers of the other set to this set if `value` is `true` and
+    /// removes them otherwise.
+    pub fn set(&mut self, other: Self, value: bool) {
+        if value {
+            self.0 |= other.0;
+        } else {
+            self.0 &= !other.0;
+        }
+    }
+}
+
+impl BitOr for Modifiers {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self {
+        Self(self.0 | rhs.0)
+    }
+}
+
+impl BitOrAssign for Modifiers {
+    fn bitor_assign(&mut self, rhs: Self) {
+        self.insert(rhs);
+    }
+}
+
+/// A hotkey is a key that needs to be pressed while a set of modifiers is held
+/// down. Hotkeys are formatted and parsed in the form `Control + Alt + Numpad1`.
+/// A key code on its own is a valid hotkey without any modifiers, so
+/// configurations that only stored key codes can still be parsed.
+#[derive(Debug, Eq, PartialEq, Hash, Copy, Clone)]
+pub struct Hotkey {
+    /// The key that needs to be pressed.
+    pub key_code: KeyCode,
+    /// The modifiers that need to be held down.
+    pub modifiers: Modifiers,
+}
+
+impl Hotkey {
+    /// Creates a new hotkey from a key code and the modifiers that need to be
+    /// held down.
+    pub const fn new(key_code: KeyCode, modifiers: Modifiers) -> Self {
+        Self {
+            key_code,
+            modifiers,
+        }
+    }
+}
+
+impl From<KeyCode> for Hotkey {
+    fn from(key_code: KeyCode) -> Self {
+        Self::new(key_code, Modifiers::empty())
+    }
+}
+
+impl fmt::Display for Hotkey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for &(modifier, name) in &Modifiers::NAMES {
+            if self.modifiers.contains(modifier) {
+                write!(f, "{} + ", name)?;
+            }
+        }
+        // The names of the key codes match their variant names.
+        write!(f, "{:?}", self.key_code)
+    }
+}
+
+impl FromStr for Hotkey {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parts = s.rsplit('+').map(str::trim);
+        let key_code = parts.next().ok_or(())?.parse()?;
+        let mut modifiers = Modifiers::empty();
+        for part in parts {
+            let &(modifier, _) = Modifiers::NAMES
+                .iter()
+                .find(|(_, name)| *name == part)
+                .ok_or(())?;
+            modifiers.insert(modifier);
+        }
+        Ok(Self::new(key_code, modifiers))
+    }
+}
+
+impl Serialize for Hotkey {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for Hotkey {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct Visitor;
+
+        impl<'de> de::Visitor<'de> for Visitor {
+            type Value = Hotkey;
+
+            fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str("a hotkey")
+            }
+
+            fn visit_str<E: de::Error>(self, v: &str) -> Result<Hotkey, E> {
+                v.parse()
+                    .map_err(|_| E::invalid_value(de::Unexpected::Str(v), &self))
+            }
+        }
+
+        deserializer.deserialize_str(Visitor)
+    }
+}
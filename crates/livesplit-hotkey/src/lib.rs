@@ -30,3 +30,6 @@ cfg_if::cfg_if! {
         pub use self::other::*;
     }
 }
+
+mod hotkey;
+pub use self::hotkey::{Hotkey, Modifiers};
@@ -0,0 +1,557 @@
+use super::{Error, KeyCode, Result};
+use crate::{Hotkey, Modifiers};
+
+use mio::{unix::SourceFd, Events, Interest, Poll, Token, Waker};
+use promising_future::{future_promise, Promise};
+use std::{
+    collections::hash_map::{Entry, HashMap},
+    fs::{self, File, OpenOptions},
+    io::{self, Read},
+    mem,
+    os::unix::{fs::OpenOptionsExt, io::AsRawFd},
+    path::PathBuf,
+    slice,
+    sync::mpsc::{channel, Sender},
+    thread::{self, JoinHandle},
+    time::{Duration, Instant},
+};
+
+const EV_KEY: u16 = 0x01;
+const KEY_RELEASED: i32 = 0;
+const KEY_PRESSED: i32 = 1;
+
+const KEY_LEFTCTRL: u16 = 29;
+const KEY_LEFTSHIFT: u16 = 42;
+const KEY_RIGHTSHIFT: u16 = 54;
+const KEY_LEFTALT: u16 = 56;
+const KEY_RIGHTCTRL: u16 = 97;
+const KEY_RIGHTALT: u16 = 100;
+const KEY_LEFTMETA: u16 = 125;
+const KEY_RIGHTMETA: u16 = 126;
+
+/// Input devices can be plugged in at any time, so the directory is scanned for
+/// new devices in this interval.
+const RESCAN_INTERVAL: Duration = Duration::from_secs(1);
+
+enum Message {
+    Register(
+        Hotkey,
+        Box<dyn FnMut() + Send + 'static>,
+        Promise<Result<()>>,
+    ),
+    Unregister(Hotkey, Promise<Result<()>>),
+    End,
+}
+
+const PING_TOKEN: Token = Token(0);
+
+pub struct Hook {
+    sender: Sender<Message>,
+    waker: Waker,
+    join_handle: Option<JoinHandle<Result<()>>>,
+}
+
+impl Drop for Hook {
+    fn drop(&mut self) {
+        self.sender.send(Message::End).ok();
+        self.waker.wake().ok();
+        if let Some(handle) = self.join_handle.take() {
+            handle.join().ok();
+        }
+    }
+}
+
+struct Device {
+    path: PathBuf,
+    file: File,
+}
+
+/// The input devices that are being listened to. Each device is registered
+/// with the token that corresponds to its index plus one, as the first token is
+/// used for waking up the thread.
+#[derive(Default)]
+struct Devices {
+    devices: Vec<Option<Device>>,
+}
+
+impl Devices {
+    /// Opens all the event devices that aren't open yet. Returns how many
+    /// devices exist and how many of them are open.
+    fn scan(&mut self, poll: &Poll) -> io::Result<(usize, usize)> {
+        let (mut found, mut open) = (0, 0);
+        for entry in fs::read_dir("/dev/input")? {
+            let path = entry?.path();
+            let is_event_device = matches!(
+                path.file_name().and_then(|n| n.to_str()),
+                Some(n) if n.starts_with("event")
+            );
+            if !is_event_device {
+                continue;
+            }
+            found += 1;
+
+            if self.devices.iter().flatten().any(|d| d.path == path) {
+                open += 1;
+                continue;
+            }
+
+            // Devices that can't be opened, most likely due to missing
+            // permissions, are skipped.
+            let file = match OpenOptions::new()
+                .read(true)
+                .custom_flags(libc::O_NONBLOCK)
+                .open(&path)
+            {
+                Ok(file) => file,
+                Err(_) => continue,
+            };
+
+            let index = match self.devices.iter().position(Option::is_none) {
+                Some(index) => index,
+                None => {
+                    self.devices.push(None);
+                    self.devices.len() - 1
+                }
+            };
+            poll.registry().register(
+                &mut SourceFd(&file.as_raw_fd()),
+                Token(index + 1),
+                Interest::READABLE,
+            )?;
+            self.devices[index] = Some(Device { path, file });
+            open += 1;
+        }
+        Ok((found, open))
+    }
+
+    /// Reads all the pending events of the device with the token provided. The
+    /// device is closed if it got unplugged.
+    fn read(&mut self, poll: &Poll, token: Token, events: &mut Vec<libc::input_event>) {
+        let slot = match self.devices.get_mut(token.0 - 1) {
+            Some(slot) => slot,
+            None => return,
+        };
+        let device = match slot {
+            Some(device) => device,
+            None => return,
+        };
+
+        let mut buf = [input_event(); 64];
+        loop {
+            let bytes = unsafe {
+                slice::from_raw_parts_mut(
+                    buf.as_mut_ptr() as *mut u8,
+                    buf.len() * mem::size_of::<libc::input_event>(),
+                )
+            };
+            match device.file.read(bytes) {
+                Ok(len) if len > 0 => {
+                    let count = len / mem::size_of::<libc::input_event>();
+                    events.extend_from_slice(&buf[..count]);
+                }
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+                Err(e) if e.kind() == io::ErrorKind::WouldBlock => return,
+                _ => break,
+            }
+        }
+
+        // The device is gone, so it doesn't need to be listened to anymore.
+        poll.registry()
+            .deregister(&mut SourceFd(&device.file.as_raw_fd()))
+            .ok();
+        *slot = None;
+    }
+}
+
+fn input_event() -> libc::input_event {
+    // The layout of the time stamp differs between the architectures.
+    unsafe { mem::zeroed() }
+}
+
+/// Keeps track of the modifier keys that are held down across all devices.
+#[derive(Default)]
+struct ModifierState {
+    held: Vec<u16>,
+}
+
+impl ModifierState {
+    fn modifiers(&self) -> Modifiers {
+        let mut modifiers = Modifiers::empty();
+        for &code in &self.held {
+            modifiers.insert(match code {
+                KEY_LEFTSHIFT | KEY_RIGHTSHIFT => Modifiers::SHIFT,
+                KEY_LEFTCTRL | KEY_RIGHTCTRL => Modifiers::CONTROL,
+                KEY_LEFTALT | KEY_RIGHTALT => Modifiers::ALT,
+                KEY_LEFTMETA | KEY_RIGHTMETA => Modifiers::META,
+                _ => continue,
+            });
+        }
+        modifiers
+    }
+
+    fn update(&mut self, code: u16, value: i32) {
+        match code {
+            KEY_LEFTSHIFT | KEY_RIGHTSHIFT | KEY_LEFTCTRL | KEY_RIGHTCTRL | KEY_LEFTALT
+            | KEY_RIGHTALT | KEY_LEFTMETA | KEY_RIGHTMETA => {}
+            _ => return,
+        }
+        match value {
+            KEY_PRESSED if !self.held.contains(&code) => self.held.push(code),
+            KEY_RELEASED => self.held.retain(|&held| held != code),
+            _ => {}
+        }
+    }
+}
+
+impl Hook {
+    pub fn new() -> Result<Self> {
+        let (sender, receiver) = channel();
+
+        let mut poll = Poll::new().map_err(|_| Error::EPoll)?;
+        let waker = Waker::new(poll.registry(), PING_TOKEN).map_err(|_| Error::EPoll)?;
+
+        let mut devices = Devices::default();
+        match devices.scan(&poll) {
+            // If there are devices, but none of them can be read, the user most
+            // likely lacks the permissions to do so.
+            Ok((found, 0)) if found > 0 => return Err(Error::OpenInputDevices),
+            Ok(_) => {}
+            Err(_) => return Err(Error::OpenInputDevices),
+        }
+
+        let join_handle = thread::spawn(move || -> Result<()> {
+            let mut events = Events::with_capacity(1024);
+            let mut input_events = Vec::new();
+            let mut hotkeys = HashMap::new();
+            let mut modifiers = ModifierState::default();
+            let mut last_scan = Instant::now();
+
+            'event_loop: loop {
+                let timeout = RESCAN_INTERVAL
+                    .checked_sub(last_scan.elapsed())
+                    .unwrap_or_default();
+                if poll.poll(&mut events, Some(timeout)).is_err() {
+                    return Err(Error::EPoll);
+                }
+
+                for mio_event in &events {
+                    if mio_event.token() == PING_TOKEN {
+                        for message in receiver.try_iter() {
+                            match message {
+                                Message::Register(hotkey, callback, promise) => {
+                                    if let Entry::Vacant(vacant) = hotkeys.entry(hotkey) {
+                                        vacant.insert(callback);
+                                        promise.set(Ok(()));
+                                    } else {
+                                        promise.set(Err(Error::AlreadyRegistered));
+                                    }
+                                }
+                                Message::Unregister(hotkey, promise) => {
+                                    if hotkeys.remove(&hotkey).is_some() {
+                                        promise.set(Ok(()));
+                                    } else {
+                                        promise.set(Err(Error::NotRegistered));
+                                    }
+                                }
+                                Message::End => {
+                                    break 'event_loop;
+                                }
+                            }
+                        }
+                    } else {
+                        devices.read(&poll, mio_event.token(), &mut input_events);
+                    }
+                }
+
+                for event in input_events.drain(..) {
+                    if event.type_ != EV_KEY {
+                        continue;
+                    }
+                    // Key repeats are ignored, so holding down a hotkey only
+                    // triggers it once.
+                    if event.value == KEY_PRESSED {
+                        if let Some(key_code) = key_code(event.code) {
+                            let hotkey = Hotkey::new(key_code, modifiers.modifiers());
+                            if let Some(callback) = hotkeys.get_mut(&hotkey) {
+                                callback();
+                            }
+                        }
+                    }
+                    modifiers.update(event.code, event.value);
+                }
+
+                if last_scan.elapsed() >= RESCAN_INTERVAL {
+                    devices.scan(&poll).ok();
+                    last_scan = Instant::now();
+                }
+            }
+
+            Ok(())
+        });
+
+        Ok(Hook {
+            sender,
+            waker,
+            join_handle: Some(join_handle),
+        })
+    }
+
+    pub fn register<F>(&self, hotkey: Hotkey, callback: F) -> Result<()>
+    where
+        F: FnMut() + Send + 'static,
+    {
+        let (future, promise) = future_promise();
+
+        self.sender
+            .send(Message::Register(hotkey, Box::new(callback), promise))
+            .map_err(|_| Error::ThreadStopped)?;
+
+        self.waker.wake().map_err(|_| Error::ThreadStopped)?;
+
+        future.value().ok_or(Error::ThreadStopped)?
+    }
+
+    pub fn unregister(&self, hotkey: Hotkey) -> Result<()> {
+        let (future, promise) = future_promise();
+
+        self.sender
+            .send(Message::Unregister(hotkey, promise))
+            .map_err(|_| Error::ThreadStopped)?;
+
+        self.waker.wake().map_err(|_| Error::ThreadStopped)?;
+
+        future.value().ok_or(Error::ThreadStopped)?
+    }
+}
+
+/// Maps the evdev key codes to the key codes of the X11 key symbols that the
+/// keys produce on a US keyboard layout. The evdev key codes describe the
+/// physical keys, so the keyboard layout that is in use doesn't matter.
+fn key_code(code: u16) -> Option<KeyCode> {
+    use self::KeyCode::*;
+    Some(match code {
+        1 => Escape,
+        2 => D1,
+        3 => D2,
+        4 => D3,
+        5 => D4,
+        6 => D5,
+        7 => D6,
+        8 => D7,
+        9 => D8,
+        10 => D9,
+        11 => D0,
+        12 => Minus,
+        13 => Equal,
+        14 => BackSpace,
+        15 => Tab,
+        16 => Q,
+        17 => W,
+        18 => E,
+        19 => R,
+        20 => T,
+        21 => Y,
+        22 => U,
+        23 => I,
+        24 => O,
+        25 => P,
+        26 => BracketLeft,
+        27 => BracketRight,
+        28 => Return,
+        KEY_LEFTCTRL => ControlL,
+        30 => A,
+        31 => S,
+        32 => D,
+        33 => F,
+        34 => G,
+        35 => H,
+        36 => J,
+        37 => K,
+        38 => L,
+        39 => Semicolon,
+        40 => Apostrophe,
+        41 => Grave,
+        KEY_LEFTSHIFT => ShiftL,
+        43 => Backslash,
+        44 => Z,
+        45 => X,
+        46 => C,
+        47 => V,
+        48 => B,
+        49 => N,
+        50 => M,
+        51 => Comma,
+        52 => Period,
+        53 => Slash,
+        KEY_RIGHTSHIFT => ShiftR,
+        55 => NumpadMultiply,
+        KEY_LEFTALT => AltL,
+        57 => Space,
+        58 => CapsLock,
+        59 => F1,
+        60 => F2,
+        61 => F3,
+        62 => F4,
+        63 => F5,
+        64 => F6,
+        65 => F7,
+        66 => F8,
+        67 => F9,
+        68 => F10,
+        69 => NumLock,
+        70 => ScrollLock,
+        71 => Numpad7,
+        72 => Numpad8,
+        73 => Numpad9,
+        74 => NumpadSubtract,
+        75 => Numpad4,
+        76 => Numpad5,
+        77 => Numpad6,
+        78 => NumpadAdd,
+        79 => Numpad1,
+        80 => Numpad2,
+        81 => Numpad3,
+        82 => Numpad0,
+        83 => NumpadDecimal,
+        86 => Less,
+        87 => F11,
+        88 => F12,
+        96 => NumpadEnter,
+        KEY_RIGHTCTRL => ControlR,
+        98 => NumpadDivide,
+        99 => Print,
+        KEY_RIGHTALT => AltR,
+        102 => Home,
+        103 => Up,
+        104 => PageUp,
+        105 => Left,
+        106 => Right,
+        107 => End,
+        108 => Down,
+        109 => PageDown,
+        110 => Insert,
+        111 => Delete,
+        117 => NumpadEqual,
+        119 => Pause,
+        KEY_LEFTMETA => SuperL,
+        KEY_RIGHTMETA => SuperR,
+        127 => Menu,
+        183 => F13,
+        184 => F14,
+        185 => F15,
+        186 => F16,
+        187 => F17,
+        188 => F18,
+        189 => F19,
+        190 => F20,
+        191 => F21,
+        192 => F22,
+        193 => F23,
+        194 => F24,
+        _ => return None,
+    })
+}
+
+#[test]
+fn modifier_state_ignores_other_keys() {
+    let state = ModifierState {
+        held: vec![KEY_LEFTCTRL, 79],
+    };
+    assert_eq!(state.modifiers(), Modifiers::CONTROL);
+}
+
+// A virtual keyboard is created through uinput, which is only possible with
+// write access to /dev/uinput.
+#[test]
+#[ignore]
+fn test() {
+    use std::{io::Write, sync::mpsc};
+
+    const UI_SET_EVBIT: libc::c_ulong = 0x4004_5564;
+    const UI_SET_KEYBIT: libc::c_ulong = 0x4004_5565;
+    const UI_DEV_CREATE: libc::c_ulong = 0x5501;
+    const UI_DEV_DESTROY: libc::c_ulong = 0x5502;
+    const EV_SYN: u16 = 0x00;
+    const KEY_KP1: u16 = 79;
+
+    let mut uinput = OpenOptions::new().write(true).open("/dev/uinput").unwrap();
+    let fd = uinput.as_raw_fd();
+    unsafe {
+        assert_eq!(libc::ioctl(fd, UI_SET_EVBIT, EV_KEY as libc::c_int), 0);
+        assert_eq!(libc::ioctl(fd, UI_SET_KEYBIT, KEY_KP1 as libc::c_int), 0);
+        assert_eq!(
+            libc::ioctl(fd, UI_SET_KEYBIT, KEY_LEFTCTRL as libc::c_int),
+            0
+        );
+    }
+
+    // struct uinput_user_dev: The name, the bus type, vendor, product and
+    // version, the maximum amount of force feedback effects and the absolute
+    // axis information.
+    let mut device = vec![0; 80 + 8 + 4 + 4 * 64 * 4];
+    let name = b"livesplit-hotkey-test";
+    device[..name.len()].copy_from_slice(name);
+    device[80..82].copy_from_slice(&0x06u16.to_ne_bytes());
+    uinput.write_all(&device).unwrap();
+    unsafe {
+        assert_eq!(libc::ioctl(fd, UI_DEV_CREATE), 0);
+    }
+
+    let mut send = |type_, code, value| {
+        let event = libc::input_event {
+            type_,
+            code,
+            value,
+            ..input_event()
+        };
+        let bytes = unsafe {
+            slice::from_raw_parts(
+                &event as *const libc::input_event as *const u8,
+                mem::size_of::<libc::input_event>(),
+            )
+        };
+        uinput.write_all(bytes).unwrap();
+    };
+    let mut press = |codes: &[u16]| {
+        for &code in codes {
+            send(EV_KEY, code, KEY_PRESSED);
+            send(EV_SYN, 0, 0);
+        }
+        for &code in codes.iter().rev() {
+            send(EV_KEY, code, KEY_RELEASED);
+            send(EV_SYN, 0, 0);
+        }
+    };
+
+    // The device node needs a moment to show up.
+    thread::sleep(Duration::from_millis(500));
+
+    let hook = Hook::new().unwrap();
+    let (sender, receiver) = mpsc::channel();
+    let plain = sender.clone();
+    hook.register(KeyCode::Numpad1.into(), move || {
+        plain.send("Numpad1").unwrap()
+    })
+    .unwrap();
+    hook.register(
+        Hotkey::new(KeyCode::Numpad1, Modifiers::CONTROL),
+        move || sender.send("Control + Numpad1").unwrap(),
+    )
+    .unwrap();
+
+    let timeout = Duration::from_secs(5);
+    press(&[KEY_KP1]);
+    assert_eq!(receiver.recv_timeout(timeout), Ok("Numpad1"));
+    press(&[KEY_LEFTCTRL, KEY_KP1]);
+    assert_eq!(receiver.recv_timeout(timeout), Ok("Control + Numpad1"));
+
+    hook.unregister(KeyCode::Numpad1.into()).unwrap();
+    press(&[KEY_KP1]);
+    press(&[KEY_LEFTCTRL, KEY_KP1]);
+    assert_eq!(receiver.recv_timeout(timeout), Ok("Control + Numpad1"));
+    assert!(receiver.try_recv().is_err());
+
+    unsafe {
+        libc::ioctl(fd, UI_DEV_DESTROY);
+    }
+}
@@ -0,0 +1,319 @@
+use super::{Error, Result};
+use crate::{Hotkey, Modifiers};
+
+use mio::{unix::SourceFd, Events, Interest, Poll, Token, Waker};
+use promising_future::{future_promise, Promise};
+use std::{
+    collections::hash_map::{Entry, HashMap},
+    mem,
+    os::raw::{c_int, c_uint},
+    ptr, slice,
+    sync::mpsc::{channel, Sender},
+    thread::{self, JoinHandle},
+};
+use x11_dl::{
+    keysym::{XK_Num_Lock, XK_Scroll_Lock},
+    xlib::{
+        AnyKey, AnyModifier, ControlMask, Display, GrabModeAsync, KeyPress, LockMask, Mod1Mask,
+        Mod4Mask, ShiftMask, XErrorEvent, XKeyEvent, Xlib,
+    },
+};
+
+enum Message {
+    Register(
+        Hotkey,
+        Box<dyn FnMut() + Send + 'static>,
+        Promise<Result<()>>,
+    ),
+    Unregister(Hotkey, Promise<Result<()>>),
+    End,
+}
+
+const X_TOKEN: Token = Token(0);
+const PING_TOKEN: Token = Token(1);
+
+pub struct Hook {
+    sender: Sender<Message>,
+    waker: Waker,
+    join_handle: Option<JoinHandle<Result<()>>>,
+}
+
+impl Drop for Hook {
+    fn drop(&mut self) {
+        self.sender.send(Message::End).ok();
+        self.waker.wake().ok();
+        if let Some(handle) = self.join_handle.take() {
+            handle.join().ok();
+        }
+    }
+}
+
+unsafe fn ungrab_all(xlib: &Xlib, display: *mut Display) {
+    let screencount = (xlib.XScreenCount)(display);
+    for screen in 0..screencount {
+        let rootwindow = (xlib.XRootWindow)(display, screen);
+        for _i in 0..rootwindow {
+            // FIXME: This loop looks very stupid, but it somehow it prevents
+            // button presses getting lost.
+            (xlib.XUngrabKey)(display, AnyKey, AnyModifier, rootwindow);
+        }
+    }
+}
+
+/// The modifiers that are relevant for hotkeys. All other modifiers, most
+/// notably Num Lock and Caps Lock, are ignored.
+const MODIFIER_MASK: c_uint = ShiftMask | ControlMask | Mod1Mask | Mod4Mask;
+
+/// Looks up the modifier mask that the key with the key symbol provided is
+/// mapped to. Returns 0 if the key isn't mapped to any modifier.
+unsafe fn lock_mask(xlib: &Xlib, display: *mut Display, keysym: c_uint) -> c_uint {
+    let code = (xlib.XKeysymToKeycode)(display, keysym as _);
+    let map = (xlib.XGetModifierMapping)(display);
+    if map.is_null() {
+        return 0;
+    }
+    let mut mask = 0;
+    let keys_per_modifier = (*map).max_keypermod as usize;
+    if code != 0 && keys_per_modifier != 0 {
+        let codes = slice::from_raw_parts((*map).modifiermap, 8 * keys_per_modifier);
+        if let Some(index) = codes
+            .chunks(keys_per_modifier)
+            .position(|codes| codes.contains(&code))
+        {
+            mask = 1 << index;
+        }
+    }
+    (xlib.XFreeModifiermap)(map);
+    mask
+}
+
+/// Grabbing a key only matches the exact modifier mask, so each hotkey needs to
+/// be grabbed once for each combination of the lock modifiers that should be
+/// ignored. These are Caps Lock and whichever modifiers Num Lock and Scroll
+/// Lock are mapped to.
+unsafe fn ignored_masks(xlib: &Xlib, display: *mut Display) -> Vec<c_uint> {
+    let locks = [
+        LockMask,
+        lock_mask(xlib, display, XK_Num_Lock),
+        lock_mask(xlib, display, XK_Scroll_Lock),
+    ];
+    let mut masks = vec![0];
+    for &lock in &locks {
+        if lock & MODIFIER_MASK != 0 {
+            continue;
+        }
+        for index in 0..masks.len() {
+            let mask = masks[index] | lock;
+            if !masks.contains(&mask) {
+                masks.push(mask);
+            }
+        }
+    }
+    masks
+}
+
+fn modifier_mask(modifiers: Modifiers) -> c_uint {
+    let mut mask = 0;
+    for &(modifier, modifier_mask) in &[
+        (Modifiers::SHIFT, ShiftMask),
+        (Modifiers::CONTROL, ControlMask),
+        (Modifiers::ALT, Mod1Mask),
+        (Modifiers::META, Mod4Mask),
+    ] {
+        if modifiers.contains(modifier) {
+            mask |= modifier_mask;
+        }
+    }
+    mask
+}
+
+unsafe fn grab_all(xlib: &Xlib, display: *mut Display, keylist: Vec<(c_uint, c_uint)>) {
+    ungrab_all(xlib, display);
+    let ignored_masks = ignored_masks(xlib, display);
+    let screencount = (xlib.XScreenCount)(display);
+    for screen in 0..screencount {
+        let rootwindow = (xlib.XRootWindow)(display, screen);
+        for &(code, mask) in &keylist {
+            for &ignored in &ignored_masks {
+                (xlib.XGrabKey)(
+                    display,
+                    code as _,
+                    mask | ignored,
+                    rootwindow,
+                    false as _,
+                    GrabModeAsync,
+                    GrabModeAsync,
+                );
+            }
+        }
+    }
+}
+
+unsafe extern "C" fn handle_error(_: *mut Display, _: *mut XErrorEvent) -> c_int {
+    0
+}
+
+impl Hook {
+    pub fn new() -> Result<Self> {
+        unsafe {
+            let (sender, receiver) = channel();
+
+            let xlib = Xlib::open().map_err(|_| Error::NoXLib)?;
+            (xlib.XSetErrorHandler)(Some(handle_error));
+
+            let display = (xlib.XOpenDisplay)(ptr::null());
+            if display.is_null() {
+                return Err(Error::OpenXServerConnection);
+            }
+
+            let fd = (xlib.XConnectionNumber)(display) as std::os::unix::io::RawFd;
+            let mut poll = Poll::new().map_err(|_| Error::EPoll)?;
+
+            let waker = Waker::new(poll.registry(), PING_TOKEN).map_err(|_| Error::EPoll)?;
+
+            poll.registry()
+                .register(
+                    &mut SourceFd(&fd),
+                    X_TOKEN,
+                    Interest::READABLE | Interest::WRITABLE,
+                )
+                .map_err(|_| Error::EPoll)?;
+
+            struct XData(Xlib, *mut Display);
+            unsafe impl Send for XData {}
+            let xdata = XData(xlib, display);
+
+            let join_handle = thread::spawn(move || -> Result<()> {
+                let XData(xlib, display) = xdata;
+
+                let mut result = Ok(());
+                let mut events = Events::with_capacity(1024);
+                let mut hotkeys = HashMap::new();
+
+                'event_loop: loop {
+                    if poll.poll(&mut events, None).is_err() {
+                        result = Err(Error::EPoll);
+                        break 'event_loop;
+                    }
+
+                    for mio_event in &events {
+                        if mio_event.token() == PING_TOKEN {
+                            for message in receiver.try_iter() {
+                                match message {
+                                    Message::Register(hotkey, callback, promise) => {
+                                        let code =
+                                            (xlib.XKeysymToKeycode)(display, hotkey.key_code as _)
+                                                as c_uint;
+                                        let key = (code, modifier_mask(hotkey.modifiers));
+
+                                        if let Entry::Vacant(vacant) = hotkeys.entry(key) {
+                                            vacant.insert(callback);
+                                            promise.set(Ok(()));
+                                        } else {
+                                            promise.set(Err(Error::AlreadyRegistered));
+                                        }
+                                        let keys = hotkeys.keys().copied().collect();
+                                        grab_all(&xlib, display, keys);
+                                    }
+                                    Message::Unregister(hotkey, promise) => {
+                                        let code =
+                                            (xlib.XKeysymToKeycode)(display, hotkey.key_code as _)
+                                                as c_uint;
+                                        let key = (code, modifier_mask(hotkey.modifiers));
+
+                                        if hotkeys.remove(&key).is_some() {
+                                            promise.set(Ok(()));
+                                        } else {
+                                            promise.set(Err(Error::NotRegistered));
+                                        }
+                                        let keys = hotkeys.keys().copied().collect();
+                                        grab_all(&xlib, display, keys);
+                                    }
+                                    Message::End => {
+                                        break 'event_loop;
+                                    }
+                                }
+                            }
+                        } else if mio_event.token() == X_TOKEN {
+                            while (xlib.XPending)(display) != 0 {
+                                let mut event = mem::MaybeUninit::uninit();
+                                (xlib.XNextEvent)(display, event.as_mut_ptr());
+                                let event = event.assume_init();
+                                if event.get_type() == KeyPress {
+                                    let event: &XKeyEvent = event.as_ref();
+                                    let key = (event.keycode, event.state & MODIFIER_MASK);
+                                    if let Some(callback) = hotkeys.get_mut(&key) {
+                                        callback();
+                                    }
+                                    // FIXME: We should check else here: these amount to lost
+                                    // keypresses.
+                                }
+                            }
+                        }
+                    }
+                }
+
+                ungrab_all(&xlib, display);
+
+                (xlib.XCloseDisplay)(display);
+
+                result
+            });
+
+            Ok(Hook {
+                sender,
+                waker,
+                join_handle: Some(join_handle),
+            })
+        }
+    }
+
+    pub fn register<F>(&self, hotkey: Hotkey, callback: F) -> Result<()>
+    where
+        F: FnMut() + Send + 'static,
+    {
+        let (future, promise) = future_promise();
+
+        self.sender
+            .send(Message::Register(hotkey, Box::new(callback), promise))
+            .map_err(|_| Error::ThreadStopped)?;
+
+        self.waker.wake().map_err(|_| Error::ThreadStopped)?;
+
+        future.value().ok_or(Error::ThreadStopped)?
+    }
+
+    pub fn unregister(&self, hotkey: Hotkey) -> Result<()> {
+        let (future, promise) = future_promise();
+
+        self.sender
+            .send(Message::Unregister(hotkey, promise))
+            .map_err(|_| Error::ThreadStopped)?;
+
+        self.waker.wake().map_err(|_| Error::ThreadStopped)?;
+
+        future.value().ok_or(Error::ThreadStopped)?
+    }
+}
+
+#[test]
+fn test() {
+    use super::KeyCode;
+
+    let hook = Hook::new().unwrap();
+    hook.register(KeyCode::Numpad1.into(), || println!("A"))
+        .unwrap();
+    println!("Press Numpad1");
+    thread::sleep(std::time::Duration::from_secs(5));
+    hook.unregister(KeyCode::Numpad1.into()).unwrap();
+    hook.register(KeyCode::Numpad4.into(), || println!("B"))
+        .unwrap();
+    println!("Press Numpad4");
+    thread::sleep(std::time::Duration::from_secs(5));
+    hook.unregister(KeyCode::Numpad4.into()).unwrap();
+    let hotkey = Hotkey::new(KeyCode::Numpad1, Modifiers::CONTROL);
+    hook.register(hotkey, || println!("C")).unwrap();
+    println!("Press Ctrl + Numpad1");
+    thread::sleep(std::time::Duration::from_secs(5));
+    hook.unregister(hotkey).unwrap();
+}
@@ -71,6 +71,39 @@ bitflags::bitflags! {
     }
 }
 
+bitflags::bitflags! {
+    #[repr(transparent)]
+    pub struct EventFlags: u64 {
+        /// Indicates that the Caps Lock key is down for a keyboard, mouse, or
+        /// flag-changed event.
+        const ALPHA_SHIFT = 0x0001_0000;
+        /// Indicates that the Shift key is down for a keyboard, mouse, or
+        /// flag-changed event.
+        const SHIFT = 0x0002_0000;
+        /// Indicates that the Control key is down for a keyboard, mouse, or
+        /// flag-changed event.
+        const CONTROL = 0x0004_0000;
+        /// Indicates that the Alt or Option key is down for a keyboard, mouse,
+        /// or flag-changed event.
+        const ALTERNATE = 0x0008_0000;
+        /// Indicates that the Command key is down for a keyboard, mouse, or
+        /// flag-changed event.
+        const COMMAND = 0x0010_0000;
+        /// Indicates that the Help modifier key is down for a keyboard, mouse,
+        /// or flag-changed event.
+        const HELP = 0x0040_0000;
+        /// Indicates that the Fn (Function) key is down for a keyboard, mouse,
+        /// or flag-changed event.
+        const SECONDARY_FN = 0x0080_0000;
+        /// Identifies key events from the numeric keypad area on extended
+        /// keyboards.
+        const NUMERIC_PAD = 0x0020_0000;
+        /// Indicates that mouse and pen movement events are not being
+        /// coalesced.
+        const NON_COALESCED = 0x0000_0100;
+    }
+}
+
 #[repr(u32)]
 #[derive(Clone, Copy, Debug)]
 #[non_exhaustive]
@@ -197,4 +230,6 @@ extern "C" {
     ) -> MachPortRef;
 
     pub fn CGEventGetIntegerValueField(event: EventRef, field: EventField) -> i64;
+
+    pub fn CGEventGetFlags(event: EventRef) -> EventFlags;
 }
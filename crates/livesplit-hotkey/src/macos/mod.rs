@@ -9,10 +9,11 @@ use self::{
         CFRunLoopRun,
     },
     cg::{
-        CGEventTapCreate, EventMask, EventRef, EventTapLocation, EventTapOptions,
+        CGEventTapCreate, EventFlags, EventMask, EventRef, EventTapLocation, EventTapOptions,
         EventTapPlacement, EventTapProxy, EventType,
     },
 };
+use crate::{Hotkey, Modifiers};
 use cg::EventField;
 use parking_lot::Mutex;
 use std::{
@@ -51,7 +52,7 @@ struct RunLoop(cf::RunLoopRef);
 
 unsafe impl Send for RunLoop {}
 
-type RegisteredKeys = Mutex<HashMap<KeyCode, Box<dyn FnMut() + Send + 'static>>>;
+type RegisteredKeys = Mutex<HashMap<Hotkey, Box<dyn FnMut() + Send + 'static>>>;
 
 pub struct Hook {
     event_loop: RunLoop,
@@ -131,7 +132,7 @@ impl Hook {
         })
     }
 
-    pub fn register<F>(&self, hotkey: KeyCode, callback: F) -> Result<()>
+    pub fn register<F>(&self, hotkey: Hotkey, callback: F) -> Result<()>
     where
         F: FnMut() + Send + 'static,
     {
@@ -143,7 +144,7 @@ impl Hook {
         }
     }
 
-    pub fn unregister(&self, hotkey: KeyCode) -> Result<()> {
+    pub fn unregister(&self, hotkey: Hotkey) -> Result<()> {
         if self.hotkeys.lock().remove(&hotkey).is_some() {
             Ok(())
         } else {
@@ -283,9 +284,16 @@ unsafe extern "C" fn callback(
             _ => return event,
         };
 
+        let flags = cg::CGEventGetFlags(event);
+        let mut modifiers = Modifiers::empty();
+        modifiers.set(Modifiers::SHIFT, flags.contains(EventFlags::SHIFT));
+        modifiers.set(Modifiers::CONTROL, flags.contains(EventFlags::CONTROL));
+        modifiers.set(Modifiers::ALT, flags.contains(EventFlags::ALTERNATE));
+        modifiers.set(Modifiers::META, flags.contains(EventFlags::COMMAND));
+
         let hotkeys = user_info as *const RegisteredKeys;
         let hotkeys = &*hotkeys;
-        if let Some(callback) = hotkeys.lock().get_mut(&key_code) {
+        if let Some(callback) = hotkeys.lock().get_mut(&Hotkey::new(key_code, modifiers)) {
             callback();
         }
     }
@@ -13,18 +13,19 @@ impl Hook {
         Ok(Hook)
     }
 
-    pub fn register<F>(&self, _: KeyCode, _: F) -> Result<()>
+    pub fn register<F>(&self, _: Hotkey, _: F) -> Result<()>
     where
         F: FnMut() + Send + 'static,
     {
         Ok(())
     }
 
-    pub fn unregister(&self, _: KeyCode) -> Result<()> {
+    pub fn unregister(&self, _: Hotkey) -> Result<()> {
         Ok(())
     }
 }
 
+use crate::Hotkey;
 use core::{result::Result as StdResult, str::FromStr};
 
 impl FromStr for KeyCode {
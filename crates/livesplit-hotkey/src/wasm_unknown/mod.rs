@@ -1,6 +1,8 @@
 mod key_code;
 pub use self::key_code::KeyCode;
 
+use crate::Hotkey;
+
 use std::collections::hash_map::{Entry, HashMap};
 use std::sync::{Arc, Mutex};
 use std::{slice, str};
@@ -16,7 +18,7 @@ pub type Result<T> = std::result::Result<T, Error>;
 pub type EventListenerHandle = Box<dyn Fn(&str)>;
 
 pub struct Hook {
-    hotkeys: Arc<Mutex<HashMap<KeyCode, Box<dyn FnMut() + Send + 'static>>>>,
+    hotkeys: Arc<Mutex<HashMap<Hotkey, Box<dyn FnMut() + Send + 'static>>>>,
     event: Option<Box<EventListenerHandle>>,
 }
 
@@ -48,7 +50,7 @@ pub unsafe extern "C" fn HotkeyHook_callback(
 impl Hook {
     pub fn new() -> Result<Self> {
         let hotkeys = Arc::new(Mutex::new(HashMap::<
-            KeyCode,
+            Hotkey,
             Box<dyn FnMut() + Send + 'static>,
         >::new()));
 
@@ -71,7 +73,7 @@ impl Hook {
         })
     }
 
-    pub fn register<F>(&self, hotkey: KeyCode, callback: F) -> Result<()>
+    pub fn register<F>(&self, hotkey: Hotkey, callback: F) -> Result<()>
     where
         F: FnMut() + Send + 'static,
     {
@@ -83,7 +85,7 @@ impl Hook {
         }
     }
 
-    pub fn unregister(&self, hotkey: KeyCode) -> Result<()> {
+    pub fn unregister(&self, hotkey: Hotkey) -> Result<()> {
         if self.hotkeys.lock().unwrap().remove(&hotkey).is_some() {
             Ok(())
         } else {
@@ -1,6 +1,8 @@
 mod key_code;
 pub use self::key_code::KeyCode;
 
+use crate::{Hotkey, Modifiers};
+
 use wasm_bindgen::{prelude::*, JsCast};
 use web_sys::{window, Gamepad, GamepadButton, KeyboardEvent};
 
@@ -21,7 +23,7 @@ pub enum Error {
 pub type Result<T> = std::result::Result<T, Error>;
 
 pub struct Hook {
-    hotkeys: Arc<Mutex<HashMap<KeyCode, Box<dyn FnMut() + Send + 'static>>>>,
+    hotkeys: Arc<Mutex<HashMap<Hotkey, Box<dyn FnMut() + Send + 'static>>>>,
     keyboard_callback: Closure<dyn FnMut(KeyboardEvent)>,
     gamepad_callback: Closure<dyn FnMut()>,
     interval_id: Cell<Option<i32>>,
@@ -68,7 +70,7 @@ static GAMEPAD_BUTTONS: [KeyCode; TOTAL_BUTTONS] = [
 impl Hook {
     pub fn new() -> Result<Self> {
         let hotkeys = Arc::new(Mutex::new(HashMap::<
-            KeyCode,
+            Hotkey,
             Box<dyn FnMut() + Send + 'static>,
         >::new()));
 
@@ -77,7 +79,13 @@ impl Hook {
         let hotkey_map = hotkeys.clone();
         let keyboard_callback = Closure::wrap(Box::new(move |event: KeyboardEvent| {
             if let Ok(code) = event.code().parse() {
-                if let Some(callback) = hotkey_map.lock().unwrap().get_mut(&code) {
+                let mut modifiers = Modifiers::empty();
+                modifiers.set(Modifiers::SHIFT, event.shift_key());
+                modifiers.set(Modifiers::CONTROL, event.ctrl_key());
+                modifiers.set(Modifiers::ALT, event.alt_key());
+                modifiers.set(Modifiers::META, event.meta_key());
+                let hotkey = Hotkey::new(code, modifiers);
+                if let Some(callback) = hotkey_map.lock().unwrap().get_mut(&hotkey) {
                     callback();
                 }
             }
@@ -113,7 +121,7 @@ impl Hook {
                                 let pressed = button.pressed();
                                 if pressed && !*state {
                                     if let Some(callback) =
-                                        hotkey_map.lock().unwrap().get_mut(&code)
+                                        hotkey_map.lock().unwrap().get_mut(&Hotkey::from(code))
                                     {
                                         callback();
                                     }
@@ -134,12 +142,12 @@ impl Hook {
         })
     }
 
-    pub fn register<F>(&self, hotkey: KeyCode, callback: F) -> Result<()>
+    pub fn register<F>(&self, hotkey: Hotkey, callback: F) -> Result<()>
     where
         F: FnMut() + Send + 'static,
     {
         if let Entry::Vacant(vacant) = self.hotkeys.lock().unwrap().entry(hotkey) {
-            if GAMEPAD_BUTTONS.contains(&hotkey) && self.interval_id.get().is_none() {
+            if GAMEPAD_BUTTONS.contains(&hotkey.key_code) && self.interval_id.get().is_none() {
                 let interval_id = window()
                     .ok_or(Error::FailedToCreateHook)?
                     .set_interval_with_callback_and_timeout_and_arguments_0(
@@ -156,7 +164,7 @@ impl Hook {
         }
     }
 
-    pub fn unregister(&self, hotkey: KeyCode) -> Result<()> {
+    pub fn unregister(&self, hotkey: Hotkey) -> Result<()> {
         if self.hotkeys.lock().unwrap().remove(&hotkey).is_some() {
             Ok(())
         } else {
@@ -1,6 +1,8 @@
 mod key_code;
 pub use self::key_code::KeyCode;
 
+use crate::{Hotkey, Modifiers};
+
 use parking_lot::Mutex;
 use std::{
     cell::RefCell,
@@ -22,8 +24,9 @@ use winapi::{
         libloaderapi::GetModuleHandleW,
         processthreadsapi::GetCurrentThreadId,
         winuser::{
-            CallNextHookEx, GetMessageW, PostThreadMessageW, SetWindowsHookExW,
-            UnhookWindowsHookEx, KBDLLHOOKSTRUCT, WH_KEYBOARD_LL, WM_KEYDOWN,
+            CallNextHookEx, GetAsyncKeyState, GetMessageW, PostThreadMessageW, SetWindowsHookExW,
+            UnhookWindowsHookEx, KBDLLHOOKSTRUCT, VK_CONTROL, VK_LWIN, VK_MENU, VK_RWIN, VK_SHIFT,
+            WH_KEYBOARD_LL, WM_KEYDOWN, WM_SYSKEYDOWN,
         },
     },
 };
@@ -43,7 +46,7 @@ pub type Result<T> = std::result::Result<T, Error>;
 
 pub struct Hook {
     thread_id: DWORD,
-    hotkeys: Arc<Mutex<HashMap<KeyCode, Box<dyn FnMut() + Send + 'static>>>>,
+    hotkeys: Arc<Mutex<HashMap<Hotkey, Box<dyn FnMut() + Send + 'static>>>>,
 }
 
 impl Drop for Hook {
@@ -56,13 +59,23 @@ impl Drop for Hook {
 
 struct State {
     hook: HHOOK,
-    events: Sender<KeyCode>,
+    events: Sender<Hotkey>,
 }
 
 thread_local! {
     static STATE: RefCell<Option<State>> = RefCell::new(None);
 }
 
+fn current_modifiers() -> Modifiers {
+    let is_down = |key| unsafe { GetAsyncKeyState(key) } as u16 & 0x8000 != 0;
+    let mut modifiers = Modifiers::empty();
+    modifiers.set(Modifiers::SHIFT, is_down(VK_SHIFT));
+    modifiers.set(Modifiers::CONTROL, is_down(VK_CONTROL));
+    modifiers.set(Modifiers::ALT, is_down(VK_MENU));
+    modifiers.set(Modifiers::META, is_down(VK_LWIN) || is_down(VK_RWIN));
+    modifiers
+}
+
 unsafe extern "system" fn callback_proc(code: c_int, wparam: WPARAM, lparam: LPARAM) -> LRESULT {
     STATE.with(|state| {
         let mut state = state.borrow_mut();
@@ -73,10 +86,11 @@ unsafe extern "system" fn callback_proc(code: c_int, wparam: WPARAM, lparam: LPA
             if hook_struct.vkCode >= 1 && hook_struct.vkCode <= 0xFE {
                 let key_code = mem::transmute(hook_struct.vkCode as u8);
                 let event = wparam as UINT;
-                if event == WM_KEYDOWN {
+                // Key presses while Alt is held down are system key presses.
+                if event == WM_KEYDOWN || event == WM_SYSKEYDOWN {
                     state
                         .events
-                        .send(key_code)
+                        .send(Hotkey::new(key_code, current_modifiers()))
                         .expect("Callback Thread disconnected");
                 }
             }
@@ -89,7 +103,7 @@ unsafe extern "system" fn callback_proc(code: c_int, wparam: WPARAM, lparam: LPA
 impl Hook {
     pub fn new() -> Result<Self> {
         let hotkeys = Arc::new(Mutex::new(HashMap::<
-            KeyCode,
+            Hotkey,
             Box<dyn FnMut() + Send + 'static>,
         >::new()));
 
@@ -160,7 +174,7 @@ impl Hook {
         Ok(Hook { thread_id, hotkeys })
     }
 
-    pub fn register<F>(&self, hotkey: KeyCode, callback: F) -> Result<()>
+    pub fn register<F>(&self, hotkey: Hotkey, callback: F) -> Result<()>
     where
         F: FnMut() + Send + 'static,
     {
@@ -172,7 +186,7 @@ impl Hook {
         }
     }
 
-    pub fn unregister(&self, hotkey: KeyCode) -> Result<()> {
+    pub fn unregister(&self, hotkey: Hotkey) -> Result<()> {
         if self.hotkeys.lock().remove(&hotkey).is_some() {
             Ok(())
         } else {
@@ -184,9 +198,13 @@ impl Hook {
 #[test]
 fn test() {
     let hook = Hook::new().unwrap();
-    hook.register(KeyCode::Numpad0, || println!("A")).unwrap();
+    hook.register(KeyCode::Numpad0.into(), || println!("A"))
+        .unwrap();
     thread::sleep(std::time::Duration::from_secs(5));
-    hook.unregister(KeyCode::Numpad0).unwrap();
-    hook.register(KeyCode::Numpad1, || println!("B")).unwrap();
+    hook.unregister(KeyCode::Numpad0.into()).unwrap();
+    hook.register(Hotkey::new(KeyCode::Numpad1, Modifiers::CONTROL), || {
+        println!("B")
+    })
+    .unwrap();
     thread::sleep(std::time::Duration::from_secs(5));
 }
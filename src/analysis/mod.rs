@@ -7,6 +7,7 @@ pub mod pb_chance;
 pub mod possible_time_save;
 mod skill_curve;
 pub mod state_helper;
+pub mod statistics;
 pub mod sum_of_segments;
 pub mod total_playtime;
 
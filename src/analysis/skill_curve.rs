@@ -59,69 +59,49 @@ impl SkillCurve {
         self.all_weighted_segment_times
             .resize_with(len, Default::default);
 
-        for ((i, segment), weighted_segment_times) in segments
-            .iter()
-            .enumerate()
-            .zip(&mut self.all_weighted_segment_times)
-        {
-            weighted_segment_times.clear();
-
-            // Collect initial weighted segments
-            let mut current_weight = 1.0;
-            for &(id, time) in segment.segment_history().iter_actual_runs().rev() {
-                if let Some(time) = time[method] {
-                    // Skip all the combined segments
-                    let skip = catch! {
-                        segments[i.checked_sub(1)?].segment_history().get(id)?[method].is_none()
-                    }
-                    .unwrap_or(false);
-
-                    if !skip {
-                        weighted_segment_times.push((current_weight, time));
-                        current_weight *= WEIGHT;
-                    }
-                }
-            }
+        for (i, weighted_segment_times) in self.all_weighted_segment_times.iter_mut().enumerate() {
+            calculate_weighted_segment_times(segments, i, method, weighted_segment_times);
 
             // End early if we don't have any segment times anymore
             if weighted_segment_times.is_empty() {
                 len = i;
                 break;
             }
+        }
 
-            // Sort everything by the times
-            weighted_segment_times
-                .sort_unstable_by_key(|&(_, time)| OrderedFloat(time.total_milliseconds()));
-
-            // Cumulative sum of the weights
-            let mut sum = 0.0;
-            for (weight, _) in weighted_segment_times.iter_mut() {
-                sum += *weight;
-                *weight = sum;
-            }
+        // Limit the slice to only the segments that have segment times
+        self.truncate(len);
+    }
 
-            // Reweigh all of the weights to be in the range 0..1
-            let min = weighted_segment_times
-                .first()
-                .map(|&(w, _)| w)
-                .unwrap_or_default();
+    /// Calculate the skill curve for only the segment with the index provided.
+    /// All previous information available in the skill curve will be
+    /// discarded. The skill curve is empty afterwards if the segment has no
+    /// segment history. The segments before the segment are only used for
+    /// skipping the segment times that are combined with skipped segments.
+    ///
+    /// # Panics
+    ///
+    /// This panics if the segment index is out of bounds.
+    pub fn for_segment(
+        &mut self,
+        segments: &[Segment],
+        segment_index: usize,
+        method: TimingMethod,
+    ) {
+        assert!(
+            segment_index < segments.len(),
+            "Segment index out of bounds"
+        );
 
-            let max = weighted_segment_times
-                .last()
-                .map(|&(w, _)| w)
-                .unwrap_or_default();
+        self.all_weighted_segment_times
+            .resize_with(1, Default::default);
 
-            let diff = max - min;
+        let weighted_segment_times = &mut self.all_weighted_segment_times[0];
+        calculate_weighted_segment_times(segments, segment_index, method, weighted_segment_times);
 
-            if diff != 0.0 {
-                for (weight, _) in weighted_segment_times.iter_mut() {
-                    *weight = (*weight - min) / diff;
-                }
-            }
+        if weighted_segment_times.is_empty() {
+            self.truncate(0);
         }
-
-        // Limit the slice to only the segments that have segment times
-        self.truncate(len);
     }
 
     /// This function returns an iterator that iterates over each segment and
@@ -210,6 +190,62 @@ impl SkillCurve {
     }
 }
 
+fn calculate_weighted_segment_times(
+    segments: &[Segment],
+    i: usize,
+    method: TimingMethod,
+    weighted_segment_times: &mut Vec<(f64, TimeSpan)>,
+) {
+    weighted_segment_times.clear();
+
+    // Collect initial weighted segments
+    let mut current_weight = 1.0;
+    for &(id, time) in segments[i].segment_history().iter_actual_runs().rev() {
+        if let Some(time) = time[method] {
+            // Skip all the combined segments
+            let skip = catch! {
+                segments[i.checked_sub(1)?].segment_history().get(id)?[method].is_none()
+            }
+            .unwrap_or(false);
+
+            if !skip {
+                weighted_segment_times.push((current_weight, time));
+                current_weight *= WEIGHT;
+            }
+        }
+    }
+
+    // Sort everything by the times
+    weighted_segment_times
+        .sort_unstable_by_key(|&(_, time)| OrderedFloat(time.total_milliseconds()));
+
+    // Cumulative sum of the weights
+    let mut sum = 0.0;
+    for (weight, _) in weighted_segment_times.iter_mut() {
+        sum += *weight;
+        *weight = sum;
+    }
+
+    // Reweigh all of the weights to be in the range 0..1
+    let min = weighted_segment_times
+        .first()
+        .map(|&(w, _)| w)
+        .unwrap_or_default();
+
+    let max = weighted_segment_times
+        .last()
+        .map(|&(w, _)| w)
+        .unwrap_or_default();
+
+    let diff = max - min;
+
+    if diff != 0.0 {
+        for (weight, _) in weighted_segment_times.iter_mut() {
+            *weight = (*weight - min) / diff;
+        }
+    }
+}
+
 fn interpolate(
     perc: f64,
     (weight_left, time_left): (f64, TimeSpan),
@@ -2,7 +2,9 @@
 
 use crate::comparison::best_segments;
 use crate::settings::SemanticColor;
-use crate::{timing::Snapshot, Run, Segment, TimeSpan, Timer, TimerPhase, TimingMethod};
+use crate::{
+    run::SegmentGroup, timing::Snapshot, Run, Segment, TimeSpan, Timer, TimerPhase, TimingMethod,
+};
 
 /// Gets the last non-live delta in the run starting from `segment_index`.
 ///
@@ -120,8 +122,44 @@ pub fn comparison_single_segment_time(
     }
 }
 
+/// Calculates the comparison's segment time of all the segments of the
+/// Segment Group with the timing method specified, combining segments if the
+/// segment before the group is empty. This is not calculating the current
+/// attempt's segment times.
+///
+/// # Panics
+///
+/// Panics if the provided Segment Group is out of bounds of the run's
+/// segments.
+pub fn comparison_combined_segment_group_time(
+    run: &Run,
+    group: &SegmentGroup,
+    comparison: &str,
+    method: TimingMethod,
+) -> Option<TimeSpan> {
+    if comparison == best_segments::NAME {
+        return run.segments()[group.range()]
+            .iter()
+            .try_fold(TimeSpan::zero(), |sum, s| {
+                Some(sum + s.best_segment_time()[method]?)
+            });
+    }
+
+    let current_comparison_time = run.segment(group.last()).comparison(comparison)[method]?;
+
+    let previous_comparison_time = find_previous_non_empty_comparison_time(
+        &run.segments()[..group.start()],
+        comparison,
+        method,
+    )
+    .unwrap_or_default();
+
+    Some(current_comparison_time - previous_comparison_time)
+}
+
 fn segment_delta(
     run: &Run,
+    start_index: usize,
     segment_index: usize,
     current_time: TimeSpan,
     comparison: &str,
@@ -131,7 +169,7 @@ fn segment_delta(
 
     Some(
         find_previous_non_empty_split_and_comparison_time(
-            &run.segments()[..segment_index],
+            &run.segments()[..start_index],
             comparison,
             method,
         )
@@ -144,11 +182,11 @@ fn segment_delta(
 
 fn segment_time(
     run: &Run,
-    segment_index: usize,
+    start_index: usize,
     current_time: TimeSpan,
     method: TimingMethod,
 ) -> TimeSpan {
-    find_previous_non_empty_split_time(&run.segments()[..segment_index], method)
+    find_previous_non_empty_split_time(&run.segments()[..start_index], method)
         .map(|split_time| current_time - split_time)
         .unwrap_or(current_time)
 }
@@ -218,6 +256,7 @@ pub fn previous_segment_delta(
     segment_delta(
         timer.run(),
         segment_index,
+        segment_index,
         timer.run().segment(segment_index).split_time()[method]?,
         comparison,
         method,
@@ -243,6 +282,106 @@ pub fn live_segment_delta(
     segment_delta(
         timer.run(),
         segment_index,
+        segment_index,
+        timer.current_time()[method]?,
+        comparison,
+        method,
+    )
+}
+
+/// Gets the length of all the segments of a Segment Group.
+///
+/// - `timer`: The current timer.
+/// - `group`: The Segment Group to calculate the length of.
+/// - `method`: The timing method that you are using.
+///
+/// Returns the length of the Segment Group, returning None if the group's
+/// last split is not completed yet.
+pub fn previous_segment_group_time(
+    timer: &Timer,
+    group: &SegmentGroup,
+    method: TimingMethod,
+) -> Option<TimeSpan> {
+    segment_time(
+        timer.run(),
+        group.start(),
+        timer.run().segment(group.last()).split_time()[method]?,
+        method,
+    )
+    .into()
+}
+
+/// Gets the length of all the segments of a Segment Group, using the live
+/// time if the group's last split is not completed yet.
+///
+/// - `timer`: The current timer.
+/// - `group`: The Segment Group to calculate the length of.
+/// - `method`: The timing method that you are using.
+///
+/// Returns the length of the Segment Group up until now.
+pub fn live_segment_group_time(
+    timer: &Snapshot<'_>,
+    group: &SegmentGroup,
+    method: TimingMethod,
+) -> Option<TimeSpan> {
+    segment_time(
+        timer.run(),
+        group.start(),
+        timer.current_time()[method]?,
+        method,
+    )
+    .into()
+}
+
+/// Gets the amount of time lost or gained on all the segments of a Segment
+/// Group.
+///
+/// - `timer`: The current timer.
+/// - `group`: The Segment Group for which the delta is calculated.
+/// - `comparison`: The comparison that you are comparing with.
+/// - `method`: The timing method that you are using.
+///
+/// Returns the delta of the Segment Group, returning None if the group's last
+/// split is not completed yet.
+pub fn previous_segment_group_delta(
+    timer: &Timer,
+    group: &SegmentGroup,
+    comparison: &str,
+    method: TimingMethod,
+) -> Option<TimeSpan> {
+    segment_delta(
+        timer.run(),
+        group.start(),
+        group.last(),
+        timer.run().segment(group.last()).split_time()[method]?,
+        comparison,
+        method,
+    )
+}
+
+/// Gets the amount of time lost or gained on a Segment Group up until the
+/// current split, using the live time.
+///
+/// - `timer`: The current timer.
+/// - `group`: The Segment Group for which the delta is calculated.
+/// - `comparison`: The comparison that you are comparing with.
+/// - `method`: The timing method that you are using.
+///
+/// Returns the live delta of the Segment Group.
+pub fn live_segment_group_delta(
+    timer: &Snapshot<'_>,
+    group: &SegmentGroup,
+    comparison: &str,
+    method: TimingMethod,
+) -> Option<TimeSpan> {
+    let segment_index = timer
+        .current_split_index()
+        .map_or(group.last(), |i| i.max(group.start()).min(group.last()));
+
+    segment_delta(
+        timer.run(),
+        group.start(),
+        segment_index,
         timer.current_time()[method]?,
         comparison,
         method,
@@ -316,8 +455,65 @@ pub fn split_color(
 ) -> SemanticColor {
     if show_best_segments && check_best_segment(timer, segment_index, method) {
         SemanticColor::BestSegment
-    } else if let Some(time_difference) = time_difference {
-        let last_delta = segment_index
+    } else {
+        delta_color(
+            timer,
+            time_difference,
+            segment_index,
+            show_segment_deltas,
+            comparison,
+            method,
+        )
+    }
+}
+
+/// Chooses a split color from the Layout Settings for a delta of an entire
+/// Segment Group. The group is considered a Best Segment if any of its
+/// segments is one.
+///
+/// - `timer`: The current timer.
+/// - `time_difference`: The delta that you want to find a color for.
+/// - `group`: The Segment Group that is associated with this delta.
+/// - `show_segment_deltas`: Can show ahead gaining and behind losing colors if
+///    true.
+/// - `show_best_segments`: Can show the best segment color if true.
+/// - `comparison`: The comparison that you are comparing this delta to.
+/// - `method`: The timing method of this delta.
+///
+/// Returns the chosen color.
+pub fn segment_group_split_color(
+    timer: &Timer,
+    time_difference: Option<TimeSpan>,
+    group: &SegmentGroup,
+    show_segment_deltas: bool,
+    show_best_segments: bool,
+    comparison: &str,
+    method: TimingMethod,
+) -> SemanticColor {
+    if show_best_segments && group.range().any(|i| check_best_segment(timer, i, method)) {
+        SemanticColor::BestSegment
+    } else {
+        delta_color(
+            timer,
+            time_difference,
+            group.start(),
+            show_segment_deltas,
+            comparison,
+            method,
+        )
+    }
+}
+
+fn delta_color(
+    timer: &Timer,
+    time_difference: Option<TimeSpan>,
+    start_index: usize,
+    show_segment_deltas: bool,
+    comparison: &str,
+    method: TimingMethod,
+) -> SemanticColor {
+    if let Some(time_difference) = time_difference {
+        let last_delta = start_index
             .checked_sub(1)
             .and_then(|n| last_delta(timer.run(), n, comparison, method));
         if time_difference < TimeSpan::zero() {
@@ -0,0 +1,300 @@
+//! Provides statistics about the attempts of a Run that are useful for
+//! reviewing practice. For every segment, the mean, the median and the
+//! standard deviation of its segment times are calculated, as well as how
+//! consistent the segment is, how often attempts are reset during it and
+//! whether the segment times are improving over time. Segment times of
+//! attempts where the previous split was skipped are not considered, as they
+//! also contain the time of the skipped segments. The statistics can also be
+//! calculated for a range of segments, such as a Segment Group, in which case
+//! the segment times of each attempt are summed up first.
+
+use crate::{
+    platform::{math::f64::sqrt, prelude::*},
+    Run, TimeSpan, TimingMethod,
+};
+use core::{cmp::Ordering, ops::Range};
+
+/// The statistics of a single segment.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SegmentStatistics {
+    /// The amount of attempts that reached the segment.
+    pub attempts: u32,
+    /// The amount of attempts that were reset during the segment.
+    pub resets: u32,
+    /// The amount of segment times the statistics are based on.
+    pub samples: u32,
+    /// The mean of the segment times.
+    pub mean: Option<TimeSpan>,
+    /// The median of the segment times.
+    pub median: Option<TimeSpan>,
+    /// The standard deviation of the segment times.
+    pub standard_deviation: Option<TimeSpan>,
+    /// The best segment time of the segment, also known as its gold.
+    pub best: Option<TimeSpan>,
+    /// How much the segment times change per attempt on average. A negative
+    /// trend means that the segment times are improving. This is based on a
+    /// linear regression over all the segment times.
+    pub trend: Option<TimeSpan>,
+}
+
+impl SegmentStatistics {
+    /// Calculates how consistent the segment is, where `1.0` means that all
+    /// the segment times are the same. The more the segment times spread out
+    /// relative to the mean, the closer the consistency gets to `0.0`.
+    pub fn consistency(&self) -> Option<f64> {
+        let mean = self.mean?.total_seconds();
+        let standard_deviation = self.standard_deviation?.total_seconds();
+        if mean > 0.0 {
+            Some((1.0 - standard_deviation / mean).max(0.0))
+        } else {
+            None
+        }
+    }
+
+    /// Calculates the share of the attempts that reached the segment, but
+    /// were reset during it.
+    pub fn reset_rate(&self) -> Option<f64> {
+        if self.attempts > 0 {
+            Some(self.resets as f64 / self.attempts as f64)
+        } else {
+            None
+        }
+    }
+
+    /// Calculates how much time is lost on average in this segment compared
+    /// to the best segment time.
+    pub fn time_lost_vs_gold(&self) -> Option<TimeSpan> {
+        Some(self.mean? - self.best?)
+    }
+}
+
+/// The statistics of all the attempts of a Run.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Statistics {
+    /// The statistics of each segment.
+    pub segments: Vec<SegmentStatistics>,
+    /// The amount of attempts in the Attempt History.
+    pub attempts: u32,
+    /// The amount of attempts that were finished.
+    pub finished: u32,
+    /// How much the final times change per attempt on average. A negative
+    /// trend means that the final times are improving. This is based on a
+    /// linear regression over all the finished attempts.
+    pub trend: Option<TimeSpan>,
+}
+
+impl Statistics {
+    /// Returns the amount of resets that happened in all of the segments.
+    pub fn resets(&self) -> u32 {
+        self.segments.iter().map(|s| s.resets).sum()
+    }
+
+    /// Returns the distribution of where the attempts were reset. For each
+    /// segment, the share of all the resets that happened during the segment
+    /// is yielded.
+    pub fn reset_distribution(&self) -> impl Iterator<Item = f64> + '_ {
+        let resets = self.resets();
+        self.segments.iter().map(move |s| {
+            if resets > 0 {
+                s.resets as f64 / resets as f64
+            } else {
+                0.0
+            }
+        })
+    }
+
+    /// Ranks the segments by how much time is lost on average compared to
+    /// their best segment times. The indices of the segments are returned,
+    /// starting with the segment where the most time is lost. Segments where
+    /// this can't be determined are not included.
+    pub fn time_lost_ranking(&self) -> Vec<usize> {
+        let mut ranking = self
+            .segments
+            .iter()
+            .enumerate()
+            .filter_map(|(index, s)| Some((index, s.time_lost_vs_gold()?)))
+            .collect::<Vec<_>>();
+        ranking.sort_by(|(_, a), (_, b)| b.cmp(a));
+        ranking.into_iter().map(|(index, _)| index).collect()
+    }
+}
+
+/// Calculates the statistics of all the attempts of the Run for the timing
+/// method provided.
+pub fn calculate(run: &Run, method: TimingMethod) -> Statistics {
+    let segments = (0..run.len())
+        .map(|index| for_segment(run, index, method))
+        .collect();
+
+    let mut finished = Vec::new();
+    for attempt in run.attempt_history() {
+        if let Some(time) = attempt.time()[method] {
+            finished.push((attempt.index(), time.total_seconds()));
+        }
+    }
+
+    Statistics {
+        segments,
+        attempts: run.attempt_history().len() as u32,
+        finished: finished.len() as u32,
+        trend: trend(&finished),
+    }
+}
+
+/// Calculates the statistics of the segment with the index provided for the
+/// timing method provided.
+///
+/// # Panics
+///
+/// This panics if the segment index is out of bounds.
+pub fn for_segment(run: &Run, segment_index: usize, method: TimingMethod) -> SegmentStatistics {
+    for_segment_range(run, segment_index..segment_index + 1, method)
+}
+
+/// Calculates the statistics of the range of segments provided for the timing
+/// method provided. The segment times of the segments are summed up for each
+/// attempt, so the statistics describe the range as a whole. The best segment
+/// time is the sum of the best segment times of the segments.
+///
+/// # Panics
+///
+/// This panics if the range is empty or out of bounds.
+pub fn for_segment_range(
+    run: &Run,
+    segments: Range<usize>,
+    method: TimingMethod,
+) -> SegmentStatistics {
+    let attempts = match segments.start.checked_sub(1) {
+        Some(previous) => run
+            .segment(previous)
+            .segment_history()
+            .iter_actual_runs()
+            .count(),
+        None => run
+            .attempt_history()
+            .iter()
+            .filter(|a| a.index() > 0)
+            .count(),
+    };
+    let completed = run
+        .segment(segments.end - 1)
+        .segment_history()
+        .iter_actual_runs()
+        .count();
+
+    let times = segment_times(run, segments.clone(), method)
+        .into_iter()
+        .map(|(index, time)| (index, time.total_seconds()))
+        .collect::<Vec<_>>();
+
+    let samples = times.len();
+    let (mean, median, standard_deviation) = if samples > 0 {
+        let mean = times.iter().map(|&(_, t)| t).sum::<f64>() / samples as f64;
+        let variance = times
+            .iter()
+            .map(|&(_, t)| (t - mean) * (t - mean))
+            .sum::<f64>()
+            / samples as f64;
+
+        let mut sorted = times.iter().map(|&(_, t)| t).collect::<Vec<_>>();
+        sorted.sort_unstable_by(|a, b| a.partial_cmp(b).unwrap_or(Ordering::Equal));
+        let median = if samples % 2 == 0 {
+            0.5 * (sorted[samples / 2 - 1] + sorted[samples / 2])
+        } else {
+            sorted[samples / 2]
+        };
+
+        (
+            Some(TimeSpan::from_seconds(mean)),
+            Some(TimeSpan::from_seconds(median)),
+            Some(TimeSpan::from_seconds(sqrt(variance))),
+        )
+    } else {
+        (None, None, None)
+    };
+
+    let best = segments
+        .map(|index| run.segment(index).best_segment_time()[method])
+        .try_fold(TimeSpan::zero(), |sum, time| Some(sum + time?));
+
+    SegmentStatistics {
+        attempts: attempts as u32,
+        resets: attempts.saturating_sub(completed) as u32,
+        samples: samples as u32,
+        mean,
+        median,
+        standard_deviation,
+        best,
+        trend: trend(&times),
+    }
+}
+
+/// Collects the segment times of the range of segments provided for each
+/// attempt that completed the range, sorted by the attempt indices. The
+/// segment times of the individual segments are summed up, where skipped
+/// splits within the range count as no time, as the time is part of the next
+/// segment time. Attempts where the split before the range was skipped are
+/// not included.
+///
+/// # Panics
+///
+/// This panics if the range is empty or out of bounds.
+pub fn segment_times(
+    run: &Run,
+    segments: Range<usize>,
+    method: TimingMethod,
+) -> Vec<(i32, TimeSpan)> {
+    let previous = segments
+        .start
+        .checked_sub(1)
+        .map(|index| run.segment(index).segment_history());
+
+    let mut times = Vec::new();
+    for &(index, time) in run
+        .segment(segments.end - 1)
+        .segment_history()
+        .iter_actual_runs()
+    {
+        let follows_skipped_split = matches!(
+            previous.and_then(|p| p.get(index)),
+            Some(previous) if previous[method].is_none()
+        );
+        if follows_skipped_split || time[method].is_none() {
+            continue;
+        }
+
+        let sum = segments
+            .clone()
+            .filter_map(|segment| run.segment(segment).segment_history().get(index))
+            .filter_map(|time| time[method])
+            .fold(TimeSpan::zero(), |sum, time| sum + time);
+        times.push((index, sum));
+    }
+
+    times.sort_by_key(|&(index, _)| index);
+    times
+}
+
+/// Calculates the slope of the linear regression through the times, with the
+/// attempt indices as the x-axis.
+fn trend(times: &[(i32, f64)]) -> Option<TimeSpan> {
+    if times.len() < 2 {
+        return None;
+    }
+    let len = times.len() as f64;
+    let mean_x = times.iter().map(|&(x, _)| x as f64).sum::<f64>() / len;
+    let mean_y = times.iter().map(|&(_, y)| y).sum::<f64>() / len;
+
+    let (mut covariance, mut variance) = (0.0, 0.0);
+    for &(x, y) in times {
+        let dx = x as f64 - mean_x;
+        covariance += dx * (y - mean_y);
+        variance += dx * dx;
+    }
+
+    if variance > 0.0 {
+        Some(TimeSpan::from_seconds(covariance / variance))
+    } else {
+        None
+    }
+}
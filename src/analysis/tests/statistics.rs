@@ -0,0 +1,114 @@
+use super::super::statistics::{calculate, for_segment, for_segment_range, segment_times};
+use crate::{
+    tests_helper::{create_timer, run_sample_attempts, run_with_splits, run_with_splits_opt},
+    TimeSpan, TimingMethod,
+};
+
+fn seconds(time: Option<TimeSpan>) -> f64 {
+    time.unwrap().total_seconds()
+}
+
+fn assert_close(actual: f64, expected: f64) {
+    assert!(
+        (actual - expected).abs() < 1e-3,
+        "{} is not close to {}",
+        actual,
+        expected
+    );
+}
+
+#[test]
+fn segment_statistics() {
+    let mut timer = create_timer(&["A", "B", "C"]);
+    run_sample_attempts(&mut timer);
+    run_with_splits(&mut timer, &[8.0]);
+    run_with_splits(&mut timer, &[]);
+
+    let statistics = calculate(timer.run(), TimingMethod::GameTime);
+    assert_eq!(statistics.attempts, 4);
+    assert_eq!(statistics.finished, 2);
+    assert_close(seconds(statistics.trend), 10.0);
+
+    let a = &statistics.segments[0];
+    assert_eq!((a.attempts, a.resets, a.samples), (4, 1, 3));
+    assert_close(seconds(a.mean), 10.0);
+    assert_close(seconds(a.median), 10.0);
+    assert_close(seconds(a.standard_deviation), (8.0f64 / 3.0).sqrt());
+    assert_close(seconds(a.time_lost_vs_gold()), 2.0);
+    assert_close(a.reset_rate().unwrap(), 0.25);
+
+    let b = &statistics.segments[1];
+    assert_eq!((b.attempts, b.resets, b.samples), (3, 1, 2));
+    assert_close(seconds(b.mean), 21.0);
+    assert_close(seconds(b.standard_deviation), 1.0);
+    assert_close(b.consistency().unwrap(), 1.0 - 1.0 / 21.0);
+    assert_close(seconds(b.trend), 2.0);
+
+    let c = &statistics.segments[2];
+    assert_eq!((c.attempts, c.resets, c.samples), (2, 0, 2));
+    assert_close(seconds(c.time_lost_vs_gold()), 3.0);
+
+    assert_eq!(statistics.resets(), 2);
+    assert_eq!(
+        statistics.reset_distribution().collect::<Vec<_>>(),
+        [0.5, 0.5, 0.0]
+    );
+    assert_eq!(statistics.time_lost_ranking()[0], 2);
+}
+
+#[test]
+fn segments_after_skipped_splits_are_ignored() {
+    let mut timer = create_timer(&["A", "B", "C"]);
+    run_with_splits_opt(&mut timer, &[None, Some(30.0), Some(60.0)]);
+    run_with_splits_opt(&mut timer, &[Some(10.0), Some(30.0), Some(60.0)]);
+
+    let b = for_segment(timer.run(), 1, TimingMethod::GameTime);
+    assert_eq!((b.attempts, b.resets, b.samples), (2, 0, 1));
+    assert_close(seconds(b.mean), 20.0);
+    assert_eq!(b.trend, None);
+
+    let c = for_segment(timer.run(), 2, TimingMethod::GameTime);
+    assert_eq!(c.samples, 2);
+    assert_close(seconds(c.mean), 30.0);
+    assert_close(c.consistency().unwrap(), 1.0);
+}
+
+#[test]
+fn segment_ranges_are_based_on_the_summed_segment_times() {
+    let mut timer = create_timer(&["A", "B", "C"]);
+    run_sample_attempts(&mut timer);
+    run_with_splits(&mut timer, &[9.0, 40.0, 65.0]);
+    run_with_splits(&mut timer, &[8.0]);
+
+    let times = segment_times(timer.run(), 1..3, TimingMethod::GameTime);
+    assert_eq!(
+        times
+            .iter()
+            .map(|&(index, time)| (index, time.total_seconds()))
+            .collect::<Vec<_>>(),
+        [(1, 50.0), (2, 58.0), (3, 56.0)]
+    );
+
+    let group = for_segment_range(timer.run(), 1..3, TimingMethod::GameTime);
+    assert_eq!((group.attempts, group.resets, group.samples), (4, 1, 3));
+    assert_close(seconds(group.median), 56.0);
+    assert_close(seconds(group.mean), 164.0 / 3.0);
+    assert_close(seconds(group.best), 45.0);
+}
+
+#[test]
+fn no_attempts() {
+    let timer = create_timer(&["A", "B"]);
+    let statistics = calculate(timer.run(), TimingMethod::RealTime);
+
+    assert_eq!(statistics.attempts, 0);
+    assert_eq!(statistics.trend, None);
+    for segment in &statistics.segments {
+        assert_eq!((segment.attempts, segment.samples), (0, 0));
+        assert_eq!(segment.mean, None);
+        assert_eq!(segment.reset_rate(), None);
+        assert_eq!(segment.consistency(), None);
+    }
+    assert!(statistics.reset_distribution().all(|share| share == 0.0));
+    assert!(statistics.time_lost_ranking().is_empty());
+}
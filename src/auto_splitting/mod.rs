@@ -0,0 +1,152 @@
+//! The auto splitting module provides a runtime for running auto splitters
+//! that can control the timer. Auto splitters are provided as WebAssembly
+//! modules that run in a sandbox on a background thread. They can attach to
+//! the game's process, read its memory and start, split and reset the timer
+//! and control the game time based on what they read. Take a look at the
+//! `livesplit-auto-splitting` crate for the functions that are available to
+//! the auto splitters. The module is optional and is not compiled in by
+//! default.
+
+use crate::{SharedTimer, TimeSpan, TimerPhase};
+use livesplit_auto_splitting::{Runtime as ScriptRuntime, Timer as AutoSplitTimer, TimerState};
+use snafu::ResultExt;
+use std::{
+    sync::mpsc::{self, Receiver, RecvTimeoutError, Sender},
+    thread,
+    time::Instant,
+};
+
+pub use livesplit_auto_splitting::CreationError;
+
+/// The Error type for requests to the auto splitting runtime that failed.
+#[derive(Debug, snafu::Snafu)]
+pub enum Error {
+    /// The auto splitter couldn't be loaded.
+    LoadScript {
+        /// The underlying error.
+        source: CreationError,
+    },
+    /// The thread running the auto splitter has stopped.
+    ThreadStopped,
+}
+
+enum Request {
+    LoadScript(Vec<u8>, Sender<Result<(), Error>>),
+    UnloadScript(Sender<()>),
+}
+
+/// A runtime that runs an auto splitter on a background thread. The auto
+/// splitter is updated on every tick until it gets unloaded or the runtime is
+/// dropped.
+pub struct Runtime {
+    sender: Sender<Request>,
+}
+
+impl Runtime {
+    /// Starts the background thread of the runtime. No auto splitter is loaded
+    /// initially. Any auto splitter that gets loaded controls the timer
+    /// provided.
+    pub fn new(timer: SharedTimer) -> Self {
+        let (sender, receiver) = mpsc::channel();
+        thread::spawn(move || run(timer, receiver));
+        Self { sender }
+    }
+
+    /// Loads the auto splitter from the WebAssembly module provided. Any auto
+    /// splitter that was previously loaded is unloaded.
+    pub fn load_script(&self, script: Vec<u8>) -> Result<(), Error> {
+        let (sender, receiver) = mpsc::channel();
+        self.sender
+            .send(Request::LoadScript(script, sender))
+            .map_err(|_| Error::ThreadStopped)?;
+        receiver.recv().map_err(|_| Error::ThreadStopped)?
+    }
+
+    /// Unloads the auto splitter that is currently loaded.
+    pub fn unload_script(&self) -> Result<(), Error> {
+        let (sender, receiver) = mpsc::channel();
+        self.sender
+            .send(Request::UnloadScript(sender))
+            .map_err(|_| Error::ThreadStopped)?;
+        receiver.recv().map_err(|_| Error::ThreadStopped)
+    }
+}
+
+fn run(timer: SharedTimer, receiver: Receiver<Request>) {
+    let mut runtime = None::<ScriptRuntime<Timer>>;
+    let mut next_tick = Instant::now();
+
+    loop {
+        let request = match &mut runtime {
+            Some(runtime) => {
+                let now = Instant::now();
+                if now >= next_tick {
+                    // An auto splitter that traps is kept running, as it may
+                    // only fail for a single tick.
+                    let _ = runtime.step();
+                    next_tick = (next_tick + runtime.tick_rate()).max(now);
+                }
+                receiver.recv_timeout(next_tick.saturating_duration_since(Instant::now()))
+            }
+            None => receiver.recv().map_err(|_| RecvTimeoutError::Disconnected),
+        };
+
+        match request {
+            Ok(Request::LoadScript(script, result)) => {
+                runtime = None;
+                let loaded = ScriptRuntime::new(&script, Timer(timer.clone()))
+                    .map(|loaded| {
+                        runtime = Some(loaded);
+                        next_tick = Instant::now();
+                    })
+                    .context(LoadScript);
+                let _ = result.send(loaded);
+            }
+            Ok(Request::UnloadScript(result)) => {
+                runtime = None;
+                let _ = result.send(());
+            }
+            Err(RecvTimeoutError::Timeout) => {}
+            Err(RecvTimeoutError::Disconnected) => break,
+        }
+    }
+}
+
+struct Timer(SharedTimer);
+
+impl AutoSplitTimer for Timer {
+    fn state(&self) -> TimerState {
+        match self.0.read().current_phase() {
+            TimerPhase::NotRunning => TimerState::NotRunning,
+            TimerPhase::Running => TimerState::Running,
+            TimerPhase::Paused => TimerState::Paused,
+            TimerPhase::Ended => TimerState::Ended,
+        }
+    }
+
+    fn start(&mut self) {
+        self.0.write().start()
+    }
+
+    fn split(&mut self) {
+        self.0.write().split()
+    }
+
+    fn reset(&mut self) {
+        self.0.write().reset(true)
+    }
+
+    fn set_game_time(&mut self, seconds: f64) {
+        self.0
+            .write()
+            .set_game_time(TimeSpan::from_seconds(seconds))
+    }
+
+    fn pause_game_time(&mut self) {
+        self.0.write().pause_game_time()
+    }
+
+    fn resume_game_time(&mut self) {
+        self.0.write().resume_game_time()
+    }
+}
@@ -64,7 +64,8 @@ impl ComparisonGenerator for AverageSegments {
     }
 
     fn generate(&mut self, segments: &mut [Segment], _: &[Attempt]) {
-        generate(segments, TimingMethod::RealTime);
-        generate(segments, TimingMethod::GameTime);
+        for &method in &TimingMethod::all() {
+            generate(segments, method);
+        }
     }
 }
@@ -94,31 +94,19 @@ pub fn generate_for_timing_method(
 pub fn generate(segments: &mut [Segment], goal_time: Time, comparison: &str) {
     let mut skill_curve = SkillCurve::new();
 
-    if let Some(real_time) = goal_time.real_time {
-        generate_for_timing_method_with_buf(
-            segments,
-            TimingMethod::RealTime,
-            Some(real_time),
-            comparison,
-            &mut skill_curve,
-        );
-    } else {
-        for segment in &mut *segments {
-            segment.comparison_mut(comparison).real_time = None;
-        }
-    }
-
-    if let Some(game_time) = goal_time.game_time {
-        generate_for_timing_method_with_buf(
-            segments,
-            TimingMethod::GameTime,
-            Some(game_time),
-            comparison,
-            &mut skill_curve,
-        );
-    } else {
-        for segment in &mut *segments {
-            segment.comparison_mut(comparison).game_time = None;
+    for &method in &TimingMethod::all() {
+        if let Some(time) = goal_time[method] {
+            generate_for_timing_method_with_buf(
+                segments,
+                method,
+                Some(time),
+                comparison,
+                &mut skill_curve,
+            );
+        } else {
+            for segment in &mut *segments {
+                segment.comparison_mut(comparison)[method] = None;
+            }
         }
     }
 }
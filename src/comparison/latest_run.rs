@@ -66,7 +66,8 @@ impl ComparisonGenerator for LatestRun {
     }
 
     fn generate(&mut self, segments: &mut [Segment], _: &[Attempt]) {
-        generate(segments, TimingMethod::RealTime);
-        generate(segments, TimingMethod::GameTime);
+        for &method in &TimingMethod::all() {
+            generate(segments, method);
+        }
     }
 }
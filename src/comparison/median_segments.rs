@@ -80,7 +80,8 @@ impl ComparisonGenerator for MedianSegments {
 
     fn generate(&mut self, segments: &mut [Segment], _: &[Attempt]) {
         let medians = &mut Vec::new();
-        generate(segments, medians, TimingMethod::RealTime);
-        generate(segments, medians, TimingMethod::GameTime);
+        for &method in &TimingMethod::all() {
+            generate(segments, medians, method);
+        }
     }
 }
@@ -14,6 +14,7 @@ pub mod goal;
 pub mod latest_run;
 pub mod median_segments;
 pub mod none;
+pub mod race;
 pub mod worst_segments;
 
 pub use self::average_segments::AverageSegments;
@@ -23,6 +24,7 @@ pub use self::best_split_times::BestSplitTimes;
 pub use self::latest_run::LatestRun;
 pub use self::median_segments::MedianSegments;
 pub use self::none::None;
+pub use self::race::Race;
 pub use self::worst_segments::WorstSegments;
 
 use crate::platform::prelude::*;
@@ -0,0 +1,250 @@
+//! Defines the Comparison Generator for races against other runners. The
+//! Personal Best of another runner's splits is shown as a comparison, so that
+//! you can see how you are doing against them. The other runner's segments
+//! are mapped onto your own segments by their names, so the splits don't need
+//! to match up exactly. The comparison can be updated at any point, even in
+//! the middle of an attempt, for example whenever the other runner's splits
+//! file changes.
+//!
+//! The other runner's splits can come from any source that produces a
+//! [`Run`], such as a splits file or a download from splits.io.
+
+use super::ComparisonGenerator;
+use crate::{platform::prelude::*, Attempt, Run, Segment, Time};
+use core::cmp::max;
+
+/// The prefix of the names of all the race comparisons. Custom comparisons are
+/// not allowed to use this prefix.
+pub const PREFIX: &str = "[Race] ";
+
+/// The minimum similarity two segment names need to have in order to be
+/// considered the same segment.
+const MIN_SIMILARITY: f64 = 0.5;
+
+/// Returns the name of the race comparison for the runner provided.
+pub fn name(runner: &str) -> String {
+    let mut name = String::with_capacity(PREFIX.len() + runner.len());
+    name.push_str(PREFIX);
+    name.push_str(runner);
+    name
+}
+
+/// The Comparison Generator for races against other runners. The Personal
+/// Best of another runner's splits is shown as a comparison. Their segments are
+/// mapped onto your own segments by their names. If the names don't match
+/// exactly, the most similar segment name is used. Segments that can't be
+/// mapped don't get a comparison time. The final split is always compared
+/// against the other runner's final time.
+#[derive(Clone, Debug)]
+pub struct Race {
+    name: String,
+    segments: Vec<(String, Time)>,
+}
+
+impl Race {
+    /// Creates a new Race Comparison Generator for the runner provided, which
+    /// compares against the Personal Best of the run provided.
+    pub fn new(runner: &str, run: &Run) -> Self {
+        let mut race = Self {
+            name: name(runner),
+            segments: Vec::new(),
+        };
+        race.update(run);
+        race
+    }
+
+    /// Accesses the name of the runner that is raced against.
+    pub fn runner(&self) -> &str {
+        &self.name[PREFIX.len()..]
+    }
+
+    /// Updates the splits of the other runner with the Personal Best of the
+    /// run provided. The comparison needs to be generated again for the update
+    /// to be visible.
+    pub fn update(&mut self, run: &Run) {
+        self.segments.clear();
+        self.segments.extend(
+            run.segments()
+                .iter()
+                .map(|s| (s.name().to_string(), s.personal_best_split_time())),
+        );
+    }
+}
+
+impl ComparisonGenerator for Race {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn generate(&mut self, segments: &mut [Segment], _: &[Attempt]) {
+        for segment in segments.iter_mut() {
+            *segment.comparison_mut(&self.name) = Time::default();
+        }
+
+        let (their_last, theirs) = match self.segments.split_last() {
+            Some(split) => split,
+            None => return,
+        };
+        let (our_last, ours) = match segments.split_last_mut() {
+            Some(split) => split,
+            None => return,
+        };
+
+        let mut remaining = 0;
+        for (their_name, time) in theirs {
+            let their_name = normalize(their_name);
+            let best_match = ours[remaining..]
+                .iter()
+                .enumerate()
+                .map(|(index, segment)| (index, similarity(&their_name, segment.name())))
+                .filter(|&(_, similarity)| similarity >= MIN_SIMILARITY)
+                .fold(
+                    None,
+                    |best: Option<(usize, f64)>, (index, similarity)| match best {
+                        Some((_, best_similarity)) if best_similarity >= similarity => best,
+                        _ => Some((index, similarity)),
+                    },
+                );
+
+            if let Some((index, _)) = best_match {
+                let index = remaining + index;
+                *ours[index].comparison_mut(&self.name) = *time;
+                remaining = index + 1;
+            }
+        }
+
+        *our_last.comparison_mut(&self.name) = their_last.1;
+    }
+}
+
+fn normalize(name: &str) -> Vec<char> {
+    name.chars()
+        .filter(|c| c.is_alphanumeric())
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+/// Calculates how similar two segment names are, where `1.0` means that they
+/// are the same, ignoring case, whitespace and punctuation. Names that contain
+/// different numbers are never considered similar, as they usually refer to
+/// different levels or bosses.
+fn similarity(a: &[char], b: &str) -> f64 {
+    let b = normalize(b);
+    if a == &*b {
+        return 1.0;
+    }
+    if a.is_empty()
+        || b.is_empty()
+        || !a
+            .iter()
+            .filter(|c| c.is_numeric())
+            .eq(b.iter().filter(|c| c.is_numeric()))
+    {
+        return 0.0;
+    }
+
+    let (shorter, longer) = if a.len() <= b.len() {
+        (a, &*b)
+    } else {
+        (&*b, a)
+    };
+
+    // Abbreviated names, like `Bowser 1` for `Bowser's Castle 1` are
+    // subsequences of the full name.
+    let mut longer_chars = longer.iter();
+    if shorter.iter().all(|c| longer_chars.any(|l| l == c)) {
+        return 0.5 + 0.5 * shorter.len() as f64 / longer.len() as f64;
+    }
+
+    1.0 - levenshtein(a, &b) as f64 / max(a.len(), b.len()) as f64
+}
+
+fn levenshtein(a: &[char], b: &[char]) -> usize {
+    let mut row = (0..=b.len()).collect::<Vec<_>>();
+    for (i, a) in a.iter().enumerate() {
+        let mut diagonal = row[0];
+        row[0] = i + 1;
+        for (j, b) in b.iter().enumerate() {
+            let substitution = diagonal + (a != b) as usize;
+            diagonal = row[j + 1];
+            row[j + 1] = substitution.min(row[j] + 1).min(diagonal + 1);
+        }
+    }
+    row[b.len()]
+}
+
+#[cfg(feature = "std")]
+pub use self::watcher::Watcher;
+
+#[cfg(feature = "std")]
+mod watcher {
+    use crate::run::parser::composite;
+    use std::{
+        fs::{self, File},
+        io::BufReader,
+        path::{Path, PathBuf},
+        time::SystemTime,
+    };
+
+    /// A Watcher keeps track of the splits file of another runner, so that the
+    /// race comparison can be updated whenever the file changes.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use livesplit_core::comparison::race::Watcher;
+    /// # let mut timer = livesplit_core::Timer::new({
+    /// #     let mut run = livesplit_core::Run::new();
+    /// #     run.push_segment(livesplit_core::Segment::new(""));
+    /// #     run
+    /// # }).unwrap();
+    ///
+    /// let mut watcher = Watcher::new("path/to/their/splits.lss");
+    ///
+    /// // Periodically check for changes.
+    /// if let Some(Ok(parsed)) = watcher.poll() {
+    ///     if !timer.update_race_comparison("Them", &parsed.run) {
+    ///         timer.add_race_comparison("Them", &parsed.run).unwrap();
+    ///     }
+    /// }
+    /// ```
+    #[derive(Debug)]
+    pub struct Watcher {
+        path: PathBuf,
+        modified: Option<SystemTime>,
+    }
+
+    impl Watcher {
+        /// Creates a new Watcher for the splits file at the path provided. The
+        /// first time the Watcher is polled, the file is always parsed.
+        pub fn new(path: impl Into<PathBuf>) -> Self {
+            Self {
+                path: path.into(),
+                modified: None,
+            }
+        }
+
+        /// Accesses the path of the splits file that is being watched.
+        pub fn path(&self) -> &Path {
+            &self.path
+        }
+
+        /// Checks whether the splits file changed since it was last
+        /// successfully parsed and parses it again if it did. `None` is
+        /// returned if the file didn't change or it can't be accessed right
+        /// now. A file that couldn't be parsed, for example because it is in
+        /// the middle of being written, is parsed again on the next poll.
+        pub fn poll(&mut self) -> Option<composite::Result<composite::ParsedRun>> {
+            let modified = fs::metadata(&self.path).ok()?.modified().ok()?;
+            if self.modified == Some(modified) {
+                return None;
+            }
+            let file = BufReader::new(File::open(&self.path).ok()?);
+            let result = composite::parse(file, Some(self.path.clone()), false);
+            if result.is_ok() {
+                self.modified = Some(modified);
+            }
+            Some(result)
+        }
+    }
+}
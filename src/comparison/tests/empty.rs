@@ -39,6 +39,11 @@ fn none() {
     test(comparison::None);
 }
 
+#[test]
+fn race() {
+    test(comparison::Race::new("", &crate::Run::new()));
+}
+
 #[test]
 fn worst_segments() {
     test(comparison::WorstSegments);
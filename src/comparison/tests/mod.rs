@@ -2,3 +2,4 @@ mod average;
 mod balanced_pb;
 mod empty;
 mod median;
+mod race;
@@ -0,0 +1,134 @@
+use crate::{
+    comparison::{personal_best, race},
+    run::ComparisonError,
+    tests_helper::{create_run, create_timer, span},
+    Run, Time, TimeSpan,
+};
+
+fn their_run(splits: &[(&str, f64)]) -> Run {
+    let names = splits.iter().map(|&(name, _)| name).collect::<Vec<_>>();
+    let mut run = create_run(&names);
+    for (segment, &(_, time)) in run.segments_mut().iter_mut().zip(splits) {
+        segment.set_personal_best_split_time(Time::new().with_real_time(Some(span(time))));
+    }
+    run
+}
+
+fn race_times(run: &Run, runner: &str) -> Vec<Option<TimeSpan>> {
+    let name = race::name(runner);
+    run.segments()
+        .iter()
+        .map(|s| s.comparison(&name).real_time)
+        .collect()
+}
+
+#[test]
+fn maps_segments_by_name() {
+    let mut run = create_run(&["Start", "Bowser's Castle 1", "Level 2", "End"]);
+    let theirs = their_run(&[
+        ("start", 5.0),
+        ("Bowser 1", 10.0),
+        ("Secret Exit", 12.0),
+        ("Level 2", 20.0),
+        ("Finish", 30.0),
+    ]);
+
+    run.add_race_comparison("Them", &theirs).unwrap();
+
+    assert!(run.comparisons().any(|c| c == "[Race] Them"));
+    assert_eq!(
+        race_times(&run, "Them"),
+        [
+            Some(span(5.0)),
+            Some(span(10.0)),
+            Some(span(20.0)),
+            Some(span(30.0))
+        ],
+    );
+}
+
+#[test]
+fn different_numbers_are_different_segments() {
+    let mut run = create_run(&["Level 1", "Level 3", "End"]);
+    let theirs = their_run(&[("Level 1", 5.0), ("Level 2", 10.0), ("Done", 15.0)]);
+
+    run.add_race_comparison("Them", &theirs).unwrap();
+
+    assert_eq!(
+        race_times(&run, "Them"),
+        [Some(span(5.0)), None, Some(span(15.0))],
+    );
+}
+
+#[test]
+fn cant_add_the_same_runner_twice() {
+    let mut run = create_run(&["A", "B"]);
+    let theirs = their_run(&[("A", 1.0), ("B", 2.0)]);
+
+    run.add_race_comparison("Them", &theirs).unwrap();
+    assert!(matches!(
+        run.add_race_comparison("Them", &theirs),
+        Err(ComparisonError::DuplicateName)
+    ));
+    assert!(matches!(
+        run.add_custom_comparison("[Race] Them"),
+        Err(ComparisonError::NameStartsWithRace)
+    ));
+}
+
+#[test]
+fn updates_during_an_attempt() {
+    let mut timer = create_timer(&["A", "B", "C"]);
+    timer
+        .add_race_comparison("Them", &their_run(&[("A", 1.0), ("B", 2.0), ("C", 3.0)]))
+        .unwrap();
+    timer.set_current_comparison("[Race] Them").unwrap();
+
+    timer.start();
+    timer.split();
+    assert!(
+        timer.update_race_comparison("Them", &their_run(&[("A", 1.5), ("B", 2.5), ("C", 3.5)]),)
+    );
+    assert!(!timer.update_race_comparison("Someone Else", &Run::new()));
+
+    assert_eq!(
+        race_times(timer.run(), "Them"),
+        [Some(span(1.5)), Some(span(2.5)), Some(span(3.5))],
+    );
+
+    // Race comparisons survive the comparisons being regenerated after the
+    // attempt.
+    timer.reset(true);
+    assert_eq!(timer.current_comparison(), "[Race] Them");
+    assert_eq!(race_times(timer.run(), "Them")[2], Some(span(3.5)));
+
+    assert!(timer.remove_race_comparison("Them"));
+    assert!(!timer.remove_race_comparison("Them"));
+    assert_eq!(timer.current_comparison(), personal_best::NAME);
+    assert!(timer
+        .run()
+        .segments()
+        .iter()
+        .all(|s| s.comparison("[Race] Them").real_time.is_none()));
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn watcher_parses_broken_files_again() {
+    use crate::run::saver::livesplit::save_run;
+    use std::{env, fs, process};
+
+    let path = env::temp_dir().join(format!("livesplit-race-watcher-{}.lss", process::id()));
+    fs::write(&path, "<?xml").unwrap();
+    let mut watcher = race::Watcher::new(&path);
+    assert!(matches!(watcher.poll(), Some(Err(_))));
+    assert!(matches!(watcher.poll(), Some(Err(_))));
+
+    let mut buf = Vec::new();
+    save_run(&their_run(&[("A", 1.0)]), &mut buf).unwrap();
+    fs::write(&path, buf).unwrap();
+    assert!(matches!(watcher.poll(), Some(Ok(_))));
+    assert!(watcher.poll().is_none());
+
+    fs::remove_file(path).unwrap();
+}
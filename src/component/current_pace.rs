@@ -10,7 +10,7 @@ use crate::{
     platform::prelude::*,
     settings::{Color, Field, Gradient, SettingsDescription, Value},
     timing::{
-        formatter::{Accuracy, Regular, TimeFormatter},
+        formatter::{Accuracy, FrameWrapper, Regular, TimeFormatter},
         Snapshot,
     },
     TimerPhase,
@@ -127,7 +127,11 @@ impl Component {
         let _ = write!(
             state.value,
             "{}",
-            Regular::with_accuracy(self.settings.accuracy).format(current_pace)
+            FrameWrapper::new(
+                timer.run().metadata().frame_rate(),
+                Regular::with_accuracy(self.settings.accuracy)
+            )
+            .format(current_pace)
         );
 
         state.key_abbreviations.clear();
@@ -9,7 +9,7 @@ use crate::{
     platform::prelude::*,
     settings::{Color, Field, Gradient, SemanticColor, SettingsDescription, Value},
     timing::{
-        formatter::{Accuracy, Delta, TimeFormatter},
+        formatter::{Accuracy, Delta, FrameWrapper, TimeFormatter},
         Snapshot,
     },
     GeneralLayoutSettings,
@@ -139,7 +139,11 @@ impl Component {
         let _ = write!(
             state.value,
             "{}",
-            Delta::custom(self.settings.drop_decimals, self.settings.accuracy).format(delta),
+            FrameWrapper::new(
+                timer.run().metadata().frame_rate(),
+                Delta::custom(self.settings.drop_decimals, self.settings.accuracy)
+            )
+            .format(delta),
         );
 
         state.key_abbreviations.clear();
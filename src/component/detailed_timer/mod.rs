@@ -11,8 +11,8 @@ use crate::{
     platform::prelude::*,
     settings::{CachedImageId, Color, Field, Gradient, ImageData, SettingsDescription, Value},
     timing::{
-        formatter::{Accuracy, DigitsFormat, SegmentTime, TimeFormatter},
-        Snapshot,
+        formatter::{Accuracy, DigitsFormat, FrameWrapper, SegmentTime, TimeFormatter},
+        FrameRate, Snapshot,
     },
     GeneralLayoutSettings, Segment, TimeSpan, TimerPhase,
 };
@@ -94,6 +94,7 @@ pub struct ComparisonState {
 fn update_comparison(
     state: &mut Option<ComparisonState>,
     new_state: Option<(&str, Option<TimeSpan>)>,
+    frame_rate: Option<FrameRate>,
 ) {
     if let Some((name, time)) = new_state {
         let state = state.get_or_insert_with(|| ComparisonState {
@@ -105,7 +106,11 @@ fn update_comparison(
         state.name.push_str(name);
 
         state.time.clear();
-        let _ = write!(state.time, "{}", SegmentTime::new().format(time));
+        let _ = write!(
+            state.time,
+            "{}",
+            FrameWrapper::new(frame_rate, SegmentTime::new()).format(time)
+        );
     } else {
         *state = None;
     }
@@ -279,8 +284,9 @@ impl Component {
         self.segment_timer
             .update_state(&mut state.segment_timer, timer, layout_settings);
 
-        update_comparison(&mut state.comparison1, comparison1);
-        update_comparison(&mut state.comparison2, comparison2);
+        let frame_rate = timer.run().metadata().frame_rate();
+        update_comparison(&mut state.comparison1, comparison1, frame_rate);
+        update_comparison(&mut state.comparison2, comparison2, frame_rate);
 
         match current_split.filter(|_| self.settings.show_segment_name) {
             Some(segment) => {
@@ -12,9 +12,12 @@ pub mod graph;
 pub mod pb_chance;
 pub mod possible_time_save;
 pub mod previous_segment;
+pub mod run_history;
+pub mod segment_distribution;
 pub mod segment_time;
 pub mod separator;
 pub mod splits;
+pub mod statistic;
 pub mod sum_of_best;
 pub mod text;
 pub mod timer;
@@ -32,9 +35,12 @@ pub use graph::Component as Graph;
 pub use pb_chance::Component as PbChance;
 pub use possible_time_save::Component as PossibleTimeSave;
 pub use previous_segment::Component as PreviousSegment;
+pub use run_history::Component as RunHistory;
+pub use segment_distribution::Component as SegmentDistribution;
 pub use segment_time::Component as SegmentTime;
 pub use separator::Component as Separator;
 pub use splits::Component as Splits;
+pub use statistic::Component as Statistic;
 pub use sum_of_best::Component as SumOfBest;
 pub use text::Component as Text;
 pub use timer::Component as Timer;
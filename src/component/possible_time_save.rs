@@ -11,7 +11,7 @@ use crate::{
     platform::prelude::*,
     settings::{Color, Field, Gradient, SettingsDescription, Value},
     timing::{
-        formatter::{Accuracy, SegmentTime, TimeFormatter},
+        formatter::{Accuracy, FrameWrapper, SegmentTime, TimeFormatter},
         Snapshot,
     },
     TimerPhase,
@@ -145,7 +145,11 @@ impl Component {
         let _ = write!(
             state.value,
             "{}",
-            SegmentTime::with_accuracy(self.settings.accuracy).format(time)
+            FrameWrapper::new(
+                timer.run().metadata().frame_rate(),
+                SegmentTime::with_accuracy(self.settings.accuracy)
+            )
+            .format(time)
         );
 
         state.key_abbreviations.clear();
@@ -11,7 +11,7 @@ use crate::{
     platform::prelude::*,
     settings::{Color, Field, Gradient, SemanticColor, SettingsDescription, Value},
     timing::{
-        formatter::{Accuracy, Delta, SegmentTime, TimeFormatter},
+        formatter::{Accuracy, Delta, FrameWrapper, SegmentTime, TimeFormatter},
         Snapshot,
     },
     GeneralLayoutSettings, TimerPhase,
@@ -209,18 +209,28 @@ impl Component {
         state.key.clear();
         state.key.push_str(&text); // FIXME: Uncow
 
+        let frame_rate = timer.run().metadata().frame_rate();
+
         state.value.clear();
         let _ = write!(
             state.value,
             "{}",
-            Delta::custom(self.settings.drop_decimals, self.settings.accuracy).format(time_change),
+            FrameWrapper::new(
+                frame_rate,
+                Delta::custom(self.settings.drop_decimals, self.settings.accuracy)
+            )
+            .format(time_change),
         );
 
         if self.settings.show_possible_time_save {
             let _ = write!(
                 state.value,
                 " / {}",
-                SegmentTime::with_accuracy(self.settings.accuracy).format(previous_possible),
+                FrameWrapper::new(
+                    frame_rate,
+                    SegmentTime::with_accuracy(self.settings.accuracy)
+                )
+                .format(previous_possible),
             );
         }
 
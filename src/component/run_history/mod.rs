@@ -0,0 +1,528 @@
+//! Provides the Run History Component and relevant types for using it. The Run
+//! History Component visualizes the history of all the attempts of the Run.
+//! The final time of every finished attempt and the split time every reset
+//! attempt reached are shown as points. Additionally the progression of the
+//! Personal Best and optionally the progression of the Sum of Best Segments
+//! over the attempts are shown as lines.
+
+use crate::{
+    platform::{math::f64::floor, prelude::*},
+    settings::{Color, Field, Gradient, SettingsDescription, Value},
+    timing::Snapshot,
+    Run, TimeSpan, TimingMethod,
+};
+use core::convert::TryFrom;
+use serde::{Deserialize, Serialize};
+
+#[cfg(test)]
+mod tests;
+
+/// The space that is kept free at the edges of the graph, so that the points
+/// at the edges are fully visible.
+const PADDING: f32 = 0.05;
+
+/// The Run History Component visualizes the history of all the attempts of the
+/// Run. The final time of every finished attempt and the split time every
+/// reset attempt reached are shown as points. Additionally the progression of
+/// the Personal Best and optionally the progression of the Sum of Best
+/// Segments over the attempts are shown as lines.
+#[derive(Default, Clone)]
+pub struct Component {
+    settings: Settings,
+    entries: Vec<Entry>,
+    entries_key: Option<EntriesKey>,
+}
+
+/// Everything the cached entries are based on. The entries only need to be
+/// collected again once any of this changes.
+type EntriesKey = (u64, TimingMethod, HistoryAxis);
+
+/// Describes what the horizontal axis of the Run History Component is based
+/// on.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub enum HistoryAxis {
+    /// The attempts are placed according to their attempt index.
+    AttemptIndex,
+    /// The attempts are placed according to the date they were started at.
+    /// Attempts without a known date are not shown.
+    Date,
+}
+
+/// The Settings for this component.
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Settings {
+    /// The background shown behind the component.
+    pub background: Gradient,
+    /// The height of the graph.
+    pub height: u32,
+    /// Specifies what the horizontal axis is based on.
+    pub x_axis: HistoryAxis,
+    /// The timing method to use for the times shown. If not specified, the
+    /// current timing method is used.
+    pub timing_method: Option<TimingMethod>,
+    /// Specifies whether the split times reached by the attempts that got
+    /// reset are shown.
+    pub show_resets: bool,
+    /// Specifies whether the progression of the Sum of Best Segments is shown.
+    pub show_sum_of_best: bool,
+    /// The color of the grid lines on the graph.
+    pub grid_lines_color: Color,
+    /// The color of the points of the finished attempts.
+    pub finished_color: Color,
+    /// The color of the points of the attempts that got reset.
+    pub reset_color: Color,
+    /// The color of the line showing the progression of the Personal Best and
+    /// of the points of the attempts that achieved a new Personal Best.
+    pub personal_best_color: Color,
+    /// The color of the line showing the progression of the Sum of Best
+    /// Segments.
+    pub sum_of_best_color: Color,
+}
+
+/// The state object describes the information to visualize for this component.
+/// All the coordinates are in the range 0..1, with the slower times at the top.
+#[derive(Default, Serialize, Deserialize)]
+pub struct State {
+    /// The background shown behind the component.
+    pub background: Gradient,
+    /// All the attempts to visualize as points.
+    pub attempts: Vec<AttemptPoint>,
+    /// The points of the line showing the progression of the Personal Best.
+    /// Connect all of them to visualize the line.
+    pub personal_best_line: Vec<Point>,
+    /// The points of the line showing the progression of the Sum of Best
+    /// Segments. Connect all of them to visualize the line. This is empty if
+    /// the progression is not supposed to be shown.
+    pub sum_of_best_line: Vec<Point>,
+    /// Contains the y coordinates of all the horizontal grid lines.
+    pub horizontal_grid_lines: Vec<f32>,
+    /// The color of the grid lines on the graph.
+    pub grid_lines_color: Color,
+    /// The color of the points of the finished attempts.
+    pub finished_color: Color,
+    /// The color of the points of the attempts that got reset.
+    pub reset_color: Color,
+    /// The color of the line showing the progression of the Personal Best and
+    /// of the points of the attempts that achieved a new Personal Best.
+    pub personal_best_color: Color,
+    /// The color of the line showing the progression of the Sum of Best
+    /// Segments.
+    pub sum_of_best_color: Color,
+    /// The height of the graph.
+    pub height: u32,
+}
+
+/// Describes an attempt to visualize as a point.
+#[derive(Copy, Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct AttemptPoint {
+    /// The x coordinate of the point.
+    pub x: f32,
+    /// The y coordinate of the point.
+    pub y: f32,
+    /// Describes whether the attempt was finished. Otherwise the attempt got
+    /// reset and the point visualizes the split time it reached.
+    pub is_finished: bool,
+    /// Describes whether the attempt achieved a new Personal Best.
+    pub is_personal_best: bool,
+}
+
+/// Describes a point of one of the lines to visualize.
+#[derive(Copy, Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Point {
+    /// The x coordinate of the point.
+    pub x: f32,
+    /// The y coordinate of the point.
+    pub y: f32,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            background: Gradient::Transparent,
+            height: 80,
+            x_axis: HistoryAxis::AttemptIndex,
+            timing_method: None,
+            show_resets: true,
+            show_sum_of_best: false,
+            grid_lines_color: Color::rgba(0.0, 0.0, 0.0, 0.15),
+            finished_color: Color::rgba(1.0, 1.0, 1.0, 1.0),
+            reset_color: Color::rgba(1.0, 1.0, 1.0, 0.35),
+            personal_best_color: Color::rgba(22.0 / 255.0, 166.0 / 255.0, 1.0, 1.0),
+            sum_of_best_color: Color::rgba(216.0 / 255.0, 175.0 / 255.0, 31.0 / 255.0, 1.0),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl State {
+    /// Encodes the state object's information as JSON.
+    pub fn write_json<W>(&self, writer: W) -> serde_json::Result<()>
+    where
+        W: std::io::Write,
+    {
+        serde_json::to_writer(writer, self)
+    }
+}
+
+/// An attempt of the Attempt History with all the information that is
+/// visualized about it. All the times are in seconds.
+#[derive(Clone)]
+struct Entry {
+    x: f64,
+    time: Option<f64>,
+    is_finished: bool,
+    is_personal_best: bool,
+    personal_best: Option<f64>,
+    sum_of_best: Option<f64>,
+}
+
+/// Tracks how far an attempt got by walking through the Segment History.
+#[derive(Default, Clone)]
+struct Progress {
+    segments_reached: usize,
+    split_time: Option<TimeSpan>,
+    previous_skipped: bool,
+}
+
+impl Component {
+    /// Creates a new Run History Component.
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Creates a new Run History Component with the given settings.
+    pub const fn with_settings(settings: Settings) -> Self {
+        Self {
+            settings,
+            entries: Vec::new(),
+            entries_key: None,
+        }
+    }
+
+    /// Accesses the settings of the component.
+    pub const fn settings(&self) -> &Settings {
+        &self.settings
+    }
+
+    /// Grants mutable access to the settings of the component.
+    pub const fn settings_mut(&mut self) -> &mut Settings {
+        &mut self.settings
+    }
+
+    /// Accesses the name of the component.
+    pub const fn name(&self) -> &'static str {
+        "Run History"
+    }
+
+    /// Updates the component's state based on the timer provided.
+    pub fn update_state(&mut self, state: &mut State, timer: &Snapshot<'_>) {
+        let method = self
+            .settings
+            .timing_method
+            .unwrap_or_else(|| timer.current_timing_method());
+
+        // Walking through the entire history is expensive, so the entries are
+        // only collected again when the history of the Run changed.
+        let key = (timer.run_revision(), method, self.settings.x_axis);
+        if self.entries_key != Some(key) {
+            self.entries = self.collect_entries(timer.run(), method);
+            self.entries_key = Some(key);
+        }
+        let entries = &self.entries;
+
+        state.attempts.clear();
+        state.personal_best_line.clear();
+        state.sum_of_best_line.clear();
+        state.horizontal_grid_lines.clear();
+
+        let times = entries.iter().flat_map(|e| {
+            let time = e
+                .time
+                .filter(|_| e.is_finished || self.settings.show_resets);
+            let sum_of_best = e.sum_of_best.filter(|_| self.settings.show_sum_of_best);
+            time.into_iter().chain(e.personal_best).chain(sum_of_best)
+        });
+
+        if let (Some((min_x, max_x)), Some((min_y, max_y))) =
+            (range(entries.iter().map(|e| e.x)), range(times))
+        {
+            let scale_x = |x: f64| scale(x, min_x, max_x);
+            let scale_y = |y: f64| 1.0 - scale(y, min_y, max_y);
+
+            for entry in entries {
+                if let Some(time) = entry.time {
+                    if entry.is_finished || self.settings.show_resets {
+                        state.attempts.push(AttemptPoint {
+                            x: scale_x(entry.x),
+                            y: scale_y(time),
+                            is_finished: entry.is_finished,
+                            is_personal_best: entry.is_personal_best,
+                        });
+                    }
+                }
+            }
+
+            step_line(
+                &mut state.personal_best_line,
+                entries.iter().map(|e| (e.x, e.personal_best)),
+                scale_x,
+                scale_y,
+            );
+
+            if self.settings.show_sum_of_best {
+                step_line(
+                    &mut state.sum_of_best_line,
+                    entries.iter().map(|e| (e.x, e.sum_of_best)),
+                    scale_x,
+                    scale_y,
+                );
+            }
+
+            // The grid lines are spaced 1, 2, 5, 10, 20, 50, ... seconds
+            // apart, so they always land on round times.
+            let mut grid_value = 1.0;
+            let mut steps = [2.0, 2.5, 2.0].iter().cycle();
+            while (max_y - min_y) / grid_value > 5.0 {
+                grid_value *= steps.next().unwrap();
+            }
+            let mut y = floor(min_y / grid_value) * grid_value;
+            while y <= max_y && max_y > min_y {
+                if y >= min_y {
+                    state.horizontal_grid_lines.push(scale_y(y));
+                }
+                y += grid_value;
+            }
+        }
+
+        state.background = self.settings.background;
+        state.grid_lines_color = self.settings.grid_lines_color;
+        state.finished_color = self.settings.finished_color;
+        state.reset_color = self.settings.reset_color;
+        state.personal_best_color = self.settings.personal_best_color;
+        state.sum_of_best_color = self.settings.sum_of_best_color;
+        state.height = self.settings.height;
+    }
+
+    /// Calculates the component's state based on the timer provided.
+    pub fn state(&mut self, timer: &Snapshot<'_>) -> State {
+        let mut state = Default::default();
+        self.update_state(&mut state, timer);
+        state
+    }
+
+    /// Accesses a generic description of the settings available for this
+    /// component and their current values.
+    pub fn settings_description(&self) -> SettingsDescription {
+        SettingsDescription::with_fields(vec![
+            Field::new("Background".into(), self.settings.background.into()),
+            Field::new("Height".into(), u64::from(self.settings.height).into()),
+            Field::new("X-Axis".into(), self.settings.x_axis.into()),
+            Field::new("Timing Method".into(), self.settings.timing_method.into()),
+            Field::new("Show Resets".into(), self.settings.show_resets.into()),
+            Field::new(
+                "Show Sum of Best".into(),
+                self.settings.show_sum_of_best.into(),
+            ),
+            Field::new(
+                "Grid Lines Color".into(),
+                self.settings.grid_lines_color.into(),
+            ),
+            Field::new("Finished Color".into(), self.settings.finished_color.into()),
+            Field::new("Reset Color".into(), self.settings.reset_color.into()),
+            Field::new(
+                "Personal Best Color".into(),
+                self.settings.personal_best_color.into(),
+            ),
+            Field::new(
+                "Sum of Best Color".into(),
+                self.settings.sum_of_best_color.into(),
+            ),
+        ])
+    }
+
+    /// Sets a setting's value by its index to the given value.
+    ///
+    /// # Panics
+    ///
+    /// This panics if the type of the value to be set is not compatible with
+    /// the type of the setting's value. A panic can also occur if the index of
+    /// the setting provided is out of bounds.
+    pub fn set_value(&mut self, index: usize, value: Value) {
+        match index {
+            0 => self.settings.background = value.into(),
+            1 => {
+                self.settings.height = u32::try_from(value.into_uint().unwrap()).unwrap_or(u32::MAX)
+            }
+            2 => self.settings.x_axis = value.into(),
+            3 => self.settings.timing_method = value.into(),
+            4 => self.settings.show_resets = value.into(),
+            5 => self.settings.show_sum_of_best = value.into(),
+            6 => self.settings.grid_lines_color = value.into(),
+            7 => self.settings.finished_color = value.into(),
+            8 => self.settings.reset_color = value.into(),
+            9 => self.settings.personal_best_color = value.into(),
+            10 => self.settings.sum_of_best_color = value.into(),
+            _ => panic!("Unsupported Setting Index"),
+        }
+    }
+
+    /// Collects the information about all the attempts in the order of their
+    /// attempt indices.
+    fn collect_entries(&self, run: &Run, method: TimingMethod) -> Vec<Entry> {
+        let mut attempts = run.attempt_history().iter().collect::<Vec<_>>();
+        attempts.sort_by_key(|a| a.index());
+        let indices = attempts.iter().map(|a| a.index()).collect::<Vec<_>>();
+
+        // The Segment History is walked through segment by segment, so that
+        // the split time every attempt reached is known without having to
+        // look up every attempt in every segment's history. While doing so,
+        // the segment times that can be used for the Sum of Best Segments are
+        // collected as well. Segment times that follow a skipped split are not
+        // used, as they also contain the skipped segments.
+        let mut progress = vec![Progress::default(); attempts.len()];
+        let mut segment_times = Vec::with_capacity(run.len());
+        for (segment_index, segment) in run.segments().iter().enumerate() {
+            let mut times = Vec::new();
+            for &(index, time) in segment.segment_history().iter() {
+                if let Ok(position) = indices.binary_search(&index) {
+                    let progress = &mut progress[position];
+                    if progress.segments_reached != segment_index {
+                        continue;
+                    }
+                    progress.segments_reached += 1;
+                    let follows_skipped_split = progress.previous_skipped;
+                    progress.previous_skipped = time[method].is_none();
+                    if let Some(time) = time[method] {
+                        progress.split_time = Some(progress.split_time.unwrap_or_default() + time);
+                        if !follows_skipped_split {
+                            times.push((index, time.total_seconds()));
+                        }
+                    }
+                } else if let Some(time) = time[method] {
+                    times.push((index, time.total_seconds()));
+                }
+            }
+            times.sort_by_key(|&(index, _)| index);
+            segment_times.push(times);
+        }
+
+        let origin = attempts
+            .iter()
+            .find_map(|a| a.started().or_else(|| a.ended()));
+
+        let mut cursors = vec![0; segment_times.len()];
+        let mut best_segments = vec![None::<f64>; segment_times.len()];
+        let mut personal_best = None::<f64>;
+
+        let mut entries = Vec::with_capacity(attempts.len());
+        for (attempt, progress) in attempts.iter().zip(progress) {
+            let index = attempt.index();
+
+            for ((times, cursor), best) in segment_times
+                .iter()
+                .zip(&mut cursors)
+                .zip(&mut best_segments)
+            {
+                while let Some(&(_, time)) = times.get(*cursor).filter(|&&(i, _)| i <= index) {
+                    *best = Some(best.map_or(time, |best: f64| best.min(time)));
+                    *cursor += 1;
+                }
+            }
+            let sum_of_best = if best_segments.is_empty() {
+                None
+            } else {
+                best_segments.iter().copied().sum::<Option<f64>>()
+            };
+
+            let final_time = attempt.time()[method].map(|t| t.total_seconds());
+            let is_finished = final_time.is_some();
+            let is_personal_best = match (final_time, personal_best) {
+                (Some(time), Some(best)) => time < best,
+                (Some(_), None) => true,
+                _ => false,
+            };
+            if is_personal_best {
+                personal_best = final_time;
+            }
+
+            let x = match self.settings.x_axis {
+                HistoryAxis::AttemptIndex => Some(f64::from(index)),
+                HistoryAxis::Date => catch! {
+                    (attempt.started().or_else(|| attempt.ended())? - origin?).total_seconds()
+                },
+            };
+
+            if let Some(x) = x {
+                entries.push(Entry {
+                    x,
+                    time: final_time.or_else(|| progress.split_time.map(|t| t.total_seconds())),
+                    is_finished,
+                    is_personal_best,
+                    personal_best,
+                    sum_of_best,
+                });
+            }
+        }
+
+        entries
+    }
+}
+
+/// Determines the minimum and the maximum of the values.
+fn range(values: impl IntoIterator<Item = f64>) -> Option<(f64, f64)> {
+    values.into_iter().fold(None, |range, value| {
+        Some(range.map_or((value, value), |(min, max): (f64, f64)| {
+            (min.min(value), max.max(value))
+        }))
+    })
+}
+
+/// Maps the value from the range provided into the range 0..1, leaving some
+/// padding at the edges. If the range is empty, the value is centered.
+fn scale(value: f64, min: f64, max: f64) -> f32 {
+    if max > min {
+        PADDING + (1.0 - 2.0 * PADDING) * ((value - min) / (max - min)) as f32
+    } else {
+        0.5
+    }
+}
+
+/// Builds a line that keeps its value until it changes, which is how the
+/// Personal Best and the Sum of Best Segments progress over the attempts.
+fn step_line(
+    line: &mut Vec<Point>,
+    values: impl Iterator<Item = (f64, Option<f64>)>,
+    scale_x: impl Fn(f64) -> f32,
+    scale_y: impl Fn(f64) -> f32,
+) {
+    let mut current = None;
+    let mut last_x = None;
+    for (x, value) in values {
+        if let Some(value) = value {
+            if current != Some(value) {
+                if let Some(current) = current {
+                    line.push(Point {
+                        x: scale_x(x),
+                        y: scale_y(current),
+                    });
+                }
+                line.push(Point {
+                    x: scale_x(x),
+                    y: scale_y(value),
+                });
+                current = Some(value);
+            }
+            last_x = Some(x);
+        }
+    }
+    if let (Some(current), Some(last_x)) = (current, last_x) {
+        let end = Point {
+            x: scale_x(last_x),
+            y: scale_y(current),
+        };
+        if line.last() != Some(&end) {
+            line.push(end);
+        }
+    }
+}
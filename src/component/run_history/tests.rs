@@ -0,0 +1,126 @@
+use super::{Component, HistoryAxis, Point};
+use crate::{
+    tests_helper::{create_timer, run_with_splits},
+    Time, TimeSpan, Timer,
+};
+
+fn create_history() -> Timer {
+    let mut timer = create_timer(&["A", "B", "C"]);
+    run_with_splits(&mut timer, &[1.0, 2.0, 3.0]);
+    run_with_splits(&mut timer, &[1.5]);
+    run_with_splits(&mut timer, &[1.0, 2.2, 2.9]);
+    timer
+}
+
+fn assert_close(actual: &[Point], expected: &[(f32, f32)]) {
+    assert_eq!(actual.len(), expected.len());
+    for (actual, &(x, y)) in actual.iter().zip(expected) {
+        assert!((actual.x - x).abs() < 1e-4, "{} != {}", actual.x, x);
+        assert!((actual.y - y).abs() < 1e-4, "{} != {}", actual.y, y);
+    }
+}
+
+#[test]
+fn shows_finished_and_reset_attempts() {
+    let timer = create_history();
+    let state = Component::new().state(&timer.snapshot());
+
+    let attempts = state
+        .attempts
+        .iter()
+        .map(|a| (a.is_finished, a.is_personal_best))
+        .collect::<Vec<_>>();
+    assert_eq!(attempts, [(true, true), (false, false), (true, true)]);
+
+    let points = state
+        .attempts
+        .iter()
+        .map(|a| Point { x: a.x, y: a.y })
+        .collect::<Vec<_>>();
+    // The times range from 1.5 seconds for the reset attempt to 3 seconds.
+    assert_close(&points, &[(0.05, 0.05), (0.5, 0.95), (0.05 + 0.9, 0.11)]);
+
+    assert_close(
+        &state.personal_best_line,
+        &[(0.05, 0.05), (0.95, 0.05), (0.95, 0.11)],
+    );
+    assert!(state.sum_of_best_line.is_empty());
+}
+
+#[test]
+fn shows_sum_of_best_progression() {
+    let timer = create_history();
+    let mut component = Component::new();
+    component.settings_mut().show_resets = false;
+    component.settings_mut().show_sum_of_best = true;
+    let state = component.state(&timer.snapshot());
+
+    assert_eq!(state.attempts.len(), 2);
+
+    // Without the reset attempt, the times range from the Sum of Best of 2.7
+    // seconds to 3 seconds.
+    assert_close(
+        &state.personal_best_line,
+        &[(0.05, 0.05), (0.95, 0.05), (0.95, 0.35)],
+    );
+    assert_close(
+        &state.sum_of_best_line,
+        &[(0.05, 0.05), (0.95, 0.05), (0.95, 0.95)],
+    );
+}
+
+#[test]
+fn skips_attempts_without_dates_on_date_axis() {
+    let mut timer = create_timer(&["A"]);
+    let mut run = timer.run().clone();
+    run.add_attempt(
+        Time::new().with_game_time(Some(TimeSpan::from_seconds(5.0))),
+        None,
+        None,
+        None,
+    );
+    timer.set_run(run).unwrap();
+
+    let mut component = Component::new();
+    component.settings_mut().timing_method = Some(crate::TimingMethod::GameTime);
+    assert_eq!(component.state(&timer.snapshot()).attempts.len(), 1);
+
+    component.settings_mut().x_axis = HistoryAxis::Date;
+    assert!(component.state(&timer.snapshot()).attempts.is_empty());
+}
+
+#[test]
+fn picks_up_new_attempts() {
+    let mut timer = create_history();
+    let mut component = Component::new();
+    assert_eq!(component.state(&timer.snapshot()).attempts.len(), 3);
+
+    run_with_splits(&mut timer, &[0.5]);
+    assert_eq!(component.state(&timer.snapshot()).attempts.len(), 4);
+}
+
+#[test]
+fn grid_lines_are_spaced_at_round_times() {
+    let mut timer = create_timer(&["A"]);
+    run_with_splits(&mut timer, &[10.0]);
+    run_with_splits(&mut timer, &[40.0]);
+
+    // The 30 seconds between the attempts are split into steps of 10 seconds.
+    let state = Component::new().state(&timer.snapshot());
+    let lines = state
+        .horizontal_grid_lines
+        .iter()
+        .map(|&y| Point { x: 0.0, y })
+        .collect::<Vec<_>>();
+    assert_close(
+        &lines,
+        &[(0.0, 0.95), (0.0, 0.65), (0.0, 0.35), (0.0, 0.05)],
+    );
+}
+
+#[test]
+fn height_is_clamped() {
+    let mut component = Component::new();
+    component.set_value(1, (u64::from(u32::MAX) + 1).into());
+    assert_eq!(component.settings().height, u32::MAX);
+}
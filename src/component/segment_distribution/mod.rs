@@ -0,0 +1,259 @@
+//! Provides the Segment Distribution Component and relevant types for using it.
+//! The Segment Distribution Component visualizes how the segment times of the
+//! current segment are distributed as a histogram and marks where the live
+//! segment time of the current attempt lands in that distribution.
+
+use crate::{
+    analysis::{self, SkillCurve},
+    platform::prelude::*,
+    settings::{Color, Field, Gradient, SettingsDescription, Value},
+    timing::Snapshot,
+    TimeSpan, TimerPhase, TimingMethod,
+};
+use serde::{Deserialize, Serialize};
+
+#[cfg(test)]
+mod tests;
+
+/// The maximum amount of bars the histogram can be divided into.
+pub const MAX_BAR_COUNT: u32 = 256;
+
+/// The Segment Distribution Component visualizes how the segment times of the
+/// current segment are distributed as a histogram and marks where the live
+/// segment time of the current attempt lands in that distribution. More recent
+/// segment times are weighted more heavily. If there is no active attempt, the
+/// distribution of the first segment is shown.
+#[derive(Default, Clone)]
+pub struct Component {
+    settings: Settings,
+    skill_curve: SkillCurve,
+}
+
+/// The Settings for this component.
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Settings {
+    /// The background shown behind the component.
+    pub background: Gradient,
+    /// The height of the histogram.
+    pub height: u32,
+    /// The amount of bars the histogram is divided into. This is limited to
+    /// `MAX_BAR_COUNT` bars.
+    pub bar_count: u32,
+    /// The timing method to use for the segment times. If not specified, the
+    /// current timing method is used.
+    pub timing_method: Option<TimingMethod>,
+    /// The color of the bars of the histogram.
+    pub bar_color: Color,
+    /// The color of the marker that shows where the live segment time lands.
+    pub marker_color: Color,
+}
+
+/// The state object describes the information to visualize for this component.
+/// All the coordinates are in the range 0..1, with the faster segment times on
+/// the left.
+#[derive(Default, Serialize, Deserialize)]
+pub struct State {
+    /// The background shown behind the component.
+    pub background: Gradient,
+    /// The heights of all the bars of the histogram from left to right. The
+    /// highest bar has a height of 1. This is empty if the segment has no
+    /// segment history.
+    pub bars: Vec<f32>,
+    /// The x coordinate of the live segment time. If the live segment time is
+    /// outside the range of the histogram, the marker is placed at the edge of
+    /// the histogram. This is `None` if there is no live segment time.
+    pub marker: Option<f32>,
+    /// The share of the weighted segment times that are faster than the live
+    /// segment time. This is `None` if there is no live segment time.
+    pub marker_percentile: Option<f64>,
+    /// The color of the bars of the histogram.
+    pub bar_color: Color,
+    /// The color of the marker that shows where the live segment time lands.
+    pub marker_color: Color,
+    /// The height of the histogram.
+    pub height: u32,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            background: Gradient::Transparent,
+            height: 60,
+            bar_count: 16,
+            timing_method: None,
+            bar_color: Color::rgba(1.0, 1.0, 1.0, 0.4),
+            marker_color: Color::rgba(1.0, 1.0, 1.0, 1.0),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl State {
+    /// Encodes the state object's information as JSON.
+    pub fn write_json<W>(&self, writer: W) -> serde_json::Result<()>
+    where
+        W: std::io::Write,
+    {
+        serde_json::to_writer(writer, self)
+    }
+}
+
+impl Component {
+    /// Creates a new Segment Distribution Component.
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Creates a new Segment Distribution Component with the given settings.
+    pub fn with_settings(settings: Settings) -> Self {
+        Self {
+            settings,
+            ..Default::default()
+        }
+    }
+
+    /// Accesses the settings of the component.
+    pub const fn settings(&self) -> &Settings {
+        &self.settings
+    }
+
+    /// Grants mutable access to the settings of the component.
+    pub const fn settings_mut(&mut self) -> &mut Settings {
+        &mut self.settings
+    }
+
+    /// Accesses the name of the component.
+    pub const fn name(&self) -> &'static str {
+        "Segment Distribution"
+    }
+
+    /// Updates the component's state based on the timer provided.
+    pub fn update_state(&mut self, state: &mut State, timer: &Snapshot<'_>) {
+        let run = timer.run();
+        let method = self
+            .settings
+            .timing_method
+            .unwrap_or_else(|| timer.current_timing_method());
+        let segment_index = timer
+            .current_split_index()
+            .unwrap_or(0)
+            .min(run.len().saturating_sub(1));
+
+        state.bars.clear();
+        state.marker = None;
+        state.marker_percentile = None;
+
+        if run.is_empty() {
+            self.skill_curve.truncate(0);
+        } else {
+            self.skill_curve
+                .for_segment(run.segments(), segment_index, method);
+        }
+
+        if !self.skill_curve.is_empty() {
+            let fastest = self.time_at_percentile(0.0);
+            let slowest = self.time_at_percentile(1.0);
+            let range = (slowest - fastest).total_seconds();
+
+            if range > 0.0 {
+                let bar_count = self.settings.bar_count.clamp(1, MAX_BAR_COUNT);
+                let mut previous = 0.0;
+                for bar in 1..=bar_count {
+                    let edge =
+                        fastest + TimeSpan::from_seconds(range * bar as f64 / bar_count as f64);
+                    let percentile = if bar == bar_count {
+                        1.0
+                    } else {
+                        self.percentile_for_time(edge)
+                    };
+                    state.bars.push((percentile - previous) as f32);
+                    previous = percentile;
+                }
+
+                let highest = state.bars.iter().copied().fold(0.0, f32::max);
+                if highest > 0.0 {
+                    for bar in &mut state.bars {
+                        *bar /= highest;
+                    }
+                }
+            } else {
+                state.bars.push(1.0);
+            }
+
+            let phase = timer.current_phase();
+            if phase == TimerPhase::Running || phase == TimerPhase::Paused {
+                if let Some(time) = analysis::live_segment_time(timer, segment_index, method) {
+                    state.marker = Some(if range > 0.0 {
+                        (((time - fastest).total_seconds() / range) as f32).clamp(0.0, 1.0)
+                    } else {
+                        0.5
+                    });
+                    state.marker_percentile = Some(self.percentile_for_time(time));
+                }
+            }
+        }
+
+        state.background = self.settings.background;
+        state.bar_color = self.settings.bar_color;
+        state.marker_color = self.settings.marker_color;
+        state.height = self.settings.height;
+    }
+
+    /// Calculates the component's state based on the timer provided.
+    pub fn state(&mut self, timer: &Snapshot<'_>) -> State {
+        let mut state = Default::default();
+        self.update_state(&mut state, timer);
+        state
+    }
+
+    /// Accesses a generic description of the settings available for this
+    /// component and their current values.
+    pub fn settings_description(&self) -> SettingsDescription {
+        SettingsDescription::with_fields(vec![
+            Field::new("Background".into(), self.settings.background.into()),
+            Field::new("Height".into(), u64::from(self.settings.height).into()),
+            Field::new(
+                "Bar Count".into(),
+                u64::from(self.settings.bar_count).into(),
+            ),
+            Field::new("Timing Method".into(), self.settings.timing_method.into()),
+            Field::new("Bar Color".into(), self.settings.bar_color.into()),
+            Field::new("Marker Color".into(), self.settings.marker_color.into()),
+        ])
+    }
+
+    /// Sets a setting's value by its index to the given value.
+    ///
+    /// # Panics
+    ///
+    /// This panics if the type of the value to be set is not compatible with
+    /// the type of the setting's value. A panic can also occur if the index of
+    /// the setting provided is out of bounds.
+    pub fn set_value(&mut self, index: usize, value: Value) {
+        match index {
+            0 => self.settings.background = value.into(),
+            1 => self.settings.height = value.into_uint().unwrap() as _,
+            2 => {
+                self.settings.bar_count =
+                    value.into_uint().unwrap().min(u64::from(MAX_BAR_COUNT)) as _
+            }
+            3 => self.settings.timing_method = value.into(),
+            4 => self.settings.bar_color = value.into(),
+            5 => self.settings.marker_color = value.into(),
+            _ => panic!("Unsupported Setting Index"),
+        }
+    }
+
+    fn time_at_percentile(&self, percentile: f64) -> TimeSpan {
+        self.skill_curve
+            .iter_segment_times_at_percentile(percentile)
+            .next()
+            .unwrap_or_default()
+    }
+
+    fn percentile_for_time(&self, time: TimeSpan) -> f64 {
+        self.skill_curve
+            .find_percentile_for_time(TimeSpan::zero(), time)
+    }
+}
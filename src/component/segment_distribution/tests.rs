@@ -0,0 +1,67 @@
+use super::{Component, MAX_BAR_COUNT};
+use crate::tests_helper::{create_timer, run_with_splits, span, start_run};
+
+fn assert_close(actual: f64, expected: f64) {
+    assert!(
+        (actual - expected).abs() < 1e-3,
+        "{} != {}",
+        actual,
+        expected
+    );
+}
+
+#[test]
+fn builds_histogram_from_weighted_segment_times() {
+    let mut timer = create_timer(&["A", "B"]);
+    run_with_splits(&mut timer, &[10.0, 20.0]);
+    run_with_splits(&mut timer, &[12.0, 22.0]);
+    run_with_splits(&mut timer, &[20.0, 30.0]);
+
+    let mut component = Component::new();
+    component.settings_mut().bar_count = 2;
+    let state = component.state(&timer.snapshot());
+
+    // The more recent segment times are weighted more heavily, so 10 and 12
+    // seconds are at the percentiles 0 and 0.43, while 20 seconds is at 1. This
+    // puts 15 seconds at the percentile 0.64.
+    assert_eq!(state.bars.len(), 2);
+    assert_close(state.bars[0].into(), 1.0);
+    assert_close(state.bars[1].into(), 0.357 / 0.643);
+    assert_eq!(state.marker, None);
+}
+
+#[test]
+fn marks_live_segment_time() {
+    let mut timer = create_timer(&["A", "B"]);
+    run_with_splits(&mut timer, &[10.0, 20.0]);
+    run_with_splits(&mut timer, &[12.0, 22.0]);
+    run_with_splits(&mut timer, &[20.0, 30.0]);
+
+    let mut component = Component::new();
+
+    start_run(&mut timer);
+    timer.set_game_time(span(15.0));
+    let state = component.state(&timer.snapshot());
+    assert_close(state.marker.unwrap().into(), 0.5);
+    assert_close(state.marker_percentile.unwrap(), 0.643);
+
+    timer.set_game_time(span(25.0));
+    let state = component.state(&timer.snapshot());
+    assert_eq!(state.marker, Some(1.0));
+    assert_close(state.marker_percentile.unwrap(), 1.0);
+}
+
+#[test]
+fn is_empty_without_segment_history() {
+    let timer = create_timer(&["A", "B"]);
+    let state = Component::new().state(&timer.snapshot());
+    assert!(state.bars.is_empty());
+    assert_eq!(state.marker, None);
+}
+
+#[test]
+fn bar_count_is_limited() {
+    let mut component = Component::new();
+    component.set_value(2, u64::MAX.into());
+    assert_eq!(component.settings().bar_count, MAX_BAR_COUNT);
+}
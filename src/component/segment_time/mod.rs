@@ -9,7 +9,7 @@ use crate::{
     comparison,
     platform::prelude::*,
     settings::{Color, Field, Gradient, SettingsDescription, Value},
-    timing::formatter::{Accuracy, SegmentTime, TimeFormatter},
+    timing::formatter::{Accuracy, FrameWrapper, SegmentTime, TimeFormatter},
     Timer, TimerPhase,
 };
 use alloc::borrow::Cow;
@@ -135,7 +135,11 @@ impl Component {
         let _ = write!(
             state.value,
             "{}",
-            SegmentTime::with_accuracy(self.settings.accuracy).format(time),
+            FrameWrapper::new(
+                timer.run().metadata().frame_rate(),
+                SegmentTime::with_accuracy(self.settings.accuracy)
+            )
+            .format(time),
         );
 
         state.key_abbreviations.clear();
@@ -1,6 +1,7 @@
 use super::Component;
 use crate::{
     tests_helper::{self, make_progress_run_with_splits_opt, run_with_splits, start_run},
+    timing::FrameRate,
     Timer,
 };
 
@@ -48,3 +49,15 @@ fn is_empty_when_attempt_is_finished() {
     let state = component.state(&timer);
     assert_eq!(&*state.value, "—");
 }
+
+#[test]
+fn is_rounded_to_frames() {
+    let mut run = tests_helper::create_run(&["A", "B"]);
+    run.metadata_mut().set_frame_rate(FrameRate::new(10.0));
+    let mut timer = Timer::new(run).unwrap();
+    run_with_splits(&mut timer, &[12.34, 45.67]);
+    start_run(&mut timer);
+    make_progress_run_with_splits_opt(&mut timer, &[Some(467.23)]);
+    let state = Component::new().state(&timer);
+    assert_eq!(&*state.value, "33.40");
+}
@@ -1,16 +1,17 @@
 use crate::{
-    analysis::{self, possible_time_save, split_color},
+    analysis::{self, possible_time_save, split_color, statistics},
     clear_vec::Clear,
-    comparison,
+    comparison::{self, personal_best},
     platform::prelude::*,
+    run::SegmentGroup,
     settings::{Color, SemanticColor},
     timing::{
-        formatter::{Delta, Regular, SegmentTime, TimeFormatter},
+        formatter::{self, Delta, FrameWrapper, Regular, SegmentTime, TimeFormatter},
         Snapshot,
     },
-    GeneralLayoutSettings, Segment, TimeSpan, TimingMethod,
+    GeneralLayoutSettings, Run, Segment, TimeSpan, TimingMethod,
 };
-use core::fmt::Write;
+use core::{fmt::Write, ops::Range};
 use serde::{Deserialize, Serialize};
 
 /// The settings of an individual column showing timing information on each
@@ -53,6 +54,22 @@ pub enum ColumnStartWith {
     /// The column starts out with the time that can be saved on each individual
     /// segment stored in the comparison that is being compared against.
     PossibleTimeSave,
+    /// The column starts out with the time that can be saved on the segment
+    /// and all the segments after it, based on the comparison that is being
+    /// compared against and the best segments.
+    BestPossibleTimeSave,
+    /// The column starts out with the average of the segment times of all the
+    /// previous attempts.
+    AverageSegmentTime,
+    /// The column starts out with the median of the segment times of all the
+    /// previous attempts.
+    MedianSegmentTime,
+    /// The column starts out with the percentage of the attempts that reached
+    /// the segment, but were reset during it.
+    ResetPercentage,
+    /// The column starts out with the amount of times a new best segment time
+    /// was achieved on the segment.
+    GoldCount,
 }
 
 /// Once a certain condition is met, which is usually being on the split or
@@ -85,6 +102,10 @@ pub enum ColumnUpdateWith {
     /// Segment component. If there is no time saved or lost, then value gets
     /// replaced by the current attempt's segment time instead.
     SegmentDeltaWithFallback,
+    /// The value gets replaced by the time that could have been saved on the
+    /// segment in the current attempt, which is how much slower the current
+    /// attempt's segment time is compared to the Personal Best's segment time.
+    PossibleTimeSave,
 }
 
 /// Specifies when a column's value gets updated.
@@ -139,33 +160,111 @@ enum ColumnFormatter {
     Time,
     Delta,
     PossibleTimeSave,
+    Percentage(Option<f64>),
+    Count(u32),
+}
+
+/// Caches the statistics the columns are based on, as calculating them means
+/// going through the entire history of the Run. The cache is cleared whenever
+/// the revision of the Run changes.
+#[derive(Default, Clone)]
+pub struct StatisticsCache {
+    revision: Option<u64>,
+    entries: Vec<CachedStatistics>,
+}
+
+#[derive(Clone)]
+struct CachedStatistics {
+    segments: Range<usize>,
+    method: TimingMethod,
+    statistics: statistics::SegmentStatistics,
+    golds: u32,
+}
+
+impl StatisticsCache {
+    /// Clears the cache if the Run changed since the statistics got cached.
+    pub fn update(&mut self, timer: &Snapshot<'_>) {
+        let revision = timer.run_revision();
+        if self.revision != Some(revision) {
+            self.revision = Some(revision);
+            self.entries.clear();
+        }
+    }
+
+    fn get(
+        &mut self,
+        run: &Run,
+        segments: Range<usize>,
+        method: TimingMethod,
+    ) -> &CachedStatistics {
+        if let Some(index) = self
+            .entries
+            .iter()
+            .position(|e| e.segments == segments && e.method == method)
+        {
+            return &self.entries[index];
+        }
+
+        // A segment time is a gold if it beat all the segment times before it.
+        let mut best = None;
+        let golds = statistics::segment_times(run, segments.clone(), method)
+            .into_iter()
+            .filter(|&(_, time)| {
+                let is_gold = best.map_or(true, |best| time < best);
+                if is_gold {
+                    best = Some(time);
+                }
+                is_gold
+            })
+            .count();
+
+        self.entries.push(CachedStatistics {
+            statistics: statistics::for_segment_range(run, segments.clone(), method),
+            segments,
+            method,
+            golds: golds as u32,
+        });
+        self.entries.last().unwrap()
+    }
+}
+
+/// The row of the Splits Component the columns are updated for.
+#[derive(Copy, Clone)]
+pub struct RowContext<'a> {
+    pub timer: &'a Snapshot<'a>,
+    pub layout_settings: &'a GeneralLayoutSettings,
+    /// The segment of the row. For Segment Groups this is the group's last
+    /// segment.
+    pub segment: &'a Segment,
+    pub segment_index: usize,
+    pub group: Option<&'a SegmentGroup>,
+    pub current_split: Option<usize>,
+    pub method: TimingMethod,
 }
 
 pub fn update_state(
     state: &mut ColumnState,
+    statistics: &mut StatisticsCache,
     column: &ColumnSettings,
-    timer: &Snapshot<'_>,
-    layout_settings: &GeneralLayoutSettings,
-    segment: &Segment,
-    segment_index: usize,
-    current_split: Option<usize>,
-    method: TimingMethod,
+    row: RowContext<'_>,
 ) {
-    let method = column.timing_method.unwrap_or(method);
-    let resolved_comparison = comparison::resolve(&column.comparison_override, timer);
-    let comparison = comparison::or_current(resolved_comparison, timer);
-
-    let update_value = column_update_value(
-        column,
+    let RowContext {
         timer,
+        layout_settings,
         segment,
         segment_index,
-        current_split,
+        group,
         method,
-        comparison,
-    );
+        ..
+    } = row;
+    let method = column.timing_method.unwrap_or(method);
+    let resolved_comparison = comparison::resolve(&column.comparison_override, timer);
+    let comparison = comparison::or_current(resolved_comparison, timer);
+
+    let update_value = column_update_value(column, row, method, comparison);
 
     let updated = update_value.is_some();
+    let segments = group.map_or(segment_index..segment_index + 1, |g| g.range());
 
     let ((column_value, semantic_color, formatter), is_live) = update_value.unwrap_or_else(|| {
         (
@@ -177,20 +276,78 @@ pub fn update_state(
                     ColumnFormatter::Time,
                 ),
                 ColumnStartWith::ComparisonSegmentTime => (
-                    analysis::comparison_combined_segment_time(
-                        timer.run(),
-                        segment_index,
-                        comparison,
-                        method,
-                    ),
+                    if let Some(group) = group {
+                        analysis::comparison_combined_segment_group_time(
+                            timer.run(),
+                            group,
+                            comparison,
+                            method,
+                        )
+                    } else {
+                        analysis::comparison_combined_segment_time(
+                            timer.run(),
+                            segment_index,
+                            comparison,
+                            method,
+                        )
+                    },
                     SemanticColor::Default,
                     ColumnFormatter::Time,
                 ),
                 ColumnStartWith::PossibleTimeSave => (
-                    possible_time_save::calculate(timer, segment_index, comparison, false),
+                    if let Some(group) = group {
+                        group
+                            .range()
+                            .filter_map(|i| {
+                                possible_time_save::calculate(timer, i, comparison, false)
+                            })
+                            .fold(None, |sum, t| Some(sum.unwrap_or_default() + t))
+                    } else {
+                        possible_time_save::calculate(timer, segment_index, comparison, false)
+                    },
+                    SemanticColor::Default,
+                    ColumnFormatter::PossibleTimeSave,
+                ),
+                ColumnStartWith::BestPossibleTimeSave => (
+                    Some(possible_time_save::calculate_total(
+                        timer,
+                        group.map_or(segment_index, |g| g.start()),
+                        comparison,
+                    )),
                     SemanticColor::Default,
                     ColumnFormatter::PossibleTimeSave,
                 ),
+                ColumnStartWith::AverageSegmentTime => (
+                    statistics
+                        .get(timer.run(), segments, method)
+                        .statistics
+                        .mean,
+                    SemanticColor::Default,
+                    ColumnFormatter::Time,
+                ),
+                ColumnStartWith::MedianSegmentTime => (
+                    statistics
+                        .get(timer.run(), segments, method)
+                        .statistics
+                        .median,
+                    SemanticColor::Default,
+                    ColumnFormatter::Time,
+                ),
+                ColumnStartWith::ResetPercentage => (
+                    None,
+                    SemanticColor::Default,
+                    ColumnFormatter::Percentage(
+                        statistics
+                            .get(timer.run(), segments, method)
+                            .statistics
+                            .reset_rate(),
+                    ),
+                ),
+                ColumnStartWith::GoldCount => (
+                    None,
+                    SemanticColor::Default,
+                    ColumnFormatter::Count(statistics.get(timer.run(), segments, method).golds),
+                ),
             },
             false,
         )
@@ -202,16 +359,28 @@ pub fn update_state(
 
     state.value.clear();
     if !is_empty {
+        let frame_rate = timer.run().metadata().frame_rate();
         let _ = match formatter {
-            ColumnFormatter::Time => write!(state.value, "{}", Regular::new().format(column_value)),
+            ColumnFormatter::Time => write!(
+                state.value,
+                "{}",
+                FrameWrapper::new(frame_rate, Regular::new()).format(column_value)
+            ),
             ColumnFormatter::Delta => write!(
                 state.value,
                 "{}",
-                Delta::with_decimal_dropping().format(column_value)
+                FrameWrapper::new(frame_rate, Delta::with_decimal_dropping()).format(column_value)
+            ),
+            ColumnFormatter::PossibleTimeSave => write!(
+                state.value,
+                "{}",
+                FrameWrapper::new(frame_rate, SegmentTime::new()).format(column_value)
             ),
-            ColumnFormatter::PossibleTimeSave => {
-                write!(state.value, "{}", SegmentTime::new().format(column_value))
+            ColumnFormatter::Percentage(Some(percentage)) => {
+                write!(state.value, "{:.1}%", 100.0 * percentage)
             }
+            ColumnFormatter::Percentage(None) => write!(state.value, "{}", formatter::DASH),
+            ColumnFormatter::Count(count) => write!(state.value, "{}", count),
         };
     }
 
@@ -221,21 +390,29 @@ pub fn update_state(
 
 fn column_update_value(
     column: &ColumnSettings,
-    timer: &Snapshot<'_>,
-    segment: &Segment,
-    segment_index: usize,
-    current_split: Option<usize>,
+    row: RowContext<'_>,
     method: TimingMethod,
     comparison: &str,
 ) -> Option<((Option<TimeSpan>, SemanticColor, ColumnFormatter), bool)> {
     use self::{ColumnUpdateTrigger::*, ColumnUpdateWith::*};
 
-    if current_split < Some(segment_index) {
+    let RowContext {
+        timer,
+        segment,
+        segment_index,
+        group,
+        current_split,
+        ..
+    } = row;
+
+    let start_index = group.map_or(segment_index, |g| g.start());
+
+    if current_split < Some(start_index) {
         // Didn't reach the segment yet.
         return None;
     }
 
-    let is_current_split = current_split == Some(segment_index);
+    let is_current_split = current_split.map_or(false, |c| c <= segment_index);
 
     if is_current_split {
         if column.update_trigger == OnEndingSegment {
@@ -284,53 +461,107 @@ fn column_update_value(
             } else {
                 (delta, ColumnFormatter::Delta)
             };
+            let color = if let Some(group) = group {
+                analysis::segment_group_split_color(
+                    timer, delta, group, true, true, comparison, method,
+                )
+            } else {
+                split_color(timer, delta, segment_index, true, true, comparison, method)
+            };
+            (value, color, formatter)
+        }
+        (Delta, true) | (DeltaWithFallback, true) => {
+            // Segment Groups compare against the split the attempt is on.
+            let live_index = current_split.map_or(segment_index, |c| c.min(segment_index));
             (
-                value,
-                split_color(timer, delta, segment_index, true, true, comparison, method),
-                formatter,
+                catch! {
+                    timer.current_time()[method]? -
+                    timer.run().segment(live_index).comparison(comparison)[method]?
+                },
+                SemanticColor::Default,
+                ColumnFormatter::Delta,
             )
         }
-        (Delta, true) | (DeltaWithFallback, true) => (
-            catch! {
-                timer.current_time()[method]? -
-                segment.comparison(comparison)[method]?
-            },
-            SemanticColor::Default,
-            ColumnFormatter::Delta,
-        ),
 
         (SegmentTime, false) => (
-            analysis::previous_segment_time(timer, segment_index, method),
+            if let Some(group) = group {
+                analysis::previous_segment_group_time(timer, group, method)
+            } else {
+                analysis::previous_segment_time(timer, segment_index, method)
+            },
             SemanticColor::Default,
             ColumnFormatter::Time,
         ),
         (SegmentTime, true) => (
-            analysis::live_segment_time(timer, segment_index, method),
+            if let Some(group) = group {
+                analysis::live_segment_group_time(timer, group, method)
+            } else {
+                analysis::live_segment_time(timer, segment_index, method)
+            },
             SemanticColor::Default,
             ColumnFormatter::Time,
         ),
 
         (SegmentDelta, false) | (SegmentDeltaWithFallback, false) => {
-            let delta = analysis::previous_segment_delta(timer, segment_index, comparison, method);
-            let (value, formatter) = if delta.is_none() && column.update_with.has_fallback() {
+            let (delta, segment_time) = if let Some(group) = group {
+                (
+                    analysis::previous_segment_group_delta(timer, group, comparison, method),
+                    analysis::previous_segment_group_time(timer, group, method),
+                )
+            } else {
                 (
+                    analysis::previous_segment_delta(timer, segment_index, comparison, method),
                     analysis::previous_segment_time(timer, segment_index, method),
-                    ColumnFormatter::Time,
                 )
+            };
+            let (value, formatter) = if delta.is_none() && column.update_with.has_fallback() {
+                (segment_time, ColumnFormatter::Time)
             } else {
                 (delta, ColumnFormatter::Delta)
             };
-            (
-                value,
-                split_color(timer, delta, segment_index, false, true, comparison, method),
-                formatter,
-            )
+            let color = if let Some(group) = group {
+                analysis::segment_group_split_color(
+                    timer, delta, group, false, true, comparison, method,
+                )
+            } else {
+                split_color(timer, delta, segment_index, false, true, comparison, method)
+            };
+            (value, color, formatter)
         }
         (SegmentDelta, true) | (SegmentDeltaWithFallback, true) => (
-            analysis::live_segment_delta(timer, segment_index, comparison, method),
+            if let Some(group) = group {
+                analysis::live_segment_group_delta(timer, group, comparison, method)
+            } else {
+                analysis::live_segment_delta(timer, segment_index, comparison, method)
+            },
             SemanticColor::Default,
             ColumnFormatter::Delta,
         ),
+
+        (PossibleTimeSave, is_live) => {
+            // Any time lost compared to the Personal Best's segment time could
+            // have been saved.
+            let comparison = personal_best::NAME;
+            let delta = match (group, is_live) {
+                (Some(group), false) => {
+                    analysis::previous_segment_group_delta(timer, group, comparison, method)
+                }
+                (Some(group), true) => {
+                    analysis::live_segment_group_delta(timer, group, comparison, method)
+                }
+                (None, false) => {
+                    analysis::previous_segment_delta(timer, segment_index, comparison, method)
+                }
+                (None, true) => {
+                    analysis::live_segment_delta(timer, segment_index, comparison, method)
+                }
+            };
+            (
+                delta.map(|delta| delta.max(TimeSpan::zero())),
+                SemanticColor::Default,
+                ColumnFormatter::PossibleTimeSave,
+            )
+        }
     };
 
     Some((value, is_live))
@@ -339,7 +570,10 @@ fn column_update_value(
 impl ColumnUpdateWith {
     const fn is_segment_based(self) -> bool {
         use ColumnUpdateWith::*;
-        matches!(self, SegmentDelta | SegmentTime | SegmentDeltaWithFallback)
+        matches!(
+            self,
+            SegmentDelta | SegmentTime | SegmentDeltaWithFallback | PossibleTimeSave
+        )
     }
 
     const fn has_fallback(self) -> bool {
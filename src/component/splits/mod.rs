@@ -3,16 +3,18 @@
 //! segment is shown in a tabular fashion showing the segment icon, segment
 //! name, the delta compared to the chosen comparison, and the split time. The
 //! list provides scrolling functionality, so not every segment needs to be
-//! shown all the time.
+//! shown all the time. Segment Groups are shown as a single row that can be
+//! collapsed.
 
 use crate::{
     clear_vec::{Clear, ClearVec},
     platform::prelude::*,
     settings::{
-        CachedImageId, Color, Field, Gradient, ImageData, ListGradient, SettingsDescription, Value,
+        CachedImageId, Color, Field, Gradient, ImageData, ListGradient, SemanticColor,
+        SettingsDescription, Value,
     },
     timing::Snapshot,
-    GeneralLayoutSettings,
+    GeneralLayoutSettings, Run,
 };
 use core::cmp::{max, min};
 use serde::{Deserialize, Serialize};
@@ -21,13 +23,17 @@ use serde::{Deserialize, Serialize};
 mod tests;
 
 mod column;
+mod style;
 
 pub use column::{
     ColumnSettings, ColumnStartWith, ColumnState, ColumnUpdateTrigger, ColumnUpdateWith,
 };
+use column::{RowContext, StatisticsCache};
+pub use style::{NamePattern, RowStyle, StyleCondition, StyleRule};
 
 const SETTINGS_BEFORE_COLUMNS: usize = 11;
 const SETTINGS_PER_COLUMN: usize = 6;
+const SETTINGS_PER_STYLE_RULE: usize = 4;
 
 /// The Splits Component is the main component for visualizing all the split
 /// times. Each segment is shown in a tabular fashion showing the segment icon,
@@ -40,6 +46,22 @@ pub struct Component {
     settings: Settings,
     current_split_index: Option<usize>,
     scroll_offset: isize,
+    rows: Vec<Row>,
+    statistics: StatisticsCache,
+}
+
+/// A single row of the list of splits. This is either a single segment or an
+/// entire Segment Group.
+#[derive(Copy, Clone)]
+enum Row {
+    Segment {
+        index: usize,
+        is_subsplit: bool,
+    },
+    Group {
+        group_index: usize,
+        is_collapsed: bool,
+    },
 }
 
 /// The Settings for this component.
@@ -85,10 +107,19 @@ pub struct Settings {
     pub current_split_gradient: Gradient,
     /// Specifies whether to show the names of the columns above the splits.
     pub show_column_labels: bool,
+    /// Specifies whether to show the Segment Groups of the run. Each group is
+    /// shown as an additional row above its segments, showing the times of
+    /// the group as a whole. A collapsed group only shows that row, unless the
+    /// current segment is part of the group.
+    pub show_segment_groups: bool,
     /// The columns to show on the splits. These can be configured in various
     /// way to show split times, segment times, deltas and so on. The columns
     /// are defined from right to left.
     pub columns: Vec<ColumnSettings>,
+    /// The rules that change the style of the rows whose segments meet
+    /// certain conditions, like being a new best segment. If multiple rules
+    /// apply to a row, later rules take precedence over earlier rules.
+    pub style_rules: Vec<StyleRule>,
 }
 
 /// The state object that describes a single segment's information to visualize.
@@ -107,6 +138,23 @@ pub struct SplitState {
     /// there can be a scrolling window, showing only a subset of segments. Each
     /// index is guaranteed to be unique.
     pub index: usize,
+    /// Describes whether this row represents an entire Segment Group rather
+    /// than a single segment. The columns then show the times of the group as
+    /// a whole.
+    pub is_segment_group: bool,
+    /// Describes whether the Segment Group this row represents is collapsed.
+    /// If it's not collapsed, the segments of the group follow this row.
+    pub is_collapsed: bool,
+    /// Describes whether this segment is part of a Segment Group that is shown
+    /// expanded. Such segments are usually shown indented.
+    pub is_subsplit: bool,
+    /// The color of the name as specified by the Style Rules that apply to
+    /// this row. If `None` is specified, the color is taken from the layout.
+    pub text_color: Option<Color>,
+    /// The background to show behind this row as specified by the Style
+    /// Rules that apply to it. If `None` is specified, the usual background
+    /// is shown.
+    pub background: Option<Gradient>,
 }
 
 impl Clear for SplitState {
@@ -186,6 +234,7 @@ impl Default for Settings {
                 Color::rgba(21.0 / 255.0, 53.0 / 255.0, 116.0 / 255.0, 1.0),
             ),
             show_column_labels: false,
+            show_segment_groups: false,
             columns: vec![
                 ColumnSettings {
                     name: String::from("Time"),
@@ -204,6 +253,7 @@ impl Default for Settings {
                     timing_method: None,
                 },
             ],
+            style_rules: Vec::new(),
         }
     }
 }
@@ -286,13 +336,40 @@ impl Component {
         let run = timer.run();
         self.icon_ids.resize(run.len(), CachedImageId::default());
 
+        let current_split = timer.current_split_index();
+        let method = timer.current_timing_method();
+
+        update_rows(
+            &mut self.rows,
+            run,
+            current_split,
+            self.settings.show_segment_groups,
+        );
+
+        let style_rules = &self.settings.style_rules;
+        if style_rules.iter().any(|rule| rule.style.is_hidden) {
+            self.rows.retain(|&row| {
+                let (segment_index, name) = row_segment(run, row);
+                let is_current_split =
+                    matches!(row, Row::Segment { index, .. } if Some(index) == current_split);
+                is_current_split
+                    || !style::resolve(style_rules, timer, segment_index, name, method).is_hidden
+            });
+        }
+        let rows = &self.rows;
+
         let mut visual_split_count = self.settings.visual_split_count;
         if visual_split_count == 0 {
-            visual_split_count = run.len();
+            visual_split_count = rows.len();
         }
 
-        let current_split = timer.current_split_index();
-        let method = timer.current_timing_method();
+        let current_row = current_split.map(|current_split| {
+            rows.iter()
+                .position(
+                    |row| matches!(*row, Row::Segment { index, .. } if index == current_split),
+                )
+                .unwrap_or(rows.len())
+        });
 
         let locked_last_split = if self.settings.always_show_last_split {
             1
@@ -300,21 +377,21 @@ impl Component {
             0
         };
         let skip_count = min(
-            current_split.map_or(0, |current_split| {
+            current_row.map_or(0, |current_row| {
                 max(
                     0,
-                    current_split as isize
+                    current_row as isize
                         + self.settings.split_preview_count as isize
                         + locked_last_split
                         + 1
                         - visual_split_count as isize,
                 )
             }),
-            run.len() as isize - visual_split_count as isize,
+            rows.len() as isize - visual_split_count as isize,
         );
         self.scroll_offset = min(
             max(self.scroll_offset, -skip_count),
-            run.len() as isize - skip_count - visual_split_count as isize,
+            rows.len() as isize - skip_count - visual_split_count as isize,
         );
         let skip_count = max(0, skip_count + self.scroll_offset) as usize;
         let take_count = visual_split_count - locked_last_split as usize;
@@ -322,7 +399,7 @@ impl Component {
 
         let show_final_separator = self.settings.separator_last_split
             && always_show_last_split
-            && skip_count + take_count + 1 < run.len();
+            && skip_count + take_count + 1 < rows.len();
 
         let Settings {
             show_thin_separators,
@@ -344,35 +421,87 @@ impl Component {
             state.column_labels = None;
         }
 
+        self.statistics.update(timer);
+
         let icon_changes = &mut state.icon_changes;
         icon_changes.clear();
 
         state.splits.clear();
-        for ((i, segment), icon_id) in run
-            .segments()
-            .iter()
-            .enumerate()
-            .zip(self.icon_ids.iter_mut())
-            .skip(skip_count)
-            .filter(|&((i, _), _)| {
-                i - skip_count < take_count || (always_show_last_split && i + 1 == run.len())
-            })
-        {
+        for (row_index, &row) in rows.iter().enumerate().skip(skip_count) {
+            if row_index - skip_count >= take_count
+                && !(always_show_last_split && row_index + 1 == rows.len())
+            {
+                continue;
+            }
+
             let state = state.splits.push_with(|| SplitState {
                 name: String::new(),
                 columns: ClearVec::new(),
                 is_current_split: false,
                 index: 0,
+                is_segment_group: false,
+                is_collapsed: false,
+                is_subsplit: false,
+                text_color: None,
+                background: None,
             });
 
-            if let Some(icon_change) = icon_id.update_with(Some(segment.icon())) {
-                icon_changes.push(IconChange {
-                    segment_index: i,
-                    icon: icon_change.into(),
-                });
-            }
+            let (segment_index, group) = match row {
+                Row::Segment { index, is_subsplit } => {
+                    let segment = run.segment(index);
+                    if let Some(icon_change) =
+                        self.icon_ids[index].update_with(Some(segment.icon()))
+                    {
+                        icon_changes.push(IconChange {
+                            segment_index: index,
+                            icon: icon_change.into(),
+                        });
+                    }
 
-            state.name.push_str(segment.name());
+                    if self.settings.show_segment_groups {
+                        state.name.push_str(segment.name());
+                    } else {
+                        // Without the Segment Groups, the segments are shown
+                        // with the groups encoded into their names, just like
+                        // the original LiveSplit shows them.
+                        state
+                            .name
+                            .push_str(&run.segment_groups().encoded_name(index, segment.name()));
+                    }
+                    state.is_current_split = Some(index) == current_split;
+                    state.index = index;
+                    state.is_segment_group = false;
+                    state.is_collapsed = false;
+                    state.is_subsplit = is_subsplit;
+
+                    (index, None)
+                }
+                Row::Group {
+                    group_index,
+                    is_collapsed,
+                } => {
+                    let group = run.segment_groups().get(group_index).unwrap();
+
+                    state.name.push_str(group.name_or_default(run.segments()));
+                    state.is_current_split = false;
+                    state.index = usize::max_value() - 2 * group_index;
+                    state.is_segment_group = true;
+                    state.is_collapsed = is_collapsed;
+                    state.is_subsplit = false;
+
+                    (group.last(), Some(group))
+                }
+            };
+
+            let row = RowContext {
+                timer,
+                layout_settings,
+                segment: run.segment(segment_index),
+                segment_index,
+                group,
+                current_split,
+                method,
+            };
 
             for column in columns {
                 column::update_state(
@@ -382,18 +511,22 @@ impl Component {
                         visual_color: Color::transparent(),
                         updates_frequently: false,
                     }),
+                    &mut self.statistics,
                     column,
-                    timer,
-                    layout_settings,
-                    segment,
-                    i,
-                    current_split,
-                    method,
+                    row,
                 );
             }
 
-            state.is_current_split = Some(i) == current_split;
-            state.index = i;
+            let style = style::resolve(style_rules, timer, segment_index, &state.name, method);
+            if let Some(text_color) = style.text_color {
+                for column in state.columns.iter_mut() {
+                    if column.semantic_color == SemanticColor::Default {
+                        column.visual_color = text_color;
+                    }
+                }
+            }
+            state.text_color = style.text_color;
+            state.background = style.background;
         }
 
         if fill_with_blank_space && state.splits.len() < visual_split_count {
@@ -404,9 +537,19 @@ impl Component {
                     columns: ClearVec::new(),
                     is_current_split: false,
                     index: 0,
+                    is_segment_group: false,
+                    is_collapsed: false,
+                    is_subsplit: false,
+                    text_color: None,
+                    background: None,
                 });
                 state.is_current_split = false;
                 state.index = (usize::max_value() ^ 1) - 2 * i;
+                state.is_segment_group = false;
+                state.is_collapsed = false;
+                state.is_subsplit = false;
+                state.text_color = None;
+                state.background = None;
             }
         }
 
@@ -478,7 +621,7 @@ impl Component {
 
         settings
             .fields
-            .reserve_exact(SETTINGS_PER_COLUMN * self.settings.columns.len());
+            .reserve_exact(SETTINGS_PER_COLUMN * self.settings.columns.len() + 1);
 
         for column in &self.settings.columns {
             settings
@@ -504,6 +647,39 @@ impl Component {
             ));
         }
 
+        settings.fields.push(Field::new(
+            "Show Segment Groups".into(),
+            self.settings.show_segment_groups.into(),
+        ));
+        settings.fields.push(Field::new(
+            "Style Rules".into(),
+            Value::UInt(self.settings.style_rules.len() as _),
+        ));
+
+        settings
+            .fields
+            .reserve_exact(SETTINGS_PER_STYLE_RULE * self.settings.style_rules.len());
+
+        for rule in &self.settings.style_rules {
+            settings.fields.push(Field::new(
+                "Condition".into(),
+                rule.condition.clone().into(),
+            ));
+            settings.fields.push(Field::new(
+                "Text Color".into(),
+                rule.style.text_color.into(),
+            ));
+            // A transparent background means that the background isn't
+            // changed.
+            settings.fields.push(Field::new(
+                "Background".into(),
+                rule.style.background.unwrap_or_default().into(),
+            ));
+            settings
+                .fields
+                .push(Field::new("Hide Row".into(), rule.style.is_hidden.into()));
+        }
+
         settings
     }
 
@@ -515,6 +691,8 @@ impl Component {
     /// the type of the setting's value. A panic can also occur if the index of
     /// the setting provided is out of bounds.
     pub fn set_value(&mut self, index: usize, value: Value) {
+        let settings_after_columns =
+            SETTINGS_BEFORE_COLUMNS + SETTINGS_PER_COLUMN * self.settings.columns.len();
         match index {
             0 => self.settings.background = value.into(),
             1 => self.settings.visual_split_count = value.into_uint().unwrap() as _,
@@ -530,6 +708,39 @@ impl Component {
                 let new_len = value.into_uint().unwrap() as usize;
                 self.settings.columns.resize(new_len, Default::default());
             }
+            index if index == settings_after_columns => {
+                self.settings.show_segment_groups = value.into()
+            }
+            index if index == settings_after_columns + 1 => {
+                let new_len = value.into_uint().unwrap() as usize;
+                self.settings
+                    .style_rules
+                    .resize_with(new_len, || StyleRule {
+                        condition: StyleCondition::BestSegment,
+                        style: RowStyle::default(),
+                    });
+            }
+            index if index > settings_after_columns => {
+                let index = index - settings_after_columns - 2;
+                let rule_index = index / SETTINGS_PER_STYLE_RULE;
+                let setting_index = index % SETTINGS_PER_STYLE_RULE;
+                if let Some(rule) = self.settings.style_rules.get_mut(rule_index) {
+                    match setting_index {
+                        0 => rule.condition = value.into(),
+                        1 => rule.style.text_color = value.into(),
+                        2 => {
+                            rule.style.background = match Gradient::from(value) {
+                                Gradient::Transparent => None,
+                                background => Some(background),
+                            }
+                        }
+                        3 => rule.style.is_hidden = value.into(),
+                        _ => unreachable!(),
+                    }
+                } else {
+                    panic!("Unsupported Setting Index")
+                }
+            }
             index => {
                 let index = index - SETTINGS_BEFORE_COLUMNS;
                 let column_index = index / SETTINGS_PER_COLUMN;
@@ -551,3 +762,54 @@ impl Component {
         }
     }
 }
+
+/// Returns the index of the segment a row's values are based on and the name
+/// the row is shown with.
+fn row_segment(run: &Run, row: Row) -> (usize, &str) {
+    match row {
+        Row::Segment { index, .. } => (index, run.segment(index).name()),
+        Row::Group { group_index, .. } => {
+            let group = run.segment_groups().get(group_index).unwrap();
+            (group.last(), group.name_or_default(run.segments()))
+        }
+    }
+}
+
+fn update_rows(rows: &mut Vec<Row>, run: &Run, current_split: Option<usize>, show_groups: bool) {
+    rows.clear();
+
+    let mut groups = run
+        .segment_groups()
+        .iter()
+        .enumerate()
+        .filter(|(_, group)| show_groups && group.end() <= run.len())
+        .peekable();
+
+    let mut index = 0;
+    while index < run.len() {
+        if let Some((group_index, group)) = groups.next_if(|(_, g)| g.start() == index) {
+            let is_collapsed =
+                group.is_collapsed() && !current_split.map_or(false, |c| group.contains(c));
+
+            rows.push(Row::Group {
+                group_index,
+                is_collapsed,
+            });
+
+            if !is_collapsed {
+                rows.extend(group.range().map(|index| Row::Segment {
+                    index,
+                    is_subsplit: true,
+                }));
+            }
+
+            index = group.end();
+        } else {
+            rows.push(Row::Segment {
+                index,
+                is_subsplit: false,
+            });
+            index += 1;
+        }
+    }
+}
@@ -0,0 +1,165 @@
+use crate::{
+    analysis,
+    settings::{Color, Gradient},
+    timing::Snapshot,
+    TimeSpan, TimingMethod,
+};
+use core::fmt;
+use regex::Regex;
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+
+/// A rule that changes the style of the rows of the Splits Component whose
+/// segments meet its condition. This can be used to highlight certain
+/// segments, like segments that are known to be risky.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct StyleRule {
+    /// The condition a row needs to meet for the style to be applied.
+    pub condition: StyleCondition,
+    /// The style to apply to the rows that meet the condition.
+    pub style: RowStyle,
+}
+
+/// The condition a row needs to meet for a Style Rule to be applied.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum StyleCondition {
+    /// The delta of the current attempt's split time to the comparison's split
+    /// time is larger than the time specified. While the segment is the
+    /// current segment, the live delta is used instead.
+    DeltaAbove(TimeSpan),
+    /// The current attempt achieved a new best segment time on the segment.
+    BestSegment,
+    /// The name of the row matches the pattern.
+    NameMatches(NamePattern),
+}
+
+/// Describes how the rows that meet the condition of a Style Rule are
+/// supposed to be styled.
+#[derive(Copy, Clone, Default, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RowStyle {
+    /// The color of the name and of the column values that don't carry any
+    /// semantic coloring. If `None` is specified, the color isn't changed.
+    pub text_color: Option<Color>,
+    /// The background shown behind the row. If `None` is specified, the
+    /// background isn't changed.
+    pub background: Option<Gradient>,
+    /// Specifies whether the row is supposed to be hidden. The row of the
+    /// current segment is never hidden.
+    pub is_hidden: bool,
+}
+
+/// A regular expression that the names of rows can be matched against.
+#[derive(Clone, Debug)]
+pub struct NamePattern(Regex);
+
+impl PartialEq for NamePattern {
+    fn eq(&self, other: &Self) -> bool {
+        self.as_str() == other.as_str()
+    }
+}
+
+impl NamePattern {
+    /// Creates a new Name Pattern from the regular expression provided. An
+    /// error is returned if the regular expression is invalid.
+    pub fn new(pattern: &str) -> Result<Self, regex::Error> {
+        Regex::new(pattern).map(NamePattern)
+    }
+
+    /// Accesses the regular expression as a string.
+    pub fn as_str(&self) -> &str {
+        self.0.as_str()
+    }
+
+    /// Returns whether the name matches the pattern.
+    pub fn is_match(&self, name: &str) -> bool {
+        self.0.is_match(name)
+    }
+}
+
+impl Serialize for NamePattern {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for NamePattern {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_str(NamePatternVisitor)
+    }
+}
+
+struct NamePatternVisitor;
+
+impl de::Visitor<'_> for NamePatternVisitor {
+    type Value = NamePattern;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.write_str("a string containing a regular expression")
+    }
+
+    fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        NamePattern::new(v).map_err(E::custom)
+    }
+}
+
+impl StyleCondition {
+    fn is_met(
+        &self,
+        timer: &Snapshot<'_>,
+        segment_index: usize,
+        name: &str,
+        method: TimingMethod,
+    ) -> bool {
+        match self {
+            StyleCondition::DeltaAbove(threshold) => {
+                let comparison = timer.current_comparison();
+                let delta = if timer.current_split_index() == Some(segment_index) {
+                    catch! {
+                        timer.current_time()[method]?
+                            - timer.run().segment(segment_index).comparison(comparison)[method]?
+                    }
+                } else {
+                    let segment = timer.run().segment(segment_index);
+                    catch! {
+                        segment.split_time()[method]? - segment.comparison(comparison)[method]?
+                    }
+                };
+                matches!(delta, Some(delta) if delta > *threshold)
+            }
+            StyleCondition::BestSegment => {
+                analysis::check_best_segment(timer, segment_index, method)
+            }
+            StyleCondition::NameMatches(pattern) => pattern.is_match(name),
+        }
+    }
+}
+
+/// Combines the styles of all the rules whose conditions the row meets. Later
+/// rules take precedence over earlier rules.
+pub fn resolve(
+    rules: &[StyleRule],
+    timer: &Snapshot<'_>,
+    segment_index: usize,
+    name: &str,
+    method: TimingMethod,
+) -> RowStyle {
+    let mut resolved = RowStyle::default();
+    for rule in rules {
+        if rule.condition.is_met(timer, segment_index, name, method) {
+            let style = &rule.style;
+            resolved.text_color = style.text_color.or(resolved.text_color);
+            resolved.background = style.background.or(resolved.background);
+            resolved.is_hidden |= style.is_hidden;
+        }
+    }
+    resolved
+}
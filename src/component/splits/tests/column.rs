@@ -455,6 +455,82 @@ fn column_comparison_segment_time_dont_update() {
     )
 }
 
+#[test]
+fn column_best_possible_time_save_dont_update() {
+    check_columns(
+        ColumnStartWith::BestPossibleTimeSave,
+        ColumnUpdateWith::DontUpdate,
+        &[(
+            [
+                "1:08.00", "1:08.00", "1:08.00", "1:08.00", "1:08.00", "1:04.00",
+            ],
+            [Text, Text, Text, Text, Text, Text],
+        ); 7],
+    )
+}
+
+#[test]
+fn column_average_segment_time_dont_update() {
+    check_columns(
+        ColumnStartWith::AverageSegmentTime,
+        ColumnUpdateWith::DontUpdate,
+        &[(
+            ["0:05", "—", "—", "0:01", "0:03", "0:33"],
+            [Text, Text, Text, Text, Text, Text],
+        ); 7],
+    )
+}
+
+#[test]
+fn column_gold_count_dont_update() {
+    check_columns(
+        ColumnStartWith::GoldCount,
+        ColumnUpdateWith::DontUpdate,
+        &[(
+            ["2", "0", "0", "1", "1", "1"],
+            [Text, Text, Text, Text, Text, Text],
+        ); 7],
+    )
+}
+
+#[test]
+fn column_reset_percentage_possible_time_save() {
+    check_columns(
+        ColumnStartWith::ResetPercentage,
+        ColumnUpdateWith::PossibleTimeSave,
+        &[
+            (
+                ["0.0%", "0.0%", "0.0%", "0.0%", "0.0%", "0.0%"],
+                [Text, Text, Text, Text, Text, Text],
+            ),
+            (
+                ["3.50", "0.0%", "0.0%", "0.0%", "0.0%", "0.0%"],
+                [Text, Text, Text, Text, Text, Text],
+            ),
+            (
+                ["3.50", "—", "0.0%", "0.0%", "0.0%", "0.0%"],
+                [Text, Text, Text, Text, Text, Text],
+            ),
+            (
+                ["3.50", "—", "—", "0.0%", "0.0%", "0.0%"],
+                [Text, Text, Text, Text, Text, Text],
+            ),
+            (
+                ["3.50", "—", "—", "0.00", "0.0%", "0.0%"],
+                [Text, Text, Text, Text, Text, Text],
+            ),
+            (
+                ["3.50", "—", "—", "0.00", "—", "0.0%"],
+                [Text, Text, Text, Text, Text, Text],
+            ),
+            (
+                ["3.50", "—", "—", "0.00", "—", "0.00"],
+                [Text, Text, Text, Text, Text, Text],
+            ),
+        ],
+    )
+}
+
 fn check_columns(
     start_with: ColumnStartWith,
     update_with: ColumnUpdateWith,
@@ -5,6 +5,7 @@ use super::{
 use crate::{Run, Segment, TimeSpan, Timer, TimingMethod};
 
 pub mod column;
+pub mod style;
 
 #[test]
 fn zero_visual_split_count_always_shows_all_splits() {
@@ -131,3 +132,62 @@ fn unique_split_indices() {
 
     assert!(indices.windows(2).all(|pair| pair[0] != pair[1]));
 }
+
+#[test]
+fn segment_groups() {
+    let mut run = Run::new();
+    for name in &["A", "B", "C", "D"] {
+        run.push_segment(Segment::new(*name));
+    }
+    run.add_segment_group(1, 3).unwrap();
+    run.segment_groups_mut()
+        .get_mut(0)
+        .unwrap()
+        .set_name(Some("Group".into()));
+
+    let mut timer = Timer::new(run).unwrap();
+    let layout_settings = Default::default();
+    let mut component = Component::with_settings(Settings {
+        visual_split_count: 0,
+        show_segment_groups: true,
+        ..Default::default()
+    });
+
+    let state = component.state(&timer.snapshot(), &layout_settings);
+    let names = state.splits.iter().map(|s| &*s.name).collect::<Vec<_>>();
+    assert_eq!(names, ["A", "Group", "B", "C", "D"]);
+    assert!(state.splits[1].is_segment_group);
+    assert!(!state.splits[1].is_collapsed);
+    assert!(state.splits[2].is_subsplit);
+    assert!(!state.splits[4].is_subsplit);
+
+    timer
+        .set_run({
+            let mut run = timer.run().clone();
+            run.segment_groups_mut()
+                .get_mut(0)
+                .unwrap()
+                .set_collapsed(true);
+            run
+        })
+        .unwrap();
+
+    let state = component.state(&timer.snapshot(), &layout_settings);
+    let names = state.splits.iter().map(|s| &*s.name).collect::<Vec<_>>();
+    assert_eq!(names, ["A", "Group", "D"]);
+    assert!(state.splits[1].is_collapsed);
+
+    timer.start();
+    timer.split();
+    let state = component.state(&timer.snapshot(), &layout_settings);
+    let names = state.splits.iter().map(|s| &*s.name).collect::<Vec<_>>();
+    assert_eq!(names, ["A", "Group", "B", "C", "D"]);
+    assert!(state.splits[2].is_current_split);
+
+    // Without the groups, the names are shown the way the original LiveSplit
+    // shows subsplits.
+    component.settings_mut().show_segment_groups = false;
+    let state = component.state(&timer.snapshot(), &layout_settings);
+    let names = state.splits.iter().map(|s| &*s.name).collect::<Vec<_>>();
+    assert_eq!(names, ["A", "-B", "{Group} C", "D"]);
+}
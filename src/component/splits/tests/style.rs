@@ -0,0 +1,131 @@
+use super::super::{Component, NamePattern, RowStyle, Settings, StyleCondition, StyleRule};
+use crate::{
+    settings::{Color, Gradient, SemanticColor, Value},
+    tests_helper::{create_timer, run_sample_attempts, span, start_run},
+};
+
+fn rule(condition: StyleCondition, style: RowStyle) -> StyleRule {
+    StyleRule { condition, style }
+}
+
+#[test]
+fn applies_matching_rules() {
+    let red = Color::rgba(1.0, 0.0, 0.0, 1.0);
+    let green = Color::rgba(0.0, 1.0, 0.0, 1.0);
+    let background = Gradient::Plain(Color::rgba(0.0, 0.0, 1.0, 1.0));
+
+    let mut timer = create_timer(&["A", "B", "C"]);
+    run_sample_attempts(&mut timer);
+    start_run(&mut timer);
+    timer.set_game_time(span(9.0));
+    timer.split();
+
+    let layout_settings = Default::default();
+    let mut component = Component::with_settings(Settings {
+        fill_with_blank_space: false,
+        style_rules: vec![
+            rule(
+                StyleCondition::BestSegment,
+                RowStyle {
+                    text_color: Some(green),
+                    ..Default::default()
+                },
+            ),
+            rule(
+                StyleCondition::NameMatches(NamePattern::new("(?i)^b$").unwrap()),
+                RowStyle {
+                    background: Some(background),
+                    ..Default::default()
+                },
+            ),
+            rule(
+                StyleCondition::DeltaAbove(span(5.0)),
+                RowStyle {
+                    text_color: Some(red),
+                    ..Default::default()
+                },
+            ),
+        ],
+        ..Default::default()
+    });
+
+    let state = component.state(&timer.snapshot(), &layout_settings);
+    let styles = state
+        .splits
+        .iter()
+        .map(|s| (s.text_color, s.background))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        styles,
+        [(Some(green), None), (None, Some(background)), (None, None),]
+    );
+
+    // The values that carry a semantic color keep their color.
+    let columns = &state.splits[0].columns;
+    assert_eq!(columns[0].visual_color, green);
+    assert_eq!(columns[1].semantic_color, SemanticColor::BestSegment);
+    assert_ne!(columns[1].visual_color, green);
+
+    // Losing time on the current segment makes the live delta apply.
+    timer.set_game_time(span(40.0));
+    let state = component.state(&timer.snapshot(), &layout_settings);
+    assert_eq!(state.splits[1].text_color, Some(red));
+}
+
+#[test]
+fn hides_rows_except_for_the_current_one() {
+    let mut timer = create_timer(&["A", "B", "C"]);
+    start_run(&mut timer);
+    timer.split();
+
+    let layout_settings = Default::default();
+    let mut component = Component::with_settings(Settings {
+        fill_with_blank_space: false,
+        style_rules: vec![rule(
+            StyleCondition::NameMatches(NamePattern::new("B|C").unwrap()),
+            RowStyle {
+                is_hidden: true,
+                ..Default::default()
+            },
+        )],
+        ..Default::default()
+    });
+
+    let state = component.state(&timer.snapshot(), &layout_settings);
+    let names = state
+        .splits
+        .iter()
+        .map(|s| s.name.as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(names, ["A", "B"]);
+}
+
+#[test]
+fn style_rules_can_be_configured_through_the_settings() {
+    let mut component = Component::new();
+    let index = component.settings_description().fields.len();
+    component.set_value(index - 1, Value::UInt(1));
+
+    let condition = StyleCondition::NameMatches(NamePattern::new("A").unwrap());
+    component.set_value(index, condition.clone().into());
+    component.set_value(index + 2, Gradient::Plain(Color::black()).into());
+    component.set_value(index + 3, true.into());
+
+    let rule = &component.settings().style_rules[0];
+    assert_eq!(rule.condition, condition);
+    assert_eq!(
+        rule.style,
+        RowStyle {
+            text_color: None,
+            background: Some(Gradient::Plain(Color::black())),
+            is_hidden: true,
+        }
+    );
+
+    let fields = component.settings_description().fields;
+    assert_eq!(fields.len(), index + 4);
+    assert_eq!(fields[index + 3].text, "Hide Row");
+
+    component.set_value(index + 2, Gradient::Transparent.into());
+    assert_eq!(component.settings().style_rules[0].style.background, None);
+}
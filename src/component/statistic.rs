@@ -0,0 +1,228 @@
+//! Provides the Statistic Component and relevant types for using it. The
+//! Statistic Component is a component that shows a chosen statistic about the
+//! past attempts for the current segment, like the average segment time or how
+//! often attempts are reset during the segment.
+
+use super::key_value;
+use crate::{
+    analysis::statistics,
+    platform::prelude::*,
+    settings::{Color, Field, Gradient, SettingsDescription, Value},
+    timing::{
+        formatter::{Accuracy, Delta, SegmentTime, TimeFormatter, DASH},
+        Snapshot,
+    },
+    TimingMethod,
+};
+use core::fmt::Write;
+use serde::{Deserialize, Serialize};
+
+/// The Statistic Component is a component that shows a chosen statistic about
+/// the past attempts for the current segment, like the average segment time or
+/// how often attempts are reset during the segment. If there is no active
+/// attempt, the statistic of the first segment is shown.
+#[derive(Default, Clone)]
+pub struct Component {
+    settings: Settings,
+}
+
+/// Describes which statistic the Statistic Component shows.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub enum Statistic {
+    /// The mean of the segment times.
+    Mean,
+    /// The median of the segment times.
+    Median,
+    /// The standard deviation of the segment times.
+    StandardDeviation,
+    /// How consistent the segment times are.
+    Consistency,
+    /// The share of the attempts that were reset during the segment.
+    ResetRate,
+    /// How much time is lost on average compared to the best segment time.
+    TimeLostVsGold,
+    /// How much the segment times change per attempt on average.
+    Trend,
+}
+
+impl Statistic {
+    /// Returns the name of the statistic.
+    pub const fn name(self) -> &'static str {
+        match self {
+            Statistic::Mean => "Average Segment",
+            Statistic::Median => "Median Segment",
+            Statistic::StandardDeviation => "Standard Deviation",
+            Statistic::Consistency => "Consistency",
+            Statistic::ResetRate => "Reset Rate",
+            Statistic::TimeLostVsGold => "Time Lost vs Gold",
+            Statistic::Trend => "Trend",
+        }
+    }
+}
+
+/// The Settings for this component.
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Settings {
+    /// The background shown behind the component.
+    pub background: Gradient,
+    /// Specifies whether to display the name of the component and its value in
+    /// two separate rows.
+    pub display_two_rows: bool,
+    /// The color of the label. If `None` is specified, the color is taken from
+    /// the layout.
+    pub label_color: Option<Color>,
+    /// The color of the value. If `None` is specified, the color is taken from
+    /// the layout.
+    pub value_color: Option<Color>,
+    /// The statistic to show.
+    pub statistic: Statistic,
+    /// The timing method to use for the statistic. If not specified, the
+    /// current timing method is used.
+    pub timing_method: Option<TimingMethod>,
+    /// The accuracy of the times shown.
+    pub accuracy: Accuracy,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            background: key_value::DEFAULT_GRADIENT,
+            display_two_rows: false,
+            label_color: None,
+            value_color: None,
+            statistic: Statistic::Mean,
+            timing_method: None,
+            accuracy: Accuracy::Hundredths,
+        }
+    }
+}
+
+impl Component {
+    /// Creates a new Statistic Component.
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Creates a new Statistic Component with the given settings.
+    pub const fn with_settings(settings: Settings) -> Self {
+        Self { settings }
+    }
+
+    /// Accesses the settings of the component.
+    pub const fn settings(&self) -> &Settings {
+        &self.settings
+    }
+
+    /// Grants mutable access to the settings of the component.
+    pub const fn settings_mut(&mut self) -> &mut Settings {
+        &mut self.settings
+    }
+
+    /// Accesses the name of the component.
+    pub const fn name(&self) -> &'static str {
+        self.settings.statistic.name()
+    }
+
+    /// Updates the component's state based on the timer provided.
+    pub fn update_state(&self, state: &mut key_value::State, timer: &Snapshot<'_>) {
+        let run = timer.run();
+        let method = self
+            .settings
+            .timing_method
+            .unwrap_or_else(|| timer.current_timing_method());
+        let segment_index = timer
+            .current_split_index()
+            .unwrap_or(0)
+            .min(run.len().saturating_sub(1));
+
+        state.value.clear();
+        if !run.is_empty() {
+            let statistics = statistics::for_segment(run, segment_index, method);
+            let accuracy = self.settings.accuracy;
+            let time = |time| SegmentTime::with_accuracy(accuracy).format(time);
+            let percentage = |value: Option<f64>| match value {
+                Some(value) => format!("{:.1}%", 100.0 * value),
+                None => DASH.into(),
+            };
+
+            let _ = match self.settings.statistic {
+                Statistic::Mean => write!(state.value, "{}", time(statistics.mean)),
+                Statistic::Median => write!(state.value, "{}", time(statistics.median)),
+                Statistic::StandardDeviation => {
+                    write!(state.value, "{}", time(statistics.standard_deviation))
+                }
+                Statistic::Consistency => {
+                    write!(state.value, "{}", percentage(statistics.consistency()))
+                }
+                Statistic::ResetRate => {
+                    write!(state.value, "{}", percentage(statistics.reset_rate()))
+                }
+                Statistic::TimeLostVsGold => {
+                    write!(state.value, "{}", time(statistics.time_lost_vs_gold()))
+                }
+                Statistic::Trend => write!(
+                    state.value,
+                    "{}",
+                    Delta::custom(false, accuracy).format(statistics.trend)
+                ),
+            };
+        }
+
+        state.background = self.settings.background;
+        state.key_color = self.settings.label_color;
+        state.value_color = self.settings.value_color;
+        state.semantic_color = Default::default();
+
+        state.key.clear();
+        state.key.push_str(self.name());
+
+        state.key_abbreviations.clear();
+        state.display_two_rows = self.settings.display_two_rows;
+        state.updates_frequently = false;
+    }
+
+    /// Calculates the component's state based on the timer provided.
+    pub fn state(&self, timer: &Snapshot<'_>) -> key_value::State {
+        let mut state = Default::default();
+        self.update_state(&mut state, timer);
+        state
+    }
+
+    /// Accesses a generic description of the settings available for this
+    /// component and their current values.
+    pub fn settings_description(&self) -> SettingsDescription {
+        SettingsDescription::with_fields(vec![
+            Field::new("Background".into(), self.settings.background.into()),
+            Field::new(
+                "Display 2 Rows".into(),
+                self.settings.display_two_rows.into(),
+            ),
+            Field::new("Label Color".into(), self.settings.label_color.into()),
+            Field::new("Value Color".into(), self.settings.value_color.into()),
+            Field::new("Statistic".into(), self.settings.statistic.into()),
+            Field::new("Timing Method".into(), self.settings.timing_method.into()),
+            Field::new("Accuracy".into(), self.settings.accuracy.into()),
+        ])
+    }
+
+    /// Sets a setting's value by its index to the given value.
+    ///
+    /// # Panics
+    ///
+    /// This panics if the type of the value to be set is not compatible with
+    /// the type of the setting's value. A panic can also occur if the index of
+    /// the setting provided is out of bounds.
+    pub fn set_value(&mut self, index: usize, value: Value) {
+        match index {
+            0 => self.settings.background = value.into(),
+            1 => self.settings.display_two_rows = value.into(),
+            2 => self.settings.label_color = value.into(),
+            3 => self.settings.value_color = value.into(),
+            4 => self.settings.statistic = value.into(),
+            5 => self.settings.timing_method = value.into(),
+            6 => self.settings.accuracy = value.into(),
+            _ => panic!("Unsupported Setting Index"),
+        }
+    }
+}
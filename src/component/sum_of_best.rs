@@ -11,7 +11,7 @@ use super::key_value;
 use crate::analysis::sum_of_segments::calculate_best;
 use crate::platform::prelude::*;
 use crate::settings::{Color, Field, Gradient, SettingsDescription, Value};
-use crate::timing::formatter::{Accuracy, Regular, TimeFormatter};
+use crate::timing::formatter::{Accuracy, FrameWrapper, Regular, TimeFormatter};
 use crate::Timer;
 use core::fmt::Write;
 use serde::{Deserialize, Serialize};
@@ -106,7 +106,11 @@ impl Component {
         let _ = write!(
             state.value,
             "{}",
-            Regular::with_accuracy(self.settings.accuracy).format(time),
+            FrameWrapper::new(
+                timer.run().metadata().frame_rate(),
+                Regular::with_accuracy(self.settings.accuracy)
+            )
+            .format(time),
         );
 
         state.key_abbreviations.clear();
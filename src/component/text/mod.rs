@@ -1,19 +1,22 @@
 //! Provides the Text Component and relevant types for using it. The Text
 //! Component simply visualizes any given text. This can either be a single
 //! centered text, or split up into a left and right text, which is suitable for
-//! a situation where you have a label and a value.
+//! a situation where you have a label and a value. The texts can also be
+//! templates with expressions that get replaced by information about the
+//! timer, like `Attempt #{attempt_count}`. The expressions and filters that are
+//! available are described in the [`template`] module.
 
 use super::key_value;
 use crate::{
     platform::prelude::*,
     settings::{Color, Field, Gradient, SettingsDescription, Value},
-    timing::formatter,
-    Timer,
+    timing::{formatter, Snapshot},
 };
 use alloc::borrow::Cow;
-use core::mem::{self, replace};
+use core::mem;
 use serde::{Deserialize, Serialize};
 
+pub mod template;
 #[cfg(test)]
 mod tests;
 
@@ -44,6 +47,10 @@ pub struct Settings {
     pub right_color: Option<Color>,
     /// The text to be shown.
     pub text: Text,
+    /// Specifies whether the centered text or the left and right text are
+    /// templates, where expressions like `{sum_of_best}` get replaced by
+    /// information about the timer. This doesn't apply to custom variables.
+    pub use_template: bool,
 }
 
 /// The text that is supposed to be shown.
@@ -148,6 +155,7 @@ impl Default for Settings {
             left_center_color: None,
             right_color: None,
             text: Text::Center(String::from("")),
+            use_template: false,
         }
     }
 }
@@ -208,13 +216,23 @@ impl Component {
     }
 
     /// Updates the component's state based on the timer provided.
-    pub fn update_state(&self, state: &mut State, timer: &Timer) {
+    pub fn update_state(&self, state: &mut State, timer: &Snapshot<'_>) {
         state.background = self.settings.background;
         state.display_two_rows = self.settings.text.is_split() && self.settings.display_two_rows;
         state.left_center_color = self.settings.left_center_color;
         state.right_color = self.settings.right_color;
 
+        let (mut rendered_left, mut rendered_right) = (String::new(), String::new());
         let (left_center, right) = match &self.settings.text {
+            Text::Center(center) if self.settings.use_template => {
+                template::render(center, timer, &mut rendered_left);
+                (rendered_left.as_str(), None)
+            }
+            Text::Split(left, right) if self.settings.use_template => {
+                template::render(left, timer, &mut rendered_left);
+                template::render(right, timer, &mut rendered_right);
+                (rendered_left.as_str(), Some(rendered_right.as_str()))
+            }
             Text::Center(center) => (center.as_str(), None),
             Text::Split(left, right) => (left.as_str(), Some(right.as_str())),
             Text::Variable(var_name, is_split) => {
@@ -263,7 +281,7 @@ impl Component {
     }
 
     /// Calculates the component's state.
-    pub fn state(&self, timer: &Timer) -> State {
+    pub fn state(&self, timer: &Snapshot<'_>) -> State {
         let mut state = Default::default();
         self.update_state(&mut state, timer);
         state
@@ -327,6 +345,13 @@ impl Component {
             ));
         }
 
+        if !is_variable {
+            fields.push(Field::new(
+                "Use Template".into(),
+                self.settings.use_template.into(),
+            ));
+        }
+
         SettingsDescription::with_fields(fields)
     }
 
@@ -338,6 +363,16 @@ impl Component {
     /// the type of the setting's value. A panic can also occur if the index of
     /// the setting provided is out of bounds.
     pub fn set_value(&mut self, mut index: usize, value: Value) {
+        let template_index = match &self.settings.text {
+            Text::Center(_) => Some(5),
+            Text::Split(_, _) => Some(8),
+            Text::Variable(_, _) => None,
+        };
+        if Some(index) == template_index {
+            self.settings.use_template = value.into();
+            return;
+        }
+
         if index >= 5 {
             if let Text::Variable(_, _) = &self.settings.text {
                 index += 1;
@@ -349,17 +384,11 @@ impl Component {
             1 => {
                 self.settings.text = match (value.into_bool().unwrap(), &mut self.settings.text) {
                     (false, Text::Variable(name, true)) => {
-                        Text::Split(replace(name, String::new()), String::new())
-                    }
-                    (false, Text::Variable(name, false)) => {
-                        Text::Center(replace(name, String::new()))
-                    }
-                    (true, Text::Center(center)) => {
-                        Text::Variable(replace(center, String::new()), false)
-                    }
-                    (true, Text::Split(left, _)) => {
-                        Text::Variable(replace(left, String::new()), true)
+                        Text::Split(mem::take(name), String::new())
                     }
+                    (false, Text::Variable(name, false)) => Text::Center(mem::take(name)),
+                    (true, Text::Center(center)) => Text::Variable(mem::take(center), false),
+                    (true, Text::Split(left, _)) => Text::Variable(mem::take(left), true),
                     _ => return,
                 };
             }
@@ -369,11 +398,11 @@ impl Component {
                         self.settings.right_color = self.settings.left_center_color;
                         self.settings.display_two_rows = false;
 
-                        Text::Split(replace(center, String::new()), String::new())
+                        Text::Split(mem::take(center), String::new())
                     }
                     (false, Text::Split(left, right)) => {
-                        let mut value = replace(left, String::new());
-                        let right = replace(right, String::new());
+                        let mut value = mem::take(left);
+                        let right = mem::take(right);
                         if !value.is_empty() && !right.is_empty() {
                             value.push(' ');
                         }
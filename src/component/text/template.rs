@@ -0,0 +1,330 @@
+//! Evaluates the templates of the Text Component. A template is a text with
+//! expressions in curly braces, like `{attempt_count}`, that get replaced by
+//! information about the timer. Literal curly braces can be written as `{{`
+//! and `}}`. Expressions that can't be evaluated are kept as they are, so
+//! mistakes in the template are easy to spot.
+//!
+//! # Expressions
+//!
+//! * `game`, `category`: The name of the game and the category.
+//! * `attempt_count`: The amount of attempts.
+//! * `comparison`: The name of the current comparison.
+//! * `current_time`: The current time of the timer.
+//! * `personal_best`: The final time of the Personal Best.
+//! * `sum_of_best`: The Sum of Best Segments.
+//! * `pb_chance`: The chance to beat the Personal Best.
+//! * `delta`, `delta("Comparison")`: The delta of the current attempt to the
+//!   current or the given comparison.
+//! * `current_pace`, `current_pace("Comparison")`: The current pace based on
+//!   the current or the given comparison.
+//! * `possible_time_save`, `possible_time_save("Comparison")`: The time that
+//!   could still be saved in the remainder of the attempt.
+//! * `current_split.name`, `current_split.number`, `current_split.split_time`,
+//!   `current_split.best_segment`, `current_split.personal_best`: Information
+//!   about the current split. The same is available for `previous_split`.
+//! * `custom.Name`, `custom("Name")`: The value of a custom variable.
+//!
+//! # Filters
+//!
+//! Expressions can be followed by filters that are separated by `|`, like
+//! `{sum_of_best | accuracy(tenths) | method(game_time)}`.
+//!
+//! * `accuracy(...)`: `seconds`, `tenths`, `hundredths` or `milliseconds`.
+//! * `digits(...)`: `single_digit_seconds`, `double_digit_seconds`,
+//!   `single_digit_minutes`, `double_digit_minutes`, `single_digit_hours` or
+//!   `double_digit_hours`.
+//! * `method(...)`: `real_time`, `game_time` or `custom1` to `custom4`. The
+//!   timing method is used for the times that are looked up directly. The
+//!   delta, the current pace, the possible time save and the PB chance always
+//!   use the timer's current timing method.
+
+use crate::{
+    analysis::{current_pace, delta, pb_chance, possible_time_save, sum_of_segments},
+    platform::prelude::*,
+    timing::{
+        formatter::{self, timer, Accuracy, DigitsFormat, Regular, TimeFormatter},
+        Snapshot,
+    },
+    Segment, TimeSpan, TimingMethod,
+};
+use core::fmt::Write;
+
+/// Renders the template into the buffer provided.
+pub fn render(template: &str, timer: &Snapshot<'_>, buf: &mut String) {
+    let mut rest = template;
+    while let Some(index) = rest.find(&['{', '}'][..]) {
+        buf.push_str(&rest[..index]);
+        let is_open = rest[index..].starts_with('{');
+        rest = &rest[index + 1..];
+
+        if !is_open {
+            rest = rest.strip_prefix('}').unwrap_or(rest);
+            buf.push('}');
+        } else if let Some(after) = rest.strip_prefix('{') {
+            rest = after;
+            buf.push('{');
+        } else if let Some(end) = find_unquoted(rest, '}') {
+            let expression = &rest[..end];
+            if evaluate(expression, timer, buf).is_none() {
+                buf.push('{');
+                buf.push_str(expression);
+                buf.push('}');
+            }
+            rest = &rest[end + 1..];
+        } else {
+            buf.push('{');
+        }
+    }
+    buf.push_str(rest);
+}
+
+enum Value<'a> {
+    Text(&'a str),
+    Number(u32),
+    Percentage(f64),
+    Time(Option<TimeSpan>),
+    Delta(Option<TimeSpan>),
+}
+
+struct Format {
+    accuracy: Option<Accuracy>,
+    digits_format: Option<DigitsFormat>,
+    method: Option<TimingMethod>,
+}
+
+fn evaluate(expression: &str, timer: &Snapshot<'_>, buf: &mut String) -> Option<()> {
+    let mut parts = split_unquoted(expression, '|');
+    let value = parts.next()?.trim();
+
+    let mut format = Format {
+        accuracy: None,
+        digits_format: None,
+        method: None,
+    };
+    for filter in parts {
+        let (name, argument) = parse_call(filter.trim())?;
+        let argument = argument?;
+        match name {
+            "accuracy" => format.accuracy = Some(parse_accuracy(argument)?),
+            "digits" => format.digits_format = Some(parse_digits_format(argument)?),
+            "method" => format.method = Some(parse_timing_method(argument)?),
+            _ => return None,
+        }
+    }
+
+    let method = format
+        .method
+        .unwrap_or_else(|| timer.current_timing_method());
+
+    let value = match parse_call(value)? {
+        ("custom", Some(name)) => custom_variable(timer, name),
+        ("delta", comparison) => {
+            let comparison = comparison.unwrap_or_else(|| timer.current_comparison());
+            Value::Delta(delta::calculate(timer, comparison).0)
+        }
+        ("current_pace", comparison) => {
+            let comparison = comparison.unwrap_or_else(|| timer.current_comparison());
+            Value::Time(current_pace::calculate(timer, comparison).0)
+        }
+        ("possible_time_save", comparison) => {
+            let comparison = comparison.unwrap_or_else(|| timer.current_comparison());
+            let segment_index = timer.current_split_index().unwrap_or_default();
+            Value::Time(if segment_index < timer.run().len() {
+                Some(possible_time_save::calculate_total(
+                    timer,
+                    segment_index,
+                    comparison,
+                ))
+            } else {
+                None
+            })
+        }
+        (name, None) => match name.split_once('.') {
+            Some(("custom", name)) => custom_variable(timer, name),
+            Some(("current_split", field)) => {
+                let index = timer.current_split_index();
+                segment_field(timer, index, field, method)?
+            }
+            Some(("previous_split", field)) => {
+                let index = timer.current_split_index().and_then(|i| i.checked_sub(1));
+                segment_field(timer, index, field, method)?
+            }
+            Some(_) => return None,
+            None => match name {
+                "game" => Value::Text(timer.run().game_name()),
+                "category" => Value::Text(timer.run().category_name()),
+                "attempt_count" => Value::Number(timer.run().attempt_count()),
+                "comparison" => Value::Text(timer.current_comparison()),
+                "current_time" => Value::Time(timer.current_time()[method]),
+                "personal_best" => Value::Time(
+                    timer
+                        .run()
+                        .segments()
+                        .last()
+                        .and_then(|s| s.personal_best_split_time()[method]),
+                ),
+                "sum_of_best" => Value::Time(sum_of_segments::calculate_best(
+                    timer.run().segments(),
+                    false,
+                    true,
+                    method,
+                )),
+                "pb_chance" => Value::Percentage(pb_chance::for_timer(timer).0),
+                _ => return None,
+            },
+        },
+        _ => return None,
+    };
+
+    let _ = match value {
+        Value::Text(text) => write!(buf, "{}", text),
+        Value::Number(number) => write!(buf, "{}", number),
+        Value::Percentage(chance) => write!(buf, "{:.1}%", 100.0 * chance),
+        Value::Time(time) => write_time(buf, time, &format),
+        Value::Delta(time) => write!(
+            buf,
+            "{}",
+            formatter::Delta::custom(
+                format.accuracy.is_none(),
+                format.accuracy.unwrap_or(Accuracy::Tenths)
+            )
+            .format(time)
+        ),
+    };
+
+    Some(())
+}
+
+fn write_time(buf: &mut String, time: Option<TimeSpan>, format: &Format) -> core::fmt::Result {
+    let accuracy = format.accuracy.unwrap_or(Accuracy::Hundredths);
+    if let Some(digits_format) = format.digits_format {
+        if time.is_none() {
+            return write!(buf, "{}", formatter::DASH);
+        }
+        write!(
+            buf,
+            "{}{}",
+            timer::Time::with_digits_format(digits_format).format(time),
+            timer::Fraction::with_accuracy(accuracy).format(time),
+        )
+    } else {
+        write!(buf, "{}", Regular::with_accuracy(accuracy).format(time))
+    }
+}
+
+fn custom_variable<'a>(timer: &'a Snapshot<'_>, name: &str) -> Value<'a> {
+    Value::Text(
+        timer
+            .run()
+            .metadata()
+            .custom_variable(name.trim())
+            .map(|var| var.value.as_str())
+            .filter(|value| !value.trim_start().is_empty())
+            .unwrap_or(formatter::DASH),
+    )
+}
+
+fn segment_field<'a>(
+    timer: &'a Snapshot<'_>,
+    index: Option<usize>,
+    field: &str,
+    method: TimingMethod,
+) -> Option<Value<'a>> {
+    let segment: Option<&Segment> = index.and_then(|i| timer.run().segments().get(i));
+    Some(match field {
+        "name" => Value::Text(segment.map_or(formatter::DASH, |s| s.name())),
+        "number" => match index {
+            Some(index) if segment.is_some() => Value::Number(index as u32 + 1),
+            _ => Value::Text(formatter::DASH),
+        },
+        "split_time" => Value::Time(segment.and_then(|s| s.split_time()[method])),
+        "best_segment" => Value::Time(segment.and_then(|s| s.best_segment_time()[method])),
+        "personal_best" => Value::Time(segment.and_then(|s| s.personal_best_split_time()[method])),
+        _ => return None,
+    })
+}
+
+/// Parses either a plain name or a call with a single, optionally quoted
+/// argument, like `delta("Best Segments")`.
+fn parse_call(text: &str) -> Option<(&str, Option<&str>)> {
+    let (name, argument) = match text.find('(') {
+        Some(index) => (&text[..index], Some(text[index + 1..].strip_suffix(')')?)),
+        None => (text, None),
+    };
+
+    let argument = match argument.map(str::trim) {
+        Some(argument) if argument.starts_with('"') => {
+            Some(argument.strip_prefix('"')?.strip_suffix('"')?)
+        }
+        argument => argument,
+    };
+
+    Some((name.trim(), argument))
+}
+
+fn parse_accuracy(text: &str) -> Option<Accuracy> {
+    Some(match text {
+        "seconds" => Accuracy::Seconds,
+        "tenths" => Accuracy::Tenths,
+        "hundredths" => Accuracy::Hundredths,
+        "milliseconds" => Accuracy::Milliseconds,
+        _ => return None,
+    })
+}
+
+fn parse_digits_format(text: &str) -> Option<DigitsFormat> {
+    Some(match text {
+        "single_digit_seconds" => DigitsFormat::SingleDigitSeconds,
+        "double_digit_seconds" => DigitsFormat::DoubleDigitSeconds,
+        "single_digit_minutes" => DigitsFormat::SingleDigitMinutes,
+        "double_digit_minutes" => DigitsFormat::DoubleDigitMinutes,
+        "single_digit_hours" => DigitsFormat::SingleDigitHours,
+        "double_digit_hours" => DigitsFormat::DoubleDigitHours,
+        _ => return None,
+    })
+}
+
+fn parse_timing_method(text: &str) -> Option<TimingMethod> {
+    Some(match text {
+        "real_time" => TimingMethod::RealTime,
+        "game_time" => TimingMethod::GameTime,
+        "custom1" => TimingMethod::Custom1,
+        "custom2" => TimingMethod::Custom2,
+        "custom3" => TimingMethod::Custom3,
+        "custom4" => TimingMethod::Custom4,
+        _ => return None,
+    })
+}
+
+/// Finds the first occurrence of the character that is not within quotes.
+fn find_unquoted(text: &str, needle: char) -> Option<usize> {
+    let mut is_quoted = false;
+    for (index, c) in text.char_indices() {
+        if c == '"' {
+            is_quoted = !is_quoted;
+        } else if c == needle && !is_quoted {
+            return Some(index);
+        }
+    }
+    None
+}
+
+fn split_unquoted(mut text: &str, separator: char) -> impl Iterator<Item = &str> {
+    let mut is_done = false;
+    core::iter::from_fn(move || {
+        if is_done {
+            return None;
+        }
+        Some(match find_unquoted(text, separator) {
+            Some(index) => {
+                let part = &text[..index];
+                text = &text[index + 1..];
+                part
+            }
+            None => {
+                is_done = true;
+                text
+            }
+        })
+    })
+}
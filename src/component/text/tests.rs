@@ -1,5 +1,9 @@
 use super::{Component, Text, TextState};
-use crate::{tests_helper::create_run, timing::formatter, Timer};
+use crate::{
+    tests_helper::{create_run, make_progress_run_with_splits_opt, run_sample_attempts, start_run},
+    timing::formatter,
+    Timer,
+};
 
 #[test]
 fn resolves_variables() {
@@ -10,7 +14,7 @@ fn resolves_variables() {
     let mut component = Component::new();
     component.settings_mut().text = Text::Variable(String::from("Goal Time"), true);
 
-    let state = component.state(&timer);
+    let state = component.state(&timer.snapshot());
 
     assert_eq!(
         state.text,
@@ -19,7 +23,7 @@ fn resolves_variables() {
 
     component.settings_mut().text = Text::Variable(String::from("Goal Time"), false);
 
-    let state = component.state(&timer);
+    let state = component.state(&timer.snapshot());
 
     assert_eq!(state.text, TextState::Center(String::from("4:20:00")));
 }
@@ -32,7 +36,7 @@ fn uses_dash_for_non_existing_variables() {
     let mut component = Component::new();
     component.settings_mut().text = Text::Variable(String::from("Goal Time"), true);
 
-    let state = component.state(&timer);
+    let state = component.state(&timer.snapshot());
 
     assert_eq!(
         state.text,
@@ -49,7 +53,7 @@ fn uses_dash_for_empty_string_variable() {
     let mut component = Component::new();
     component.settings_mut().text = Text::Variable(String::from("Goal Time"), true);
 
-    let state = component.state(&timer);
+    let state = component.state(&timer.snapshot());
 
     assert_eq!(
         state.text,
@@ -66,10 +70,65 @@ fn uses_dash_for_whitespace_variable() {
     let mut component = Component::new();
     component.settings_mut().text = Text::Variable(String::from("Goal Time"), true);
 
-    let state = component.state(&timer);
+    let state = component.state(&timer.snapshot());
 
     assert_eq!(
         state.text,
         TextState::Split(String::from("Goal Time"), String::from(formatter::DASH))
     );
 }
+
+#[test]
+fn evaluates_templates() {
+    let mut run = create_run(&["A", "B", "C"]);
+    run.metadata_mut().custom_variable_mut("Deaths").value = String::from("3");
+    let mut timer = Timer::new(run).unwrap();
+    run_sample_attempts(&mut timer);
+    start_run(&mut timer);
+    make_progress_run_with_splits_opt(&mut timer, &[Some(11.0)]);
+
+    let mut component = Component::new();
+    let settings = component.settings_mut();
+    settings.use_template = true;
+    settings.text = Text::Split(
+        String::from("#{attempt_count} {current_split.name} ({custom.Deaths}) {{x}}"),
+        String::from(
+            "{delta(\"Personal Best\")} \
+             {sum_of_best | accuracy(tenths)} \
+             {current_split.personal_best | digits(double_digit_minutes) | accuracy(tenths)} \
+             {previous_split.split_time} \
+             {previous_split.split_time | method(custom1)} {nope} {sum_of_best | nope}",
+        ),
+    );
+
+    let state = component.state(&timer.snapshot());
+
+    assert_eq!(
+        state.text,
+        TextState::Split(
+            String::from("#3 B (3) {x}"),
+            format!(
+                "+1.0 1:00.0 00:30.0 0:11.00 {} {{nope}} {{sum_of_best | nope}}",
+                formatter::DASH
+            ),
+        )
+    );
+}
+
+#[test]
+fn templates_are_only_evaluated_when_enabled() {
+    let timer = Timer::new(create_run(&["A"])).unwrap();
+
+    let mut component = Component::new();
+    component.settings_mut().text = Text::Center(String::from("{attempt_count}"));
+
+    let state = component.state(&timer.snapshot());
+    assert_eq!(
+        state.text,
+        TextState::Center(String::from("{attempt_count}"))
+    );
+
+    component.set_value(5, true.into());
+    let state = component.state(&timer.snapshot());
+    assert_eq!(state.text, TextState::Center(String::from("0")));
+}
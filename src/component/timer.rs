@@ -8,7 +8,7 @@ use crate::{
     platform::prelude::*,
     settings::{Color, Field, Gradient, SemanticColor, SettingsDescription, Value},
     timing::{
-        formatter::{timer as formatter, Accuracy, DigitsFormat, TimeFormatter},
+        formatter::{timer as formatter, Accuracy, DigitsFormat, FrameWrapper, TimeFormatter},
         Snapshot,
     },
     GeneralLayoutSettings, TimeSpan, TimerPhase, TimingMethod,
@@ -156,7 +156,7 @@ impl Component {
             };
             let mut segment_time = calculate_live_segment_time(timer, method, last_split_index);
 
-            if segment_time.is_none() && method == TimingMethod::GameTime {
+            if segment_time.is_none() && method != TimingMethod::RealTime {
                 segment_time =
                     calculate_live_segment_time(timer, TimingMethod::RealTime, last_split_index);
             }
@@ -224,18 +224,28 @@ impl Component {
 
         state.background = self.settings.background;
 
+        let frame_rate = timer.run().metadata().frame_rate();
+
         state.time.clear();
         let _ = write!(
             state.time,
             "{}",
-            formatter::Time::with_digits_format(self.settings.digits_format).format(time),
+            FrameWrapper::new(
+                frame_rate,
+                formatter::Time::with_digits_format(self.settings.digits_format)
+            )
+            .format(time),
         );
 
         state.fraction.clear();
         let _ = write!(
             state.fraction,
             "{}",
-            formatter::Fraction::with_accuracy(self.settings.accuracy).format(time),
+            FrameWrapper::new(
+                frame_rate,
+                formatter::Fraction::with_accuracy(self.settings.accuracy)
+            )
+            .format(time),
         );
 
         state.updates_frequently = phase.is_running() && time.is_some();
@@ -1,7 +1,7 @@
 #![allow(clippy::trivially_copy_pass_by_ref)]
 
 use crate::{
-    hotkey::KeyCode,
+    hotkey::Hotkey,
     platform::prelude::*,
     settings::{Field, SettingsDescription, Value},
 };
@@ -13,25 +13,25 @@ use serde::{Deserialize, Serialize};
 #[serde(default)]
 pub struct HotkeyConfig {
     /// The key to use for splitting and starting a new attempt.
-    pub split: Option<KeyCode>,
+    pub split: Option<Hotkey>,
     /// The key to use for resetting the current attempt.
-    pub reset: Option<KeyCode>,
+    pub reset: Option<Hotkey>,
     /// The key to use for undoing the last split.
-    pub undo: Option<KeyCode>,
+    pub undo: Option<Hotkey>,
     /// The key to use for skipping the current split.
-    pub skip: Option<KeyCode>,
+    pub skip: Option<Hotkey>,
     /// The key to use for pausing the current attempt and starting a new
     /// attempt.
-    pub pause: Option<KeyCode>,
+    pub pause: Option<Hotkey>,
     /// The key to use for removing all the pause times from the current time.
-    pub undo_all_pauses: Option<KeyCode>,
+    pub undo_all_pauses: Option<Hotkey>,
     /// The key to use for switching to the previous comparison.
-    pub previous_comparison: Option<KeyCode>,
+    pub previous_comparison: Option<Hotkey>,
     /// The key to use for switching to the next comparison.
-    pub next_comparison: Option<KeyCode>,
+    pub next_comparison: Option<Hotkey>,
     /// The key to use for toggling between the `Real Time` and `Game Time`
     /// timing methods.
-    pub toggle_timing_method: Option<KeyCode>,
+    pub toggle_timing_method: Option<Hotkey>,
 }
 
 #[cfg(any(
@@ -44,14 +44,14 @@ impl Default for HotkeyConfig {
     fn default() -> Self {
         use crate::hotkey::KeyCode::*;
         Self {
-            split: Some(Numpad1),
-            reset: Some(Numpad3),
-            undo: Some(Numpad8),
-            skip: Some(Numpad2),
-            pause: Some(Numpad5),
+            split: Some(Numpad1.into()),
+            reset: Some(Numpad3.into()),
+            undo: Some(Numpad8.into()),
+            skip: Some(Numpad2.into()),
+            pause: Some(Numpad5.into()),
             undo_all_pauses: None,
-            previous_comparison: Some(Numpad4),
-            next_comparison: Some(Numpad6),
+            previous_comparison: Some(Numpad4.into()),
+            next_comparison: Some(Numpad6.into()),
             toggle_timing_method: None,
         }
     }
@@ -65,15 +65,16 @@ impl Default for HotkeyConfig {
 )))]
 impl Default for HotkeyConfig {
     fn default() -> Self {
+        use crate::hotkey::KeyCode;
         Self {
-            split: Some(KeyCode),
-            reset: Some(KeyCode),
-            undo: Some(KeyCode),
-            skip: Some(KeyCode),
-            pause: Some(KeyCode),
+            split: Some(KeyCode.into()),
+            reset: Some(KeyCode.into()),
+            undo: Some(KeyCode.into()),
+            skip: Some(KeyCode.into()),
+            pause: Some(KeyCode.into()),
             undo_all_pauses: None,
-            previous_comparison: Some(KeyCode),
-            next_comparison: Some(KeyCode),
+            previous_comparison: Some(KeyCode.into()),
+            next_comparison: Some(KeyCode.into()),
             toggle_timing_method: None,
         }
     }
@@ -115,7 +116,7 @@ impl HotkeyConfig {
     /// the type of the setting's value. A panic can also occur if the index of
     /// the setting provided is out of bounds.
     pub fn set_value(&mut self, index: usize, value: Value) -> Result<(), ()> {
-        let value: Option<KeyCode> = value.into();
+        let value: Option<Hotkey> = value.into();
 
         if value.is_some() {
             let any = [
@@ -1,5 +1,5 @@
 use crate::{
-    hotkey::{Hook, KeyCode},
+    hotkey::{Hook, Hotkey},
     HotkeyConfig, SharedTimer,
 };
 
@@ -7,7 +7,7 @@ pub use crate::hotkey::{Error, Result};
 
 // This enum might be better situated in hotkey_config, but the last method should stay in this file
 #[derive(Debug, Copy, Clone, Eq, PartialEq)]
-enum Hotkey {
+enum Action {
     Split,
     /// The key to use for resetting the current attempt.
     Reset,
@@ -29,48 +29,48 @@ enum Hotkey {
     ToggleTimingMethod,
 }
 
-impl Hotkey {
-    fn set_keycode(self, config: &mut HotkeyConfig, keycode: Option<KeyCode>) {
+impl Action {
+    fn set_keycode(self, config: &mut HotkeyConfig, keycode: Option<Hotkey>) {
         match self {
-            Hotkey::Split => config.split = keycode,
-            Hotkey::Reset => config.reset = keycode,
-            Hotkey::Undo => config.undo = keycode,
-            Hotkey::Skip => config.skip = keycode,
-            Hotkey::Pause => config.pause = keycode,
-            Hotkey::UndoAllPauses => config.undo_all_pauses = keycode,
-            Hotkey::PreviousComparison => config.previous_comparison = keycode,
-            Hotkey::NextComparison => config.next_comparison = keycode,
-            Hotkey::ToggleTimingMethod => config.toggle_timing_method = keycode,
+            Action::Split => config.split = keycode,
+            Action::Reset => config.reset = keycode,
+            Action::Undo => config.undo = keycode,
+            Action::Skip => config.skip = keycode,
+            Action::Pause => config.pause = keycode,
+            Action::UndoAllPauses => config.undo_all_pauses = keycode,
+            Action::PreviousComparison => config.previous_comparison = keycode,
+            Action::NextComparison => config.next_comparison = keycode,
+            Action::ToggleTimingMethod => config.toggle_timing_method = keycode,
         }
     }
 
-    const fn get_keycode(self, config: &HotkeyConfig) -> Option<KeyCode> {
+    const fn get_keycode(self, config: &HotkeyConfig) -> Option<Hotkey> {
         match self {
-            Hotkey::Split => config.split,
-            Hotkey::Reset => config.reset,
-            Hotkey::Undo => config.undo,
-            Hotkey::Skip => config.skip,
-            Hotkey::Pause => config.pause,
-            Hotkey::UndoAllPauses => config.undo_all_pauses,
-            Hotkey::PreviousComparison => config.previous_comparison,
-            Hotkey::NextComparison => config.next_comparison,
-            Hotkey::ToggleTimingMethod => config.toggle_timing_method,
+            Action::Split => config.split,
+            Action::Reset => config.reset,
+            Action::Undo => config.undo,
+            Action::Skip => config.skip,
+            Action::Pause => config.pause,
+            Action::UndoAllPauses => config.undo_all_pauses,
+            Action::PreviousComparison => config.previous_comparison,
+            Action::NextComparison => config.next_comparison,
+            Action::ToggleTimingMethod => config.toggle_timing_method,
         }
     }
 
     fn callback(self, timer: SharedTimer) -> Box<dyn FnMut() + Send + 'static> {
         match self {
-            Hotkey::Split => Box::new(move || timer.write().split_or_start()),
-            Hotkey::Reset => Box::new(move || timer.write().reset(true)),
-            Hotkey::Undo => Box::new(move || timer.write().undo_split()),
-            Hotkey::Skip => Box::new(move || timer.write().skip_split()),
-            Hotkey::Pause => Box::new(move || timer.write().toggle_pause_or_start()),
-            Hotkey::UndoAllPauses => Box::new(move || timer.write().undo_all_pauses()),
-            Hotkey::PreviousComparison => {
+            Action::Split => Box::new(move || timer.write().split_or_start()),
+            Action::Reset => Box::new(move || timer.write().reset(true)),
+            Action::Undo => Box::new(move || timer.write().undo_split()),
+            Action::Skip => Box::new(move || timer.write().skip_split()),
+            Action::Pause => Box::new(move || timer.write().toggle_pause_or_start()),
+            Action::UndoAllPauses => Box::new(move || timer.write().undo_all_pauses()),
+            Action::PreviousComparison => {
                 Box::new(move || timer.write().switch_to_previous_comparison())
             }
-            Hotkey::NextComparison => Box::new(move || timer.write().switch_to_next_comparison()),
-            Hotkey::ToggleTimingMethod => Box::new(move || timer.write().toggle_timing_method()),
+            Action::NextComparison => Box::new(move || timer.write().switch_to_next_comparison()),
+            Action::ToggleTimingMethod => Box::new(move || timer.write().toggle_timing_method()),
         }
     }
 }
@@ -108,91 +108,91 @@ impl HotkeySystem {
 
     // This method should never be public, because it might mess up the internal state and we might
     // leak a registered hotkey
-    unsafe fn register_raw(&mut self, hotkey: Hotkey) -> Result<()> {
+    unsafe fn register_raw(&mut self, action: Action) -> Result<()> {
         let inner = self.timer.clone();
-        if let Some(keycode) = hotkey.get_keycode(&self.config) {
-            self.hook.register(keycode, hotkey.callback(inner))?;
+        if let Some(keycode) = action.get_keycode(&self.config) {
+            self.hook.register(keycode, action.callback(inner))?;
         }
         Ok(())
     }
 
-    fn register(&mut self, hotkey: Hotkey, keycode: Option<KeyCode>) -> Result<()> {
-        hotkey.set_keycode(&mut self.config, keycode);
-        unsafe { self.register_raw(hotkey) }
+    fn register(&mut self, action: Action, keycode: Option<Hotkey>) -> Result<()> {
+        action.set_keycode(&mut self.config, keycode);
+        unsafe { self.register_raw(action) }
     }
 
     // This method should never be public, because it might mess up the internal state and we might
     // leak a registered hotkey
-    unsafe fn unregister_raw(&mut self, hotkey: Hotkey) -> Result<()> {
-        if let Some(keycode) = hotkey.get_keycode(&self.config) {
+    unsafe fn unregister_raw(&mut self, action: Action) -> Result<()> {
+        if let Some(keycode) = action.get_keycode(&self.config) {
             self.hook.unregister(keycode)?;
         }
         Ok(())
     }
 
-    fn unregister(&mut self, hotkey: Hotkey) -> Result<()> {
-        hotkey.set_keycode(&mut self.config, None);
-        unsafe { self.unregister_raw(hotkey) }
+    fn unregister(&mut self, action: Action) -> Result<()> {
+        action.set_keycode(&mut self.config, None);
+        unsafe { self.unregister_raw(action) }
     }
 
-    fn set_hotkey(&mut self, hotkey: Hotkey, keycode: Option<KeyCode>) -> Result<()> {
+    fn set_hotkey(&mut self, action: Action, keycode: Option<Hotkey>) -> Result<()> {
         // FixMe: We do not check whether the keycode is already in use
-        if hotkey.get_keycode(&self.config) == keycode {
+        if action.get_keycode(&self.config) == keycode {
             return Ok(());
         }
         if self.is_active {
-            self.unregister(hotkey)?;
-            self.register(hotkey, keycode)?;
+            self.unregister(action)?;
+            self.register(action, keycode)?;
         }
         Ok(())
     }
 
     /// Sets the key to use for splitting and starting a new attempt.
-    pub fn set_split(&mut self, hotkey: Option<KeyCode>) -> Result<()> {
-        self.set_hotkey(Hotkey::Split, hotkey)
+    pub fn set_split(&mut self, hotkey: Option<Hotkey>) -> Result<()> {
+        self.set_hotkey(Action::Split, hotkey)
     }
 
     /// Sets the key to use for resetting the current attempt.
-    pub fn set_reset(&mut self, hotkey: Option<KeyCode>) -> Result<()> {
-        self.set_hotkey(Hotkey::Reset, hotkey)
+    pub fn set_reset(&mut self, hotkey: Option<Hotkey>) -> Result<()> {
+        self.set_hotkey(Action::Reset, hotkey)
     }
 
     /// Sets the key to use for pausing the current attempt and starting a new
     /// attempt.
-    pub fn set_pause(&mut self, hotkey: Option<KeyCode>) -> Result<()> {
-        self.set_hotkey(Hotkey::Pause, hotkey)
+    pub fn set_pause(&mut self, hotkey: Option<Hotkey>) -> Result<()> {
+        self.set_hotkey(Action::Pause, hotkey)
     }
 
     /// Sets the key to use for skipping the current split.
-    pub fn set_skip(&mut self, hotkey: Option<KeyCode>) -> Result<()> {
-        self.set_hotkey(Hotkey::Skip, hotkey)
+    pub fn set_skip(&mut self, hotkey: Option<Hotkey>) -> Result<()> {
+        self.set_hotkey(Action::Skip, hotkey)
     }
 
     /// Sets the key to use for undoing the last split.
-    pub fn set_undo(&mut self, hotkey: Option<KeyCode>) -> Result<()> {
-        self.set_hotkey(Hotkey::Undo, hotkey)
+    pub fn set_undo(&mut self, hotkey: Option<Hotkey>) -> Result<()> {
+        self.set_hotkey(Action::Undo, hotkey)
     }
 
     /// Sets the key to use for switching to the previous comparison.
-    pub fn set_previous_comparison(&mut self, hotkey: Option<KeyCode>) -> Result<()> {
-        self.set_hotkey(Hotkey::PreviousComparison, hotkey)
+    pub fn set_previous_comparison(&mut self, hotkey: Option<Hotkey>) -> Result<()> {
+        self.set_hotkey(Action::PreviousComparison, hotkey)
     }
 
     /// Sets the key to use for switching to the next comparison.
-    pub fn set_next_comparison(&mut self, hotkey: Option<KeyCode>) -> Result<()> {
-        self.set_hotkey(Hotkey::NextComparison, hotkey)
+    pub fn set_next_comparison(&mut self, hotkey: Option<Hotkey>) -> Result<()> {
+        self.set_hotkey(Action::NextComparison, hotkey)
     }
 
     /// Sets the key to use for removing all the pause times from the current
     /// time.
-    pub fn set_undo_all_pauses(&mut self, hotkey: Option<KeyCode>) -> Result<()> {
-        self.set_hotkey(Hotkey::UndoAllPauses, hotkey)
+    pub fn set_undo_all_pauses(&mut self, hotkey: Option<Hotkey>) -> Result<()> {
+        self.set_hotkey(Action::UndoAllPauses, hotkey)
     }
 
     /// Sets the key to use for toggling between the `Real Time` and `Game Time`
     /// timing methods.
-    pub fn set_toggle_timing_method(&mut self, hotkey: Option<KeyCode>) -> Result<()> {
-        self.set_hotkey(Hotkey::ToggleTimingMethod, hotkey)
+    pub fn set_toggle_timing_method(&mut self, hotkey: Option<Hotkey>) -> Result<()> {
+        self.set_hotkey(Action::ToggleTimingMethod, hotkey)
     }
 
     /// Deactivates the Hotkey System. No hotkeys will go through until it gets
@@ -200,15 +200,15 @@ impl HotkeySystem {
     pub fn deactivate(&mut self) -> Result<()> {
         if self.is_active {
             unsafe {
-                self.unregister_raw(Hotkey::Split)?;
-                self.unregister_raw(Hotkey::Reset)?;
-                self.unregister_raw(Hotkey::Undo)?;
-                self.unregister_raw(Hotkey::Skip)?;
-                self.unregister_raw(Hotkey::Pause)?;
-                self.unregister_raw(Hotkey::UndoAllPauses)?;
-                self.unregister_raw(Hotkey::PreviousComparison)?;
-                self.unregister_raw(Hotkey::NextComparison)?;
-                self.unregister_raw(Hotkey::ToggleTimingMethod)?;
+                self.unregister_raw(Action::Split)?;
+                self.unregister_raw(Action::Reset)?;
+                self.unregister_raw(Action::Undo)?;
+                self.unregister_raw(Action::Skip)?;
+                self.unregister_raw(Action::Pause)?;
+                self.unregister_raw(Action::UndoAllPauses)?;
+                self.unregister_raw(Action::PreviousComparison)?;
+                self.unregister_raw(Action::NextComparison)?;
+                self.unregister_raw(Action::ToggleTimingMethod)?;
             }
         }
         self.is_active = false;
@@ -220,15 +220,15 @@ impl HotkeySystem {
     pub fn activate(&mut self) -> Result<()> {
         if !self.is_active {
             unsafe {
-                self.register_raw(Hotkey::Split)?;
-                self.register_raw(Hotkey::Reset)?;
-                self.register_raw(Hotkey::Undo)?;
-                self.register_raw(Hotkey::Skip)?;
-                self.register_raw(Hotkey::Pause)?;
-                self.register_raw(Hotkey::UndoAllPauses)?;
-                self.register_raw(Hotkey::PreviousComparison)?;
-                self.register_raw(Hotkey::NextComparison)?;
-                self.register_raw(Hotkey::ToggleTimingMethod)?;
+                self.register_raw(Action::Split)?;
+                self.register_raw(Action::Reset)?;
+                self.register_raw(Action::Undo)?;
+                self.register_raw(Action::Skip)?;
+                self.register_raw(Action::Pause)?;
+                self.register_raw(Action::UndoAllPauses)?;
+                self.register_raw(Action::PreviousComparison)?;
+                self.register_raw(Action::NextComparison)?;
+                self.register_raw(Action::ToggleTimingMethod)?;
             }
         }
         self.is_active = true;
@@ -1,8 +1,8 @@
 use super::{ComponentSettings, ComponentState, GeneralSettings};
 use crate::component::{
     blank_space, current_comparison, current_pace, delta, detailed_timer, graph, pb_chance,
-    possible_time_save, previous_segment, segment_time, separator, splits, sum_of_best, text,
-    timer, title, total_playtime,
+    possible_time_save, previous_segment, run_history, segment_distribution, segment_time,
+    separator, splits, statistic, sum_of_best, text, timer, title, total_playtime,
 };
 use crate::platform::prelude::*;
 use crate::settings::{SettingsDescription, Value};
@@ -31,12 +31,18 @@ pub enum Component {
     PossibleTimeSave(possible_time_save::Component),
     /// The Previous Segment Component.
     PreviousSegment(previous_segment::Component),
+    /// The Run History Component.
+    RunHistory(run_history::Component),
+    /// The Segment Distribution Component.
+    SegmentDistribution(segment_distribution::Component),
     /// The Segment Time Component.
     SegmentTime(segment_time::Component),
     /// The Separator Component.
     Separator(separator::Component),
     /// The Splits Component.
     Splits(splits::Component),
+    /// The Statistic Component.
+    Statistic(statistic::Component),
     /// The Sum of Best Component.
     SumOfBest(sum_of_best::Component),
     /// The Text Component.
@@ -103,6 +109,18 @@ impl From<previous_segment::Component> for Component {
     }
 }
 
+impl From<run_history::Component> for Component {
+    fn from(component: run_history::Component) -> Self {
+        Self::RunHistory(component)
+    }
+}
+
+impl From<segment_distribution::Component> for Component {
+    fn from(component: segment_distribution::Component) -> Self {
+        Self::SegmentDistribution(component)
+    }
+}
+
 impl From<segment_time::Component> for Component {
     fn from(component: segment_time::Component) -> Self {
         Self::SegmentTime(component)
@@ -121,6 +139,12 @@ impl From<splits::Component> for Component {
     }
 }
 
+impl From<statistic::Component> for Component {
+    fn from(component: statistic::Component) -> Self {
+        Self::Statistic(component)
+    }
+}
+
 impl From<sum_of_best::Component> for Component {
     fn from(component: sum_of_best::Component) -> Self {
         Self::SumOfBest(component)
@@ -190,6 +214,13 @@ impl Component {
             (ComponentState::KeyValue(state), Component::PreviousSegment(component)) => {
                 component.update_state(state, timer, layout_settings)
             }
+            (ComponentState::RunHistory(state), Component::RunHistory(component)) => {
+                component.update_state(state, timer)
+            }
+            (
+                ComponentState::SegmentDistribution(state),
+                Component::SegmentDistribution(component),
+            ) => component.update_state(state, timer),
             (ComponentState::KeyValue(state), Component::SegmentTime(component)) => {
                 component.update_state(state, timer)
             }
@@ -199,6 +230,9 @@ impl Component {
             (ComponentState::Splits(state), Component::Splits(component)) => {
                 component.update_state(state, timer, layout_settings)
             }
+            (ComponentState::KeyValue(state), Component::Statistic(component)) => {
+                component.update_state(state, timer)
+            }
             (ComponentState::KeyValue(state), Component::SumOfBest(component)) => {
                 component.update_state(state, timer)
             }
@@ -249,11 +283,16 @@ impl Component {
             Component::PreviousSegment(component) => {
                 ComponentState::KeyValue(component.state(timer, layout_settings))
             }
+            Component::RunHistory(component) => ComponentState::RunHistory(component.state(timer)),
+            Component::SegmentDistribution(component) => {
+                ComponentState::SegmentDistribution(component.state(timer))
+            }
             Component::SegmentTime(component) => ComponentState::KeyValue(component.state(timer)),
             Component::Separator(component) => ComponentState::Separator(component.state()),
             Component::Splits(component) => {
                 ComponentState::Splits(component.state(timer, layout_settings))
             }
+            Component::Statistic(component) => ComponentState::KeyValue(component.state(timer)),
             Component::SumOfBest(component) => ComponentState::KeyValue(component.state(timer)),
             Component::Text(component) => ComponentState::Text(component.state(timer)),
             Component::Timer(component) => {
@@ -293,11 +332,20 @@ impl Component {
             Component::PreviousSegment(component) => {
                 ComponentSettings::PreviousSegment(component.settings().clone())
             }
+            Component::RunHistory(component) => {
+                ComponentSettings::RunHistory(component.settings().clone())
+            }
+            Component::SegmentDistribution(component) => {
+                ComponentSettings::SegmentDistribution(component.settings().clone())
+            }
             Component::SegmentTime(component) => {
                 ComponentSettings::SegmentTime(component.settings().clone())
             }
             Component::Separator(_) => ComponentSettings::Separator,
             Component::Splits(component) => ComponentSettings::Splits(component.settings().clone()),
+            Component::Statistic(component) => {
+                ComponentSettings::Statistic(component.settings().clone())
+            }
             Component::SumOfBest(component) => {
                 ComponentSettings::SumOfBest(component.settings().clone())
             }
@@ -322,9 +370,12 @@ impl Component {
             Component::PbChance(component) => component.name().into(),
             Component::PossibleTimeSave(component) => component.name(),
             Component::PreviousSegment(component) => component.name(),
+            Component::RunHistory(component) => component.name().into(),
+            Component::SegmentDistribution(component) => component.name().into(),
             Component::SegmentTime(component) => component.name(),
             Component::Separator(component) => component.name().into(),
             Component::Splits(component) => component.name().into(),
+            Component::Statistic(component) => component.name().into(),
             Component::SumOfBest(component) => component.name().into(),
             Component::Text(component) => component.name(),
             Component::Timer(component) => component.name().into(),
@@ -377,9 +428,12 @@ impl Component {
             Component::PbChance(component) => component.settings_description(),
             Component::PossibleTimeSave(component) => component.settings_description(),
             Component::PreviousSegment(component) => component.settings_description(),
+            Component::RunHistory(component) => component.settings_description(),
+            Component::SegmentDistribution(component) => component.settings_description(),
             Component::SegmentTime(component) => component.settings_description(),
             Component::Separator(component) => component.settings_description(),
             Component::Splits(component) => component.settings_description(),
+            Component::Statistic(component) => component.settings_description(),
             Component::SumOfBest(component) => component.settings_description(),
             Component::Text(component) => component.settings_description(),
             Component::Timer(component) => component.settings_description(),
@@ -407,9 +461,12 @@ impl Component {
             Component::PbChance(component) => component.set_value(index, value),
             Component::PossibleTimeSave(component) => component.set_value(index, value),
             Component::PreviousSegment(component) => component.set_value(index, value),
+            Component::RunHistory(component) => component.set_value(index, value),
+            Component::SegmentDistribution(component) => component.set_value(index, value),
             Component::SegmentTime(component) => component.set_value(index, value),
             Component::Separator(component) => component.set_value(index, value),
             Component::Splits(component) => component.set_value(index, value),
+            Component::Statistic(component) => component.set_value(index, value),
             Component::SumOfBest(component) => component.set_value(index, value),
             Component::Text(component) => component.set_value(index, value),
             Component::Timer(component) => component.set_value(index, value),
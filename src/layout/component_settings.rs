@@ -1,8 +1,8 @@
 use super::Component;
 use crate::component::{
     blank_space, current_comparison, current_pace, delta, detailed_timer, graph, pb_chance,
-    possible_time_save, previous_segment, segment_time, separator, splits, sum_of_best, text,
-    timer, title, total_playtime,
+    possible_time_save, previous_segment, run_history, segment_distribution, segment_time,
+    separator, splits, statistic, sum_of_best, text, timer, title, total_playtime,
 };
 use crate::platform::prelude::*;
 use serde::{Deserialize, Serialize};
@@ -28,12 +28,18 @@ pub enum ComponentSettings {
     PossibleTimeSave(possible_time_save::Settings),
     /// The Settings for the Previous Segment Component.
     PreviousSegment(previous_segment::Settings),
+    /// The Settings for the Run History Component.
+    RunHistory(run_history::Settings),
+    /// The Settings for the Segment Distribution Component.
+    SegmentDistribution(segment_distribution::Settings),
     /// The Settings for the Segment Time Component.
     SegmentTime(segment_time::Settings),
     /// The Settings for the Separator Component.
     Separator,
     /// The Settings for the Splits Component.
     Splits(splits::Settings),
+    /// The Settings for the Statistic Component.
+    Statistic(statistic::Settings),
     /// The Settings for the Sum Of Best Component.
     SumOfBest(sum_of_best::Settings),
     /// The Settings for the Text Component.
@@ -76,6 +82,12 @@ impl From<ComponentSettings> for Component {
             ComponentSettings::PreviousSegment(settings) => {
                 Component::PreviousSegment(previous_segment::Component::with_settings(settings))
             }
+            ComponentSettings::RunHistory(settings) => {
+                Component::RunHistory(run_history::Component::with_settings(settings))
+            }
+            ComponentSettings::SegmentDistribution(settings) => Component::SegmentDistribution(
+                segment_distribution::Component::with_settings(settings),
+            ),
             ComponentSettings::SegmentTime(settings) => {
                 Component::SegmentTime(segment_time::Component::with_settings(settings))
             }
@@ -83,6 +95,9 @@ impl From<ComponentSettings> for Component {
             ComponentSettings::Splits(settings) => {
                 Component::Splits(splits::Component::with_settings(settings))
             }
+            ComponentSettings::Statistic(settings) => {
+                Component::Statistic(statistic::Component::with_settings(settings))
+            }
             ComponentSettings::SumOfBest(settings) => {
                 Component::SumOfBest(sum_of_best::Component::with_settings(settings))
             }
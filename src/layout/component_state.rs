@@ -1,5 +1,6 @@
 use crate::component::{
-    blank_space, detailed_timer, graph, key_value, separator, splits, text, timer, title,
+    blank_space, detailed_timer, graph, key_value, run_history, segment_distribution, separator,
+    splits, text, timer, title,
 };
 use crate::platform::prelude::*;
 use serde::{Deserialize, Serialize};
@@ -15,6 +16,10 @@ pub enum ComponentState {
     Graph(graph::State),
     /// The state object for a key value based component.
     KeyValue(key_value::State),
+    /// The state object for the Run History Component.
+    RunHistory(run_history::State),
+    /// The state object for the Segment Distribution Component.
+    SegmentDistribution(segment_distribution::State),
     /// The state object for the Separator Component.
     Separator(separator::State),
     /// The state object for the Splits Component.
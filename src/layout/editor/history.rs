@@ -0,0 +1,232 @@
+use crate::{
+    layout::{Component, Layout},
+    platform::prelude::*,
+    settings::Value,
+};
+use core::mem::{replace, swap, take};
+use serde::{Deserialize, Serialize};
+
+/// The maximum amount of actions that can be undone. If more actions are
+/// applied, the oldest ones can't be undone anymore.
+const MAX_UNDO_ACTIONS: usize = 100;
+
+/// Describes an action that modified the Layout in the Layout Editor. Every
+/// action can be undone and redone.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Action {
+    /// A component was added.
+    AddComponent,
+    /// The selected components were removed.
+    RemoveComponents,
+    /// The selected components were moved up.
+    MoveComponentsUp,
+    /// The selected components were moved down.
+    MoveComponentsDown,
+    /// The selected components were moved to a different index.
+    MoveComponents,
+    /// The selected components were duplicated.
+    DuplicateComponents,
+    /// A setting of the selected components was changed.
+    SetComponentSettingsValue,
+    /// A setting of the general settings was changed.
+    SetGeneralSettingsValue,
+}
+
+impl Action {
+    /// Returns a label describing the action that can be shown to the user,
+    /// for example as part of an undo or redo button.
+    pub const fn label(self) -> &'static str {
+        match self {
+            Action::AddComponent => "Add Component",
+            Action::RemoveComponents => "Remove Components",
+            Action::MoveComponentsUp => "Move Components Up",
+            Action::MoveComponentsDown => "Move Components Down",
+            Action::MoveComponents => "Move Components",
+            Action::DuplicateComponents => "Duplicate Components",
+            Action::SetComponentSettingsValue => "Change Component Setting",
+            Action::SetGeneralSettingsValue => "Change General Setting",
+        }
+    }
+}
+
+/// Text settings are usually applied on every key stroke, so consecutive
+/// changes to the same text setting are merged into a single action.
+pub(super) const fn is_merged(value: &Value) -> bool {
+    matches!(value, Value::String(_) | Value::OptionalString(_))
+}
+
+/// Identifies the setting that was changed by an action, so that only changes
+/// to the same setting of the same component get merged.
+#[derive(Copy, Clone, PartialEq, Eq)]
+pub(super) enum MergeKey {
+    /// The setting with the given index of the component with the given index.
+    Component(usize, usize),
+    /// The general setting with the given index.
+    General(usize),
+}
+
+/// A change to the Layout. Applying it to the Layout turns it into the change
+/// that reverts it, so undoing and redoing is a matter of applying the change
+/// stored in the entry.
+pub(super) enum Change {
+    /// The components are inserted at their indices, in ascending order.
+    Insert(Vec<(usize, Component)>),
+    /// The components at the indices are removed. The indices are in ascending
+    /// order.
+    Remove(Vec<usize>),
+    /// The components are reordered, so that the component at each index is
+    /// the one that was at the index stored there.
+    Reorder(Vec<usize>),
+    /// The components at the indices are replaced.
+    Replace(Vec<(usize, Component)>),
+    /// The value of the general setting with the given index is replaced.
+    GeneralSetting(usize, Value),
+}
+
+impl Change {
+    /// Applies the change to the Layout and turns it into the change that
+    /// reverts it.
+    pub fn apply(&mut self, layout: &mut Layout) {
+        let components = &mut layout.components;
+        match self {
+            Change::Insert(inserted) => {
+                let mut indices = Vec::with_capacity(inserted.len());
+                for (index, component) in inserted.drain(..) {
+                    components.insert(index, component);
+                    indices.push(index);
+                }
+                *self = Change::Remove(indices);
+            }
+            Change::Remove(indices) => {
+                let mut removed = indices
+                    .iter()
+                    .rev()
+                    .map(|&index| (index, components.remove(index)))
+                    .collect::<Vec<_>>();
+                removed.reverse();
+                *self = Change::Insert(removed);
+            }
+            Change::Reorder(order) => {
+                let mut previous = take(components).into_iter().map(Some).collect::<Vec<_>>();
+                components.extend(order.iter().map(|&index| previous[index].take().unwrap()));
+                let mut reverted = vec![0; order.len()];
+                for (index, &previous_index) in order.iter().enumerate() {
+                    reverted[previous_index] = index;
+                }
+                *order = reverted;
+            }
+            Change::Replace(replaced) => {
+                for (index, component) in replaced {
+                    swap(component, &mut components[*index]);
+                }
+            }
+            Change::GeneralSetting(index, value) => {
+                let settings = layout.general_settings_mut();
+                let current = settings.settings_description().fields.remove(*index).value;
+                settings.set_value(*index, replace(value, current));
+            }
+        }
+    }
+}
+
+struct Entry {
+    action: Action,
+    merge_key: Option<MergeKey>,
+    change: Change,
+    selected_components: Vec<usize>,
+}
+
+impl Entry {
+    fn apply(&mut self, layout: &mut Layout, selected_components: &mut Vec<usize>) {
+        self.change.apply(layout);
+        swap(&mut self.selected_components, selected_components);
+    }
+}
+
+/// The undo and redo stacks of the Layout Editor. Each entry stores the change
+/// that reverts the action, along with the components that were selected
+/// right before (or after, for the redo stack) the action was applied.
+#[derive(Default)]
+pub(super) struct History {
+    undo: Vec<Entry>,
+    redo: Vec<Entry>,
+    can_merge: bool,
+}
+
+impl History {
+    /// Records an action along with the change that reverts it and the
+    /// components that were selected before it was applied. Consecutive
+    /// actions of the same kind with the same merge key are merged into a
+    /// single action, unless merging was stopped in between.
+    pub fn record<F>(
+        &mut self,
+        action: Action,
+        merge_key: Option<MergeKey>,
+        selected_components: &[usize],
+        change: F,
+    ) where
+        F: FnOnce() -> Change,
+    {
+        self.redo.clear();
+
+        if self.can_merge && merge_key.is_some() {
+            if let Some(last) = self.undo.last() {
+                if last.action == action && last.merge_key == merge_key {
+                    return;
+                }
+            }
+        }
+
+        if self.undo.len() == MAX_UNDO_ACTIONS {
+            self.undo.remove(0);
+        }
+        self.undo.push(Entry {
+            action,
+            merge_key,
+            change: change(),
+            selected_components: selected_components.to_vec(),
+        });
+        self.can_merge = true;
+    }
+
+    /// Ensures the next action is not merged into the most recent one.
+    pub const fn stop_merging(&mut self) {
+        self.can_merge = false;
+    }
+
+    pub fn undo_action(&self) -> Option<Action> {
+        self.undo.last().map(|e| e.action)
+    }
+
+    pub fn redo_action(&self) -> Option<Action> {
+        self.redo.last().map(|e| e.action)
+    }
+
+    /// Reverts the most recent action. Returns `false` if there's nothing to
+    /// undo.
+    pub fn undo(&mut self, layout: &mut Layout, selected_components: &mut Vec<usize>) -> bool {
+        match self.undo.pop() {
+            Some(mut entry) => {
+                entry.apply(layout, selected_components);
+                self.redo.push(entry);
+                self.can_merge = false;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Applies the most recently undone action again. Returns `false` if
+    /// there's nothing to redo.
+    pub fn redo(&mut self, layout: &mut Layout, selected_components: &mut Vec<usize>) -> bool {
+        match self.redo.pop() {
+            Some(mut entry) => {
+                entry.apply(layout, selected_components);
+                self.undo.push(entry);
+                self.can_merge = false;
+                true
+            }
+            None => false,
+        }
+    }
+}
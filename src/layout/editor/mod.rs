@@ -5,20 +5,29 @@
 //! Interface.
 
 use super::{Component, Layout, LayoutState};
-use crate::{settings::Value, timing::Snapshot};
+use crate::{platform::prelude::*, settings::Value, timing::Snapshot};
+use core::mem::{discriminant, replace};
 use core::result::Result as StdResult;
 
+mod history;
 mod state;
 
+#[cfg(test)]
+mod tests;
+
+pub use self::history::Action;
 pub use self::state::{Buttons as ButtonsState, State};
 
+use self::history::{is_merged, Change, History, MergeKey};
+
 /// The Layout Editor allows modifying Layouts while ensuring all the different
 /// invariants of the Layout objects are upheld no matter what kind of
 /// operations are being applied. It provides the current state of the editor as
 /// state objects that can be visualized by any kind of User Interface.
 pub struct Editor {
     layout: Layout,
-    selected_component: usize,
+    selected_components: Vec<usize>,
+    history: History,
 }
 
 /// Describes an Error that occurred while opening the Layout Editor.
@@ -44,7 +53,8 @@ impl Editor {
 
         Ok(Self {
             layout,
-            selected_component: 0,
+            selected_components: vec![0],
+            history: History::default(),
         })
     }
 
@@ -70,106 +80,308 @@ impl Editor {
         self.layout.update_state(state, timer)
     }
 
+    fn active_component_index(&self) -> usize {
+        *self.selected_components.last().unwrap()
+    }
+
+    /// Applies the change to the Layout and records the change reverting it,
+    /// so the action can be undone.
+    fn apply(&mut self, action: Action, selected_components: &[usize], mut change: Change) {
+        change.apply(&mut self.layout);
+        self.layout.remount();
+        self.history
+            .record(action, None, selected_components, || change);
+    }
+
+    /// Accesses the most recent action that can be undone. Returns `None` if
+    /// there is nothing to undo.
+    pub fn undo_action(&self) -> Option<Action> {
+        self.history.undo_action()
+    }
+
+    /// Accesses the most recently undone action that can be redone. Returns
+    /// `None` if there is nothing to redo.
+    pub fn redo_action(&self) -> Option<Action> {
+        self.history.redo_action()
+    }
+
+    /// Checks if there is an action that can be undone.
+    pub fn can_undo(&self) -> bool {
+        self.undo_action().is_some()
+    }
+
+    /// Checks if there is an action that can be redone.
+    pub fn can_redo(&self) -> bool {
+        self.redo_action().is_some()
+    }
+
+    /// Undoes the most recent action. The Layout and the component selection
+    /// are restored to the state before the action was applied. Returns
+    /// `false` if there is nothing to undo.
+    pub fn undo(&mut self) -> bool {
+        let undone = self
+            .history
+            .undo(&mut self.layout, &mut self.selected_components);
+        if undone {
+            self.layout.remount();
+        }
+        undone
+    }
+
+    /// Redoes the most recently undone action. Any new action that is applied
+    /// after undoing an action discards all the actions that could be redone.
+    /// Returns `false` if there is nothing to redo.
+    pub fn redo(&mut self) -> bool {
+        let redone = self
+            .history
+            .redo(&mut self.layout, &mut self.selected_components);
+        if redone {
+            self.layout.remount();
+        }
+        redone
+    }
+
     /// Selects the component with the given index in order to modify its
-    /// settings. Only a single component is selected at any given time. You may
-    /// not provide an invalid index.
+    /// settings. All other components are unselected. The component chosen
+    /// also becomes the active component. You may not provide an invalid index.
     pub fn select(&mut self, index: usize) {
         if index < self.layout.components.len() {
-            self.selected_component = index;
+            self.selected_components.clear();
+            self.selected_components.push(index);
+            self.history.stop_merging();
+        }
+    }
+
+    /// In addition to the components that are already selected, the component
+    /// with the given index is being selected. The component chosen also
+    /// becomes the active component, whose settings are shown. You may not
+    /// provide an invalid index.
+    pub fn select_additionally(&mut self, index: usize) {
+        if index < self.layout.components.len() {
+            self.selected_components.retain(|&i| i != index);
+            self.selected_components.push(index);
+            self.history.stop_merging();
+        }
+    }
+
+    /// Select all components from the currently active component to the
+    /// component at the index provided. The component at the index provided
+    /// becomes the new active component. You may not provide an invalid index.
+    pub fn select_range(&mut self, index: usize) {
+        if index < self.layout.components.len() {
+            let active = self.active_component_index();
+            let range = if index < active {
+                index + 1..active
+            } else {
+                active + 1..index
+            };
+            for i in range {
+                if !self.selected_components.contains(&i) {
+                    self.selected_components.push(i);
+                }
+            }
+            self.select_additionally(index);
+        }
+    }
+
+    /// Unselects the component with the given index. If it's not selected or
+    /// the index is out of bounds, nothing happens. The component is not
+    /// unselected, when it is the only component that is selected. If the
+    /// active component is unselected, the most recently selected component
+    /// remaining becomes the active component.
+    pub fn unselect(&mut self, index: usize) {
+        if self.selected_components.len() > 1 {
+            self.selected_components.retain(|&i| i != index);
+            self.history.stop_merging();
         }
     }
 
     /// Adds the component provided to the end of the layout. The newly added
-    /// component becomes the selected component.
+    /// component becomes the only selected component.
     pub fn add_component<C: Into<Component>>(&mut self, component: C) {
-        self.selected_component = self.layout.components.len();
+        let index = self.layout.components.len();
+        let selected_components = replace(&mut self.selected_components, vec![index]);
         self.layout.push(component);
+        self.history
+            .record(Action::AddComponent, None, &selected_components, || {
+                Change::Remove(vec![index])
+            });
     }
 
-    /// Checks if the currently selected component can be removed. If there's
-    /// only one component in the layout, it can't be removed.
+    /// Checks if the currently selected components can be removed. If all
+    /// components are selected, they can't be removed.
     pub fn can_remove_component(&self) -> bool {
         // We need to ensure there's always at least one component.
-        self.layout.components.len() > 1
+        self.layout.components.len() > self.selected_components.len()
     }
 
-    /// Removes the currently selected component, unless there's only one
-    /// component in the layout. The next component becomes the selected
-    /// component. If there's none, the previous component becomes the selected
-    /// component instead.
+    /// Removes all the selected components, unless all of them are selected.
+    /// The next not-to-be-removed component after the active component
+    /// becomes the only selected component. If there's none, the previous
+    /// not-to-be-removed component becomes the selected component instead.
     pub fn remove_component(&mut self) {
-        if self.can_remove_component() {
-            self.layout.components.remove(self.selected_component);
-            if self.selected_component >= self.layout.components.len() {
-                self.selected_component = self.layout.components.len() - 1;
-            }
-            self.layout.remount();
+        if !self.can_remove_component() {
+            return;
         }
+
+        let active = self.active_component_index();
+        let above_count = self
+            .selected_components
+            .iter()
+            .filter(|&&i| i < active)
+            .count();
+
+        let mut removed = self.selected_components.clone();
+        removed.sort_unstable();
+        let new_index =
+            (active - above_count).min(self.layout.components.len() - removed.len() - 1);
+        let selected_components = replace(&mut self.selected_components, vec![new_index]);
+        self.apply(
+            Action::RemoveComponents,
+            &selected_components,
+            Change::Remove(removed),
+        );
     }
 
-    /// Checks if the currently selected component can be moved up. If the first
-    /// component is selected, it can't be moved up.
-    pub const fn can_move_component_up(&self) -> bool {
-        self.selected_component > 0
+    /// Checks if the currently selected components can be moved up. If any one
+    /// of the selected components is the first component, they can't be moved
+    /// up.
+    pub fn can_move_component_up(&self) -> bool {
+        !self.selected_components.contains(&0)
     }
 
-    /// Moves the selected component up, unless the first component is selected.
+    /// Moves all the selected components up, unless the first component is
+    /// selected. The active component stays the active component.
     pub fn move_component_up(&mut self) {
         if self.can_move_component_up() {
-            self.layout
-                .components
-                .swap(self.selected_component, self.selected_component - 1);
-            self.selected_component -= 1;
-            self.layout.remount();
+            let selected_components = self.selected_components.clone();
+            let mut order = self.order();
+            self.move_selection_up(&mut order);
+            self.apply(
+                Action::MoveComponentsUp,
+                &selected_components,
+                Change::Reorder(order),
+            );
+        }
+    }
+
+    /// Returns the current order of the components. The moves are applied to
+    /// the order, before the components are reordered accordingly.
+    fn order(&self) -> Vec<usize> {
+        (0..self.layout.components.len()).collect()
+    }
+
+    fn move_selection_up(&mut self, order: &mut [usize]) {
+        for i in 1..order.len() {
+            if self.selected_components.contains(&i) {
+                order.swap(i, i - 1);
+            }
+        }
+        for index in &mut self.selected_components {
+            *index -= 1;
         }
     }
 
-    /// Checks if the currently selected component can be moved down. If the
-    /// last component is selected, it can't be moved down.
+    /// Checks if the currently selected components can be moved down. If any
+    /// one of the selected components is the last component, they can't be
+    /// moved down.
     pub fn can_move_component_down(&self) -> bool {
-        self.selected_component < self.layout.components.len() - 1
+        let last_index = self.layout.components.len() - 1;
+        !self.selected_components.contains(&last_index)
     }
 
-    /// Moves the selected component down, unless the last component is
-    /// selected.
+    /// Moves all the selected components down, unless the last component is
+    /// selected. The active component stays the active component.
     pub fn move_component_down(&mut self) {
         if self.can_move_component_down() {
-            self.layout
-                .components
-                .swap(self.selected_component, self.selected_component + 1);
-            self.selected_component += 1;
-            self.layout.remount();
+            let selected_components = self.selected_components.clone();
+            let mut order = self.order();
+            self.move_selection_down(&mut order);
+            self.apply(
+                Action::MoveComponentsDown,
+                &selected_components,
+                Change::Reorder(order),
+            );
         }
     }
 
-    /// Moves the selected component to the index provided. You may not provide
-    /// an invalid index.
-    pub fn move_component(&mut self, dst_index: usize) {
-        if dst_index < self.layout.components.len() {
-            while self.selected_component > dst_index {
-                self.move_component_up();
-            }
-            while self.selected_component < dst_index {
-                self.move_component_down();
+    fn move_selection_down(&mut self, order: &mut [usize]) {
+        for i in (0..order.len() - 1).rev() {
+            if self.selected_components.contains(&i) {
+                order.swap(i, i + 1);
             }
         }
+        for index in &mut self.selected_components {
+            *index += 1;
+        }
+    }
+
+    /// Moves the selected components, so that the active component ends up at
+    /// the index provided. All the other selected components are moved by the
+    /// same amount. If they can't be moved that far, they are moved as far as
+    /// possible. You may not provide an invalid index.
+    pub fn move_component(&mut self, dst_index: usize) {
+        let active = self.active_component_index();
+        if dst_index >= self.layout.components.len() || dst_index == active {
+            return;
+        }
+        if (dst_index < active && !self.can_move_component_up())
+            || (dst_index > active && !self.can_move_component_down())
+        {
+            return;
+        }
+
+        let selected_components = self.selected_components.clone();
+        let mut order = self.order();
+        while self.active_component_index() > dst_index && self.can_move_component_up() {
+            self.move_selection_up(&mut order);
+        }
+        while self.active_component_index() < dst_index && self.can_move_component_down() {
+            self.move_selection_down(&mut order);
+        }
+        self.apply(
+            Action::MoveComponents,
+            &selected_components,
+            Change::Reorder(order),
+        );
     }
 
-    /// Duplicates the currently selected component. The copy gets placed right
-    /// after the selected component and becomes the newly selected component.
+    /// Duplicates all the selected components. Each copy gets placed right
+    /// after the component it is a copy of. The copies become the newly
+    /// selected components.
     pub fn duplicate_component(&mut self) {
-        let index = self.selected_component;
-        let new_index = index + 1;
+        if self.selected_components.is_empty() {
+            return;
+        }
+        let selected_components = self.selected_components.clone();
+        let mut sorted = self.selected_components.clone();
+        sorted.sort_unstable();
+        let copies = sorted
+            .iter()
+            .enumerate()
+            .map(|(inserted, &index)| {
+                // Every copy placed before this component shifts it down by
+                // one. Its own copy is placed right after it.
+                (index + inserted + 1, self.layout.components[index].clone())
+            })
+            .collect();
 
-        let component = self.layout.components[index].clone();
-        self.layout.components.insert(new_index, component);
+        for index in &mut self.selected_components {
+            *index += sorted.iter().filter(|&&i| i < *index).count() + 1;
+        }
 
-        self.selected_component = new_index;
-        self.layout.remount();
+        self.apply(
+            Action::DuplicateComponents,
+            &selected_components,
+            Change::Insert(copies),
+        );
     }
 
-    /// Sets a setting's value of the selected component by its setting index
-    /// to the given value.
+    /// Sets a setting's value of the active component by its setting index
+    /// to the given value. The setting is also changed for all the other
+    /// selected components that have a setting with the same name and the
+    /// same type of value. This allows changing a shared setting, such as the
+    /// background, of multiple components at once.
     ///
     /// # Panics
     ///
@@ -177,7 +389,64 @@ impl Editor {
     /// the type of the setting's value. A panic can also occur if the index of
     /// the setting provided is out of bounds.
     pub fn set_component_settings_value(&mut self, index: usize, value: Value) {
-        self.layout.components[self.selected_component].set_value(index, value);
+        let active = self.active_component_index();
+        let merge_key = if is_merged(&value) {
+            Some(MergeKey::Component(active, index))
+        } else {
+            None
+        };
+
+        // The components that have the setting, along with its index.
+        let mut fields = Vec::new();
+        if self.selected_components.len() > 1 {
+            let description = self.layout.components[active].settings_description();
+            let text = &description.fields[index].text;
+
+            for &component_index in &self.selected_components {
+                if component_index == active {
+                    continue;
+                }
+                let field_index = self.layout.components[component_index]
+                    .settings_description()
+                    .fields
+                    .iter()
+                    .position(|f| {
+                        &f.text == text && discriminant(&f.value) == discriminant(&value)
+                    });
+                if let Some(field_index) = field_index {
+                    fields.push((component_index, field_index));
+                }
+            }
+        }
+        fields.push((active, index));
+
+        let components = &mut self.layout.components;
+        fields.retain(|&(component_index, field_index)| {
+            components[component_index].settings_description().fields[field_index].value != value
+        });
+        if fields.is_empty() {
+            return;
+        }
+
+        self.history.record(
+            Action::SetComponentSettingsValue,
+            merge_key,
+            &self.selected_components,
+            || {
+                Change::Replace(
+                    fields
+                        .iter()
+                        .map(|&(component_index, _)| {
+                            (component_index, components[component_index].clone())
+                        })
+                        .collect(),
+                )
+            },
+        );
+
+        for (component_index, field_index) in fields {
+            components[component_index].set_value(field_index, value.clone());
+        }
     }
 
     /// Sets a setting's value of the general settings by its setting index to
@@ -189,6 +458,28 @@ impl Editor {
     /// the type of the setting's value. A panic can also occur if the index of
     /// the setting provided is out of bounds.
     pub fn set_general_settings_value(&mut self, index: usize, value: Value) {
+        let merge_key = if is_merged(&value) {
+            Some(MergeKey::General(index))
+        } else {
+            None
+        };
+        let current = self
+            .layout
+            .general_settings()
+            .settings_description()
+            .fields
+            .remove(index)
+            .value;
+        if current == value {
+            return;
+        }
+
+        self.history.record(
+            Action::SetGeneralSettingsValue,
+            merge_key,
+            &self.selected_components,
+            || Change::GeneralSetting(index, current),
+        );
         self.layout.general_settings_mut().set_value(index, value);
     }
 }
@@ -11,9 +11,13 @@ pub struct State {
     pub components: Vec<String>,
     /// Describes which actions are currently available.
     pub buttons: Buttons,
-    /// The index of the currently selected component.
+    /// The index of the active component. This is the most recently selected
+    /// component, whose settings are shown.
     pub selected_component: u32,
-    /// A generic description of the settings available for the selected
+    /// The indices of all the selected components, including the active
+    /// component.
+    pub selected_components: Vec<u32>,
+    /// A generic description of the settings available for the active
     /// component and their current values.
     pub component_settings: SettingsDescription,
     /// A generic description of the general settings available for the layout
@@ -22,19 +26,27 @@ pub struct State {
 }
 
 /// Describes which actions are currently available. Depending on how many
-/// components exist and which one is selected, only some actions can be
+/// components exist and which ones are selected, only some actions can be
 /// executed successfully.
 #[derive(Serialize, Deserialize)]
 pub struct Buttons {
-    /// Describes whether the currently selected component can be removed. If
-    /// there's only one component in the layout, it can't be removed.
+    /// Describes whether the currently selected components can be removed. If
+    /// all the components are selected, they can't be removed.
     pub can_remove: bool,
-    /// Describes whether the currently selected component can be moved up. If
-    /// the first component is selected, it can't be moved.
+    /// Describes whether the currently selected components can be moved up. If
+    /// the first component is selected, they can't be moved.
     pub can_move_up: bool,
-    /// Describes whether the currently selected component can be moved down. If
-    /// the last component is selected, it can't be moved.
+    /// Describes whether the currently selected components can be moved down.
+    /// If the last component is selected, they can't be moved.
     pub can_move_down: bool,
+    /// Describes whether there is an action that can be undone.
+    pub can_undo: bool,
+    /// Describes whether there is an action that can be redone.
+    pub can_redo: bool,
+    /// The label of the action that would be undone, if there is one.
+    pub undo_action: Option<String>,
+    /// The label of the action that would be redone, if there is one.
+    pub redo_action: Option<String>,
 }
 
 #[cfg(feature = "std")]
@@ -62,14 +74,20 @@ impl Editor {
             can_remove: self.can_remove_component(),
             can_move_up: self.can_move_component_up(),
             can_move_down: self.can_move_component_down(),
+            can_undo: self.can_undo(),
+            can_redo: self.can_redo(),
+            undo_action: self.undo_action().map(|a| a.label().to_string()),
+            redo_action: self.redo_action().map(|a| a.label().to_string()),
         };
 
+        let active_component = self.active_component_index();
+
         State {
             components,
             buttons,
-            selected_component: self.selected_component as u32,
-            component_settings: self.layout.components[self.selected_component]
-                .settings_description(),
+            selected_component: active_component as u32,
+            selected_components: self.selected_components.iter().map(|&i| i as u32).collect(),
+            component_settings: self.layout.components[active_component].settings_description(),
             general_settings: self.layout.general_settings().settings_description(),
         }
     }
@@ -0,0 +1,243 @@
+use super::{Action, Editor};
+use crate::component::{blank_space, separator, text, title};
+use crate::layout::{Component, Layout};
+use crate::settings::{Color, Gradient, Value};
+
+fn editor() -> Editor {
+    let mut layout = Layout::new();
+    layout.push(title::Component::new());
+    layout.push(blank_space::Component::new());
+    layout.push(separator::Component::new());
+    layout.push(text::Component::new());
+    Editor::new(layout).unwrap()
+}
+
+fn names(editor: &Editor) -> Vec<String> {
+    editor.state().components
+}
+
+fn background(component: &Component) -> Option<Value> {
+    component
+        .settings_description()
+        .fields
+        .into_iter()
+        .find(|f| f.text == "Background")
+        .map(|f| f.value)
+}
+
+#[test]
+fn remove_multiple_components() {
+    let mut editor = editor();
+    editor.select(1);
+    editor.select_additionally(2);
+    editor.remove_component();
+
+    let state = editor.state();
+    assert_eq!(state.components, ["Title", "Text"]);
+    assert_eq!(state.selected_component, 1);
+    assert_eq!(state.selected_components, [1]);
+}
+
+#[test]
+fn can_not_remove_all_components() {
+    let mut editor = editor();
+    editor.select_range(3);
+    assert!(!editor.can_remove_component());
+    editor.remove_component();
+    assert_eq!(names(&editor).len(), 4);
+    assert!(!editor.can_undo());
+}
+
+#[test]
+fn move_multiple_components() {
+    let mut editor = editor();
+    editor.select(1);
+    editor.select_additionally(3);
+    assert!(!editor.can_move_component_down());
+
+    editor.move_component_up();
+    assert_eq!(
+        names(&editor),
+        ["Blank Space", "Title", "Text", "Separator"]
+    );
+    assert_eq!(editor.state().selected_components, [0, 2]);
+    assert!(!editor.can_move_component_up());
+
+    editor.move_component(3);
+    assert_eq!(
+        names(&editor),
+        ["Title", "Blank Space", "Separator", "Text"]
+    );
+    assert_eq!(editor.state().selected_component, 3);
+}
+
+#[test]
+fn duplicate_multiple_components() {
+    let mut editor = editor();
+    editor.select(2);
+    editor.select_additionally(0);
+    editor.duplicate_component();
+
+    let state = editor.state();
+    assert_eq!(
+        state.components,
+        [
+            "Title",
+            "Title",
+            "Blank Space",
+            "Separator",
+            "Separator",
+            "Text"
+        ],
+    );
+    assert_eq!(state.selected_components, [4, 1]);
+}
+
+#[test]
+fn bulk_edit_shared_setting() {
+    let mut editor = editor();
+    editor.select(1);
+    editor.select_additionally(2);
+    editor.select_additionally(0);
+
+    let index = editor
+        .state()
+        .component_settings
+        .fields
+        .iter()
+        .position(|f| f.text == "Background")
+        .unwrap();
+    let value: Value = Gradient::Plain(Color::rgba(1.0, 0.0, 0.0, 1.0)).into();
+    editor.set_component_settings_value(index, value.clone());
+
+    let layout = editor.close();
+    assert!(background(&layout.components[0]) == Some(value.clone()));
+    assert!(background(&layout.components[1]) == Some(value.clone()));
+    // The separator doesn't have a background and isn't affected.
+    assert!(background(&layout.components[2]).is_none());
+    assert!(background(&layout.components[3]) != Some(value));
+}
+
+#[test]
+fn undo_and_redo() {
+    let mut editor = editor();
+    assert!(!editor.undo());
+
+    editor.select(3);
+    editor.remove_component();
+    editor.add_component(separator::Component::new());
+    assert_eq!(editor.undo_action(), Some(Action::AddComponent));
+
+    assert!(editor.undo());
+    assert_eq!(names(&editor), ["Title", "Blank Space", "Separator"]);
+    assert!(editor.undo());
+    assert_eq!(
+        names(&editor),
+        ["Title", "Blank Space", "Separator", "Text"]
+    );
+    assert_eq!(editor.state().selected_components, [3]);
+    assert!(!editor.can_undo());
+
+    assert!(editor.redo());
+    assert_eq!(editor.redo_action(), Some(Action::AddComponent));
+    editor.move_component_up();
+    assert!(!editor.can_redo());
+}
+
+#[test]
+fn undo_and_redo_structural_changes() {
+    let mut editor = editor();
+    editor.select(1);
+    editor.select_additionally(3);
+    editor.duplicate_component();
+    editor.move_component(0);
+    editor.remove_component();
+    let after = names(&editor);
+
+    for _ in 0..3 {
+        assert!(editor.undo());
+    }
+    assert_eq!(
+        names(&editor),
+        ["Title", "Blank Space", "Separator", "Text"]
+    );
+    assert_eq!(editor.state().selected_components, [1, 3]);
+
+    for _ in 0..3 {
+        assert!(editor.redo());
+    }
+    assert_eq!(names(&editor), after);
+}
+
+#[test]
+fn unchanged_settings_are_not_recorded() {
+    let mut editor = editor();
+    let value = editor.state().component_settings.fields[0].value.clone();
+    editor.set_component_settings_value(0, value);
+    let value = editor.state().general_settings.fields[4].value.clone();
+    editor.set_general_settings_value(4, value);
+    assert!(!editor.can_undo());
+}
+
+#[test]
+fn undo_and_redo_bulk_edit() {
+    let mut editor = editor();
+    editor.select(1);
+    editor.select_additionally(0);
+    let value: Value = Gradient::Plain(Color::rgba(1.0, 0.0, 0.0, 1.0)).into();
+    editor.set_component_settings_value(0, value.clone());
+
+    assert!(editor.undo());
+    let layout = editor.close();
+    assert!(background(&layout.components[0]) != Some(value.clone()));
+    assert!(background(&layout.components[1]) != Some(value));
+}
+
+#[test]
+fn text_setting_changes_are_merged() {
+    let mut editor = editor();
+    editor.select(3);
+    editor.set_component_settings_value(3, Value::String("A".into()));
+    editor.set_component_settings_value(3, Value::String("AB".into()));
+    editor.set_general_settings_value(4, Gradient::Transparent.into());
+
+    let text = |editor: &Editor| editor.state().component_settings.fields[3].value.clone();
+    assert!(editor.undo());
+    assert!(text(&editor) == Value::String("AB".into()));
+    assert!(editor.undo());
+    assert!(text(&editor) == Value::String("".into()));
+    assert!(!editor.can_undo());
+}
+
+#[test]
+fn text_setting_changes_of_different_components_are_not_merged() {
+    let mut layout = Layout::new();
+    layout.push(text::Component::new());
+    layout.push(text::Component::new());
+    let mut editor = Editor::new(layout).unwrap();
+
+    editor.set_component_settings_value(3, Value::String("A".into()));
+    editor.select(1);
+    editor.set_component_settings_value(3, Value::String("B".into()));
+
+    let text = |editor: &Editor| editor.state().component_settings.fields[3].value.clone();
+    assert!(editor.undo());
+    assert!(text(&editor) == Value::String("".into()));
+    assert!(editor.undo());
+    assert!(!editor.can_undo());
+}
+
+#[test]
+fn state_contains_undo_labels() {
+    let mut editor = editor();
+    editor.duplicate_component();
+    let buttons = editor.state().buttons;
+    assert!(buttons.can_undo);
+    assert!(!buttons.can_redo);
+    assert_eq!(buttons.undo_action.as_deref(), Some("Duplicate Components"));
+
+    editor.undo();
+    let buttons = editor.state().buttons;
+    assert_eq!(buttons.undo_action, None);
+    assert_eq!(buttons.redo_action.as_deref(), Some("Duplicate Components"));
+}
@@ -1,9 +1,28 @@
 use super::{ComponentSettings, GeneralSettings};
 use crate::platform::prelude::*;
 use serde::{Deserialize, Serialize};
+#[cfg(feature = "std")]
+use serde_json::{Map, Value};
 
 /// Describes a whole layout by its settings in a way that can easily be
 /// serialized and deserialized.
+///
+/// # JSON Format
+///
+/// The JSON representation is an object with the following fields:
+///
+/// - `version`: The version of the format, which is
+///   [`LayoutSettings::VERSION`] for newly written layouts. Layouts without a
+///   version were written before the format was versioned.
+/// - `components`: The settings of all the components in the order they are
+///   shown in.
+/// - `general`: The general settings of the layout.
+///
+/// Whenever the format changes in a way that older layouts can't be decoded
+/// anymore, the version is increased and a migration is added that converts
+/// the older layouts into the new format. Decoding a layout automatically
+/// applies all the migrations that are necessary. Layouts of a newer version
+/// than the one supported are rejected.
 #[derive(Clone, Serialize, Deserialize)]
 pub struct LayoutSettings {
     /// The settings for all the components.
@@ -12,21 +31,72 @@ pub struct LayoutSettings {
     pub general: GeneralSettings,
 }
 
+impl LayoutSettings {
+    /// The version of the JSON format that is written by `write_json`. Layouts
+    /// written before the format was versioned are treated as version 0.
+    pub const VERSION: u32 = 1;
+}
+
+/// The migrations that convert the JSON of one version into the JSON of the
+/// next version. The migration at index `i` converts version `i` into version
+/// `i + 1`.
+#[cfg(feature = "std")]
+const MIGRATIONS: [fn(&mut Map<String, Value>); LayoutSettings::VERSION as usize] = [
+    // The unversioned format only lacks the version.
+    |_| {},
+];
+
+#[cfg(feature = "std")]
+#[derive(Serialize)]
+struct Versioned<'a> {
+    version: u32,
+    #[serde(flatten)]
+    settings: &'a LayoutSettings,
+}
+
 #[cfg(feature = "std")]
 impl LayoutSettings {
-    /// Decodes the layout's settings from JSON.
+    /// Decodes the layout's settings from JSON. Layouts of older versions of
+    /// the format are migrated to the current version.
     pub fn from_json<R>(reader: R) -> serde_json::Result<LayoutSettings>
     where
         R: std::io::Read,
     {
-        serde_json::from_reader(reader)
+        use serde::de::Error;
+
+        let mut map = match serde_json::from_reader(reader)? {
+            Value::Object(map) => map,
+            _ => return Err(Error::custom("expected the layout to be an object")),
+        };
+
+        let version = match map.remove("version") {
+            None => 0,
+            Some(version) => version
+                .as_u64()
+                .filter(|&v| v <= u64::from(Self::VERSION))
+                .ok_or_else(|| Error::custom(format!("unsupported layout version {}", version)))?
+                as usize,
+        };
+
+        for migration in &MIGRATIONS[version..] {
+            migration(&mut map);
+        }
+
+        serde_json::from_value(Value::Object(map))
     }
 
-    /// Encodes the layout's settings as JSON.
+    /// Encodes the layout's settings as JSON in the current version of the
+    /// format.
     pub fn write_json<W>(&self, writer: W) -> serde_json::Result<()>
     where
         W: std::io::Write,
     {
-        serde_json::to_writer(writer, self)
+        serde_json::to_writer(
+            writer,
+            &Versioned {
+                version: Self::VERSION,
+                settings: self,
+            },
+        )
     }
 }
@@ -12,6 +12,8 @@ mod layout_settings;
 mod layout_state;
 #[cfg(feature = "std")]
 pub mod parser;
+#[cfg(feature = "std")]
+pub mod saver;
 
 pub use self::{
     component::Component, component_settings::ComponentSettings, component_state::ComponentState,
@@ -38,7 +38,7 @@ mod total_playtime;
 // 1.0 units high in component space.
 // 24 pixels high in LiveSplit One's pixel coordinate space.
 // ~30.5 pixels high in the original LiveSplit's pixel coordinate space.
-const PIXEL_SPACE_RATIO: f32 = 24.0 / 30.5;
+pub(super) const PIXEL_SPACE_RATIO: f32 = 24.0 / 30.5;
 
 fn translate_size(v: u32) -> u32 {
     (v as f32 * PIXEL_SPACE_RATIO).round() as u32
@@ -83,6 +83,8 @@ pub enum Error {
     ParseAlignment,
     /// Failed to parse a column type.
     ParseColumnType,
+    /// Failed to parse a style rule of the Splits Component.
+    ParseStyleRule,
     /// Failed to parse a font.
     ParseFont,
     /// Parsed an empty layout, which is considered an invalid layout.
@@ -254,6 +256,24 @@ impl<T: GradientType> GradientBuilder<T> {
     }
 }
 
+/// Adjusts the alpha of a color from the original LiveSplit, so it matches the
+/// appearance of the color in the original LiveSplit more closely.
+pub(super) fn adjusted_alpha(color: Color) -> f32 {
+    let [r, g, b, a] = color.to_array();
+
+    // Adjust alpha based on the lightness of the color. The formula is
+    // based on two sRGB curves measured for white on top of a black
+    // background and for black on top of a white background. We interpolate
+    // between the two curves based on the lightness of the color. The
+    // problem is that we only have the foreground color, so based on the
+    // actual background color, this may be wrong. Therefore this is only a
+    // heuristic. We often have white on dark grey, instead of white on
+    // black. Because of that, we use 1.75 as the exponent denominator for
+    // the white on black case instead of the usual 2.2 for sRGB.
+    let lightness = (r + g + b) / 3.0;
+    (1.0 - lightness) * (1.0 - (1.0 - a).powf(1.0 / 2.2)) + lightness * a.powf(1.0 / 1.75)
+}
+
 fn color<R, F>(reader: &mut Reader<R>, buf: &mut Vec<u8>, func: F) -> Result<()>
 where
     R: BufRead,
@@ -263,20 +283,7 @@ where
         let number = u32::from_str_radix(&text, 16)?;
         let [a, r, g, b] = number.to_be_bytes();
         let mut color = Color::rgba8(r, g, b, a);
-        let [r, g, b, a] = color.to_array();
-
-        // Adjust alpha based on the lightness of the color. The formula is
-        // based on two sRGB curves measured for white on top of a black
-        // background and for black on top of a white background. We interpolate
-        // between the two curves based on the lightness of the color. The
-        // problem is that we only have the foreground color, so based on the
-        // actual background color, this may be wrong. Therefore this is only a
-        // heuristic. We often have white on dark grey, instead of white on
-        // black. Because of that, we use 1.75 as the exponent denominator for
-        // the white on black case instead of the usual 2.2 for sRGB.
-        let lightness = (r + g + b) / 3.0;
-        color.alpha =
-            (1.0 - lightness) * (1.0 - (1.0 - a).powf(1.0 / 2.2)) + lightness * a.powf(1.0 / 1.75);
+        color.alpha = adjusted_alpha(color);
 
         func(color);
         Ok(())
@@ -596,9 +603,12 @@ where
                     Component::PreviousSegment(c) => {
                         previous_segment::settings(reader, tag.into_buf(), c)
                     }
+                    Component::RunHistory(_) => end_tag(reader, tag.into_buf()),
+                    Component::SegmentDistribution(_) => end_tag(reader, tag.into_buf()),
                     Component::SegmentTime(_) => end_tag(reader, tag.into_buf()),
                     Component::Separator(_) => end_tag(reader, tag.into_buf()),
                     Component::Splits(c) => splits::settings(reader, tag.into_buf(), c),
+                    Component::Statistic(_) => end_tag(reader, tag.into_buf()),
                     Component::SumOfBest(c) => sum_of_best::settings(reader, tag.into_buf(), c),
                     Component::Text(c) => text::settings(reader, tag.into_buf(), c),
                     Component::Timer(c) => timer::settings(reader, tag.into_buf(), c),
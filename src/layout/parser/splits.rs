@@ -1,5 +1,5 @@
 use super::{
-    comparison_override, end_tag, parse_bool, parse_children, text, text_err, text_parsed,
+    color, comparison_override, end_tag, parse_bool, parse_children, text, text_err, text_parsed,
     timing_method_override, Error, GradientBuilder, GradientKind, ListGradientKind, Result,
 };
 use quick_xml::Reader;
@@ -113,6 +113,53 @@ where
                                     column.update_trigger = update_trigger;
                                     Ok(())
                                 })
+                            } else if tag.name() == b"StartWith" {
+                                // These are only written by us and follow the
+                                // Type, so they override what the Type
+                                // specified.
+                                text_err(reader, tag.into_buf(), |v| {
+                                    use self::splits::ColumnStartWith::*;
+                                    column.start_with = match &*v {
+                                        "Empty" => Empty,
+                                        "ComparisonTime" => ComparisonTime,
+                                        "ComparisonSegmentTime" => ComparisonSegmentTime,
+                                        "PossibleTimeSave" => PossibleTimeSave,
+                                        "BestPossibleTimeSave" => BestPossibleTimeSave,
+                                        "AverageSegmentTime" => AverageSegmentTime,
+                                        "MedianSegmentTime" => MedianSegmentTime,
+                                        "ResetPercentage" => ResetPercentage,
+                                        "GoldCount" => GoldCount,
+                                        _ => return Err(Error::ParseColumnType),
+                                    };
+                                    Ok(())
+                                })
+                            } else if tag.name() == b"UpdateWith" {
+                                text_err(reader, tag.into_buf(), |v| {
+                                    use self::splits::ColumnUpdateWith::*;
+                                    column.update_with = match &*v {
+                                        "DontUpdate" => DontUpdate,
+                                        "SplitTime" => SplitTime,
+                                        "Delta" => Delta,
+                                        "DeltaWithFallback" => DeltaWithFallback,
+                                        "SegmentTime" => SegmentTime,
+                                        "SegmentDelta" => SegmentDelta,
+                                        "SegmentDeltaWithFallback" => SegmentDeltaWithFallback,
+                                        "PossibleTimeSave" => PossibleTimeSave,
+                                        _ => return Err(Error::ParseColumnType),
+                                    };
+                                    Ok(())
+                                })
+                            } else if tag.name() == b"UpdateTrigger" {
+                                text_err(reader, tag.into_buf(), |v| {
+                                    use self::splits::ColumnUpdateTrigger::*;
+                                    column.update_trigger = match &*v {
+                                        "OnStartingSegment" => OnStartingSegment,
+                                        "Contextual" => Contextual,
+                                        "OnEndingSegment" => OnEndingSegment,
+                                        _ => return Err(Error::ParseColumnType),
+                                    };
+                                    Ok(())
+                                })
                             } else {
                                 end_tag(reader, tag.into_buf())
                             }
@@ -120,6 +167,64 @@ where
                         settings.columns.insert(0, column);
                         Ok(())
                     })
+                } else if tag.name() == b"StyleRules" {
+                    settings.style_rules.clear();
+
+                    parse_children(reader, tag.into_buf(), |reader, tag| {
+                        let (mut condition, mut style) = (None, splits::RowStyle::default());
+                        let (mut override_text_color, mut override_background) = (false, false);
+                        let mut background_builder = GradientBuilder::new();
+
+                        parse_children(reader, tag.into_buf(), |reader, tag| {
+                            if let Some(tag) = background_builder.parse_background(reader, tag)? {
+                                if tag.name() == b"DeltaAbove" {
+                                    text_err(reader, tag.into_buf(), |v| {
+                                        let threshold =
+                                            v.parse().map_err(|_| Error::ParseStyleRule)?;
+                                        condition =
+                                            Some(splits::StyleCondition::DeltaAbove(threshold));
+                                        Ok(())
+                                    })
+                                } else if tag.name() == b"BestSegment" {
+                                    condition = Some(splits::StyleCondition::BestSegment);
+                                    end_tag(reader, tag.into_buf())
+                                } else if tag.name() == b"NameMatches" {
+                                    text_err(reader, tag.into_buf(), |v| {
+                                        let pattern = splits::NamePattern::new(&v)
+                                            .map_err(|_| Error::ParseStyleRule)?;
+                                        condition =
+                                            Some(splits::StyleCondition::NameMatches(pattern));
+                                        Ok(())
+                                    })
+                                } else if tag.name() == b"TextColor" {
+                                    color(reader, tag.into_buf(), |c| style.text_color = Some(c))
+                                } else if tag.name() == b"OverrideTextColor" {
+                                    parse_bool(reader, tag.into_buf(), |b| override_text_color = b)
+                                } else if tag.name() == b"OverrideBackground" {
+                                    parse_bool(reader, tag.into_buf(), |b| override_background = b)
+                                } else if tag.name() == b"Hide" {
+                                    parse_bool(reader, tag.into_buf(), |b| style.is_hidden = b)
+                                } else {
+                                    end_tag(reader, tag.into_buf())
+                                }
+                            } else {
+                                Ok(())
+                            }
+                        })?;
+
+                        if !override_text_color {
+                            style.text_color = None;
+                        }
+                        if override_background {
+                            style.background = Some(background_builder.build());
+                        }
+
+                        settings.style_rules.push(splits::StyleRule {
+                            condition: condition.ok_or(Error::ParseStyleRule)?,
+                            style,
+                        });
+                        Ok(())
+                    })
                 } else if tag.name() == b"Comparison" {
                     // Version < 1.5
                     comparison_override(reader, tag.into_buf(), |v| {
@@ -0,0 +1,1199 @@
+//! The LiveSplit Saver saves Layouts as LiveSplit layout files (*.lsl). The
+//! original LiveSplit doesn't support everything livesplit-core supports, so
+//! some settings are approximated as closely as possible. Components that
+//! don't exist in the original LiveSplit, like the Segment Time component, are
+//! skipped entirely.
+//!
+//! # Examples
+//!
+//! Using the LiveSplit Saver to save a Layout as a LiveSplit layout file.
+//!
+//! ```no_run
+//! use livesplit_core::layout::saver::livesplit;
+//! use livesplit_core::Layout;
+//! use std::fs::File;
+//! use std::io::BufWriter;
+//!
+//! // Create a layout object that we can use.
+//! let layout = Layout::default_layout();
+//!
+//! // Create the layout file.
+//! let file = File::create("path/to/layout_file.lsl");
+//! let writer = BufWriter::new(file.expect("Failed creating the file"));
+//!
+//! // Save the layout as a LiveSplit layout file.
+//! livesplit::save_layout(&layout, writer).expect("Couldn't save the layout file");
+//! ```
+
+use super::super::{
+    parser::{adjusted_alpha, PIXEL_SPACE_RATIO},
+    Component, GeneralSettings, Layout, LayoutDirection,
+};
+use crate::{
+    component::{
+        blank_space, current_comparison, current_pace, delta, detailed_timer, graph,
+        possible_time_save, previous_segment, splits, sum_of_best, text, timer, title,
+        total_playtime,
+    },
+    settings::{
+        Alignment, Color, Font, FontStretch, FontStyle, FontWeight, Gradient, ListGradient,
+    },
+    timing::{
+        formatter::{Accuracy, DigitsFormat},
+        TimingMethod,
+    },
+    xml_util::writer::{self as xml, new_tag, scoped, vec_as_string, write_end, write_start},
+};
+use byteorder::{WriteBytesExt, LE};
+use core::fmt::Display;
+use core::result::Result as StdResult;
+use quick_xml::events::{BytesDecl, BytesStart, BytesText, Event};
+use quick_xml::{Error as XmlError, Result as XmlResult, Writer};
+use std::io::Write;
+
+static LSL_FONT_HEADER: &[u8; 229] = include_bytes!("lsl_font_header.bin");
+
+// The remaining records of a serialized System.Drawing.Font, which follow the
+// name and size of the font. They describe the FontStyle and GraphicsUnit
+// enums, whose values follow right after each of them.
+static FONT_STYLE_RECORD: &[u8] =
+    b"\x05\xfc\xff\xff\xff\x18System.Drawing.FontStyle\x01\x00\x00\x00\x07value__\x00\x08\x02\x00\x00\x00";
+static GRAPHICS_UNIT_RECORD: &[u8] =
+    b"\x05\xfb\xff\xff\xff\x1bSystem.Drawing.GraphicsUnit\x01\x00\x00\x00\x07value__\x00\x08\x02\x00\x00\x00";
+const MESSAGE_END: u8 = 0x0B;
+
+const GRAPHICS_UNIT_PIXEL: u32 = 2;
+const GRAPHICS_UNIT_POINT: u32 = 3;
+
+#[derive(Debug, snafu::Snafu)]
+/// The Error type for layout files that couldn't be saved by the LiveSplit
+/// Saver.
+pub enum Error {
+    /// Failed writing the XML.
+    #[snafu(display("{}", error))]
+    Xml {
+        /// The underlying error.
+        error: XmlError,
+    },
+}
+
+impl From<XmlError> for Error {
+    fn from(error: XmlError) -> Self {
+        Error::Xml { error }
+    }
+}
+
+/// The Result type for the LiveSplit Saver.
+pub type Result<T> = StdResult<T, Error>;
+
+/// The font the original LiveSplit uses for a font slot if no custom font is
+/// chosen.
+struct DefaultFont {
+    family: &'static str,
+    size: f32,
+    unit: u32,
+    bold: bool,
+}
+
+const TIMER_FONT: DefaultFont = DefaultFont {
+    family: "Calibri",
+    size: 43.75,
+    unit: GRAPHICS_UNIT_PIXEL,
+    bold: true,
+};
+
+const TIMES_FONT: DefaultFont = DefaultFont {
+    family: "Segoe UI",
+    size: 12.0,
+    unit: GRAPHICS_UNIT_POINT,
+    bold: true,
+};
+
+const TEXT_FONT: DefaultFont = DefaultFont {
+    family: "Segoe UI",
+    size: 12.0,
+    unit: GRAPHICS_UNIT_POINT,
+    bold: false,
+};
+
+fn text<W: Write, T: AsRef<[u8]>>(writer: &mut Writer<W>, tag: &[u8], text: T) -> XmlResult<()> {
+    xml::text(writer, new_tag(tag), text)
+}
+
+fn write_display<W: Write, D: Display>(
+    writer: &mut Writer<W>,
+    tag: &[u8],
+    value: D,
+    buf: &mut Vec<u8>,
+) -> XmlResult<()> {
+    xml::write_display(writer, new_tag(tag), value, buf)
+}
+
+fn bool<W: Write>(writer: &mut Writer<W>, tag: &[u8], value: bool) -> XmlResult<()> {
+    text(writer, tag, if value { "True" } else { "False" })
+}
+
+fn untranslate_size(v: u32) -> u32 {
+    (v as f32 / PIXEL_SPACE_RATIO).round() as u32
+}
+
+fn color<W: Write>(
+    writer: &mut Writer<W>,
+    tag: &[u8],
+    color: Color,
+    buf: &mut Vec<u8>,
+) -> XmlResult<()> {
+    let [r, g, b, _] = color.to_rgba8();
+
+    // The parser adjusts the alpha based on the lightness of the color, so we
+    // look for the alpha that results in the closest match when parsing it
+    // again.
+    let a = (0..=255)
+        .map(|a| {
+            let adjusted = adjusted_alpha(Color::rgba8(r, g, b, a));
+            ((adjusted - color.alpha).abs(), a)
+        })
+        .min_by(|(x, _), (y, _)| x.partial_cmp(y).unwrap_or(core::cmp::Ordering::Equal))
+        .map_or(0, |(_, a)| a);
+
+    write_display(
+        writer,
+        tag,
+        format_args!("{:02X}{:02X}{:02X}{:02X}", a, r, g, b),
+        buf,
+    )
+}
+
+fn color_override<W: Write>(
+    writer: &mut Writer<W>,
+    color_tag: &[u8],
+    override_tag: &[u8],
+    value: Option<Color>,
+    buf: &mut Vec<u8>,
+) -> XmlResult<()> {
+    color(writer, color_tag, value.unwrap_or_else(Color::white), buf)?;
+    bool(writer, override_tag, value.is_some())
+}
+
+fn font_name(font: &Font) -> String {
+    let mut name = font.family.trim().to_owned();
+
+    let weight = match font.weight {
+        FontWeight::Thin => Some("Thin"),
+        FontWeight::ExtraLight => Some("ExtraLight"),
+        FontWeight::Light => Some("Light"),
+        FontWeight::SemiLight => Some("SemiLight"),
+        FontWeight::Medium => Some("Medium"),
+        FontWeight::SemiBold => Some("SemiBold"),
+        FontWeight::ExtraBold => Some("ExtraBold"),
+        FontWeight::Black => Some("Black"),
+        FontWeight::ExtraBlack => Some("ExtraBlack"),
+        // Bold is stored in the style flags instead.
+        FontWeight::Normal | FontWeight::Bold => None,
+    };
+
+    let stretch = match font.stretch {
+        FontStretch::UltraCondensed => Some("UltraCondensed"),
+        FontStretch::ExtraCondensed => Some("ExtraCondensed"),
+        FontStretch::Condensed => Some("Condensed"),
+        FontStretch::SemiCondensed => Some("SemiCondensed"),
+        FontStretch::SemiExpanded => Some("SemiExpanded"),
+        FontStretch::Expanded => Some("Expanded"),
+        FontStretch::ExtraExpanded => Some("ExtraExpanded"),
+        FontStretch::UltraExpanded => Some("UltraExpanded"),
+        FontStretch::Normal => None,
+    };
+
+    for token in weight.into_iter().chain(stretch) {
+        name.push(' ');
+        name.push_str(token);
+    }
+
+    name
+}
+
+fn font<W: Write>(
+    writer: &mut Writer<W>,
+    tag: &[u8],
+    font: Option<&Font>,
+    default: &DefaultFont,
+    buf: &mut Vec<u8>,
+    font_buf: &mut Vec<u8>,
+) -> XmlResult<()> {
+    // This writes a serialized System.Drawing.Font in the .NET Remoting Binary
+    // Format. The parser documents the format in more detail.
+    let (name, bold, italic) = match font {
+        Some(font) => (
+            font_name(font),
+            font.weight == FontWeight::Bold,
+            font.style == FontStyle::Italic,
+        ),
+        None => (default.family.to_owned(), default.bold, false),
+    };
+
+    font_buf.clear();
+    font_buf.extend_from_slice(LSL_FONT_HEADER);
+
+    // Strings are encoded as varint for the length + the UTF-8 string data.
+    let mut len = name.len();
+    loop {
+        let byte = (len & 0b0111_1111) as u8;
+        len >>= 7;
+        if len == 0 {
+            font_buf.push(byte);
+            break;
+        }
+        font_buf.push(byte | 0b1000_0000);
+    }
+    font_buf.extend_from_slice(name.as_bytes());
+
+    font_buf.write_f32::<LE>(default.size).unwrap();
+    font_buf.extend_from_slice(FONT_STYLE_RECORD);
+    font_buf
+        .write_u32::<LE>(bold as u32 | (italic as u32) << 1)
+        .unwrap();
+    font_buf.extend_from_slice(GRAPHICS_UNIT_RECORD);
+    font_buf.write_u32::<LE>(default.unit).unwrap();
+    font_buf.push(MESSAGE_END);
+
+    buf.clear();
+    vec_as_string(buf, |s| {
+        base64::encode_config_buf(&*font_buf, base64::STANDARD, s)
+    });
+
+    writer.write_event(Event::Start(new_tag(tag)))?;
+    writer.write_event(Event::CData(BytesText::from_plain(buf)))?;
+    write_end(writer, tag)
+}
+
+fn gradient<W: Write>(
+    writer: &mut Writer<W>,
+    gradient: Gradient,
+    buf: &mut Vec<u8>,
+) -> XmlResult<()> {
+    gradient_with_tags(
+        writer,
+        gradient,
+        b"BackgroundColor",
+        b"BackgroundColor2",
+        b"BackgroundGradient",
+        buf,
+    )
+}
+
+fn gradient_with_tags<W: Write>(
+    writer: &mut Writer<W>,
+    gradient: Gradient,
+    tag_color1: &[u8],
+    tag_color2: &[u8],
+    tag_kind: &[u8],
+    buf: &mut Vec<u8>,
+) -> XmlResult<()> {
+    let (first, second, kind) = match gradient {
+        Gradient::Transparent => (Color::transparent(), Color::transparent(), "Plain"),
+        Gradient::Plain(c) => (c, c, "Plain"),
+        Gradient::Vertical(first, second) => (first, second, "Vertical"),
+        Gradient::Horizontal(first, second) => (first, second, "Horizontal"),
+    };
+    color(writer, tag_color1, first, buf)?;
+    color(writer, tag_color2, second, buf)?;
+    text(writer, tag_kind, kind)
+}
+
+fn list_gradient<W: Write>(
+    writer: &mut Writer<W>,
+    gradient: ListGradient,
+    buf: &mut Vec<u8>,
+) -> XmlResult<()> {
+    match gradient {
+        ListGradient::Same(same) => self::gradient(writer, same, buf),
+        ListGradient::Alternating(even, odd) => {
+            color(writer, b"BackgroundColor", even, buf)?;
+            color(writer, b"BackgroundColor2", odd, buf)?;
+            text(writer, b"BackgroundGradient", "Alternating")
+        }
+    }
+}
+
+fn comparison_override<W: Write>(
+    writer: &mut Writer<W>,
+    tag: &[u8],
+    comparison: Option<&str>,
+) -> XmlResult<()> {
+    text(writer, tag, comparison.unwrap_or("Current Comparison"))
+}
+
+fn timing_method_override<W: Write>(
+    writer: &mut Writer<W>,
+    tag: &[u8],
+    method: Option<TimingMethod>,
+) -> XmlResult<()> {
+    text(
+        writer,
+        tag,
+        match method {
+            Some(TimingMethod::RealTime) => "Real Time",
+            Some(TimingMethod::GameTime) => "Game Time",
+            // LiveSplit doesn't know about the custom timing methods.
+            _ => "Current Timing Method",
+        },
+    )
+}
+
+fn accuracy<W: Write>(writer: &mut Writer<W>, tag: &[u8], accuracy: Accuracy) -> XmlResult<()> {
+    text(
+        writer,
+        tag,
+        match accuracy {
+            Accuracy::Seconds => "Seconds",
+            Accuracy::Tenths => "Tenths",
+            // The original LiveSplit doesn't support milliseconds here.
+            Accuracy::Hundredths | Accuracy::Milliseconds => "Hundredths",
+        },
+    )
+}
+
+fn timer_format<W: Write>(
+    writer: &mut Writer<W>,
+    tag: &[u8],
+    digits_format: DigitsFormat,
+    accuracy: Accuracy,
+    buf: &mut Vec<u8>,
+) -> XmlResult<()> {
+    let digits_format = match digits_format {
+        DigitsFormat::SingleDigitSeconds | DigitsFormat::DoubleDigitSeconds => "1",
+        DigitsFormat::SingleDigitMinutes | DigitsFormat::DoubleDigitMinutes => "00:01",
+        DigitsFormat::SingleDigitHours => "0:00:01",
+        DigitsFormat::DoubleDigitHours => "00:00:01",
+    };
+    let accuracy = match accuracy {
+        Accuracy::Seconds => "",
+        Accuracy::Tenths => ".2",
+        Accuracy::Hundredths | Accuracy::Milliseconds => ".23",
+    };
+    write_display(
+        writer,
+        tag,
+        format_args!("{}{}", digits_format, accuracy),
+        buf,
+    )
+}
+
+fn blank_space<W: Write>(
+    writer: &mut Writer<W>,
+    settings: &blank_space::Settings,
+    buf: &mut Vec<u8>,
+) -> XmlResult<()> {
+    text(writer, b"Version", "1.7")?;
+    write_display(writer, b"SpaceHeight", untranslate_size(settings.size), buf)?;
+    gradient(writer, settings.background, buf)
+}
+
+fn current_comparison<W: Write>(
+    writer: &mut Writer<W>,
+    settings: &current_comparison::Settings,
+    buf: &mut Vec<u8>,
+) -> XmlResult<()> {
+    text(writer, b"Version", "1.4")?;
+    color_override(
+        writer,
+        b"TextColor",
+        b"OverrideTextColor",
+        settings.label_color,
+        buf,
+    )?;
+    color_override(
+        writer,
+        b"TimeColor",
+        b"OverrideTimeColor",
+        settings.value_color,
+        buf,
+    )?;
+    gradient(writer, settings.background, buf)?;
+    bool(writer, b"Display2Rows", settings.display_two_rows)
+}
+
+fn current_pace<W: Write>(
+    writer: &mut Writer<W>,
+    settings: &current_pace::Settings,
+    buf: &mut Vec<u8>,
+) -> XmlResult<()> {
+    text(writer, b"Version", "1.4")?;
+    color_override(
+        writer,
+        b"TextColor",
+        b"OverrideTextColor",
+        settings.label_color,
+        buf,
+    )?;
+    color_override(
+        writer,
+        b"TimeColor",
+        b"OverrideTimeColor",
+        settings.value_color,
+        buf,
+    )?;
+    accuracy(writer, b"Accuracy", settings.accuracy)?;
+    gradient(writer, settings.background, buf)?;
+    comparison_override(
+        writer,
+        b"Comparison",
+        settings.comparison_override.as_deref(),
+    )?;
+    bool(writer, b"Display2Rows", settings.display_two_rows)
+}
+
+fn delta<W: Write>(
+    writer: &mut Writer<W>,
+    settings: &delta::Settings,
+    buf: &mut Vec<u8>,
+) -> XmlResult<()> {
+    text(writer, b"Version", "1.4")?;
+    color_override(
+        writer,
+        b"TextColor",
+        b"OverrideTextColor",
+        settings.label_color,
+        buf,
+    )?;
+    accuracy(writer, b"Accuracy", settings.accuracy)?;
+    gradient(writer, settings.background, buf)?;
+    comparison_override(
+        writer,
+        b"Comparison",
+        settings.comparison_override.as_deref(),
+    )?;
+    bool(writer, b"Display2Rows", settings.display_two_rows)?;
+    bool(writer, b"DropDecimals", settings.drop_decimals)
+}
+
+fn detailed_timer<W: Write>(
+    writer: &mut Writer<W>,
+    settings: &detailed_timer::Settings,
+    buf: &mut Vec<u8>,
+) -> XmlResult<()> {
+    let total_height = settings.timer.height + settings.segment_timer.height;
+    // The parser calculates the height of the segment timer with floating
+    // point math, so we look for the ratio that results in the same height.
+    let segment_timer_ratio = (0..=100u32)
+        .find(|&ratio| {
+            (total_height as f32 * (ratio as f32 / 100.0)) as u32 == settings.segment_timer.height
+        })
+        .unwrap_or(40);
+
+    text(writer, b"Version", "1.5")?;
+    write_display(writer, b"Height", untranslate_size(total_height), buf)?;
+    write_display(writer, b"SegmentTimerSizeRatio", segment_timer_ratio, buf)?;
+    bool(writer, b"TimerShowGradient", settings.timer.show_gradient)?;
+    bool(
+        writer,
+        b"OverrideTimerColors",
+        settings.timer.color_override.is_some(),
+    )?;
+    bool(
+        writer,
+        b"SegmentTimerShowGradient",
+        settings.segment_timer.show_gradient,
+    )?;
+    timer_format(
+        writer,
+        b"TimerFormat",
+        settings.timer.digits_format,
+        settings.timer.accuracy,
+        buf,
+    )?;
+    timer_format(
+        writer,
+        b"SegmentTimerFormat",
+        settings.segment_timer.digits_format,
+        settings.segment_timer.accuracy,
+        buf,
+    )?;
+    color(
+        writer,
+        b"TimerColor",
+        settings
+            .timer
+            .color_override
+            .unwrap_or_else(|| Color::rgba8(170, 170, 170, 255)),
+        buf,
+    )?;
+    bool(writer, b"DisplayIcon", settings.display_icon)?;
+    bool(writer, b"ShowSplitName", settings.show_segment_name)?;
+    gradient(writer, settings.background, buf)?;
+    comparison_override(writer, b"Comparison", settings.comparison1.as_deref())?;
+    comparison_override(writer, b"Comparison2", settings.comparison2.as_deref())?;
+    bool(writer, b"HideComparison", settings.hide_second_comparison)?;
+    timing_method_override(writer, b"TimingMethod", settings.timer.timing_method)
+}
+
+fn graph<W: Write>(
+    writer: &mut Writer<W>,
+    settings: &graph::Settings,
+    buf: &mut Vec<u8>,
+) -> XmlResult<()> {
+    text(writer, b"Version", "1.5")?;
+    write_display(writer, b"Height", untranslate_size(settings.height), buf)?;
+    color(
+        writer,
+        b"BehindGraphColor",
+        settings.behind_background_color,
+        buf,
+    )?;
+    color(
+        writer,
+        b"AheadGraphColor",
+        settings.ahead_background_color,
+        buf,
+    )?;
+    color(writer, b"GridlinesColor", settings.grid_lines_color, buf)?;
+    color(
+        writer,
+        b"PartialFillColorBehind",
+        settings.partial_fill_color,
+        buf,
+    )?;
+    color(
+        writer,
+        b"CompleteFillColorBehind",
+        settings.complete_fill_color,
+        buf,
+    )?;
+    color(
+        writer,
+        b"PartialFillColorAhead",
+        settings.partial_fill_color,
+        buf,
+    )?;
+    color(
+        writer,
+        b"CompleteFillColorAhead",
+        settings.complete_fill_color,
+        buf,
+    )?;
+    color(writer, b"GraphColor", settings.graph_lines_color, buf)?;
+    bool(writer, b"LiveGraph", settings.live_graph)?;
+    bool(writer, b"FlipGraph", settings.flip_graph)?;
+    comparison_override(
+        writer,
+        b"Comparison",
+        settings.comparison_override.as_deref(),
+    )?;
+    bool(writer, b"ShowBestSegments", settings.show_best_segments)
+}
+
+fn possible_time_save<W: Write>(
+    writer: &mut Writer<W>,
+    settings: &possible_time_save::Settings,
+    buf: &mut Vec<u8>,
+) -> XmlResult<()> {
+    text(writer, b"Version", "1.5")?;
+    color_override(
+        writer,
+        b"TextColor",
+        b"OverrideTextColor",
+        settings.label_color,
+        buf,
+    )?;
+    color_override(
+        writer,
+        b"TimeColor",
+        b"OverrideTimeColor",
+        settings.value_color,
+        buf,
+    )?;
+    accuracy(writer, b"Accuracy", settings.accuracy)?;
+    gradient(writer, settings.background, buf)?;
+    comparison_override(
+        writer,
+        b"Comparison",
+        settings.comparison_override.as_deref(),
+    )?;
+    bool(writer, b"Display2Rows", settings.display_two_rows)?;
+    bool(writer, b"TotalTimeSave", settings.total_possible_time_save)
+}
+
+fn previous_segment<W: Write>(
+    writer: &mut Writer<W>,
+    settings: &previous_segment::Settings,
+    buf: &mut Vec<u8>,
+) -> XmlResult<()> {
+    text(writer, b"Version", "1.6")?;
+    color_override(
+        writer,
+        b"TextColor",
+        b"OverrideTextColor",
+        settings.label_color,
+        buf,
+    )?;
+    accuracy(writer, b"DeltaAccuracy", settings.accuracy)?;
+    bool(writer, b"DropDecimals", settings.drop_decimals)?;
+    gradient(writer, settings.background, buf)?;
+    comparison_override(
+        writer,
+        b"Comparison",
+        settings.comparison_override.as_deref(),
+    )?;
+    bool(writer, b"Display2Rows", settings.display_two_rows)?;
+    bool(
+        writer,
+        b"ShowPossibleTimeSave",
+        settings.show_possible_time_save,
+    )
+}
+
+const fn column_type(column: &splits::ColumnSettings) -> &'static str {
+    use splits::{ColumnStartWith, ColumnUpdateWith};
+
+    // The original LiveSplit only supports a fixed set of column types, so we
+    // choose the one that is closest to the column's behavior.
+    match column.update_with {
+        ColumnUpdateWith::Delta => "Delta",
+        ColumnUpdateWith::SplitTime => "SplitTime",
+        ColumnUpdateWith::DeltaWithFallback => "DeltaorSplitTime",
+        ColumnUpdateWith::SegmentDelta => "SegmentDelta",
+        ColumnUpdateWith::SegmentTime => "SegmentTime",
+        ColumnUpdateWith::SegmentDeltaWithFallback => "SegmentDeltaorSegmentTime",
+        ColumnUpdateWith::PossibleTimeSave => "SegmentTime",
+        ColumnUpdateWith::DontUpdate => match column.start_with {
+            ColumnStartWith::ComparisonTime => "SplitTime",
+            ColumnStartWith::ComparisonSegmentTime
+            | ColumnStartWith::PossibleTimeSave
+            | ColumnStartWith::BestPossibleTimeSave
+            | ColumnStartWith::AverageSegmentTime
+            | ColumnStartWith::MedianSegmentTime => "SegmentTime",
+            ColumnStartWith::Empty
+            | ColumnStartWith::ResetPercentage
+            | ColumnStartWith::GoldCount => "Delta",
+        },
+    }
+}
+
+/// Checks whether the column behaves exactly like one of the column types of
+/// the original LiveSplit.
+const fn is_livesplit_column(column: &splits::ColumnSettings) -> bool {
+    use splits::{ColumnStartWith::*, ColumnUpdateTrigger::*, ColumnUpdateWith::*};
+
+    matches!(
+        (column.start_with, column.update_with, column.update_trigger),
+        (Empty, Delta, Contextual)
+            | (ComparisonTime, SplitTime, OnEndingSegment)
+            | (ComparisonTime, DeltaWithFallback, Contextual)
+            | (Empty, SegmentDelta, Contextual)
+            | (ComparisonSegmentTime, SegmentTime, OnEndingSegment)
+            | (ComparisonSegmentTime, SegmentDeltaWithFallback, Contextual)
+    )
+}
+
+const fn column_start_with(start_with: splits::ColumnStartWith) -> &'static str {
+    use splits::ColumnStartWith::*;
+
+    match start_with {
+        Empty => "Empty",
+        ComparisonTime => "ComparisonTime",
+        ComparisonSegmentTime => "ComparisonSegmentTime",
+        PossibleTimeSave => "PossibleTimeSave",
+        BestPossibleTimeSave => "BestPossibleTimeSave",
+        AverageSegmentTime => "AverageSegmentTime",
+        MedianSegmentTime => "MedianSegmentTime",
+        ResetPercentage => "ResetPercentage",
+        GoldCount => "GoldCount",
+    }
+}
+
+const fn column_update_with(update_with: splits::ColumnUpdateWith) -> &'static str {
+    use splits::ColumnUpdateWith::*;
+
+    match update_with {
+        DontUpdate => "DontUpdate",
+        SplitTime => "SplitTime",
+        Delta => "Delta",
+        DeltaWithFallback => "DeltaWithFallback",
+        SegmentTime => "SegmentTime",
+        SegmentDelta => "SegmentDelta",
+        SegmentDeltaWithFallback => "SegmentDeltaWithFallback",
+        PossibleTimeSave => "PossibleTimeSave",
+    }
+}
+
+const fn column_update_trigger(update_trigger: splits::ColumnUpdateTrigger) -> &'static str {
+    use splits::ColumnUpdateTrigger::*;
+
+    match update_trigger {
+        OnStartingSegment => "OnStartingSegment",
+        Contextual => "Contextual",
+        OnEndingSegment => "OnEndingSegment",
+    }
+}
+
+fn splits<W: Write>(
+    writer: &mut Writer<W>,
+    settings: &splits::Settings,
+    buf: &mut Vec<u8>,
+) -> XmlResult<()> {
+    text(writer, b"Version", "1.6")?;
+    gradient_with_tags(
+        writer,
+        settings.current_split_gradient,
+        b"CurrentSplitTopColor",
+        b"CurrentSplitBottomColor",
+        b"CurrentSplitGradient",
+        buf,
+    )?;
+    write_display(
+        writer,
+        b"VisualSplitCount",
+        settings.visual_split_count,
+        buf,
+    )?;
+    write_display(
+        writer,
+        b"SplitPreviewCount",
+        settings.split_preview_count,
+        buf,
+    )?;
+    bool(writer, b"ShowThinSeparators", settings.show_thin_separators)?;
+    bool(
+        writer,
+        b"AlwaysShowLastSplit",
+        settings.always_show_last_split,
+    )?;
+    bool(writer, b"ShowBlankSplits", settings.fill_with_blank_space)?;
+    list_gradient(writer, settings.background, buf)?;
+    bool(writer, b"SeparatorLastSplit", settings.separator_last_split)?;
+    bool(writer, b"Display2Rows", settings.display_two_rows)?;
+    bool(writer, b"ShowColumnLabels", settings.show_column_labels)?;
+
+    // The original LiveSplit stores the columns from right to left, while we
+    // store them from left to right.
+    scoped(
+        writer,
+        new_tag(b"Columns"),
+        settings.columns.is_empty(),
+        |writer| {
+            for column in settings.columns.iter().rev() {
+                write_start(writer, new_tag(b"Settings"))?;
+                text(writer, b"Version", "1.5")?;
+                text(writer, b"Name", &column.name)?;
+                text(writer, b"Type", column_type(column))?;
+                if !is_livesplit_column(column) {
+                    // The original LiveSplit ignores these, so the column
+                    // falls back to its closest type there, while we can
+                    // restore the column's actual behavior.
+                    text(writer, b"StartWith", column_start_with(column.start_with))?;
+                    text(
+                        writer,
+                        b"UpdateWith",
+                        column_update_with(column.update_with),
+                    )?;
+                    text(
+                        writer,
+                        b"UpdateTrigger",
+                        column_update_trigger(column.update_trigger),
+                    )?;
+                }
+                comparison_override(writer, b"Comparison", column.comparison_override.as_deref())?;
+                timing_method_override(writer, b"TimingMethod", column.timing_method)?;
+                write_end(writer, b"Settings")?;
+            }
+            Ok(())
+        },
+    )?;
+
+    // The original LiveSplit doesn't know about the style rules, so it simply
+    // ignores them.
+    scoped(
+        writer,
+        new_tag(b"StyleRules"),
+        settings.style_rules.is_empty(),
+        |writer| {
+            for rule in &settings.style_rules {
+                write_start(writer, new_tag(b"StyleRule"))?;
+                match &rule.condition {
+                    splits::StyleCondition::DeltaAbove(threshold) => {
+                        write_display(writer, b"DeltaAbove", threshold.total_seconds(), buf)?
+                    }
+                    splits::StyleCondition::BestSegment => text(writer, b"BestSegment", "")?,
+                    splits::StyleCondition::NameMatches(pattern) => {
+                        text(writer, b"NameMatches", pattern.as_str())?
+                    }
+                }
+                color_override(
+                    writer,
+                    b"TextColor",
+                    b"OverrideTextColor",
+                    rule.style.text_color,
+                    buf,
+                )?;
+                gradient(writer, rule.style.background.unwrap_or_default(), buf)?;
+                bool(
+                    writer,
+                    b"OverrideBackground",
+                    rule.style.background.is_some(),
+                )?;
+                bool(writer, b"Hide", rule.style.is_hidden)?;
+                write_end(writer, b"StyleRule")?;
+            }
+            Ok(())
+        },
+    )
+}
+
+fn sum_of_best<W: Write>(
+    writer: &mut Writer<W>,
+    settings: &sum_of_best::Settings,
+    buf: &mut Vec<u8>,
+) -> XmlResult<()> {
+    text(writer, b"Version", "1.4")?;
+    color_override(
+        writer,
+        b"TextColor",
+        b"OverrideTextColor",
+        settings.label_color,
+        buf,
+    )?;
+    color_override(
+        writer,
+        b"TimeColor",
+        b"OverrideTimeColor",
+        settings.value_color,
+        buf,
+    )?;
+    accuracy(writer, b"Accuracy", settings.accuracy)?;
+    gradient(writer, settings.background, buf)?;
+    bool(writer, b"Display2Rows", settings.display_two_rows)
+}
+
+fn text_component<W: Write>(
+    writer: &mut Writer<W>,
+    settings: &text::Settings,
+    buf: &mut Vec<u8>,
+) -> XmlResult<()> {
+    let (text1, text2) = match &settings.text {
+        text::Text::Center(center) => (&**center, ""),
+        text::Text::Split(left, right) => (&**left, &**right),
+        // The original LiveSplit can't show custom variables, so we show the
+        // name of the variable instead.
+        text::Text::Variable(name, _) => (&**name, ""),
+    };
+
+    text(writer, b"Version", "1.4")?;
+    color_override(
+        writer,
+        b"TextColor",
+        b"OverrideTextColor",
+        settings.left_center_color,
+        buf,
+    )?;
+    color_override(
+        writer,
+        b"TimeColor",
+        b"OverrideTimeColor",
+        settings.right_color,
+        buf,
+    )?;
+    gradient(writer, settings.background, buf)?;
+    text(writer, b"Text1", text1)?;
+    text(writer, b"Text2", text2)?;
+    bool(writer, b"Display2Rows", settings.display_two_rows)
+}
+
+fn timer<W: Write>(
+    writer: &mut Writer<W>,
+    settings: &timer::Settings,
+    buf: &mut Vec<u8>,
+) -> XmlResult<()> {
+    text(writer, b"Version", "1.5")?;
+    write_display(
+        writer,
+        b"TimerHeight",
+        untranslate_size(settings.height),
+        buf,
+    )?;
+    timer_format(
+        writer,
+        b"TimerFormat",
+        settings.digits_format,
+        settings.accuracy,
+        buf,
+    )?;
+    bool(
+        writer,
+        b"OverrideSplitColors",
+        settings.color_override.is_some(),
+    )?;
+    bool(writer, b"ShowGradient", settings.show_gradient)?;
+    color(
+        writer,
+        b"TimerColor",
+        settings
+            .color_override
+            .unwrap_or_else(|| Color::rgba8(170, 170, 170, 255)),
+        buf,
+    )?;
+    gradient(writer, settings.background, buf)?;
+    timing_method_override(writer, b"TimingMethod", settings.timing_method)
+}
+
+fn title<W: Write>(
+    writer: &mut Writer<W>,
+    settings: &title::Settings,
+    buf: &mut Vec<u8>,
+) -> XmlResult<()> {
+    text(writer, b"Version", "1.7.3")?;
+    bool(writer, b"ShowGameName", settings.show_game_name)?;
+    bool(writer, b"ShowCategoryName", settings.show_category_name)?;
+    bool(writer, b"ShowAttemptCount", settings.show_attempt_count)?;
+    bool(
+        writer,
+        b"ShowFinishedRunsCount",
+        settings.show_finished_runs_count,
+    )?;
+    color_override(
+        writer,
+        b"TitleColor",
+        b"OverrideTitleColor",
+        settings.text_color,
+        buf,
+    )?;
+    text(
+        writer,
+        b"TextAlignment",
+        match settings.text_alignment {
+            Alignment::Auto => "0",
+            Alignment::Left => "1",
+            Alignment::Center => "2",
+        },
+    )?;
+    bool(writer, b"SingleLine", settings.display_as_single_line)?;
+    bool(writer, b"DisplayGameIcon", settings.display_game_icon)?;
+    bool(writer, b"ShowRegion", settings.show_region)?;
+    bool(writer, b"ShowPlatform", settings.show_platform)?;
+    bool(writer, b"ShowVariables", settings.show_variables)?;
+    gradient(writer, settings.background, buf)
+}
+
+fn total_playtime<W: Write>(
+    writer: &mut Writer<W>,
+    settings: &total_playtime::Settings,
+    buf: &mut Vec<u8>,
+) -> XmlResult<()> {
+    text(writer, b"Version", "1.6")?;
+    color_override(
+        writer,
+        b"TextColor",
+        b"OverrideTextColor",
+        settings.label_color,
+        buf,
+    )?;
+    color_override(
+        writer,
+        b"TimeColor",
+        b"OverrideTimeColor",
+        settings.value_color,
+        buf,
+    )?;
+    gradient(writer, settings.background, buf)?;
+    bool(writer, b"Display2Rows", settings.display_two_rows)?;
+    bool(writer, b"ShowTotalHours", !settings.show_days)
+}
+
+fn component<W: Write>(
+    writer: &mut Writer<W>,
+    component: &Component,
+    buf: &mut Vec<u8>,
+) -> XmlResult<()> {
+    let path = match component {
+        Component::BlankSpace(_) => "LiveSplit.BlankSpace.dll",
+        Component::CurrentComparison(_) => "LiveSplit.CurrentComparison.dll",
+        Component::CurrentPace(_) => "LiveSplit.RunPrediction.dll",
+        Component::Delta(_) => "LiveSplit.Delta.dll",
+        Component::DetailedTimer(_) => "LiveSplit.DetailedTimer.dll",
+        Component::Graph(_) => "LiveSplit.Graph.dll",
+        Component::PbChance(_) => "PBChance.dll",
+        Component::PossibleTimeSave(_) => "LiveSplit.PossibleTimeSave.dll",
+        Component::PreviousSegment(_) => "LiveSplit.PreviousSegment.dll",
+        Component::Separator(_) => "",
+        Component::Splits(_) => "LiveSplit.Splits.dll",
+        Component::SumOfBest(_) => "LiveSplit.SumOfBest.dll",
+        Component::Text(_) => "LiveSplit.Text.dll",
+        Component::Timer(_) => "LiveSplit.Timer.dll",
+        Component::Title(_) => "LiveSplit.Title.dll",
+        Component::TotalPlaytime(_) => "LiveSplit.TotalPlaytime.dll",
+        // The original LiveSplit doesn't have components like these.
+        Component::RunHistory(_)
+        | Component::SegmentDistribution(_)
+        | Component::SegmentTime(_)
+        | Component::Statistic(_) => return Ok(()),
+    };
+
+    write_start(writer, new_tag(b"Component"))?;
+    text(writer, b"Path", path)?;
+
+    let is_empty = matches!(component, Component::PbChance(_) | Component::Separator(_));
+
+    scoped(
+        writer,
+        new_tag(b"Settings"),
+        is_empty,
+        |writer| match component {
+            Component::BlankSpace(c) => blank_space(writer, c.settings(), buf),
+            Component::CurrentComparison(c) => current_comparison(writer, c.settings(), buf),
+            Component::CurrentPace(c) => current_pace(writer, c.settings(), buf),
+            Component::Delta(c) => delta(writer, c.settings(), buf),
+            Component::DetailedTimer(c) => detailed_timer(writer, c.settings(), buf),
+            Component::Graph(c) => graph(writer, c.settings(), buf),
+            Component::PossibleTimeSave(c) => possible_time_save(writer, c.settings(), buf),
+            Component::PreviousSegment(c) => previous_segment(writer, c.settings(), buf),
+            Component::Splits(c) => splits(writer, c.settings(), buf),
+            Component::SumOfBest(c) => sum_of_best(writer, c.settings(), buf),
+            Component::Text(c) => text_component(writer, c.settings(), buf),
+            Component::Timer(c) => timer(writer, c.settings(), buf),
+            Component::Title(c) => title(writer, c.settings(), buf),
+            Component::TotalPlaytime(c) => total_playtime(writer, c.settings(), buf),
+            Component::PbChance(_)
+            | Component::Separator(_)
+            | Component::RunHistory(_)
+            | Component::SegmentDistribution(_)
+            | Component::SegmentTime(_)
+            | Component::Statistic(_) => Ok(()),
+        },
+    )?;
+
+    write_end(writer, b"Component")
+}
+
+fn general_settings<W: Write>(
+    writer: &mut Writer<W>,
+    settings: &GeneralSettings,
+    buf: &mut Vec<u8>,
+    font_buf: &mut Vec<u8>,
+) -> XmlResult<()> {
+    write_start(writer, new_tag(b"Settings"))?;
+
+    let (first, second, background_type) = match settings.background {
+        Gradient::Transparent => (Color::transparent(), Color::transparent(), "SolidColor"),
+        Gradient::Plain(c) => (c, Color::transparent(), "SolidColor"),
+        Gradient::Vertical(first, second) => (first, second, "VerticalGradient"),
+        Gradient::Horizontal(first, second) => (first, second, "HorizontalGradient"),
+    };
+
+    color(writer, b"TextColor", settings.text_color, buf)?;
+    color(writer, b"BackgroundColor", first, buf)?;
+    color(writer, b"BackgroundColor2", second, buf)?;
+    color(
+        writer,
+        b"ThinSeparatorsColor",
+        settings.thin_separators_color,
+        buf,
+    )?;
+    color(writer, b"SeparatorsColor", settings.separators_color, buf)?;
+    color(
+        writer,
+        b"PersonalBestColor",
+        settings.personal_best_color,
+        buf,
+    )?;
+    color(
+        writer,
+        b"AheadGainingTimeColor",
+        settings.ahead_gaining_time_color,
+        buf,
+    )?;
+    color(
+        writer,
+        b"AheadLosingTimeColor",
+        settings.ahead_losing_time_color,
+        buf,
+    )?;
+    color(
+        writer,
+        b"BehindGainingTimeColor",
+        settings.behind_gaining_time_color,
+        buf,
+    )?;
+    color(
+        writer,
+        b"BehindLosingTimeColor",
+        settings.behind_losing_time_color,
+        buf,
+    )?;
+    color(
+        writer,
+        b"BestSegmentColor",
+        settings.best_segment_color,
+        buf,
+    )?;
+    bool(writer, b"UseRainbowColor", false)?;
+    color(writer, b"NotRunningColor", settings.not_running_color, buf)?;
+    color(writer, b"PausedColor", settings.paused_color, buf)?;
+    font(
+        writer,
+        b"TimesFont",
+        settings.times_font.as_ref(),
+        &TIMES_FONT,
+        buf,
+        font_buf,
+    )?;
+    font(
+        writer,
+        b"TimerFont",
+        settings.timer_font.as_ref(),
+        &TIMER_FONT,
+        buf,
+        font_buf,
+    )?;
+    font(
+        writer,
+        b"TextFont",
+        settings.text_font.as_ref(),
+        &TEXT_FONT,
+        buf,
+        font_buf,
+    )?;
+    text(writer, b"BackgroundType", background_type)?;
+
+    write_end(writer, b"Settings")
+}
+
+/// Saves a Layout as a LiveSplit layout file (*.lsl).
+pub fn save_layout<W: Write>(layout: &Layout, writer: W) -> Result<()> {
+    let writer = &mut Writer::new(writer);
+
+    let buf = &mut Vec::new();
+    let font_buf = &mut Vec::new();
+
+    writer.write_event(Event::Decl(BytesDecl::new(b"1.0", Some(b"UTF-8"), None)))?;
+    writer.write_event(Event::Start(BytesStart::borrowed(
+        br#"Layout version="1.6.1""#,
+        6,
+    )))?;
+
+    let settings = layout.general_settings();
+
+    text(
+        writer,
+        b"Mode",
+        match settings.direction {
+            LayoutDirection::Vertical => "Vertical",
+            LayoutDirection::Horizontal => "Horizontal",
+        },
+    )?;
+
+    general_settings(writer, settings, buf, font_buf)?;
+
+    scoped(
+        writer,
+        new_tag(b"Components"),
+        layout.components.is_empty(),
+        |writer| {
+            for c in &layout.components {
+                component(writer, c, buf)?;
+            }
+            Ok(())
+        },
+    )?;
+
+    write_end(writer, b"Layout")?;
+    Ok(())
+}
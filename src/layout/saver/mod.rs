@@ -0,0 +1,25 @@
+//! The saver module provides all the different ways to save Layouts as layout
+//! files.
+//!
+//! # Examples
+//!
+//! Using the LiveSplit Saver to save a Layout as a LiveSplit layout file.
+//!
+//! ```no_run
+//! use livesplit_core::layout::saver::livesplit;
+//! use livesplit_core::Layout;
+//! use std::fs::File;
+//! use std::io::BufWriter;
+//!
+//! // Create a layout object that we can use.
+//! let layout = Layout::default_layout();
+//!
+//! // Create the layout file.
+//! let file = File::create("path/to/layout_file.lsl");
+//! let writer = BufWriter::new(file.expect("Failed creating the file"));
+//!
+//! // Save the layout as a LiveSplit layout file.
+//! livesplit::save_layout(&layout, writer).expect("Couldn't save the layout file");
+//! ```
+
+pub mod livesplit;
@@ -59,6 +59,8 @@ macro_rules! catch {
 }
 
 pub mod analysis;
+#[cfg(feature = "auto-splitting")]
+pub mod auto_splitting;
 pub mod clear_vec;
 pub mod comparison;
 pub mod component;
@@ -72,6 +74,8 @@ pub mod networking;
 #[cfg(feature = "rendering")]
 pub mod rendering;
 pub mod run;
+#[cfg(feature = "server")]
+pub mod server;
 pub mod settings;
 #[cfg(test)]
 pub mod tests_helper;
@@ -84,8 +88,8 @@ pub use crate::{
     platform::{indexmap, DateTime, Utc},
     run::{Attempt, Editor as RunEditor, Run, RunMetadata, Segment, SegmentHistory},
     timing::{
-        AtomicDateTime, GameTime, RealTime, Time, TimeSpan, TimeStamp, Timer, TimerPhase,
-        TimingMethod,
+        AtomicDateTime, GameTime, RealTime, Time, TimeSpan, TimeStamp, Timer, TimerEvent,
+        TimerPhase, TimingMethod,
     },
 };
 pub use livesplit_hotkey as hotkey;
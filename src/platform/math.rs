@@ -10,6 +10,11 @@ cfg_if::cfg_if! {
             pub fn floor(x: f64) -> f64 {
                 x.floor()
             }
+
+            #[inline(always)]
+            pub fn sqrt(x: f64) -> f64 {
+                x.sqrt()
+            }
         }
 
         pub mod f32 {
@@ -20,7 +25,7 @@ cfg_if::cfg_if! {
         }
     } else {
         pub mod f64 {
-            pub use libm::{fabs as abs, floor as floor};
+            pub use libm::{fabs as abs, floor as floor, sqrt};
         }
 
         pub mod f32 {
@@ -10,6 +10,8 @@ pub mod blank_space;
 pub mod detailed_timer;
 pub mod graph;
 pub mod key_value;
+pub mod run_history;
+pub mod segment_distribution;
 pub mod separator;
 pub mod splits;
 pub mod text;
@@ -30,6 +32,8 @@ pub fn width(component: &ComponentState) -> f32 {
         ComponentState::DetailedTimer(_) => 7.0,
         ComponentState::Graph(_) => 7.0,
         ComponentState::KeyValue(_) => 6.0,
+        ComponentState::RunHistory(_) => 7.0,
+        ComponentState::SegmentDistribution(_) => 7.0,
         ComponentState::Separator(_) => SEPARATOR_THICKNESS,
         ComponentState::Splits(state) => {
             let column_count = 2.0; // FIXME: Not always 2.
@@ -54,6 +58,8 @@ pub fn height(component: &ComponentState) -> f32 {
                 DEFAULT_COMPONENT_HEIGHT
             }
         }
+        ComponentState::RunHistory(state) => state.height as f32 * PSEUDO_PIXELS,
+        ComponentState::SegmentDistribution(state) => state.height as f32 * PSEUDO_PIXELS,
         ComponentState::Separator(_) => SEPARATOR_THICKNESS,
         ComponentState::Splits(state) => {
             state.splits.len() as f32
@@ -98,6 +104,12 @@ pub(super) fn render<A: ResourceAllocator>(
         ),
         ComponentState::Graph(component) => graph::render(context, dim, component, state),
         ComponentState::KeyValue(component) => key_value::render(context, dim, component, state),
+        ComponentState::RunHistory(component) => {
+            run_history::render(context, dim, component, state)
+        }
+        ComponentState::SegmentDistribution(component) => {
+            segment_distribution::render(context, dim, component, state)
+        }
         ComponentState::Separator(component) => separator::render(context, dim, component, state),
         ComponentState::Splits(component) => {
             splits::render(context, dim, component, state, &mut icons.split_icons)
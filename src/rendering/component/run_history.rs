@@ -0,0 +1,84 @@
+use crate::{
+    component::run_history::{Point, State},
+    layout::LayoutState,
+    rendering::{PathBuilder, RenderContext, ResourceAllocator},
+    settings::{Color, Gradient},
+};
+
+pub(in crate::rendering) fn render(
+    context: &mut RenderContext<'_, impl ResourceAllocator>,
+    [width, height]: [f32; 2],
+    component: &State,
+    _layout_state: &LayoutState,
+) {
+    context.render_rectangle([0.0, 0.0], [width, height], &component.background);
+
+    let old_transform = context.transform;
+    context.scale(height);
+    let width = width / height;
+
+    const GRID_LINE_WIDTH: f32 = 0.015;
+    const LINE_WIDTH: f32 = 0.025;
+    const CIRCLE_RADIUS: f32 = 0.03;
+
+    for &y in &component.horizontal_grid_lines {
+        context.render_top_rectangle(
+            [0.0, y - GRID_LINE_WIDTH],
+            [width, y + GRID_LINE_WIDTH],
+            &Gradient::Plain(component.grid_lines_color),
+        );
+    }
+
+    render_line(
+        context,
+        width,
+        &component.sum_of_best_line,
+        component.sum_of_best_color,
+        LINE_WIDTH,
+    );
+    render_line(
+        context,
+        width,
+        &component.personal_best_line,
+        component.personal_best_color,
+        LINE_WIDTH,
+    );
+
+    for point in &component.attempts {
+        let color = if point.is_personal_best {
+            component.personal_best_color
+        } else if point.is_finished {
+            component.finished_color
+        } else {
+            component.reset_color
+        };
+
+        let circle_path = context
+            .handles
+            .build_circle(width * point.x, point.y, CIRCLE_RADIUS);
+        context.top_layer_path(circle_path, color);
+    }
+
+    context.transform = old_transform;
+}
+
+fn render_line(
+    context: &mut RenderContext<'_, impl ResourceAllocator>,
+    width: f32,
+    points: &[Point],
+    color: Color,
+    line_width: f32,
+) {
+    if let [first, rest @ ..] = points {
+        if rest.is_empty() {
+            return;
+        }
+        let mut builder = context.handles.path_builder();
+        builder.move_to(width * first.x, first.y);
+        for point in rest {
+            builder.line_to(width * point.x, point.y);
+        }
+        let line_path = builder.finish(&mut context.handles);
+        context.top_layer_stroke_path(line_path, color, line_width);
+    }
+}
@@ -0,0 +1,47 @@
+use crate::{
+    component::segment_distribution::State,
+    layout::LayoutState,
+    rendering::{RenderContext, ResourceAllocator},
+    settings::Gradient,
+};
+
+pub(in crate::rendering) fn render(
+    context: &mut RenderContext<'_, impl ResourceAllocator>,
+    [width, height]: [f32; 2],
+    component: &State,
+    _layout_state: &LayoutState,
+) {
+    context.render_rectangle([0.0, 0.0], [width, height], &component.background);
+
+    let old_transform = context.transform;
+    context.scale(height);
+    let width = width / height;
+
+    const PADDING: f32 = 0.05;
+    const BAR_GAP: f32 = 0.02;
+    const MARKER_WIDTH: f32 = 0.025;
+
+    let bar_width = width / component.bars.len().max(1) as f32;
+    let bar_color = Gradient::Plain(component.bar_color);
+
+    for (i, &bar) in component.bars.iter().enumerate() {
+        let left = i as f32 * bar_width;
+        let top = 1.0 - bar * (1.0 - PADDING);
+        context.render_top_rectangle(
+            [left + BAR_GAP, top],
+            [left + bar_width - BAR_GAP, 1.0],
+            &bar_color,
+        );
+    }
+
+    if let Some(x) = component.marker {
+        let x = (width * x).max(MARKER_WIDTH).min(width - MARKER_WIDTH);
+        context.render_top_rectangle(
+            [x - MARKER_WIDTH, 0.0],
+            [x + MARKER_WIDTH, 1.0],
+            &Gradient::Plain(component.marker_color),
+        );
+    }
+
+    context.transform = old_transform;
+}
@@ -15,6 +15,7 @@ use crate::{
 };
 
 pub const COLUMN_WIDTH: f32 = 2.75;
+const SUBSPLIT_INDENT: f32 = 0.5;
 
 pub(in crate::rendering) fn render<B: ResourceAllocator>(
     context: &mut RenderContext<'_, B>,
@@ -119,6 +120,8 @@ pub(in crate::rendering) fn render<B: ResourceAllocator>(
                 [split_width, split_height],
                 &component.current_split_gradient,
             );
+        } else if let Some(background) = &split.background {
+            context.render_rectangle([0.0, 0.0], split_background_bottom_right, background);
         } else if let Some((even, odd)) = &split_background {
             let color = if split.index % 2 == 0 { even } else { odd };
             context.render_rectangle([0.0, 0.0], split_background_bottom_right, color);
@@ -148,11 +151,17 @@ pub(in crate::rendering) fn render<B: ResourceAllocator>(
                 left_x = split_width;
             }
 
+            let name_x = if split.is_subsplit {
+                icon_right + SUBSPLIT_INDENT
+            } else {
+                icon_right
+            };
+
             context.render_text_ellipsis(
                 &split.name,
-                [icon_right, TEXT_ALIGN_TOP],
+                [name_x, TEXT_ALIGN_TOP],
                 DEFAULT_TEXT_SIZE,
-                text_color,
+                split.text_color.map_or(text_color, |c| solid(&c)),
                 left_x - PADDING,
             );
         }
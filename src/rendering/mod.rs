@@ -9,7 +9,8 @@
 //! curves. An optional software renderer is available behind the
 //! `software-rendering` feature that uses tiny-skia to efficiently render the
 //! paths on the CPU. It is surprisingly fast and can be considered the default
-//! renderer.
+//! renderer. There is also an optional SVG renderer behind the `svg-rendering`
+//! feature that turns the scene into a resolution independent SVG document.
 
 // # Coordinate spaces used in this module
 //
@@ -78,8 +79,12 @@ mod icon;
 mod resource;
 mod scene;
 
+#[cfg(feature = "software-rendering")]
+pub mod recorder;
 #[cfg(feature = "software-rendering")]
 pub mod software;
+#[cfg(feature = "svg-rendering")]
+pub mod svg;
 
 use self::{
     consts::{
@@ -0,0 +1,298 @@
+//! Provides a recorder that replays an attempt from a Run's history and renders
+//! it frame by frame with the software renderer. The attempt is replayed
+//! through a [`Timer`] that is driven by a [`ManualClock`], so the frames
+//! don't depend on how fast they are rendered. The frames can be written out
+//! as PNG files or as a stream of raw RGBA8 encoded pixels, which can be piped
+//! into a video encoder.
+//!
+//! # Examples
+//!
+//! Piping the frames into `ffmpeg` to create a video.
+//!
+//! ```no_run
+//! # fn foo(run: livesplit_core::Run) -> Result<(), Box<dyn std::error::Error>> {
+//! use livesplit_core::{rendering::recorder::Recorder, Layout};
+//! use std::process::{Command, Stdio};
+//!
+//! let index = run.attempt_history().last().unwrap().index();
+//! let mut recorder = Recorder::new(run, index, Layout::default_layout(), [300, 500], 60.0)?;
+//!
+//! let mut ffmpeg = Command::new("ffmpeg")
+//!     .args(&["-f", "rawvideo", "-pix_fmt", "rgba", "-s", "300x500", "-r", "60"])
+//!     .args(&["-i", "-", "recap.mp4"])
+//!     .stdin(Stdio::piped())
+//!     .spawn()?;
+//! recorder.write_raw_frames(ffmpeg.stdin.take().unwrap())?;
+//! ffmpeg.wait()?;
+//! # Ok(())
+//! # }
+//! ```
+
+use super::software::Renderer;
+use crate::{
+    timing::{FrameRate, ManualClock, TimerCreationError},
+    Layout, Run, TimeSpan, Timer,
+};
+use snafu::{OptionExt, ResultExt};
+use std::{
+    fs,
+    io::{self, Write},
+    path::Path,
+};
+
+/// The Error type for replaying and recording attempts.
+#[derive(Debug, snafu::Snafu)]
+pub enum Error {
+    /// The frame rate is not a positive number.
+    InvalidFrameRate,
+    /// The Run has no attempt with the index provided.
+    AttemptNotFound,
+    /// The Timer for replaying the attempt couldn't be created.
+    CreateTimer {
+        /// The underlying error.
+        source: TimerCreationError,
+    },
+    /// Failed writing a frame.
+    Io {
+        /// The underlying error.
+        source: io::Error,
+    },
+    /// Failed encoding a frame as a PNG file.
+    EncodePng {
+        /// The underlying error.
+        source: image::ImageError,
+    },
+}
+
+/// The Result type for replaying and recording attempts.
+pub type Result<T = ()> = std::result::Result<T, Error>;
+
+/// The final state of the attempt stays visible for this long at the end of
+/// the recording.
+const HOLD_SECONDS: f64 = 2.0;
+
+#[derive(Copy, Clone)]
+enum Action {
+    Split,
+    Skip,
+}
+
+/// A recorder replays an attempt from a Run's history and renders it frame by
+/// frame at a fixed frame rate. The attempt is replayed from its segment
+/// history, so the recorded split times match the ones of the original attempt.
+/// Pauses are not part of the segment history, so they are not replayed. If
+/// the attempt tracked Game Time, the Game Time is interpolated between the
+/// splits.
+pub struct Recorder {
+    timer: Timer,
+    layout: Layout,
+    renderer: Renderer,
+    resolution: [u32; 2],
+    frame_rate: FrameRate,
+    // The points in time of the actions are relative to the start of the
+    // attempt, so they don't include the Run's offset.
+    actions: Vec<(TimeSpan, Action)>,
+    offset: TimeSpan,
+    next_action: usize,
+    game_times: Option<Vec<(TimeSpan, TimeSpan)>>,
+    frame: u64,
+    frame_count: u64,
+}
+
+impl Recorder {
+    /// Creates a new recorder that replays the attempt with the index provided
+    /// from the Run's attempt history. The frames are rendered with the layout
+    /// and resolution provided at the frame rate specified in frames per
+    /// second. The Run is used as is, so the attempt is compared against the
+    /// comparisons that are stored in it. The frame rate needs to be a finite
+    /// positive number.
+    pub fn new(
+        run: Run,
+        attempt_index: i32,
+        layout: Layout,
+        resolution: [u32; 2],
+        fps: f64,
+    ) -> Result<Self> {
+        let frame_rate = FrameRate::new(fps).context(InvalidFrameRate)?;
+
+        let attempt = run
+            .attempt_history()
+            .iter()
+            .find(|a| a.index() == attempt_index)
+            .cloned()
+            .context(AttemptNotFound)?;
+
+        // The Timer starts at the offset, so the split times in the segment
+        // history include it, while the clock only measures the time since the
+        // start of the attempt.
+        let offset = run.offset();
+        let mut actions = Vec::new();
+        // Just like the Real Time, the Game Time starts at the offset.
+        let mut game_times = vec![(offset, offset)];
+        let mut has_game_time = false;
+        let (mut real_time, mut game_time) = (TimeSpan::zero(), TimeSpan::zero());
+        // The segment history doesn't store when a split got skipped, so the
+        // skips are replayed right before the next split.
+        let mut skipped = 0;
+
+        for segment in run.segments() {
+            // The segment history only contains the segments the attempt
+            // reached, so the first segment without an entry is where the
+            // attempt got reset.
+            let time = match segment.segment_history().get(attempt_index) {
+                Some(time) => time,
+                None => break,
+            };
+            match time.real_time {
+                Some(segment_time) => {
+                    real_time += segment_time;
+                    if let Some(segment_time) = time.game_time {
+                        game_time += segment_time;
+                        has_game_time = true;
+                    }
+                    let action_time = real_time - offset;
+                    actions.extend((0..skipped).map(|_| (action_time, Action::Skip)));
+                    actions.push((action_time, Action::Split));
+                    game_times.push((real_time, game_time));
+                    skipped = 0;
+                }
+                None => skipped += 1,
+            }
+        }
+
+        // An attempt that got reset keeps going until the point in time it got
+        // reset at.
+        let mut end = real_time - offset;
+        if attempt.time().real_time.is_none() {
+            if let Some(duration) = attempt.duration() {
+                let pause_time = attempt.pause_time().unwrap_or_default();
+                end = end.max(duration - pause_time);
+            }
+        }
+        actions.extend((0..skipped).map(|_| (end, Action::Skip)));
+        let end = end.total_seconds() + HOLD_SECONDS;
+
+        let timer = Timer::with_clock(run, ManualClock::new()).context(CreateTimer)?;
+
+        Ok(Self {
+            timer,
+            layout,
+            renderer: Renderer::new(),
+            resolution,
+            frame_rate,
+            actions,
+            offset,
+            next_action: 0,
+            game_times: if has_game_time {
+                Some(game_times)
+            } else {
+                None
+            },
+            frame: 0,
+            frame_count: (end * frame_rate.frames_per_second()).ceil() as u64 + 1,
+        })
+    }
+
+    /// Returns the total amount of frames of the recording.
+    pub const fn frame_count(&self) -> u64 {
+        self.frame_count
+    }
+
+    /// Accesses the Timer that replays the attempt.
+    pub const fn timer(&self) -> &Timer {
+        &self.timer
+    }
+
+    /// Renders the next frame and returns it as RGBA8 encoded pixels (red,
+    /// green, blue, alpha with each channel being an u8). Returns `None` once
+    /// all the frames are rendered.
+    pub fn next_frame(&mut self) -> Option<&[u8]> {
+        if self.frame >= self.frame_count {
+            return None;
+        }
+        let time = self.frame_rate.time_span(self.frame as i64);
+        self.frame += 1;
+
+        if self.frame == 1 {
+            self.timer.start();
+            if self.game_times.is_some() {
+                self.timer.initialize_game_time();
+                self.timer.pause_game_time();
+            }
+        }
+
+        // The clock is moved to the exact points in time of the splits, so the
+        // split times match the original ones.
+        while let Some(&(action_time, action)) = self.actions.get(self.next_action) {
+            if action_time > time {
+                break;
+            }
+            self.set_elapsed(action_time);
+            match action {
+                Action::Split => self.timer.split(),
+                Action::Skip => self.timer.skip_split(),
+            }
+            self.next_action += 1;
+        }
+
+        self.set_elapsed(time);
+
+        let state = self.layout.state(&self.timer.snapshot());
+        self.renderer.render(&state, self.resolution);
+        Some(self.renderer.image_data())
+    }
+
+    /// Renders all the remaining frames as PNG files into the directory
+    /// provided. The files are named by their frame number, starting at
+    /// `frame_000000.png`.
+    pub fn write_png_frames<P: AsRef<Path>>(&mut self, directory: P) -> Result {
+        let directory = directory.as_ref();
+        fs::create_dir_all(directory).context(Io)?;
+        while self.next_frame().is_some() {
+            let path = directory.join(format!("frame_{:06}.png", self.frame - 1));
+            self.renderer.image().save(path).context(EncodePng)?;
+        }
+        Ok(())
+    }
+
+    /// Renders all the remaining frames and writes them as raw RGBA8 encoded
+    /// pixels into the writer provided. The frames are written directly after
+    /// each other without any kind of header.
+    pub fn write_raw_frames<W: Write>(&mut self, mut writer: W) -> Result {
+        while let Some(frame) = self.next_frame() {
+            writer.write_all(frame).context(Io)?;
+        }
+        writer.flush().context(Io)
+    }
+
+    fn set_elapsed(&mut self, time: TimeSpan) {
+        if let Some(clock) = self.timer.clock_mut::<ManualClock>() {
+            clock.set_elapsed(time);
+        }
+        self.update_game_time(time + self.offset);
+    }
+
+    fn update_game_time(&mut self, real_time: TimeSpan) {
+        let game_times = match &self.game_times {
+            Some(game_times) => game_times,
+            None => return,
+        };
+        // The Game Time moves linearly between the splits. After the last
+        // split it doesn't move anymore.
+        let next = game_times
+            .iter()
+            .position(|&(split_time, _)| split_time > real_time);
+        let game_time = match next {
+            Some(next) => {
+                let (start_real, start_game) = game_times[next - 1];
+                let (end_real, end_game) = game_times[next];
+                let progress = (real_time - start_real).total_seconds()
+                    / (end_real - start_real).total_seconds();
+                start_game
+                    + TimeSpan::from_seconds((end_game - start_game).total_seconds() * progress)
+            }
+            None => game_times.last().unwrap().1,
+        };
+        self.timer.set_game_time(game_time);
+    }
+}
@@ -0,0 +1,367 @@
+//! Provides a renderer that turns the scene into a standalone SVG document. The
+//! paths are stored as vector graphics, so the document can be scaled to any
+//! resolution without losing quality. Images are embedded as PNG files.
+
+use std::{
+    collections::HashMap,
+    fmt::{self, Write},
+    hash::Hash,
+    sync::Arc,
+};
+
+use super::{
+    entity::Entity,
+    resource::{self, Handle, ResourceAllocator},
+    FillShader, Rgba, Scene, SceneManager, Transform,
+};
+use crate::layout::LayoutState;
+use image::{codecs::png, ColorType};
+
+type SvgPath = Arc<str>;
+type SvgImage = Arc<str>;
+
+struct SvgBuilder(String);
+
+impl resource::PathBuilder<SvgAllocator> for SvgBuilder {
+    type Path = SvgPath;
+
+    fn move_to(&mut self, x: f32, y: f32) {
+        let _ = write!(self.0, "M{} {}", Num(x), Num(y));
+    }
+
+    fn line_to(&mut self, x: f32, y: f32) {
+        let _ = write!(self.0, "L{} {}", Num(x), Num(y));
+    }
+
+    fn quad_to(&mut self, x1: f32, y1: f32, x: f32, y: f32) {
+        let _ = write!(self.0, "Q{} {} {} {}", Num(x1), Num(y1), Num(x), Num(y));
+    }
+
+    fn curve_to(&mut self, x1: f32, y1: f32, x2: f32, y2: f32, x: f32, y: f32) {
+        let _ = write!(
+            self.0,
+            "C{} {} {} {} {} {}",
+            Num(x1),
+            Num(y1),
+            Num(x2),
+            Num(y2),
+            Num(x),
+            Num(y),
+        );
+    }
+
+    fn close(&mut self) {
+        self.0.push('Z');
+    }
+
+    fn finish(self, _: &mut SvgAllocator) -> Self::Path {
+        self.0.into()
+    }
+}
+
+struct SvgAllocator;
+
+impl ResourceAllocator for SvgAllocator {
+    type PathBuilder = SvgBuilder;
+    type Path = SvgPath;
+    type Image = SvgImage;
+
+    fn path_builder(&mut self) -> Self::PathBuilder {
+        SvgBuilder(String::new())
+    }
+
+    fn create_image(&mut self, width: u32, height: u32, data: &[u8]) -> Self::Image {
+        let mut png = Vec::new();
+        if png::PngEncoder::new(&mut png)
+            .encode(data, width, height, ColorType::Rgba8)
+            .is_err()
+        {
+            return "".into();
+        }
+        format!("data:image/png;base64,{}", base64::encode(&png)).into()
+    }
+}
+
+/// The SVG renderer turns layouts into standalone SVG documents. All the paths
+/// are written out as vector graphics and all the images are embedded, so the
+/// document doesn't depend on any other files. As the document is resolution
+/// independent, the resolution provided only determines its default size and
+/// aspect ratio.
+pub struct Renderer {
+    scene_manager: SceneManager<SvgPath, SvgImage>,
+}
+
+impl Default for Renderer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Renderer {
+    /// Creates a new SVG renderer.
+    pub fn new() -> Self {
+        Self {
+            scene_manager: SceneManager::new(SvgAllocator),
+        }
+    }
+
+    /// Renders the layout state provided with the chosen resolution as an SVG
+    /// document into the writer provided. It may detect that the layout got
+    /// resized. In that case it returns the new ideal size. This is just a hint
+    /// and can be ignored entirely. The document is always rendered with the
+    /// resolution provided.
+    pub fn render<W: fmt::Write>(
+        &mut self,
+        writer: &mut W,
+        state: &LayoutState,
+        [width, height]: [f32; 2],
+    ) -> Result<Option<(f32, f32)>, fmt::Error> {
+        let new_resolution = self
+            .scene_manager
+            .update_scene(SvgAllocator, (width, height), state);
+
+        write_document(writer, self.scene_manager.scene(), width, height)?;
+
+        Ok(new_resolution)
+    }
+}
+
+/// Resources that are used multiple times are only stored once in the `defs`
+/// section of the document and referenced from there. The IDs are assigned in
+/// the order in which the resources are first used, so rendering the same
+/// layout state always results in the same document.
+struct Defs<'a> {
+    content: String,
+    paths: HashMap<&'a Handle<SvgPath>, usize>,
+    images: HashMap<&'a Handle<SvgImage>, usize>,
+    gradients: HashMap<String, usize>,
+}
+
+impl<'a> Defs<'a> {
+    fn path(&mut self, path: &'a Handle<SvgPath>) -> Result<usize, fmt::Error> {
+        define(&mut self.paths, &mut self.content, path, |content, id| {
+            writeln!(content, r#"<path id="p{}" d="{}"/>"#, id, &***path)
+        })
+    }
+
+    fn image(&mut self, image: &'a Handle<SvgImage>) -> Result<usize, fmt::Error> {
+        define(&mut self.images, &mut self.content, image, |content, id| {
+            writeln!(
+                content,
+                r#"<image id="i{}" width="1" height="1" preserveAspectRatio="none" xlink:href="{}"/>"#,
+                id, &***image,
+            )
+        })
+    }
+
+    fn gradient(&mut self, direction: &str, start: &Rgba, end: &Rgba) -> Result<usize, fmt::Error> {
+        let mut gradient = String::new();
+        write!(
+            gradient,
+            r#"{}><stop{}/><stop offset="1"{}/></linearGradient>"#,
+            direction,
+            StopColor(start),
+            StopColor(end),
+        )?;
+        define(
+            &mut self.gradients,
+            &mut self.content,
+            gradient.clone(),
+            |content, id| writeln!(content, r#"<linearGradient id="g{}"{}"#, id, gradient),
+        )
+    }
+
+    /// Returns the value of the fill attribute to use for the shader.
+    fn fill(&mut self, shader: &FillShader) -> Result<Fill, fmt::Error> {
+        Ok(match shader {
+            FillShader::SolidColor(color) => Fill::Color(*color),
+            FillShader::VerticalGradient(top, bottom) => {
+                Fill::Gradient(self.gradient(r#" x2="0" y2="1""#, top, bottom)?)
+            }
+            FillShader::HorizontalGradient(left, right) => {
+                Fill::Gradient(self.gradient("", left, right)?)
+            }
+        })
+    }
+}
+
+fn define<K: Hash + Eq>(
+    ids: &mut HashMap<K, usize>,
+    content: &mut String,
+    key: K,
+    write: impl FnOnce(&mut String, usize) -> fmt::Result,
+) -> Result<usize, fmt::Error> {
+    if let Some(&id) = ids.get(&key) {
+        return Ok(id);
+    }
+    let id = ids.len();
+    write(content, id)?;
+    ids.insert(key, id);
+    Ok(id)
+}
+
+enum Fill {
+    Color(Rgba),
+    Gradient(usize),
+}
+
+impl fmt::Display for Fill {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Fill::Color(color) => {
+                write!(f, r#" fill="{}""#, Color(color))?;
+                if color[3] < 1.0 {
+                    write!(f, r#" fill-opacity="{}""#, Num(color[3]))?;
+                }
+                Ok(())
+            }
+            Fill::Gradient(id) => write!(f, r#" fill="url(#g{})""#, id),
+        }
+    }
+}
+
+struct Color<'a>(&'a Rgba);
+
+impl fmt::Display for Color<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let &[r, g, b, _] = self.0;
+        let [r, g, b] = [r, g, b].map(|c| (c.clamp(0.0, 1.0) * 255.0).round() as u8);
+        write!(f, "#{:02x}{:02x}{:02x}", r, g, b)
+    }
+}
+
+struct StopColor<'a>(&'a Rgba);
+
+impl fmt::Display for StopColor<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, r#" stop-color="{}""#, Color(self.0))?;
+        if self.0[3] < 1.0 {
+            write!(f, r#" stop-opacity="{}""#, Num(self.0[3]))?;
+        }
+        Ok(())
+    }
+}
+
+struct Matrix<'a>(&'a Transform);
+
+impl fmt::Display for Matrix<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let [sx, ky, kx, sy, tx, ty] = self.0.to_array();
+        write!(
+            f,
+            r#" transform="matrix({} {} {} {} {} {})""#,
+            Num(sx),
+            Num(ky),
+            Num(kx),
+            Num(sy),
+            Num(tx),
+            Num(ty),
+        )
+    }
+}
+
+/// Formats a number rounded to 6 significant digits. This keeps the documents
+/// small and makes them independent of tiny differences in the floating point
+/// calculations between platforms.
+struct Num(f32);
+
+impl fmt::Display for Num {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let value = f64::from(self.0);
+        if value == 0.0 || !value.is_finite() {
+            return write!(f, "{}", value.abs());
+        }
+        let digits = 5 - value.abs().log10().floor() as i32;
+        let rounded = if digits >= 0 {
+            let scale = 10f64.powi(digits);
+            (value * scale).round() / scale
+        } else {
+            let scale = 10f64.powi(-digits);
+            (value / scale).round() * scale
+        };
+        // Adding zero turns negative zero into positive zero.
+        write!(f, "{}", rounded + 0.0)
+    }
+}
+
+fn write_document(
+    writer: &mut impl fmt::Write,
+    scene: &Scene<SvgPath, SvgImage>,
+    width: f32,
+    height: f32,
+) -> fmt::Result {
+    let mut defs = Defs {
+        content: String::new(),
+        paths: HashMap::new(),
+        images: HashMap::new(),
+        gradients: HashMap::new(),
+    };
+    let mut body = String::new();
+
+    if let Some(background) = scene.background() {
+        let fill = defs.fill(background)?;
+        writeln!(
+            body,
+            r#"<rect width="{}" height="{}"{}/>"#,
+            Num(width),
+            Num(height),
+            fill,
+        )?;
+    }
+
+    for entity in scene.bottom_layer().iter().chain(scene.top_layer()) {
+        match entity {
+            Entity::FillPath(path, shader, transform) => {
+                let id = defs.path(path)?;
+                let fill = defs.fill(shader)?;
+                writeln!(
+                    body,
+                    r##"<use xlink:href="#p{}"{}{}/>"##,
+                    id,
+                    fill,
+                    Matrix(transform),
+                )?;
+            }
+            Entity::StrokePath(path, stroke_width, color, transform) => {
+                let id = defs.path(path)?;
+                write!(
+                    body,
+                    r##"<use xlink:href="#p{}" fill="none" stroke="{}" stroke-width="{}""##,
+                    id,
+                    Color(color),
+                    Num(*stroke_width),
+                )?;
+                if color[3] < 1.0 {
+                    write!(body, r#" stroke-opacity="{}""#, Num(color[3]))?;
+                }
+                writeln!(body, "{}/>", Matrix(transform))?;
+            }
+            Entity::Image(image, transform) => {
+                // Images that couldn't be encoded are skipped.
+                if !image.is_empty() {
+                    let id = defs.image(image)?;
+                    writeln!(
+                        body,
+                        r##"<use xlink:href="#i{}"{}/>"##,
+                        id,
+                        Matrix(transform)
+                    )?;
+                }
+            }
+        }
+    }
+
+    writeln!(
+        writer,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" xmlns:xlink="http://www.w3.org/1999/xlink" width="{0}" height="{1}" viewBox="0 0 {0} {1}">"#,
+        Num(width),
+        Num(height),
+    )?;
+    if !defs.content.is_empty() {
+        writeln!(writer, "<defs>")?;
+        writer.write_str(&defs.content)?;
+        writeln!(writer, "</defs>")?;
+    }
+    writer.write_str(&body)?;
+    writeln!(writer, "</svg>")
+}
@@ -6,11 +6,12 @@
 //! occurrences of this and allows you to delete them individually if any of
 //! them seem wrong.
 
+use super::history::{Action, History};
 use crate::analysis::sum_of_segments::{best, track_branch, Prediction};
 use crate::platform::prelude::*;
 use crate::platform::Local;
 use crate::timing::formatter::{Short, TimeFormatter};
-use crate::{Attempt, Run, Segment, TimeSpan, TimingMethod};
+use crate::{Attempt, Run, Segment, Time, TimeSpan, TimingMethod};
 use core::fmt;
 use core::mem::replace;
 
@@ -24,6 +25,16 @@ pub struct SumOfBestCleaner<'r> {
     run: &'r mut Run,
     predictions: Vec<Option<Prediction>>,
     state: State,
+    recorder: Option<Recorder<'r>>,
+}
+
+/// Records the clean ups applied by a Sum of Best Cleaner that was created by
+/// the Run Editor, so they can be undone.
+pub(super) struct Recorder<'r> {
+    pub history: &'r mut History,
+    pub selected_segments: &'r [usize],
+    pub active_segment: usize,
+    pub previous_personal_best_time: Time,
 }
 
 enum State {
@@ -55,7 +66,7 @@ pub struct PotentialCleanUp<'r> {
     time_between: TimeSpan,
     combined_sum_of_best: Option<TimeSpan>,
     attempt: &'r Attempt,
-    method: TimingMethod,
+    method_name: &'r str,
     clean_up: CleanUp,
 }
 
@@ -69,15 +80,10 @@ impl fmt::Display for PotentialCleanUp<'_> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let short = Short::new();
 
-        let method = match self.method {
-            TimingMethod::RealTime => "Real Time",
-            TimingMethod::GameTime => "Game Time",
-        };
-
         write!(
             f,
             "You had a {} segment time of {} between ",
-            method,
+            self.method_name,
             short.format(self.time_between)
         )?;
 
@@ -126,12 +132,30 @@ impl<'r> SumOfBestCleaner<'r> {
             run,
             predictions,
             state: State::WithTimingMethod(TimingMethod::RealTime),
+            recorder: None,
+        }
+    }
+
+    pub(super) fn with_history(run: &'r mut Run, recorder: Recorder<'r>) -> Self {
+        Self {
+            recorder: Some(recorder),
+            ..Self::new(run)
         }
     }
 
     /// Applies a clean up to the Run.
     #[allow(clippy::needless_pass_by_value)]
     pub fn apply(&mut self, clean_up: CleanUp) {
+        if let Some(recorder) = &mut self.recorder {
+            recorder.history.record(
+                Action::CleanSumOfBest,
+                recorder.active_segment,
+                self.run,
+                recorder.selected_segments,
+                recorder.previous_personal_best_time,
+            );
+        }
+
         self.run
             .segment_mut(clean_up.ending_index)
             .segment_history_mut()
@@ -162,8 +186,13 @@ impl<'r> SumOfBestCleaner<'r> {
                             current_time: current_prediction.map(|p| p.time),
                             skip_count: 0,
                         })
-                    } else if state.method == TimingMethod::RealTime {
-                        State::WithTimingMethod(TimingMethod::GameTime)
+                    } else if let Some(method) = self
+                        .run
+                        .timing_methods()
+                        .skip_while(|&method| method != state.method)
+                        .nth(1)
+                    {
+                        State::WithTimingMethod(method)
                     } else {
                         State::Done
                     };
@@ -249,7 +278,7 @@ fn check_prediction<'a>(
                         .iter()
                         .find(|attempt| attempt.index() == run_index)
                         .expect("The attempt has to exist"),
-                    method,
+                    method_name: run.timing_method_name(method),
                     clean_up: CleanUp {
                         ending_index,
                         run_index,
@@ -0,0 +1,602 @@
+use crate::{
+    platform::prelude::*,
+    run::{RunMetadata, SegmentGroups},
+    settings::Image,
+    Attempt, Run, Segment, Time, TimeSpan, TimingMethod,
+};
+use core::mem::swap;
+use serde::{Deserialize, Serialize};
+
+/// The maximum amount of actions that can be undone. If more actions are
+/// applied, the oldest ones can't be undone anymore.
+const MAX_UNDO_ACTIONS: usize = 100;
+
+/// Describes an action that modified the Run in the Run Editor. Every action
+/// can be undone and redone.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Action {
+    /// The name of the game was changed.
+    SetGameName,
+    /// The name of the category was changed.
+    SetCategoryName,
+    /// The timer offset was changed.
+    SetOffset,
+    /// The attempt count was changed.
+    SetAttemptCount,
+    /// The game's icon was changed.
+    SetGameIcon,
+    /// The game's icon was removed.
+    RemoveGameIcon,
+    /// The speedrun.com Run ID was changed.
+    SetRunId,
+    /// The name of the region was changed.
+    SetRegionName,
+    /// The name of the platform was changed.
+    SetPlatformName,
+    /// Whether an emulator is used was changed.
+    SetEmulatorUsage,
+    /// The frame rate was changed.
+    SetFrameRate,
+    /// A speedrun.com variable was changed.
+    SetSpeedrunComVariable,
+    /// A speedrun.com variable was removed.
+    RemoveSpeedrunComVariable,
+    /// A custom variable was added.
+    AddCustomVariable,
+    /// The value of a custom variable was changed.
+    SetCustomVariable,
+    /// A custom variable was removed.
+    RemoveCustomVariable,
+    /// All the metadata was cleared.
+    ClearMetadata,
+    /// The icon of a segment was changed.
+    SetSegmentIcon,
+    /// The icon of a segment was removed.
+    RemoveSegmentIcon,
+    /// The name of a segment was changed.
+    SetSegmentName,
+    /// The split time of a segment was changed.
+    SetSplitTime,
+    /// The segment time of a segment was changed.
+    SetSegmentTime,
+    /// The best segment time of a segment was changed.
+    SetBestSegmentTime,
+    /// A comparison time of a segment was changed.
+    SetComparisonTime,
+    /// A segment was inserted.
+    InsertSegment,
+    /// The selected segments were removed.
+    RemoveSegments,
+    /// The selected segments were moved up.
+    MoveSegmentsUp,
+    /// The selected segments were moved down.
+    MoveSegmentsDown,
+    /// A segment was split into two segments.
+    SplitSegment,
+    /// The selected segments were merged into a single segment.
+    MergeSegments,
+    /// The segments were reordered.
+    ReorderSegments,
+    /// A Segment Group was created.
+    CreateSegmentGroup,
+    /// Segment Groups were merged.
+    MergeSegmentGroups,
+    /// Segment Groups were dissolved.
+    DissolveSegmentGroups,
+    /// The name of a Segment Group was changed.
+    SetSegmentGroupName,
+    /// A Segment Group was collapsed or expanded.
+    SetSegmentGroupCollapsed,
+    /// A custom comparison was added.
+    AddComparison,
+    /// A comparison was imported from another Run.
+    ImportComparison,
+    /// A custom comparison was removed.
+    RemoveComparison,
+    /// A custom comparison was renamed.
+    RenameComparison,
+    /// A custom comparison was moved.
+    MoveComparison,
+    /// A goal comparison was generated.
+    GenerateGoalComparison,
+    /// The name of a custom timing method was changed.
+    SetCustomTimingMethodName,
+    /// The history was cleared.
+    ClearHistory,
+    /// All the times were cleared.
+    ClearTimes,
+    /// A clean up of the Sum of Best Cleaner was applied.
+    CleanSumOfBest,
+}
+
+impl Action {
+    /// Returns a label describing the action that can be shown to the user,
+    /// for example as part of an undo or redo button.
+    pub const fn label(self) -> &'static str {
+        match self {
+            Action::SetGameName => "Change Game Name",
+            Action::SetCategoryName => "Change Category Name",
+            Action::SetOffset => "Change Offset",
+            Action::SetAttemptCount => "Change Attempt Count",
+            Action::SetGameIcon => "Change Game Icon",
+            Action::RemoveGameIcon => "Remove Game Icon",
+            Action::SetRunId => "Change Run ID",
+            Action::SetRegionName => "Change Region",
+            Action::SetPlatformName => "Change Platform",
+            Action::SetEmulatorUsage => "Change Emulator Usage",
+            Action::SetFrameRate => "Change Frame Rate",
+            Action::SetSpeedrunComVariable => "Change Variable",
+            Action::RemoveSpeedrunComVariable => "Remove Variable",
+            Action::AddCustomVariable => "Add Custom Variable",
+            Action::SetCustomVariable => "Change Custom Variable",
+            Action::RemoveCustomVariable => "Remove Custom Variable",
+            Action::ClearMetadata => "Clear Metadata",
+            Action::SetSegmentIcon => "Change Segment Icon",
+            Action::RemoveSegmentIcon => "Remove Segment Icon",
+            Action::SetSegmentName => "Change Segment Name",
+            Action::SetSplitTime => "Change Split Time",
+            Action::SetSegmentTime => "Change Segment Time",
+            Action::SetBestSegmentTime => "Change Best Segment",
+            Action::SetComparisonTime => "Change Comparison Time",
+            Action::InsertSegment => "Insert Segment",
+            Action::RemoveSegments => "Remove Segments",
+            Action::MoveSegmentsUp => "Move Segments Up",
+            Action::MoveSegmentsDown => "Move Segments Down",
+            Action::SplitSegment => "Split Segment",
+            Action::MergeSegments => "Merge Segments",
+            Action::ReorderSegments => "Reorder Segments",
+            Action::CreateSegmentGroup => "Create Segment Group",
+            Action::MergeSegmentGroups => "Merge Segment Groups",
+            Action::DissolveSegmentGroups => "Dissolve Segment Groups",
+            Action::SetSegmentGroupName => "Change Segment Group Name",
+            Action::SetSegmentGroupCollapsed => "Collapse Segment Group",
+            Action::AddComparison => "Add Comparison",
+            Action::ImportComparison => "Import Comparison",
+            Action::RemoveComparison => "Remove Comparison",
+            Action::RenameComparison => "Rename Comparison",
+            Action::MoveComparison => "Move Comparison",
+            Action::GenerateGoalComparison => "Generate Goal Comparison",
+            Action::SetCustomTimingMethodName => "Rename Custom Timing Method",
+            Action::ClearHistory => "Clear History",
+            Action::ClearTimes => "Clear Times",
+            Action::CleanSumOfBest => "Clean Sum of Best",
+        }
+    }
+
+    /// Returns the parts of the Run that the action may change. Only those are
+    /// stored for undoing it.
+    const fn scope(self) -> Scope {
+        match self {
+            Action::SetGameName
+            | Action::SetCategoryName
+            | Action::SetOffset
+            | Action::SetAttemptCount
+            | Action::SetGameIcon
+            | Action::RemoveGameIcon
+            | Action::SetRunId
+            | Action::SetRegionName
+            | Action::SetPlatformName
+            | Action::SetEmulatorUsage
+            | Action::SetFrameRate
+            | Action::SetSpeedrunComVariable
+            | Action::RemoveSpeedrunComVariable
+            | Action::AddCustomVariable
+            | Action::SetCustomVariable
+            | Action::RemoveCustomVariable
+            | Action::ClearMetadata
+            | Action::SetCustomTimingMethodName => Scope {
+                info: true,
+                ..Scope::NOTHING
+            },
+            Action::SetSegmentIcon | Action::RemoveSegmentIcon | Action::SetSegmentName => Scope {
+                active_segment: true,
+                ..Scope::NOTHING
+            },
+            Action::CreateSegmentGroup
+            | Action::MergeSegmentGroups
+            | Action::DissolveSegmentGroups
+            | Action::SetSegmentGroupName
+            | Action::SetSegmentGroupCollapsed => Scope {
+                segment_groups: true,
+                ..Scope::NOTHING
+            },
+            // Changing times fixes up the splits of all the segments and may
+            // dissociate the Run from its speedrun.com Run ID.
+            Action::SetSplitTime
+            | Action::SetSegmentTime
+            | Action::SetBestSegmentTime
+            | Action::SetComparisonTime
+            | Action::AddComparison
+            | Action::ImportComparison
+            | Action::RemoveComparison
+            | Action::RenameComparison
+            | Action::MoveComparison
+            | Action::GenerateGoalComparison
+            | Action::CleanSumOfBest => Scope {
+                info: true,
+                segments: true,
+                ..Scope::NOTHING
+            },
+            Action::InsertSegment
+            | Action::RemoveSegments
+            | Action::MoveSegmentsUp
+            | Action::MoveSegmentsDown
+            | Action::SplitSegment
+            | Action::MergeSegments
+            | Action::ReorderSegments => Scope {
+                info: true,
+                segments: true,
+                segment_groups: true,
+                ..Scope::NOTHING
+            },
+            Action::ClearHistory | Action::ClearTimes => Scope {
+                info: true,
+                segments: true,
+                attempt_history: true,
+                ..Scope::NOTHING
+            },
+        }
+    }
+
+    /// Text fields are usually applied on every key stroke, so consecutive
+    /// changes to the same text are merged into a single action.
+    const fn is_merged(self) -> bool {
+        matches!(
+            self,
+            Action::SetGameName
+                | Action::SetCategoryName
+                | Action::SetRunId
+                | Action::SetRegionName
+                | Action::SetPlatformName
+                | Action::SetSegmentName
+                | Action::SetSegmentGroupName
+        )
+    }
+}
+
+/// The parts of the Run that aren't stored per segment and that can be changed
+/// by the Run Editor.
+struct Info {
+    game_icon: Image,
+    game_name: String,
+    category_name: String,
+    offset: TimeSpan,
+    attempt_count: u32,
+    metadata: RunMetadata,
+    custom_comparisons: Vec<String>,
+    custom_timing_methods: [String; TimingMethod::CUSTOM_COUNT],
+}
+
+impl Info {
+    fn new(run: &Run) -> Self {
+        Self {
+            game_icon: run.game_icon.clone(),
+            game_name: run.game_name.clone(),
+            category_name: run.category_name.clone(),
+            offset: run.offset,
+            attempt_count: run.attempt_count,
+            metadata: run.metadata.clone(),
+            custom_comparisons: run.custom_comparisons.clone(),
+            custom_timing_methods: run.custom_timing_methods.clone(),
+        }
+    }
+
+    fn differs_from(&self, run: &Run) -> bool {
+        self.game_icon != run.game_icon
+            || self.game_name != run.game_name
+            || self.category_name != run.category_name
+            || self.offset != run.offset
+            || self.attempt_count != run.attempt_count
+            || self.metadata != run.metadata
+            || self.custom_comparisons != run.custom_comparisons
+            || self.custom_timing_methods != run.custom_timing_methods
+    }
+
+    const fn swap(&mut self, run: &mut Run) {
+        swap(&mut self.game_icon, &mut run.game_icon);
+        swap(&mut self.game_name, &mut run.game_name);
+        swap(&mut self.category_name, &mut run.category_name);
+        swap(&mut self.offset, &mut run.offset);
+        swap(&mut self.attempt_count, &mut run.attempt_count);
+        swap(&mut self.metadata, &mut run.metadata);
+        swap(&mut self.custom_comparisons, &mut run.custom_comparisons);
+        swap(
+            &mut self.custom_timing_methods,
+            &mut run.custom_timing_methods,
+        );
+    }
+}
+
+/// A part of the Run that got changed by an action. It stores the part's
+/// value from before the action (or after, for the redo stack), so undoing
+/// and redoing is a matter of swapping it with the Run's current value.
+enum Change {
+    Info(Box<Info>),
+    /// A single segment changed, while the amount of segments stayed the same.
+    Segment(usize, Box<Segment>),
+    /// The amount of segments changed, so all of them are stored.
+    Segments(Vec<Segment>),
+    SegmentGroups(SegmentGroups),
+    AttemptHistory(Vec<Attempt>),
+}
+
+impl Change {
+    fn swap(&mut self, run: &mut Run) {
+        match self {
+            Change::Info(info) => info.swap(run),
+            Change::Segment(index, segment) => swap(&mut **segment, &mut run.segments[*index]),
+            Change::Segments(segments) => swap(segments, &mut run.segments),
+            Change::SegmentGroups(groups) => swap(groups, &mut run.segment_groups),
+            Change::AttemptHistory(history) => swap(history, &mut run.attempt_history),
+        }
+    }
+}
+
+/// The parts of the Run that an action may change.
+#[derive(Copy, Clone)]
+struct Scope {
+    info: bool,
+    active_segment: bool,
+    segments: bool,
+    segment_groups: bool,
+    attempt_history: bool,
+}
+
+impl Scope {
+    const NOTHING: Scope = Scope {
+        info: false,
+        active_segment: false,
+        segments: false,
+        segment_groups: false,
+        attempt_history: false,
+    };
+}
+
+/// The parts of the Run from before the action that is currently being
+/// applied. Only the parts that are in the scope of the action are kept.
+struct Before {
+    info: Option<Box<Info>>,
+    active_segment: Option<(usize, Box<Segment>)>,
+    segments: Option<Vec<Segment>>,
+    segment_groups: Option<SegmentGroups>,
+    attempt_history: Option<Vec<Attempt>>,
+}
+
+impl Before {
+    fn new(scope: Scope, run: &Run, active_segment: usize) -> Self {
+        Self {
+            info: if scope.info {
+                Some(Box::new(Info::new(run)))
+            } else {
+                None
+            },
+            active_segment: if scope.active_segment {
+                Some((
+                    active_segment,
+                    Box::new(run.segment(active_segment).clone()),
+                ))
+            } else {
+                None
+            },
+            segments: if scope.segments {
+                Some(run.segments.clone())
+            } else {
+                None
+            },
+            segment_groups: if scope.segment_groups {
+                Some(run.segment_groups.clone())
+            } else {
+                None
+            },
+            attempt_history: if scope.attempt_history {
+                Some(run.attempt_history.clone())
+            } else {
+                None
+            },
+        }
+    }
+
+    /// Checks whether any of the stored parts differ from the Run.
+    fn differs_from(&self, run: &Run) -> bool {
+        matches!(&self.info, Some(info) if info.differs_from(run))
+            || matches!(&self.active_segment, Some((index, segment)) if **segment != run.segments[*index])
+            || matches!(&self.segments, Some(segments) if *segments != run.segments)
+            || matches!(&self.segment_groups, Some(groups) if *groups != run.segment_groups)
+            || matches!(&self.attempt_history, Some(history) if *history != run.attempt_history)
+    }
+
+    /// Determines the parts of the Run that got changed by comparing it with
+    /// the state from before the action.
+    fn into_changes(self, run: &Run) -> Vec<Change> {
+        let mut changes = Vec::new();
+        if let Some(info) = self.info {
+            if info.differs_from(run) {
+                changes.push(Change::Info(info));
+            }
+        }
+        if let Some((index, segment)) = self.active_segment {
+            if *segment != run.segments[index] {
+                changes.push(Change::Segment(index, segment));
+            }
+        }
+        if let Some(segments) = self.segments {
+            if segments.len() == run.segments.len() {
+                changes.extend(
+                    segments
+                        .into_iter()
+                        .zip(&run.segments)
+                        .enumerate()
+                        .filter(|(_, (before, after))| before != *after)
+                        .map(|(index, (before, _))| Change::Segment(index, Box::new(before))),
+                );
+            } else {
+                changes.push(Change::Segments(segments));
+            }
+        }
+        if let Some(segment_groups) = self.segment_groups {
+            if segment_groups != run.segment_groups {
+                changes.push(Change::SegmentGroups(segment_groups));
+            }
+        }
+        if let Some(attempt_history) = self.attempt_history {
+            if attempt_history != run.attempt_history {
+                changes.push(Change::AttemptHistory(attempt_history));
+            }
+        }
+        changes
+    }
+}
+
+/// The state of the Run Editor that is affected by undoing and redoing an
+/// action.
+pub(super) struct State<'a> {
+    pub run: &'a mut Run,
+    pub selected_segments: &'a mut Vec<usize>,
+    pub previous_personal_best_time: &'a mut Time,
+}
+
+struct Entry {
+    action: Action,
+    changes: Vec<Change>,
+    selected_segments: Vec<usize>,
+    previous_personal_best_time: Time,
+}
+
+impl Entry {
+    fn swap(&mut self, state: &mut State<'_>) {
+        for change in &mut self.changes {
+            change.swap(state.run);
+        }
+        swap(&mut self.selected_segments, state.selected_segments);
+        swap(
+            &mut self.previous_personal_best_time,
+            state.previous_personal_best_time,
+        );
+    }
+}
+
+/// An action that is currently being applied. The parts of the Run it changed
+/// are determined once the next action is recorded or the action is undone.
+struct Pending {
+    action: Action,
+    segment_index: usize,
+    before: Before,
+    selected_segments: Vec<usize>,
+    previous_personal_best_time: Time,
+}
+
+/// The undo and redo stacks of the Run Editor. Each entry stores the parts of
+/// the Run that were changed by the action, with their values from right
+/// before (or after, for the redo stack) the action was applied.
+#[derive(Default)]
+pub(super) struct History {
+    undo: Vec<Entry>,
+    redo: Vec<Entry>,
+    pending: Option<Pending>,
+    can_merge: bool,
+}
+
+impl History {
+    /// Records the state of the editor before the action provided is applied.
+    /// The segment index is used to decide whether consecutive changes to a
+    /// segment's text can be merged.
+    pub fn record(
+        &mut self,
+        action: Action,
+        segment_index: usize,
+        run: &Run,
+        selected_segments: &[usize],
+        previous_personal_best_time: Time,
+    ) {
+        if self.can_merge && action.is_merged() {
+            if let Some(pending) = &self.pending {
+                if pending.action == action && pending.segment_index == segment_index {
+                    return;
+                }
+            }
+        }
+
+        self.finish_pending(run);
+
+        self.pending = Some(Pending {
+            action,
+            segment_index,
+            before: Before::new(action.scope(), run, segment_index),
+            selected_segments: selected_segments.to_vec(),
+            previous_personal_best_time,
+        });
+        self.can_merge = true;
+    }
+
+    fn finish_pending(&mut self, run: &Run) {
+        if let Some(pending) = self.pending.take() {
+            // Actions that didn't change anything can't be undone and don't
+            // discard the actions that can be redone.
+            let changes = pending.before.into_changes(run);
+            if changes.is_empty() {
+                return;
+            }
+            self.redo.clear();
+            if self.undo.len() == MAX_UNDO_ACTIONS {
+                self.undo.remove(0);
+            }
+            self.undo.push(Entry {
+                action: pending.action,
+                changes,
+                selected_segments: pending.selected_segments,
+                previous_personal_best_time: pending.previous_personal_best_time,
+            });
+        }
+    }
+
+    /// Returns the action that is currently being applied if it changed the
+    /// Run.
+    fn pending_action(&self, run: &Run) -> Option<Action> {
+        self.pending
+            .as_ref()
+            .filter(|pending| pending.before.differs_from(run))
+            .map(|pending| pending.action)
+    }
+
+    pub fn undo_action(&self, run: &Run) -> Option<Action> {
+        self.pending_action(run)
+            .or_else(|| self.undo.last().map(|e| e.action))
+    }
+
+    pub fn redo_action(&self, run: &Run) -> Option<Action> {
+        if self.pending_action(run).is_some() {
+            return None;
+        }
+        self.redo.last().map(|e| e.action)
+    }
+
+    /// Restores the state of the editor from before the most recent action.
+    /// Returns `false` if there's nothing to undo.
+    pub fn undo(&mut self, mut state: State<'_>) -> bool {
+        self.finish_pending(state.run);
+        match self.undo.pop() {
+            Some(mut entry) => {
+                entry.swap(&mut state);
+                self.redo.push(entry);
+                self.can_merge = false;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Restores the state of the editor from after the most recently undone
+    /// action. Returns `false` if there's nothing to redo.
+    pub fn redo(&mut self, mut state: State<'_>) -> bool {
+        self.finish_pending(state.run);
+        match self.redo.pop() {
+            Some(mut entry) => {
+                entry.swap(&mut state);
+                self.undo.push(entry);
+                self.can_merge = false;
+                true
+            }
+            None => false,
+        }
+    }
+}
@@ -8,15 +8,22 @@ use super::{ComparisonError, ComparisonResult};
 use crate::{
     comparison,
     platform::prelude::*,
+    run::SegmentGroup,
     settings::{CachedImageId, Image},
-    timing::ParseError as ParseTimeSpanError,
+    timing::{FrameRate, ParseError as ParseTimeSpanError},
     Run, Segment, Time, TimeSpan, TimingMethod,
 };
-use core::{mem::swap, num::ParseIntError};
+use core::{
+    mem::{swap, take},
+    num::ParseIntError,
+    ops::Range,
+};
+use history::{History, State as HistoryState};
 use snafu::{OptionExt, ResultExt};
 
 pub mod cleaning;
 mod fuzzy_list;
+mod history;
 mod segment_row;
 mod state;
 #[cfg(test)]
@@ -25,8 +32,12 @@ mod tests;
 pub use self::{
     cleaning::SumOfBestCleaner,
     fuzzy_list::FuzzyList,
+    history::Action,
     segment_row::SegmentRow,
-    state::{Buttons as ButtonsState, Segment as SegmentState, SelectionState, State},
+    state::{
+        Buttons as ButtonsState, Segment as SegmentState, SegmentGroup as SegmentGroupState,
+        SelectionState, State,
+    },
 };
 
 /// Describes an Error that occurred while parsing a time.
@@ -37,6 +48,11 @@ pub enum ParseError {
         /// The underlying error.
         source: ParseTimeSpanError,
     },
+    /// Couldn't parse the amount of frames.
+    ParseFrames {
+        /// The underlying error.
+        source: ParseIntError,
+    },
     /// Negative times are not allowed here.
     NegativeTimeNotAllowed,
     /// Empty times are not allowed here.
@@ -75,6 +91,7 @@ pub struct Editor {
     game_icon_id: CachedImageId,
     segment_icon_ids: Vec<CachedImageId>,
     segment_times: Vec<Option<TimeSpan>>,
+    history: History,
 }
 
 impl Editor {
@@ -97,6 +114,7 @@ impl Editor {
             game_icon_id: CachedImageId::default(),
             segment_icon_ids: Vec::with_capacity(len),
             segment_times: Vec::with_capacity(len),
+            history: History::default(),
         };
 
         editor.update_segment_list();
@@ -130,6 +148,20 @@ impl Editor {
         self.update_segment_list();
     }
 
+    /// Sets the name of the custom timing method provided. An empty name marks
+    /// the custom timing method as not being in use by the Run. Real Time and
+    /// Game Time can't be renamed, so nothing happens for them.
+    pub fn set_custom_timing_method_name<S>(&mut self, method: TimingMethod, name: S)
+    where
+        S: AsRef<str>,
+    {
+        if method.is_custom() {
+            self.record(Action::SetCustomTimingMethodName);
+            self.run.set_custom_timing_method_name(method, name);
+            self.raise_run_edited();
+        }
+    }
+
     fn active_segment_index(&self) -> usize {
         *self.selected_segments.last().unwrap()
     }
@@ -208,6 +240,76 @@ impl Editor {
         self.run.mark_as_modified();
     }
 
+    /// Records the current state, so the action that is about to be applied
+    /// can be undone.
+    fn record(&mut self, action: Action) {
+        let active_segment = self.active_segment_index();
+        self.history.record(
+            action,
+            active_segment,
+            &self.run,
+            &self.selected_segments,
+            self.previous_personal_best_time,
+        );
+    }
+
+    fn restored(&mut self) {
+        self.update_segment_list();
+        self.raise_run_edited();
+    }
+
+    /// Accesses the most recent action that can be undone. Returns `None` if
+    /// there is nothing to undo.
+    pub fn undo_action(&self) -> Option<Action> {
+        self.history.undo_action(&self.run)
+    }
+
+    /// Accesses the most recently undone action that can be redone. Returns
+    /// `None` if there is nothing to redo.
+    pub fn redo_action(&self) -> Option<Action> {
+        self.history.redo_action(&self.run)
+    }
+
+    /// Checks if there is an action that can be undone.
+    pub fn can_undo(&self) -> bool {
+        self.undo_action().is_some()
+    }
+
+    /// Checks if there is an action that can be redone.
+    pub fn can_redo(&self) -> bool {
+        self.redo_action().is_some()
+    }
+
+    /// Undoes the most recent action. The Run and the segment selection are
+    /// restored to the state before the action was applied. Returns `false`
+    /// if there is nothing to undo.
+    pub fn undo(&mut self) -> bool {
+        let undone = self.history.undo(HistoryState {
+            run: &mut self.run,
+            selected_segments: &mut self.selected_segments,
+            previous_personal_best_time: &mut self.previous_personal_best_time,
+        });
+        if undone {
+            self.restored();
+        }
+        undone
+    }
+
+    /// Redoes the most recently undone action. Any new action that is applied
+    /// after undoing an action discards all the actions that could be redone.
+    /// Returns `false` if there is nothing to redo.
+    pub fn redo(&mut self) -> bool {
+        let redone = self.history.redo(HistoryState {
+            run: &mut self.run,
+            selected_segments: &mut self.selected_segments,
+            previous_personal_best_time: &mut self.previous_personal_best_time,
+        });
+        if redone {
+            self.restored();
+        }
+        redone
+    }
+
     /// Accesses the name of the game.
     pub fn game_name(&self) -> &str {
         self.run.game_name()
@@ -218,6 +320,7 @@ impl Editor {
     where
         S: AsRef<str>,
     {
+        self.record(Action::SetGameName);
         self.run.set_game_name(name);
         self.raise_run_edited();
         self.run.clear_run_id();
@@ -233,6 +336,7 @@ impl Editor {
     where
         S: AsRef<str>,
     {
+        self.record(Action::SetCategoryName);
         self.run.set_category_name(name);
         self.raise_run_edited();
         self.run.clear_run_id();
@@ -247,6 +351,7 @@ impl Editor {
     /// Sets the timer offset. The timer offset specifies the time, the timer
     /// starts at when starting a new attempt.
     pub fn set_offset(&mut self, offset: TimeSpan) {
+        self.record(Action::SetOffset);
         self.run.set_offset(offset);
         self.raise_run_edited();
     }
@@ -258,7 +363,7 @@ impl Editor {
     where
         S: AsRef<str>,
     {
-        self.set_offset(offset.as_ref().parse().context(ParseTime)?);
+        self.set_offset(self.parse_time(offset.as_ref())?);
         Ok(())
     }
 
@@ -271,6 +376,7 @@ impl Editor {
     /// history or the segment history. This number is mostly just a visual
     /// number for the runner.
     pub fn set_attempt_count(&mut self, attempts: u32) {
+        self.record(Action::SetAttemptCount);
         self.run.set_attempt_count(attempts);
         self.raise_run_edited();
     }
@@ -293,12 +399,14 @@ impl Editor {
 
     /// Sets the game's icon.
     pub fn set_game_icon<D: Into<Image>>(&mut self, image: D) {
+        self.record(Action::SetGameIcon);
         self.run.set_game_icon(image);
         self.raise_run_edited();
     }
 
     /// Removes the game's icon.
     pub fn remove_game_icon(&mut self) {
+        self.record(Action::RemoveGameIcon);
         self.run.set_game_icon(&[]);
         self.raise_run_edited();
     }
@@ -315,6 +423,7 @@ impl Editor {
     where
         S: AsRef<str>,
     {
+        self.record(Action::SetRunId);
         self.run.metadata_mut().set_run_id(id);
         self.raise_run_edited();
     }
@@ -325,6 +434,7 @@ impl Editor {
     where
         S: AsRef<str>,
     {
+        self.record(Action::SetRegionName);
         self.run.metadata_mut().set_region_name(name);
         self.metadata_modified();
     }
@@ -335,13 +445,30 @@ impl Editor {
     where
         S: AsRef<str>,
     {
+        self.record(Action::SetPlatformName);
         self.run.metadata_mut().set_platform_name(name);
         self.metadata_modified();
     }
 
+    /// Accesses the frame rate the game runs at. If it is specified, the times
+    /// are entered, shown and rounded as frames at this frame rate.
+    pub const fn frame_rate(&self) -> Option<FrameRate> {
+        self.run.metadata().frame_rate()
+    }
+
+    /// Sets the frame rate the game runs at. If it is specified, the times
+    /// are entered, shown and rounded as frames at this frame rate. The times
+    /// that are already stored are not modified.
+    pub fn set_frame_rate(&mut self, frame_rate: Option<FrameRate>) {
+        self.record(Action::SetFrameRate);
+        self.run.metadata_mut().set_frame_rate(frame_rate);
+        self.raise_run_edited();
+    }
+
     /// Specifies whether this speedrun is done on an emulator. Keep in mind
     /// that `false` may also mean that this information is simply not known.
     pub fn set_emulator_usage(&mut self, uses_emulator: bool) {
+        self.record(Action::SetEmulatorUsage);
         self.run.metadata_mut().set_emulator_usage(uses_emulator);
         self.metadata_modified();
     }
@@ -356,6 +483,7 @@ impl Editor {
         N: Into<String>,
         V: Into<String>,
     {
+        self.record(Action::SetSpeedrunComVariable);
         self.run
             .metadata_mut()
             .set_speedrun_com_variable(name, value);
@@ -367,6 +495,7 @@ impl Editor {
     where
         S: AsRef<str>,
     {
+        self.record(Action::RemoveSpeedrunComVariable);
         self.run.metadata_mut().remove_speedrun_com_variable(name);
         self.metadata_modified();
     }
@@ -379,6 +508,7 @@ impl Editor {
     where
         N: Into<String>,
     {
+        self.record(Action::AddCustomVariable);
         self.run
             .metadata_mut()
             .custom_variable_mut(name)
@@ -395,15 +525,19 @@ impl Editor {
         V: AsRef<str>,
     {
         let name = name.into();
-        if self.run.metadata().custom_variable(&name).is_none() {
+        if !self
+            .run
+            .metadata()
+            .custom_variable(&name)
+            .map_or(false, |v| v.is_permanent)
+        {
             return;
         }
+        self.record(Action::SetCustomVariable);
         let variable = self.run.metadata_mut().custom_variable_mut(name);
-        if variable.is_permanent {
-            variable.value.clear();
-            variable.value.push_str(value.as_ref());
-            self.raise_run_edited();
-        }
+        variable.value.clear();
+        variable.value.push_str(value.as_ref());
+        self.raise_run_edited();
     }
 
     /// Removes the custom variable with the name specified. If the custom
@@ -416,6 +550,7 @@ impl Editor {
         let name = name.as_ref();
         if let Some(variable) = self.run.metadata().custom_variable(name) {
             if variable.is_permanent {
+                self.record(Action::RemoveCustomVariable);
                 self.run.metadata_mut().remove_custom_variable(name);
                 self.raise_run_edited();
             }
@@ -424,6 +559,7 @@ impl Editor {
 
     /// Resets all the Metadata Information.
     pub fn clear_metadata(&mut self) {
+        self.record(Action::ClearMetadata);
         self.run.metadata_mut().clear();
         self.raise_run_edited();
     }
@@ -491,6 +627,7 @@ impl Editor {
     /// Run's history information accordingly. The newly created segment is then
     /// the only selected segment and also the active segment.
     pub fn insert_segment_above(&mut self) {
+        self.record(Action::InsertSegment);
         let active_segment = self.active_segment_index();
 
         let mut segment = Segment::new("");
@@ -502,6 +639,7 @@ impl Editor {
             segment.segment_history_mut().insert(x, Default::default());
         }
         self.run.segments_mut().insert(active_segment, segment);
+        self.run.segment_groups_mut().insert_segment(active_segment);
 
         self.select_only(active_segment);
 
@@ -513,6 +651,7 @@ impl Editor {
     /// Run's history information accordingly. The newly created segment is then
     /// the only selected segment and also the active segment.
     pub fn insert_segment_below(&mut self) {
+        self.record(Action::InsertSegment);
         let active_segment = self.active_segment_index();
         let next_segment = active_segment + 1;
 
@@ -527,6 +666,7 @@ impl Editor {
             segment.segment_history_mut().insert(x, Default::default());
         }
         self.run.segments_mut().insert(next_segment, segment);
+        self.run.segment_groups_mut().insert_segment(next_segment);
 
         self.select_only(next_segment);
 
@@ -535,8 +675,9 @@ impl Editor {
     }
 
     fn fix_after_deletion(&mut self, index: usize) {
-        self.fix_with_timing_method(index, TimingMethod::RealTime);
-        self.fix_with_timing_method(index, TimingMethod::GameTime);
+        for &method in &TimingMethod::all() {
+            self.fix_with_timing_method(index, method);
+        }
     }
 
     fn fix_with_timing_method(&mut self, index: usize, method: TimingMethod) {
@@ -620,12 +761,15 @@ impl Editor {
             return;
         }
 
+        self.record(Action::RemoveSegments);
+
         let mut removed = 0;
         for i in 0..self.run.len() {
             if self.selected_segments.contains(&i) {
                 let segment_index = i - removed;
                 self.fix_after_deletion(segment_index);
                 self.run.segments_mut().remove(segment_index);
+                self.run.segment_groups_mut().remove_segment(segment_index);
                 removed += 1;
             }
         }
@@ -664,9 +808,9 @@ impl Editor {
             let first_history = first.segment_history().get(run_index);
             let second_history = second.segment_history().get(run_index);
             if let (Some(first_history), Some(second_history)) = (first_history, second_history) {
-                if first_history.real_time.is_some() != second_history.real_time.is_some()
-                    || first_history.game_time.is_some() != second_history.game_time.is_some()
-                {
+                if TimingMethod::all().iter().any(|&method| {
+                    first_history[method].is_some() != second_history[method].is_some()
+                }) {
                     first.segment_history_mut().remove(run_index);
                     second.segment_history_mut().remove(run_index);
                 }
@@ -690,20 +834,35 @@ impl Editor {
         swap(first, second);
     }
 
+    /// Segments can't be moved across the boundary of a Segment Group, as that
+    /// would change which segments the group consists of.
+    fn are_in_same_segment_group(&self, first: usize, second: usize) -> bool {
+        let groups = self.run.segment_groups();
+        groups.group_of(first).map(SegmentGroup::start)
+            == groups.group_of(second).map(SegmentGroup::start)
+    }
+
     /// Checks if the currently selected segments can be moved up. If any one of
-    /// the selected segments is the first segment, then they can't be moved.
+    /// the selected segments is the first segment or would be moved across the
+    /// boundary of a Segment Group, then they can't be moved.
     pub fn can_move_segments_up(&self) -> bool {
-        !self.selected_segments.iter().any(|&s| s == 0)
+        !self
+            .selected_segments
+            .iter()
+            .any(|&s| s == 0 || !self.are_in_same_segment_group(s - 1, s))
     }
 
     /// Moves all the selected segments up, unless the first segment is
-    /// selected. The run's information is automatically adjusted properly. The
+    /// selected or a segment would be moved across the boundary of a Segment
+    /// Group. The run's information is automatically adjusted properly. The
     /// active segment stays the active segment.
     pub fn move_segments_up(&mut self) {
         if !self.can_move_segments_up() {
             return;
         }
 
+        self.record(Action::MoveSegmentsUp);
+
         for i in 0..self.run.len() - 1 {
             if self.selected_segments.contains(&(i + 1)) {
                 self.switch_segments(i);
@@ -719,20 +878,27 @@ impl Editor {
     }
 
     /// Checks if the currently selected segments can be moved down. If any one
-    /// of the selected segments is the last segment, then they can't be moved.
+    /// of the selected segments is the last segment or would be moved across
+    /// the boundary of a Segment Group, then they can't be moved.
     pub fn can_move_segments_down(&self) -> bool {
         let last_index = self.run.len() - 1;
-        !self.selected_segments.iter().any(|&s| s == last_index)
+        !self
+            .selected_segments
+            .iter()
+            .any(|&s| s == last_index || !self.are_in_same_segment_group(s, s + 1))
     }
 
     /// Moves all the selected segments down, unless the last segment is
-    /// selected. The run's information is automatically adjusted properly. The
+    /// selected or a segment would be moved across the boundary of a Segment
+    /// Group. The run's information is automatically adjusted properly. The
     /// active segment stays the active segment.
     pub fn move_segments_down(&mut self) {
         if !self.can_move_segments_down() {
             return;
         }
 
+        self.record(Action::MoveSegmentsDown);
+
         for i in (0..self.run.len() - 1).rev() {
             if self.selected_segments.contains(&i) {
                 self.switch_segments(i);
@@ -749,10 +915,366 @@ impl Editor {
         self.fix();
     }
 
+    /// Splits the active segment into two segments, for example because the
+    /// route now splits in the middle of that segment. A new segment is
+    /// inserted above the active segment. It takes over the proportion
+    /// provided of all the active segment's times, while the active segment
+    /// keeps the rest. This applies to the Segment History, the Best Segment
+    /// Time and the comparisons, so the Sum of Best stays the same. The
+    /// proportion needs to be between 0 and 1, otherwise nothing happens. The
+    /// newly created segment is then the only selected segment and also the
+    /// active segment.
+    pub fn split_segment(&mut self, proportion: f64) {
+        if !(proportion > 0.0 && proportion < 1.0) {
+            return;
+        }
+
+        self.record(Action::SplitSegment);
+        let active_segment = self.active_segment_index();
+
+        let split = |time: Time| {
+            let mut first = Time::new();
+            for &method in &TimingMethod::all() {
+                first[method] =
+                    time[method].map(|t| TimeSpan::from_seconds(proportion * t.total_seconds()));
+            }
+            let second = Time::op(time, first, |a, b| a - b);
+            (first, second)
+        };
+
+        let mut segment = Segment::new("");
+        let previous = active_segment.checked_sub(1).map(|i| self.run.segment(i));
+        let current = self.run.segment(active_segment);
+        for comparison in self.run.custom_comparisons() {
+            let previous_time = previous
+                .map(|p| p.comparison(comparison))
+                .unwrap_or_else(Time::zero);
+            let current_time = current.comparison(comparison);
+            let (first, _) = split(Time::op(current_time, previous_time, |a, b| a - b));
+            *segment.comparison_mut(comparison) = Time::op(previous_time, first, |a, b| a + b);
+        }
+
+        let current = self.run.segment_mut(active_segment);
+        let (first, second) = split(current.best_segment_time());
+        segment.set_best_segment_time(first);
+        current.set_best_segment_time(second);
+
+        for (index, time) in current.segment_history_mut().iter_mut() {
+            let (first, second) = split(*time);
+            segment.segment_history_mut().insert(*index, first);
+            *time = second;
+        }
+
+        self.run.segments_mut().insert(active_segment, segment);
+        self.run.segment_groups_mut().insert_segment(active_segment);
+
+        self.select_only(active_segment);
+
+        self.times_modified();
+        self.fix();
+    }
+
+    /// Checks if the selected segments can be merged. This requires at least
+    /// two segments to be selected.
+    pub fn can_merge_segments(&self) -> bool {
+        self.selected_range().len() >= 2
+    }
+
+    /// Merges all the segments from the first to the last selected segment
+    /// into a single segment, for example because the route doesn't split in
+    /// between them anymore. The times of these segments are combined into the
+    /// last of them, which keeps its name and icon. The merged segment is then
+    /// the only selected segment and also the active segment.
+    pub fn merge_segments(&mut self) {
+        if !self.can_merge_segments() {
+            return;
+        }
+
+        self.record(Action::MergeSegments);
+
+        let range = self.selected_range();
+        for _ in 1..range.len() {
+            self.fix_after_deletion(range.start);
+            self.run.segments_mut().remove(range.start);
+            self.run.segment_groups_mut().remove_segment(range.start);
+        }
+
+        self.select_only(range.start);
+
+        self.times_modified();
+        self.fix();
+    }
+
+    /// Reorders the segments, for example because the route now visits the
+    /// segments in a different order. The order is provided as the list of the
+    /// current indices of the segments in their new order. The Best Segment
+    /// Times and the comparisons are adjusted to the new order. The Segment
+    /// History of each attempt is kept for all the segments the attempt still
+    /// reached in the new order. The history of attempts that skipped any of
+    /// those segments is removed, as the skipped times can't be attributed to
+    /// the segments anymore. Segment Groups that don't stay together are
+    /// dissolved. Returns whether the segments were reordered, which fails if
+    /// the order provided is not a reordering of all the segments.
+    pub fn reorder_segments(&mut self, order: &[usize]) -> bool {
+        let len = self.run.len();
+        if order.len() != len {
+            return false;
+        }
+        let mut new_positions = vec![usize::MAX; len];
+        for (new_index, &old_index) in order.iter().enumerate() {
+            match new_positions.get_mut(old_index) {
+                Some(position) if *position == usize::MAX => *position = new_index,
+                _ => return false,
+            }
+        }
+
+        self.record(Action::ReorderSegments);
+
+        let max_index = self.run.max_attempt_history_index().unwrap_or(0);
+        let comparisons = self.run.custom_comparisons().to_vec();
+        let mut segments = take(self.run.segments_mut())
+            .into_iter()
+            .map(Some)
+            .collect::<Vec<_>>();
+
+        // The segment times of the comparisons don't depend on the order, so
+        // the split times are calculated again from them.
+        let mut comparison_segment_times = Vec::with_capacity(comparisons.len());
+        for comparison in &comparisons {
+            let mut previous_time = Time::zero();
+            let segment_times = segments
+                .iter()
+                .flatten()
+                .map(|segment| {
+                    let time = segment.comparison(comparison);
+                    let segment_time = Time::op(time, previous_time, |a, b| a - b);
+                    for &method in &TimingMethod::all() {
+                        if time[method].is_some() {
+                            previous_time[method] = time[method];
+                        }
+                    }
+                    segment_time
+                })
+                .collect::<Vec<_>>();
+            comparison_segment_times.push(segment_times);
+        }
+
+        let mut histories = segments
+            .iter_mut()
+            .flatten()
+            .map(|s| take(s.segment_history_mut()))
+            .collect::<Vec<_>>();
+
+        for &old_index in order {
+            let segment = segments[old_index].take().unwrap();
+            self.run.segments_mut().push(segment);
+        }
+
+        for (comparison, segment_times) in comparisons.iter().zip(comparison_segment_times) {
+            let mut total = Time::zero();
+            for (segment, &old_index) in self.run.segments_mut().iter_mut().zip(order) {
+                let segment_time = segment_times[old_index];
+                let time = segment.comparison_mut(comparison);
+                for &method in &TimingMethod::all() {
+                    time[method] = catch! { total[method]? + segment_time[method]? };
+                    if time[method].is_some() {
+                        total[method] = time[method];
+                    }
+                }
+            }
+        }
+
+        for (segment, &old_index) in self.run.segments_mut().iter_mut().zip(order) {
+            // Elements that don't belong to an attempt aren't affected by the
+            // order of the segments.
+            let history = segment.segment_history_mut();
+            histories[old_index].retain(|&(index, time)| {
+                if index < 1 {
+                    history.insert(index, time);
+                }
+                index >= 1
+            });
+        }
+
+        for run_index in 1..=max_index {
+            let reached = order
+                .iter()
+                .map_while(|&old_index| histories[old_index].get(run_index))
+                .collect::<Vec<_>>();
+
+            let skipped = TimingMethod::all().iter().any(|&method| {
+                reached.iter().any(|t| t[method].is_none())
+                    && reached.iter().any(|t| t[method].is_some())
+            });
+            if skipped {
+                continue;
+            }
+
+            for (segment, time) in self.run.segments_mut().iter_mut().zip(reached) {
+                segment.segment_history_mut().insert(run_index, time);
+            }
+        }
+
+        self.run
+            .segment_groups_mut()
+            .reorder_segments(&new_positions);
+        for segment in &mut self.selected_segments {
+            *segment = new_positions[*segment];
+        }
+
+        self.times_modified();
+        self.fix();
+
+        true
+    }
+
+    fn selected_range(&self) -> Range<usize> {
+        let start = *self.selected_segments.iter().min().unwrap();
+        let end = *self.selected_segments.iter().max().unwrap() + 1;
+        start..end
+    }
+
+    fn selected_group_indices(&self) -> Range<usize> {
+        let range = self.selected_range();
+        let groups = self.run.segment_groups();
+        let start = groups.iter().take_while(|g| g.end() <= range.start).count();
+        let end = groups.iter().take_while(|g| g.start() < range.end).count();
+        start..end
+    }
+
+    /// Checks if a Segment Group can be created from the selected segments.
+    /// The group spans from the first to the last selected segment. This isn't
+    /// possible if any existing group only partially overlaps with these
+    /// segments or if the group already exists.
+    pub fn can_create_segment_group(&self) -> bool {
+        let range = self.selected_range();
+        let groups = self.run.segment_groups();
+        self.selected_group_indices().all(|i| {
+            let group = groups.get(i).unwrap();
+            range.start <= group.start() && group.end() <= range.end && group.range() != range
+        })
+    }
+
+    /// Creates a new Segment Group that spans from the first to the last
+    /// selected segment. Existing groups within these segments become part of
+    /// the new group. The new group doesn't have a name, so the name of its
+    /// last segment is used for the group.
+    pub fn create_segment_group(&mut self) {
+        if !self.can_create_segment_group() {
+            return;
+        }
+
+        self.record(Action::CreateSegmentGroup);
+
+        let range = self.selected_range();
+        let groups = self.run.segment_groups_mut();
+        groups.retain(|g| g.end() <= range.start || g.start() >= range.end);
+        let _ = groups.insert(SegmentGroup::new(range.start, range.end));
+
+        self.raise_run_edited();
+    }
+
+    /// Checks if the Segment Groups of the selected segments can be merged.
+    /// This requires the selected segments to be part of at least two
+    /// different groups.
+    pub fn can_merge_segment_groups(&self) -> bool {
+        self.selected_group_indices().len() >= 2
+    }
+
+    /// Merges all the Segment Groups that overlap with the selected segments
+    /// into a single group. The merged group spans from the start of the first
+    /// group to the end of the last group, so ungrouped segments in between
+    /// become part of it as well. The name and the collapsed state of the first
+    /// group are kept.
+    pub fn merge_segment_groups(&mut self) {
+        if !self.can_merge_segment_groups() {
+            return;
+        }
+
+        self.record(Action::MergeSegmentGroups);
+
+        let group_indices = self.selected_group_indices();
+        let groups = self.run.segment_groups_mut();
+        let end = groups.get(group_indices.end - 1).unwrap().end();
+        for index in group_indices.clone().skip(1).rev() {
+            groups.remove(index);
+        }
+        let first = groups.remove(group_indices.start).unwrap();
+        let mut merged = SegmentGroup::new(first.start(), end);
+        merged.set_name(first.name().map(String::from));
+        merged.set_collapsed(first.is_collapsed());
+        let _ = groups.insert(merged);
+
+        self.raise_run_edited();
+    }
+
+    /// Checks if any of the selected segments is part of a Segment Group that
+    /// can be dissolved.
+    pub fn can_dissolve_segment_groups(&self) -> bool {
+        let groups = self.run.segment_groups();
+        self.selected_segments
+            .iter()
+            .any(|&i| groups.group_index(i).is_some())
+    }
+
+    /// Dissolves all the Segment Groups that any of the selected segments are
+    /// part of. The segments themselves are kept.
+    pub fn dissolve_segment_groups(&mut self) {
+        if !self.can_dissolve_segment_groups() {
+            return;
+        }
+
+        self.record(Action::DissolveSegmentGroups);
+
+        let selected_segments = &self.selected_segments;
+        self.run
+            .segment_groups_mut()
+            .retain(|g| !selected_segments.iter().any(|&i| g.contains(i)));
+
+        self.raise_run_edited();
+    }
+
+    /// Sets the name of the Segment Group that the active segment is part of.
+    /// If the name is empty, the group doesn't have a name, so the name of its
+    /// last segment is used for the group instead. If the active segment isn't
+    /// part of any group, nothing happens.
+    pub fn set_segment_group_name<S>(&mut self, name: S)
+    where
+        S: AsRef<str>,
+    {
+        let active_segment = self.active_segment_index();
+        if let Some(index) = self.run.segment_groups().group_index(active_segment) {
+            self.record(Action::SetSegmentGroupName);
+            let name = name.as_ref();
+            let group = self.run.segment_groups_mut().get_mut(index).unwrap();
+            group.set_name(if name.is_empty() {
+                None
+            } else {
+                Some(String::from(name))
+            });
+            self.raise_run_edited();
+        }
+    }
+
+    /// Sets whether the Segment Group that the active segment is part of is
+    /// collapsed. If the active segment isn't part of any group, nothing
+    /// happens.
+    pub fn set_segment_group_collapsed(&mut self, collapsed: bool) {
+        let active_segment = self.active_segment_index();
+        if let Some(index) = self.run.segment_groups().group_index(active_segment) {
+            self.record(Action::SetSegmentGroupCollapsed);
+            let group = self.run.segment_groups_mut().get_mut(index).unwrap();
+            group.set_collapsed(collapsed);
+            self.raise_run_edited();
+        }
+    }
+
     /// Adds a new custom comparison. It can't be added if it starts with
     /// `[Race]` or already exists.
     pub fn add_comparison<S: Into<String>>(&mut self, comparison: S) -> ComparisonResult<()> {
         let comparison = comparison.into();
+        self.run.validate_comparison_name(&comparison)?;
+        self.record(Action::AddComparison);
         self.run.add_custom_comparison(comparison)?;
         self.fix();
         Ok(())
@@ -767,6 +1289,8 @@ impl Editor {
         comparison: S,
     ) -> ComparisonResult<()> {
         let comparison = comparison.into();
+        self.run.validate_comparison_name(&comparison)?;
+        self.record(Action::ImportComparison);
         self.run.add_custom_comparison(comparison.as_str())?;
 
         let mut remaining_segments = self.run.segments_mut().as_mut_slice();
@@ -795,10 +1319,18 @@ impl Editor {
     /// Removes the chosen custom comparison. You can't remove a Comparison
     /// Generator's Comparison or the Personal Best.
     pub fn remove_comparison(&mut self, comparison: &str) {
-        if comparison == comparison::personal_best::NAME {
+        if comparison == comparison::personal_best::NAME
+            || !self
+                .run
+                .custom_comparisons()
+                .iter()
+                .any(|c| c == comparison)
+        {
             return;
         }
 
+        self.record(Action::RemoveComparison);
+
         self.run
             .custom_comparisons_mut()
             .retain(|c| c != comparison);
@@ -825,6 +1357,12 @@ impl Editor {
             .validate_comparison_name(new)
             .context(InvalidName)?;
 
+        if !self.run.custom_comparisons().iter().any(|c| c == old) {
+            return Err(RenameError::OldNameNotFound);
+        }
+
+        self.record(Action::RenameComparison);
+
         {
             let comparison_name = self
                 .run
@@ -853,15 +1391,18 @@ impl Editor {
     /// one of the indices is invalid. The indices are based on the
     /// `comparison_names` field of the Run Editor's `State`.
     pub fn move_comparison(&mut self, src_index: usize, dst_index: usize) -> Result<(), ()> {
-        let comparisons = self.run.custom_comparisons_mut();
+        let len = self.run.custom_comparisons().len();
         let (src_index, dst_index) = (src_index + 1, dst_index + 1);
-        if src_index >= comparisons.len() || dst_index >= comparisons.len() {
+        if src_index >= len || dst_index >= len {
             return Err(());
         }
         if src_index == dst_index {
             return Ok(());
         }
 
+        self.record(Action::MoveComparison);
+        let comparisons = self.run.custom_comparisons_mut();
+
         if src_index > dst_index {
             comparisons[dst_index..=src_index].rotate_left(src_index - dst_index);
         } else {
@@ -883,6 +1424,7 @@ impl Editor {
     /// modified by this, so you can call this again with the other timing
     /// method to generate the comparison times for both timing methods.
     pub fn generate_goal_comparison(&mut self, time: TimeSpan) {
+        self.record(Action::GenerateGoalComparison);
         if !self
             .run
             .custom_comparisons()
@@ -919,7 +1461,8 @@ impl Editor {
         S: AsRef<str>,
     {
         self.generate_goal_comparison(
-            parse_positive(time)?.ok_or(ParseError::EmptyTimeNotAllowed)?,
+            self.parse_positive(time)?
+                .ok_or(ParseError::EmptyTimeNotAllowed)?,
         );
         Ok(())
     }
@@ -927,6 +1470,7 @@ impl Editor {
     /// Clears out the Attempt History and the Segment Histories of all the
     /// segments.
     pub fn clear_history(&mut self) {
+        self.record(Action::ClearHistory);
         self.run.clear_history();
         self.fix();
     }
@@ -936,6 +1480,7 @@ impl Editor {
     /// association. All Custom Comparisons other than `Personal Best` are
     /// deleted as well.
     pub fn clear_times(&mut self) {
+        self.record(Action::ClearTimes);
         self.run.clear_times();
         self.fix();
     }
@@ -947,18 +1492,48 @@ impl Editor {
     /// best segments. The Sum of Best Cleaner will point out all of these and
     /// allows you to delete them individually if any of them seem wrong.
     pub fn clean_sum_of_best(&mut self) -> SumOfBestCleaner<'_> {
-        SumOfBestCleaner::new(&mut self.run)
+        let active_segment = self.active_segment_index();
+        SumOfBestCleaner::with_history(
+            &mut self.run,
+            cleaning::Recorder {
+                history: &mut self.history,
+                selected_segments: &self.selected_segments,
+                active_segment,
+                previous_personal_best_time: self.previous_personal_best_time,
+            },
+        )
+    }
+
+    /// Parses a time, taking the frame rate of the run into account. If the
+    /// run specifies a frame rate, times can also be entered as an amount of
+    /// frames, like `1234f`, and all other times are snapped to the closest
+    /// frame.
+    fn parse_time(&self, time: &str) -> Result<TimeSpan, ParseError> {
+        if let Some(frame_rate) = self.frame_rate() {
+            if let Some(frames) = time.trim().strip_suffix('f') {
+                let frames = frames.trim_end().parse().context(ParseFrames)?;
+                Ok(frame_rate.time_span(frames))
+            } else {
+                Ok(frame_rate.snap(time.parse().context(ParseTime)?))
+            }
+        } else {
+            time.parse().context(ParseTime)
+        }
     }
-}
 
-fn parse_positive<S>(time: S) -> Result<Option<TimeSpan>, ParseError>
-where
-    S: AsRef<str>,
-{
-    let time = TimeSpan::parse_opt(time).context(ParseTime)?;
-    if time.map_or(false, |t| t < TimeSpan::zero()) {
-        Err(ParseError::NegativeTimeNotAllowed)
-    } else {
-        Ok(time)
+    fn parse_positive<S>(&self, time: S) -> Result<Option<TimeSpan>, ParseError>
+    where
+        S: AsRef<str>,
+    {
+        let time = time.as_ref();
+        if time.trim().is_empty() {
+            return Ok(None);
+        }
+        let time = self.parse_time(time)?;
+        if time < TimeSpan::zero() {
+            Err(ParseError::NegativeTimeNotAllowed)
+        } else {
+            Ok(Some(time))
+        }
     }
 }
@@ -1,4 +1,4 @@
-use super::{parse_positive, Editor, ParseError};
+use super::{Action, Editor, ParseError};
 use crate::{settings::Image, TimeSpan};
 
 /// A Segment Row describes the segment in the Run Editor actively selected for
@@ -20,12 +20,14 @@ impl<'a> SegmentRow<'a> {
 
     /// Sets the icon of the segment.
     pub fn set_icon<D: Into<Image>>(&mut self, image: D) {
+        self.editor.record(Action::SetSegmentIcon);
         self.editor.run.segment_mut(self.index).set_icon(image);
         self.editor.raise_run_edited();
     }
 
     /// Removes the icon of the segment.
     pub fn remove_icon(&mut self) {
+        self.editor.record(Action::RemoveSegmentIcon);
         self.editor.run.segment_mut(self.index).set_icon(&[]);
         self.editor.raise_run_edited();
     }
@@ -40,6 +42,7 @@ impl<'a> SegmentRow<'a> {
     where
         S: AsRef<str>,
     {
+        self.editor.record(Action::SetSegmentName);
         self.editor.run.segment_mut(self.index).set_name(name);
         self.editor.raise_run_edited();
     }
@@ -55,6 +58,7 @@ impl<'a> SegmentRow<'a> {
 
     /// Sets the split time of the segment for the active timing method.
     pub fn set_split_time(&mut self, time: Option<TimeSpan>) {
+        self.editor.record(Action::SetSplitTime);
         let method = self.editor.selected_method;
         self.editor
             .run
@@ -70,7 +74,7 @@ impl<'a> SegmentRow<'a> {
     where
         S: AsRef<str>,
     {
-        self.set_split_time(parse_positive(time)?);
+        self.set_split_time(self.editor.parse_positive(time)?);
         Ok(())
     }
 
@@ -81,6 +85,7 @@ impl<'a> SegmentRow<'a> {
 
     /// Sets the segment time of the segment for the active timing method.
     pub fn set_segment_time(&mut self, time: Option<TimeSpan>) {
+        self.editor.record(Action::SetSegmentTime);
         self.editor.segment_times[self.index] = time;
         self.editor.fix_splits_from_segments();
         self.editor.times_modified();
@@ -93,7 +98,7 @@ impl<'a> SegmentRow<'a> {
     where
         S: AsRef<str>,
     {
-        self.set_segment_time(parse_positive(time)?);
+        self.set_segment_time(self.editor.parse_positive(time)?);
         Ok(())
     }
 
@@ -105,6 +110,7 @@ impl<'a> SegmentRow<'a> {
 
     /// Sets the best segment time of the segment for the active timing method.
     pub fn set_best_segment_time(&mut self, time: Option<TimeSpan>) {
+        self.editor.record(Action::SetBestSegmentTime);
         let method = self.editor.selected_method;
         self.editor
             .run
@@ -120,7 +126,7 @@ impl<'a> SegmentRow<'a> {
     where
         S: AsRef<str>,
     {
-        self.set_best_segment_time(parse_positive(time)?);
+        self.set_best_segment_time(self.editor.parse_positive(time)?);
         Ok(())
     }
 
@@ -133,6 +139,7 @@ impl<'a> SegmentRow<'a> {
 
     /// Sets the provided comparison's time of the segment for the active timing method.
     pub fn set_comparison_time(&mut self, comparison: &str, time: Option<TimeSpan>) {
+        self.editor.record(Action::SetComparisonTime);
         let method = self.editor.selected_method;
         self.editor
             .run
@@ -152,7 +159,7 @@ impl<'a> SegmentRow<'a> {
     where
         S: AsRef<str>,
     {
-        self.set_comparison_time(comparison, parse_positive(time)?);
+        self.set_comparison_time(comparison, self.editor.parse_positive(time)?);
         Ok(())
     }
 }
@@ -4,7 +4,8 @@ use crate::{
     platform::prelude::*,
     run::RunMetadata,
     settings::{CachedImageId, ImageData},
-    timing::formatter::{none_wrapper::EmptyWrapper, Accuracy, Short, TimeFormatter},
+    timing::formatter::{none_wrapper::EmptyWrapper, Accuracy, Frames, Short, TimeFormatter},
+    TimeSpan,
 };
 use serde::{Deserialize, Serialize};
 
@@ -29,8 +30,13 @@ pub struct State {
     /// The timing method that is currently selected to be visualized and
     /// edited.
     pub timing_method: TimingMethod,
+    /// The names of the custom timing methods, ordered by their custom index.
+    /// The name is empty if the custom timing method is not in use.
+    pub custom_timing_method_names: Vec<String>,
     /// The state of all the segments.
     pub segments: Vec<Segment>,
+    /// The Segment Groups of the Run, ordered by the segments they contain.
+    pub segment_groups: Vec<SegmentGroup>,
     /// The names of all the custom comparisons that exist for this Run.
     pub comparison_names: Vec<String>,
     /// Describes which actions are currently available.
@@ -56,6 +62,27 @@ pub struct Buttons {
     /// any one of the selected segments is the last segment, then they can't be
     /// moved.
     pub can_move_down: bool,
+    /// Describes whether the currently selected segments can be merged into a
+    /// single segment. This requires at least two segments to be selected.
+    pub can_merge: bool,
+    /// Describes whether a Segment Group can be created from the currently
+    /// selected segments.
+    pub can_create_group: bool,
+    /// Describes whether the Segment Groups of the currently selected segments
+    /// can be merged. This requires the selected segments to be part of at
+    /// least two different groups.
+    pub can_merge_groups: bool,
+    /// Describes whether any of the currently selected segments is part of a
+    /// Segment Group that can be dissolved.
+    pub can_dissolve_groups: bool,
+    /// Describes whether there is an action that can be undone.
+    pub can_undo: bool,
+    /// Describes whether there is an action that can be redone.
+    pub can_redo: bool,
+    /// The label of the action that would be undone, if there is any.
+    pub undo_action: Option<String>,
+    /// The label of the action that would be redone, if there is any.
+    pub redo_action: Option<String>,
 }
 
 /// Describes the current state of a segment.
@@ -81,6 +108,20 @@ pub struct Segment {
     pub selected: SelectionState,
 }
 
+/// Describes the current state of a Segment Group.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SegmentGroup {
+    /// The index of the first segment of the group.
+    pub start: usize,
+    /// The index of the segment right after the last segment of the group.
+    pub end: usize,
+    /// The name of the group. If it is empty, the group doesn't have a name
+    /// and the name of its last segment is used instead.
+    pub name: String,
+    /// Describes whether the group is collapsed.
+    pub is_collapsed: bool,
+}
+
 /// Describes a segment's selection state.
 #[derive(Debug, Serialize, Deserialize)]
 pub enum SelectionState {
@@ -114,7 +155,16 @@ impl State {
 impl Editor {
     /// Calculates the Run Editor's state in order to visualize it.
     pub fn state(&mut self) -> State {
-        let formatter = EmptyWrapper::new(Short::with_accuracy(Accuracy::Hundredths));
+        // If the run is timed in frames, the times need to be shown with
+        // enough accuracy to be able to tell the individual frames apart.
+        let frame_rate = self.frame_rate();
+        let short = EmptyWrapper::new(Short::with_accuracy(Accuracy::Hundredths));
+        let frames =
+            frame_rate.map(|f| EmptyWrapper::new(Frames::with_accuracy(f, Accuracy::Milliseconds)));
+        let format = |time: Option<TimeSpan>| match &frames {
+            Some(frames) => frames.format(time).to_string(),
+            None => short.format(time).to_string(),
+        };
 
         let icon_change = self
             .game_icon_id
@@ -122,9 +172,18 @@ impl Editor {
             .map(Into::into);
         let game = self.game_name().to_string();
         let category = self.category_name().to_string();
-        let offset = formatter.format(self.offset()).to_string();
+        let offset = format(Some(self.offset()));
         let attempts = self.attempt_count();
         let timing_method = self.selected_timing_method();
+        let custom_timing_method_names = TimingMethod::custom_methods()
+            .iter()
+            .map(|&method| {
+                self.run
+                    .custom_timing_method_name(method)
+                    .unwrap_or_default()
+                    .to_string()
+            })
+            .collect();
         let comparison_names = self
             .custom_comparisons()
             .iter()
@@ -136,6 +195,14 @@ impl Editor {
             can_remove: self.can_remove_segments(),
             can_move_up: self.can_move_segments_up(),
             can_move_down: self.can_move_segments_down(),
+            can_merge: self.can_merge_segments(),
+            can_create_group: self.can_create_segment_group(),
+            can_merge_groups: self.can_merge_segment_groups(),
+            can_dissolve_groups: self.can_dissolve_segment_groups(),
+            can_undo: self.can_undo(),
+            can_redo: self.can_redo(),
+            undo_action: self.undo_action().map(|a| a.label().to_string()),
+            redo_action: self.redo_action().map(|a| a.label().to_string()),
         };
         let mut segments = Vec::with_capacity(self.run.len());
 
@@ -147,12 +214,12 @@ impl Editor {
             {
                 let row = SegmentRow::new(segment_index, self);
                 name = row.name().to_string();
-                split_time = formatter.format(row.split_time()).to_string();
-                segment_time = formatter.format(row.segment_time()).to_string();
-                best_segment_time = formatter.format(row.best_segment_time()).to_string();
+                split_time = format(row.split_time());
+                segment_time = format(row.segment_time());
+                best_segment_time = format(row.best_segment_time());
                 comparison_times = comparison_names
                     .iter()
-                    .map(|c| formatter.format(row.comparison_time(c)).to_string())
+                    .map(|c| format(row.comparison_time(c)))
                     .collect();
             }
 
@@ -179,6 +246,18 @@ impl Editor {
             });
         }
 
+        let segment_groups = self
+            .run
+            .segment_groups()
+            .iter()
+            .map(|g| SegmentGroup {
+                start: g.start(),
+                end: g.end(),
+                name: g.name().unwrap_or_default().to_string(),
+                is_collapsed: g.is_collapsed(),
+            })
+            .collect();
+
         State {
             icon_change,
             game,
@@ -186,7 +265,9 @@ impl Editor {
             offset,
             attempts,
             timing_method,
+            custom_timing_method_names,
             segments,
+            segment_groups,
             comparison_names,
             buttons,
             metadata: self.run.metadata().clone(),
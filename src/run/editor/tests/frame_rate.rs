@@ -0,0 +1,86 @@
+use super::super::{Editor, ParseError};
+use crate::{tests_helper::create_run, timing::FrameRate, TimeSpan};
+
+fn editor() -> (Editor, FrameRate) {
+    let frame_rate = FrameRate::new(60.0).unwrap();
+    let mut editor = Editor::new(create_run(&["A", "B"])).unwrap();
+    editor.set_frame_rate(Some(frame_rate));
+    (editor, frame_rate)
+}
+
+#[test]
+fn parses_frame_counts() {
+    let (mut editor, frame_rate) = editor();
+
+    editor
+        .active_segment()
+        .parse_and_set_split_time("90f")
+        .unwrap();
+    assert_eq!(
+        editor.active_segment().split_time(),
+        Some(frame_rate.time_span(90))
+    );
+
+    editor.parse_and_set_offset("-12f").unwrap();
+    assert_eq!(editor.offset(), -frame_rate.time_span(12));
+}
+
+#[test]
+fn snaps_times_to_frames() {
+    let (mut editor, frame_rate) = editor();
+
+    editor
+        .active_segment()
+        .parse_and_set_split_time("1.51")
+        .unwrap();
+    assert_eq!(
+        editor.active_segment().split_time(),
+        Some(frame_rate.time_span(91))
+    );
+
+    // The times are shown with enough accuracy to round trip them.
+    let state = editor.state();
+    assert_eq!(state.segments[0].split_time, "1.516");
+    editor
+        .active_segment()
+        .parse_and_set_split_time(&state.segments[0].split_time)
+        .unwrap();
+    assert_eq!(
+        editor.active_segment().split_time(),
+        Some(frame_rate.time_span(91))
+    );
+}
+
+#[test]
+fn frames_are_only_accepted_with_a_frame_rate() {
+    let mut editor = Editor::new(create_run(&["A"])).unwrap();
+
+    assert!(matches!(
+        editor.active_segment().parse_and_set_split_time("90f"),
+        Err(ParseError::ParseTime { .. })
+    ));
+
+    editor
+        .active_segment()
+        .parse_and_set_split_time("1.51")
+        .unwrap();
+    assert_eq!(
+        editor.active_segment().split_time(),
+        Some(TimeSpan::from_seconds(1.51))
+    );
+    assert_eq!(editor.state().segments[0].split_time, "1.51");
+}
+
+#[test]
+fn invalid_frame_counts() {
+    let (mut editor, _) = editor();
+
+    assert!(matches!(
+        editor.active_segment().parse_and_set_split_time("1.5f"),
+        Err(ParseError::ParseFrames { .. })
+    ));
+    assert!(matches!(
+        editor.active_segment().parse_and_set_split_time("-3f"),
+        Err(ParseError::NegativeTimeNotAllowed)
+    ));
+}
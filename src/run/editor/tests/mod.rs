@@ -5,7 +5,11 @@ use crate::{Run, Segment};
 mod comparison;
 mod custom_variables;
 mod dissociate_run;
+mod frame_rate;
 mod mark_as_modified;
+mod route_changes;
+mod segment_groups;
+mod undo;
 
 #[test]
 fn new_best_segment() {
@@ -0,0 +1,184 @@
+use super::super::{Action, Editor};
+use crate::{
+    analysis::sum_of_segments::calculate_best,
+    comparison::personal_best,
+    tests_helper::{
+        create_timer, run_sample_attempts, run_with_splits, run_with_splits_opt, segment_history,
+        span,
+    },
+    Run, TimeSpan, TimingMethod,
+};
+
+fn run() -> Run {
+    let mut timer = create_timer(&["A", "B", "C"]);
+    run_sample_attempts(&mut timer);
+    run_with_splits(&mut timer, &[8.0]);
+    timer.into_run(true)
+}
+
+fn names(run: &Run) -> Vec<&str> {
+    run.segments().iter().map(|s| s.name()).collect()
+}
+
+fn personal_best(run: &Run) -> Vec<Option<TimeSpan>> {
+    run.segments()
+        .iter()
+        .map(|s| s.comparison(personal_best::NAME).game_time)
+        .collect()
+}
+
+fn sum_of_best(run: &Run) -> Option<TimeSpan> {
+    calculate_best(run.segments(), false, false, TimingMethod::GameTime)
+}
+
+#[test]
+fn split_segment() {
+    let run = run();
+    let previous_sum_of_best = sum_of_best(&run);
+
+    let mut editor = Editor::new(run).unwrap();
+    editor.select_only(1);
+    editor.split_segment(0.0);
+    editor.split_segment(1.5);
+    assert_eq!(editor.undo_action(), None);
+
+    editor.split_segment(0.25);
+    assert_eq!(editor.undo_action(), Some(Action::SplitSegment));
+    let run = editor.close();
+
+    assert_eq!(names(&run), ["A", "", "B", "C"]);
+    assert_eq!(
+        segment_history(&run, 1, TimingMethod::GameTime),
+        [(1, Some(span(5.0))), (2, Some(span(5.5)))]
+    );
+    assert_eq!(
+        segment_history(&run, 2, TimingMethod::GameTime),
+        [(1, Some(span(15.0))), (2, Some(span(16.5)))]
+    );
+    assert_eq!(
+        run.segment(1).best_segment_time().game_time,
+        Some(span(5.0))
+    );
+    assert_eq!(
+        run.segment(2).best_segment_time().game_time,
+        Some(span(15.0))
+    );
+    assert_eq!(
+        personal_best(&run),
+        [
+            Some(span(10.0)),
+            Some(span(15.0)),
+            Some(span(30.0)),
+            Some(span(60.0))
+        ]
+    );
+    assert_eq!(sum_of_best(&run), previous_sum_of_best);
+}
+
+#[test]
+fn merge_segments() {
+    let mut editor = Editor::new(run()).unwrap();
+    editor.select_only(0);
+    assert!(!editor.can_merge_segments());
+    editor.select_additionally(1);
+    assert!(editor.can_merge_segments());
+
+    editor.merge_segments();
+    let run = editor.close();
+
+    assert_eq!(names(&run), ["B", "C"]);
+    assert_eq!(
+        segment_history(&run, 0, TimingMethod::GameTime),
+        [(1, Some(span(30.0))), (2, Some(span(34.0)))]
+    );
+    // The best segments of both segments are combined, so the Sum of Best
+    // doesn't change.
+    assert_eq!(
+        run.segment(0).best_segment_time().game_time,
+        Some(span(28.0))
+    );
+    assert_eq!(personal_best(&run), [Some(span(30.0)), Some(span(60.0))]);
+    assert_eq!(sum_of_best(&run), Some(span(58.0)));
+}
+
+#[test]
+fn reorder_segments() {
+    let mut editor = Editor::new(run()).unwrap();
+    editor.select_only(0);
+
+    assert!(!editor.reorder_segments(&[1, 0]));
+    assert!(!editor.reorder_segments(&[1, 1, 2]));
+    assert!(!editor.reorder_segments(&[1, 0, 3]));
+    assert_eq!(editor.undo_action(), None);
+
+    assert!(editor.reorder_segments(&[1, 0, 2]));
+    assert_eq!(editor.selected_segments, [1]);
+    let run = editor.close();
+
+    assert_eq!(names(&run), ["B", "A", "C"]);
+    assert_eq!(
+        segment_history(&run, 0, TimingMethod::GameTime),
+        [(1, Some(span(20.0))), (2, Some(span(22.0)))]
+    );
+    assert_eq!(
+        segment_history(&run, 1, TimingMethod::GameTime),
+        [(1, Some(span(10.0))), (2, Some(span(12.0)))]
+    );
+    assert_eq!(
+        run.segment(0).best_segment_time().game_time,
+        Some(span(20.0))
+    );
+    assert_eq!(
+        run.segment(1).best_segment_time().game_time,
+        Some(span(8.0))
+    );
+    assert_eq!(
+        personal_best(&run),
+        [Some(span(20.0)), Some(span(30.0)), Some(span(60.0))]
+    );
+    assert_eq!(sum_of_best(&run), Some(span(58.0)));
+}
+
+#[test]
+fn reordering_drops_attempts_with_skipped_segments() {
+    let mut timer = create_timer(&["A", "B", "C"]);
+    run_with_splits(&mut timer, &[10.0, 30.0, 60.0]);
+    run_with_splits_opt(&mut timer, &[None, Some(25.0), Some(55.0)]);
+
+    let mut editor = Editor::new(timer.into_run(true)).unwrap();
+    assert!(editor.reorder_segments(&[1, 0, 2]));
+    let run = editor.close();
+
+    for segment in 0..3 {
+        assert!(run
+            .segment(segment)
+            .segment_history()
+            .iter()
+            .all(|&(index, _)| index != 2));
+    }
+    assert_eq!(
+        segment_history(&run, 2, TimingMethod::GameTime),
+        [(1, Some(span(30.0)))]
+    );
+}
+
+#[test]
+fn reordering_keeps_groups_that_stay_together() {
+    let mut editor = Editor::new(run()).unwrap();
+    editor.select_only(0);
+    editor.select_additionally(1);
+    editor.create_segment_group();
+
+    assert!(editor.reorder_segments(&[2, 0, 1]));
+    let groups = editor.run.segment_groups();
+    assert_eq!(
+        groups
+            .iter()
+            .map(|g| (g.start(), g.end()))
+            .collect::<Vec<_>>(),
+        [(1, 3)]
+    );
+
+    assert!(editor.reorder_segments(&[1, 0, 2]));
+    assert!(editor.run.segment_groups().is_empty());
+}
@@ -0,0 +1,151 @@
+use super::super::Editor;
+use crate::{Run, Segment};
+
+fn editor_with_segments(count: usize) -> Editor {
+    let mut run = Run::new();
+    for i in 0..count {
+        run.push_segment(Segment::new(i.to_string()));
+    }
+    Editor::new(run).unwrap()
+}
+
+fn group_ranges(editor: &Editor) -> Vec<(usize, usize)> {
+    editor
+        .run
+        .segment_groups()
+        .iter()
+        .map(|g| (g.start(), g.end()))
+        .collect()
+}
+
+#[test]
+fn create_group_from_selection() {
+    let mut editor = editor_with_segments(5);
+    editor.select_only(1);
+    editor.select_additionally(3);
+    assert!(editor.can_create_segment_group());
+    editor.create_segment_group();
+    assert_eq!(group_ranges(&editor), [(1, 4)]);
+
+    assert!(!editor.can_create_segment_group());
+
+    editor.select_only(3);
+    editor.select_additionally(4);
+    assert!(!editor.can_create_segment_group());
+
+    editor.select_only(0);
+    editor.select_additionally(4);
+    assert!(editor.can_create_segment_group());
+    editor.create_segment_group();
+    assert_eq!(group_ranges(&editor), [(0, 5)]);
+}
+
+#[test]
+fn merge_groups() {
+    let mut editor = editor_with_segments(6);
+    editor.select_only(0);
+    editor.select_additionally(1);
+    editor.create_segment_group();
+    editor.set_segment_group_name("First");
+    editor.select_only(3);
+    editor.select_additionally(4);
+    editor.create_segment_group();
+    assert_eq!(group_ranges(&editor), [(0, 2), (3, 5)]);
+
+    editor.select_only(1);
+    assert!(!editor.can_merge_segment_groups());
+    editor.select_additionally(4);
+    assert!(editor.can_merge_segment_groups());
+    editor.merge_segment_groups();
+
+    assert_eq!(group_ranges(&editor), [(0, 5)]);
+    let group = editor.run.segment_groups().get(0).unwrap();
+    assert_eq!(group.name(), Some("First"));
+}
+
+#[test]
+fn dissolve_groups() {
+    let mut editor = editor_with_segments(4);
+    editor.select_only(1);
+    editor.select_additionally(2);
+    editor.create_segment_group();
+
+    editor.select_only(0);
+    assert!(!editor.can_dissolve_segment_groups());
+    editor.select_additionally(2);
+    assert!(editor.can_dissolve_segment_groups());
+    editor.dissolve_segment_groups();
+    assert!(editor.run.segment_groups().is_empty());
+    assert_eq!(editor.run.len(), 4);
+}
+
+#[test]
+fn groups_follow_inserted_and_removed_segments() {
+    let mut editor = editor_with_segments(4);
+    editor.select_only(1);
+    editor.select_additionally(2);
+    editor.create_segment_group();
+
+    editor.select_only(0);
+    editor.insert_segment_above();
+    assert_eq!(group_ranges(&editor), [(2, 4)]);
+
+    editor.select_only(2);
+    editor.insert_segment_below();
+    assert_eq!(group_ranges(&editor), [(2, 5)]);
+
+    editor.select_only(2);
+    editor.select_additionally(3);
+    editor.select_additionally(4);
+    editor.remove_segments();
+    assert!(editor.run.segment_groups().is_empty());
+}
+
+#[test]
+fn name_and_collapse_group_of_active_segment() {
+    let mut editor = editor_with_segments(3);
+    editor.select_only(0);
+    editor.set_segment_group_name("Ignored");
+    assert!(editor.run.segment_groups().is_empty());
+
+    editor.select_additionally(1);
+    editor.create_segment_group();
+    editor.set_segment_group_name("Group");
+    editor.set_segment_group_collapsed(true);
+
+    let state = editor.state();
+    assert_eq!(state.segment_groups.len(), 1);
+    assert_eq!(state.segment_groups[0].name, "Group");
+    assert!(state.segment_groups[0].is_collapsed);
+
+    editor.set_segment_group_name("");
+    assert_eq!(editor.run.segment_groups().get(0).unwrap().name(), None);
+}
+
+#[test]
+fn segments_dont_move_across_group_boundaries() {
+    let mut editor = editor_with_segments(5);
+    editor.select_only(1);
+    editor.select_additionally(2);
+    editor.create_segment_group();
+
+    editor.select_only(1);
+    assert!(!editor.can_move_segments_up());
+    editor.move_segments_up();
+    assert_eq!(editor.run.segment(0).name(), "0");
+    assert!(editor.can_move_segments_down());
+    editor.move_segments_down();
+    assert_eq!(editor.run.segment(2).name(), "1");
+
+    assert!(!editor.can_move_segments_down());
+    editor.move_segments_down();
+    assert_eq!(editor.run.segment(2).name(), "1");
+
+    editor.select_only(3);
+    assert!(!editor.can_move_segments_up());
+    assert!(editor.can_move_segments_down());
+
+    editor.select_additionally(0);
+    assert!(!editor.can_move_segments_down());
+    assert_eq!(group_ranges(&editor), [(1, 3)]);
+}
@@ -0,0 +1,238 @@
+use super::super::{Action, Editor};
+use crate::tests_helper::{create_timer, run_with_splits, run_with_splits_opt};
+use crate::{Run, Segment};
+
+fn editor_with_segments(count: usize) -> Editor {
+    let mut run = Run::new();
+    for i in 0..count {
+        run.push_segment(Segment::new(i.to_string()));
+    }
+    Editor::new(run).unwrap()
+}
+
+fn segment_names(editor: &Editor) -> Vec<&str> {
+    editor.run().segments().iter().map(|s| s.name()).collect()
+}
+
+#[test]
+fn nothing_to_undo_initially() {
+    let mut editor = editor_with_segments(2);
+    assert!(!editor.can_undo());
+    assert!(!editor.can_redo());
+    assert!(!editor.undo());
+    assert!(!editor.redo());
+}
+
+#[test]
+fn undo_and_redo_game_name() {
+    let mut editor = editor_with_segments(1);
+    editor.set_game_name("Game");
+    assert_eq!(editor.undo_action(), Some(Action::SetGameName));
+
+    assert!(editor.undo());
+    assert_eq!(editor.game_name(), "");
+    assert_eq!(editor.redo_action(), Some(Action::SetGameName));
+
+    assert!(editor.redo());
+    assert_eq!(editor.game_name(), "Game");
+    assert!(!editor.can_redo());
+}
+
+#[test]
+fn consecutive_text_changes_are_merged() {
+    let mut editor = editor_with_segments(2);
+    editor.set_game_name("G");
+    editor.set_game_name("Ga");
+    editor.set_game_name("Game");
+    editor.active_segment().set_name("A");
+    editor.active_segment().set_name("AB");
+    editor.select_only(1);
+    editor.active_segment().set_name("C");
+
+    assert!(editor.undo());
+    assert_eq!(segment_names(&editor), ["AB", "1"]);
+    assert!(editor.undo());
+    assert_eq!(segment_names(&editor), ["0", "1"]);
+    assert!(editor.undo());
+    assert_eq!(editor.game_name(), "");
+    assert!(!editor.can_undo());
+}
+
+#[test]
+fn text_changes_are_not_merged_after_undoing() {
+    let mut editor = editor_with_segments(1);
+    editor.set_game_name("First");
+    editor.set_category_name("Any%");
+    assert!(editor.undo());
+    editor.set_game_name("Second");
+
+    assert!(editor.undo());
+    assert_eq!(editor.game_name(), "First");
+    assert!(editor.undo());
+    assert_eq!(editor.game_name(), "");
+}
+
+#[test]
+fn new_action_discards_redo() {
+    let mut editor = editor_with_segments(2);
+    editor.set_attempt_count(5);
+    assert!(editor.undo());
+    assert!(editor.can_redo());
+
+    editor.set_attempt_count(7);
+    assert!(!editor.can_redo());
+    assert!(editor.undo());
+    assert_eq!(editor.attempt_count(), 0);
+}
+
+#[test]
+fn actions_without_changes_are_not_recorded() {
+    let mut editor = editor_with_segments(2);
+    editor.set_attempt_count(5);
+    editor.set_game_name("");
+    editor.active_segment().set_name("0");
+    editor.set_offset(editor.offset());
+    assert_eq!(editor.undo_action(), Some(Action::SetAttemptCount));
+
+    assert!(editor.undo());
+    editor.set_category_name("");
+    assert_eq!(editor.redo_action(), Some(Action::SetAttemptCount));
+    assert!(editor.redo());
+    assert_eq!(editor.attempt_count(), 5);
+}
+
+#[test]
+fn undo_removing_segments_restores_selection() {
+    let mut editor = editor_with_segments(4);
+    editor.select_only(1);
+    editor.select_additionally(2);
+    editor.remove_segments();
+    assert_eq!(segment_names(&editor), ["0", "3"]);
+
+    assert!(editor.undo());
+    assert_eq!(segment_names(&editor), ["0", "1", "2", "3"]);
+    let state = editor.state();
+    assert!(state.segments[1].selected.is_selected_or_active());
+    assert!(state.segments[2].selected.is_selected_or_active());
+    assert!(!state.segments[0].selected.is_selected_or_active());
+
+    assert!(editor.redo());
+    assert_eq!(segment_names(&editor), ["0", "3"]);
+}
+
+#[test]
+fn undo_split_time_restores_segment_times() {
+    let mut editor = editor_with_segments(2);
+    editor
+        .active_segment()
+        .parse_and_set_split_time("1:00")
+        .unwrap();
+    editor
+        .active_segment()
+        .parse_and_set_split_time("2:00")
+        .unwrap();
+
+    assert!(editor.undo());
+    assert_eq!(
+        editor.active_segment().segment_time(),
+        editor.active_segment().split_time(),
+    );
+    assert_eq!(
+        editor.run().segment(0).personal_best_split_time().real_time,
+        editor.active_segment().segment_time(),
+    );
+    assert!(editor.undo());
+    assert_eq!(editor.active_segment().segment_time(), None);
+}
+
+#[test]
+fn undo_restores_segments_fixed_by_the_action() {
+    let mut editor = editor_with_segments(3);
+    for (index, time) in ["1:00", "2:00", "3:00"].iter().enumerate() {
+        editor.select_only(index);
+        editor
+            .active_segment()
+            .parse_and_set_split_time(time)
+            .unwrap();
+    }
+    let before = editor.run().segments().to_vec();
+
+    // The split times can't decrease, so the later segments get adjusted.
+    editor.select_only(0);
+    editor
+        .active_segment()
+        .parse_and_set_split_time("2:30")
+        .unwrap();
+    let after = editor.run().segments().to_vec();
+    assert_ne!(after[1], before[1]);
+
+    assert!(editor.undo());
+    assert_eq!(editor.run().segments(), &*before);
+    assert!(editor.redo());
+    assert_eq!(editor.run().segments(), &*after);
+}
+
+#[test]
+fn failed_actions_are_not_recorded() {
+    let mut editor = editor_with_segments(1);
+    editor.add_comparison("[Race]Bad").unwrap_err();
+    editor.rename_comparison("Missing", "New").unwrap_err();
+    editor.remove_comparison("Missing");
+    editor.move_comparison(3, 4).unwrap_err();
+    editor.set_custom_variable("Missing", "Value");
+    editor.remove_custom_variable("Missing");
+    editor.set_segment_group_name("Group");
+    editor.dissolve_segment_groups();
+    assert!(!editor.can_undo());
+}
+
+#[test]
+fn undo_comparison_actions() {
+    let mut editor = editor_with_segments(1);
+    editor.add_comparison("Custom").unwrap();
+    editor.rename_comparison("Custom", "Renamed").unwrap();
+    assert_eq!(editor.undo_action(), Some(Action::RenameComparison));
+
+    assert!(editor.undo());
+    assert!(editor.custom_comparisons().iter().any(|c| c == "Custom"));
+    assert!(editor.undo());
+    assert!(!editor.custom_comparisons().iter().any(|c| c == "Custom"));
+}
+
+#[test]
+fn undo_clean_sum_of_best() {
+    let mut timer = create_timer(&["A", "B", "C"]);
+    run_with_splits(&mut timer, &[10.0, 20.0, 30.0]);
+    run_with_splits_opt(&mut timer, &[Some(25.0), None, Some(31.0)]);
+    let mut editor = Editor::new(timer.into_run(true)).unwrap();
+
+    let history_len = |editor: &Editor| editor.run().segment(2).segment_history().iter().count();
+    let before = history_len(&editor);
+
+    {
+        let mut cleaner = editor.clean_sum_of_best();
+        let clean_up = cleaner.next_potential_clean_up().unwrap().into();
+        cleaner.apply(clean_up);
+    }
+    assert_eq!(history_len(&editor), before - 1);
+    assert_eq!(editor.undo_action(), Some(Action::CleanSumOfBest));
+
+    assert!(editor.undo());
+    assert_eq!(history_len(&editor), before);
+}
+
+#[test]
+fn state_contains_undo_labels() {
+    let mut editor = editor_with_segments(2);
+    editor.insert_segment_below();
+    let state = editor.state();
+    assert!(state.buttons.can_undo);
+    assert!(!state.buttons.can_redo);
+    assert_eq!(state.buttons.undo_action.as_deref(), Some("Insert Segment"));
+    assert_eq!(state.buttons.redo_action, None);
+
+    editor.undo();
+    let state = editor.state();
+    assert_eq!(state.buttons.undo_action, None);
+    assert_eq!(state.buttons.redo_action.as_deref(), Some("Insert Segment"));
+}
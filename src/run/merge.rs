@@ -0,0 +1,353 @@
+use super::{Attempt, Run, SegmentHistory};
+use crate::{comparison::personal_best, platform::prelude::*, Time, TimingMethod};
+use core::{cmp::Ordering, mem};
+use hashbrown::HashMap;
+
+/// The Error type for Runs that can't be merged.
+#[derive(PartialEq, Debug, snafu::Snafu)]
+pub enum MergeError {
+    /// The Runs don't have the same amount of segments, so they can't be
+    /// copies of the same route.
+    #[snafu(display(
+        "The runs have a different amount of segments ({} and {}).",
+        ours,
+        theirs
+    ))]
+    SegmentCountMismatch {
+        /// The amount of segments of the Run that is merged into.
+        ours: usize,
+        /// The amount of segments of the other Run.
+        theirs: usize,
+    },
+}
+
+/// The Result type for merging Runs.
+pub type MergeResult<T> = Result<T, MergeError>;
+
+/// Describes information that differs between two Runs that got merged. In
+/// all of these cases the information of the Run that got merged into is kept.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum MergeConflict {
+    /// The Runs have different game names.
+    GameName {
+        /// The game name of the other Run.
+        theirs: String,
+    },
+    /// The Runs have different category names.
+    CategoryName {
+        /// The category name of the other Run.
+        theirs: String,
+    },
+    /// A segment has a different name in the other Run.
+    SegmentName {
+        /// The index of the segment.
+        index: usize,
+        /// The name of the segment in the other Run.
+        theirs: String,
+    },
+}
+
+/// Describes the result of merging two Runs.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct MergeReport {
+    /// The information that differs between the Runs.
+    pub conflicts: Vec<MergeConflict>,
+    /// The amount of attempts of the other Run that were added to the Attempt
+    /// History.
+    pub added_attempts: usize,
+    /// The amount of attempts of the other Run that were already in the
+    /// Attempt History. This is usually the case if both Runs were copied
+    /// from the same splits file at some point.
+    pub duplicate_attempts: usize,
+}
+
+impl MergeReport {
+    /// Returns whether the Runs were merged without any conflicts.
+    pub const fn is_conflict_free(&self) -> bool {
+        self.conflicts.is_empty()
+    }
+}
+
+impl Run {
+    /// Merges another copy of the same Run into this Run. This is useful if a
+    /// runner practices on multiple machines and the splits files diverged.
+    /// Both Attempt Histories are combined, ordered by the point in time the
+    /// attempts started at, and the attempts are renumbered. Attempts that are
+    /// in both Runs are only kept once, just like Segment History elements that
+    /// don't belong to any attempt. The Segment Histories are merged
+    /// accordingly, the Best Segment Times are the best of both Runs and the
+    /// Personal Best is the faster one of both Runs for each timing method.
+    /// Custom Comparisons only the other Run has are added as well.
+    ///
+    /// Both Runs need to have the same amount of segments. Information that
+    /// differs between the Runs, like the names of segments, is reported in
+    /// the returned Merge Report. In those cases this Run's information is
+    /// kept.
+    pub fn merge(&mut self, other: &Run) -> MergeResult<MergeReport> {
+        if self.len() != other.len() {
+            return Err(MergeError::SegmentCountMismatch {
+                ours: self.len(),
+                theirs: other.len(),
+            });
+        }
+
+        let mut report = MergeReport::default();
+
+        if self.game_name != other.game_name {
+            report.conflicts.push(MergeConflict::GameName {
+                theirs: other.game_name.clone(),
+            });
+        }
+        if self.category_name != other.category_name {
+            report.conflicts.push(MergeConflict::CategoryName {
+                theirs: other.category_name.clone(),
+            });
+        }
+        for (index, (ours, theirs)) in self.segments.iter().zip(&other.segments).enumerate() {
+            if ours.name() != theirs.name() {
+                report.conflicts.push(MergeConflict::SegmentName {
+                    index,
+                    theirs: theirs.name().to_owned(),
+                });
+            }
+        }
+
+        let (our_indices, their_indices) = self.merge_attempt_history(other, &mut report);
+        self.attempt_count = (self.attempt_count + other.attempt_count)
+            .saturating_sub(report.duplicate_attempts as u32)
+            .max(self.attempt_history.len() as u32);
+
+        let mut min_index = self
+            .min_segment_history_index()
+            .unwrap_or_default()
+            .min(other.min_segment_history_index().unwrap_or_default());
+
+        // The elements that don't belong to any attempt, like imported Personal
+        // Bests, are moved below all the other elements, so they don't collide.
+        // If both Runs contain the same element, it is only kept once.
+        let our_unattached = unattached_history(self, &our_indices);
+        let their_unattached = unattached_history(other, &their_indices);
+        let mut our_unattached_ids = HashMap::new();
+        let mut their_unattached_ids = HashMap::new();
+        for (index, _) in &our_unattached {
+            min_index -= 1;
+            our_unattached_ids.insert(*index, min_index);
+        }
+        for (index, times) in &their_unattached {
+            let duplicate = our_unattached
+                .iter()
+                .find(|(_, ours)| ours == times)
+                .map(|(our_index, _)| our_unattached_ids[our_index]);
+            let id = match duplicate {
+                Some(id) => id,
+                None => {
+                    min_index -= 1;
+                    min_index
+                }
+            };
+            their_unattached_ids.insert(*index, id);
+        }
+
+        let merge_history = |history: &SegmentHistory,
+                             indices: &HashMap<i32, i32>,
+                             unattached_ids: &HashMap<i32, i32>,
+                             merged: &mut SegmentHistory| {
+            for &(index, time) in history.iter() {
+                let index = match indices.get(&index) {
+                    Some(&index) => index,
+                    None => unattached_ids[&index],
+                };
+                merged.insert(index, time);
+            }
+        };
+
+        for (ours, theirs) in self.segments.iter_mut().zip(&other.segments) {
+            let mut merged = SegmentHistory::default();
+            merge_history(
+                ours.segment_history(),
+                &our_indices,
+                &our_unattached_ids,
+                &mut merged,
+            );
+            merge_history(
+                theirs.segment_history(),
+                &their_indices,
+                &their_unattached_ids,
+                &mut merged,
+            );
+            *ours.segment_history_mut() = merged;
+
+            for &method in &TimingMethod::all() {
+                let best_segment_time = match (
+                    ours.best_segment_time()[method],
+                    theirs.best_segment_time()[method],
+                ) {
+                    (Some(ours), Some(theirs)) => Some(ours.min(theirs)),
+                    (ours, theirs) => ours.or(theirs),
+                };
+                ours.best_segment_time_mut()[method] = best_segment_time;
+            }
+        }
+
+        self.merge_personal_best(other);
+
+        for comparison in &other.custom_comparisons {
+            if !self.custom_comparisons.contains(comparison) {
+                self.custom_comparisons.push(comparison.clone());
+                for (ours, theirs) in self.segments.iter_mut().zip(&other.segments) {
+                    *ours.comparison_mut(comparison) = theirs.comparison(comparison);
+                }
+            }
+        }
+
+        self.fix_splits();
+        self.regenerate_comparisons();
+        self.mark_as_modified();
+
+        Ok(report)
+    }
+
+    /// Combines both Attempt Histories and returns how the indices of both
+    /// Runs map to the new indices.
+    fn merge_attempt_history(
+        &mut self,
+        other: &Run,
+        report: &mut MergeReport,
+    ) -> (HashMap<i32, i32>, HashMap<i32, i32>) {
+        let our_attempts = mem::take(&mut self.attempt_history);
+        let (mut our_indices, mut their_indices) = (HashMap::new(), HashMap::new());
+
+        let mut duplicates = Vec::new();
+        let their_attempts = other
+            .attempt_history
+            .iter()
+            .filter(|&theirs| {
+                match our_attempts
+                    .iter()
+                    .find(|ours| is_same_attempt(ours, theirs))
+                {
+                    Some(ours) => {
+                        duplicates.push((theirs.index(), ours.index()));
+                        false
+                    }
+                    None => true,
+                }
+            })
+            .collect::<Vec<_>>();
+        report.duplicate_attempts = duplicates.len();
+
+        let (mut ours, mut theirs) = (
+            our_attempts.iter().peekable(),
+            their_attempts.iter().peekable(),
+        );
+
+        // Both Attempt Histories are already ordered, so they only need to be
+        // interleaved. If it's unknown when an attempt started, our attempts
+        // come first.
+        loop {
+            let take_ours = match (ours.peek(), theirs.peek()) {
+                (Some(a), Some(b)) => match (a.started(), b.started()) {
+                    (Some(a), Some(b)) => a.time.cmp(&b.time) != Ordering::Greater,
+                    _ => true,
+                },
+                (Some(_), None) => true,
+                (None, Some(_)) => false,
+                (None, None) => break,
+            };
+
+            let index = self.attempt_history.len() as i32 + 1;
+            let attempt = if take_ours {
+                let attempt = ours.next().unwrap();
+                our_indices.insert(attempt.index(), index);
+                attempt
+            } else {
+                let attempt = *theirs.next().unwrap();
+                their_indices.insert(attempt.index(), index);
+                report.added_attempts += 1;
+                attempt
+            };
+
+            self.attempt_history.push(Attempt::new(
+                index,
+                attempt.time(),
+                attempt.started(),
+                attempt.ended(),
+                attempt.pause_time(),
+            ));
+        }
+
+        // The Segment History elements of duplicate attempts end up at the
+        // same index as our copy of the attempt.
+        for (their_index, our_index) in duplicates {
+            their_indices.insert(their_index, our_indices[&our_index]);
+        }
+
+        (our_indices, their_indices)
+    }
+
+    /// Takes over the Personal Best split times of the other Run for every
+    /// timing method where its Personal Best is faster.
+    fn merge_personal_best(&mut self, other: &Run) {
+        let (ours, theirs) = match (self.segments.last(), other.segments.last()) {
+            (Some(ours), Some(theirs)) => (
+                ours.personal_best_split_time(),
+                theirs.personal_best_split_time(),
+            ),
+            _ => return,
+        };
+
+        for &method in &TimingMethod::all() {
+            let is_theirs_faster = match (ours[method], theirs[method]) {
+                (Some(ours), Some(theirs)) => theirs < ours,
+                (None, Some(_)) => true,
+                _ => false,
+            };
+            if is_theirs_faster {
+                for (ours, theirs) in self.segments.iter_mut().zip(&other.segments) {
+                    ours.comparison_mut(personal_best::NAME)[method] =
+                        theirs.personal_best_split_time()[method];
+                }
+            }
+        }
+    }
+}
+
+/// Collects the Segment History elements that don't belong to any of the
+/// attempts provided, ordered from the highest index to the lowest. Each of
+/// them comes with its times for all the segments.
+fn unattached_history(
+    run: &Run,
+    attempt_indices: &HashMap<i32, i32>,
+) -> Vec<(i32, Vec<Option<Time>>)> {
+    let mut indices = run
+        .segments
+        .iter()
+        .flat_map(|segment| segment.segment_history().iter().map(|&(index, _)| index))
+        .filter(|index| !attempt_indices.contains_key(index))
+        .collect::<Vec<_>>();
+    indices.sort_unstable_by(|a, b| b.cmp(a));
+    indices.dedup();
+
+    indices
+        .into_iter()
+        .map(|index| {
+            let times = run
+                .segments
+                .iter()
+                .map(|segment| segment.segment_history().get(index))
+                .collect();
+            (index, times)
+        })
+        .collect()
+}
+
+/// Two attempts are considered the same attempt if they started and ended at
+/// the same points in time and have the same final time. If it's unknown when
+/// they started, the indices need to match as well.
+fn is_same_attempt(a: &Attempt, b: &Attempt) -> bool {
+    a.started() == b.started()
+        && a.ended() == b.ended()
+        && a.time() == b.time()
+        && a.pause_time() == b.pause_time()
+        && (a.started().is_some() || a.index() == b.index())
+}
@@ -18,12 +18,14 @@
 mod attempt;
 mod comparisons;
 pub mod editor;
+mod merge;
 #[cfg(feature = "std")]
 pub mod parser;
 mod run_metadata;
 #[cfg(feature = "std")]
 pub mod saver;
 mod segment;
+mod segment_group;
 mod segment_history;
 
 #[cfg(test)]
@@ -32,12 +34,18 @@ mod tests;
 pub use attempt::Attempt;
 pub use comparisons::Comparisons;
 pub use editor::{Editor, RenameError};
+pub use merge::{MergeConflict, MergeError, MergeReport, MergeResult};
 pub use run_metadata::{CustomVariable, RunMetadata};
 pub use segment::Segment;
+pub use segment_group::{SegmentGroup, SegmentGroupError, SegmentGroups};
 pub use segment_history::SegmentHistory;
 
 use crate::{
-    comparison::{default_generators, personal_best, ComparisonGenerator},
+    comparison::{
+        default_generators, personal_best,
+        race::{self, Race},
+        ComparisonGenerator,
+    },
     platform::prelude::*,
     settings::Image,
     AtomicDateTime, Time, TimeSpan, TimingMethod,
@@ -78,7 +86,9 @@ pub struct Run {
     has_been_modified: bool,
     path: Option<PathBuf>,
     segments: Vec<Segment>,
+    segment_groups: SegmentGroups,
     custom_comparisons: Vec<String>,
+    custom_timing_methods: [String; TimingMethod::CUSTOM_COUNT],
     comparison_generators: ComparisonGenerators,
     auto_splitter_settings: Vec<u8>,
 }
@@ -122,7 +132,9 @@ impl Run {
             has_been_modified: false,
             path: None,
             segments: Vec::new(),
+            segment_groups: SegmentGroups::new(),
             custom_comparisons: vec![personal_best::NAME.to_string()],
+            custom_timing_methods: Default::default(),
             comparison_generators: ComparisonGenerators(default_generators()),
             auto_splitter_settings: Vec::new(),
         }
@@ -222,6 +234,50 @@ impl Run {
         self.offset
     }
 
+    /// Accesses the name the Run gave to the custom timing method provided.
+    /// `None` is returned if the custom timing method is not in use by the
+    /// Run or if the timing method provided is not a custom timing method.
+    pub fn custom_timing_method_name(&self, method: TimingMethod) -> Option<&str> {
+        let name = &self.custom_timing_methods[method.custom_index()?];
+        if name.is_empty() {
+            None
+        } else {
+            Some(name)
+        }
+    }
+
+    /// Sets the name of the custom timing method provided. An empty name marks
+    /// the custom timing method as not being in use by the Run. Real Time and
+    /// Game Time can't be renamed, so nothing happens for them.
+    pub fn set_custom_timing_method_name<S>(&mut self, method: TimingMethod, name: S)
+    where
+        S: AsRef<str>,
+    {
+        if let Some(index) = method.custom_index() {
+            let custom_name = &mut self.custom_timing_methods[index];
+            custom_name.clear();
+            custom_name.push_str(name.as_ref());
+        }
+    }
+
+    /// Returns the name to show for the timing method provided. This is the
+    /// name the Run gave to a custom timing method or the default name of the
+    /// timing method otherwise.
+    pub fn timing_method_name(&self, method: TimingMethod) -> &str {
+        self.custom_timing_method_name(method)
+            .unwrap_or_else(|| method.default_name())
+    }
+
+    /// Returns an iterator over all the timing methods in use by the Run. This
+    /// is Real Time, Game Time and all the custom timing methods the Run gave
+    /// a name.
+    pub fn timing_methods(&self) -> impl Iterator<Item = TimingMethod> + '_ {
+        const ALL: [TimingMethod; 2 + TimingMethod::CUSTOM_COUNT] = TimingMethod::all();
+        ALL.iter().copied().filter(move |&method| {
+            !method.is_custom() || self.custom_timing_method_name(method).is_some()
+        })
+    }
+
     /// Marks a Run that a new Attempt has started. If you use it with a Timer,
     /// this is done automatically.
     pub fn start_next_run(&mut self) {
@@ -267,6 +323,37 @@ impl Run {
         &mut self.segments[index]
     }
 
+    /// Accesses the Segment Groups of this Run. A Segment Group combines a
+    /// range of consecutive segments, which is commonly known as subsplits.
+    #[inline]
+    pub const fn segment_groups(&self) -> &SegmentGroups {
+        &self.segment_groups
+    }
+
+    /// Grants mutable access to the Segment Groups of this Run.
+    ///
+    /// # Warning
+    ///
+    /// You need to ensure that the Segment Groups stay within the bounds of
+    /// the segments of this Run.
+    #[inline]
+    pub const fn segment_groups_mut(&mut self) -> &mut SegmentGroups {
+        &mut self.segment_groups
+    }
+
+    /// Adds a new Segment Group spanning the segments from `start` up to but
+    /// excluding `end`. The group may not overlap with any of the existing
+    /// groups. Segments that are out of bounds are not part of the group. The
+    /// index of the new group is returned.
+    pub fn add_segment_group(
+        &mut self,
+        start: usize,
+        end: usize,
+    ) -> Result<usize, SegmentGroupError> {
+        self.segment_groups
+            .insert(SegmentGroup::new(start, end.min(self.len())))
+    }
+
     /// Accesses the history of all the runs that have been attempted. This does
     /// not store the actual segment times, just the overall attempt
     /// information. Information about the individual segments is stored within
@@ -436,6 +523,57 @@ impl Run {
         }
     }
 
+    /// Adds a race comparison against the runner provided, which compares
+    /// against the Personal Best of the run provided. The comparison is called
+    /// `[Race] <runner>` and its segments are mapped onto the segments of this
+    /// Run by their names. The comparison can't be added if one against the
+    /// same runner already exists.
+    pub fn add_race_comparison(&mut self, runner: &str, run: &Run) -> ComparisonResult<()> {
+        let mut race = Race::new(runner, run);
+        if self.comparisons().any(|c| c == race.name()) {
+            return Err(ComparisonError::DuplicateName);
+        }
+        race.generate(&mut self.segments, &self.attempt_history);
+        self.comparison_generators.0.push(Box::new(race));
+        Ok(())
+    }
+
+    /// Updates the race comparison against the runner provided with the
+    /// Personal Best of the run provided. This can be done at any point, even
+    /// in the middle of an attempt. Returns `false` if there is no race
+    /// comparison against the runner.
+    pub fn update_race_comparison(&mut self, runner: &str, run: &Run) -> bool {
+        let mut race = Race::new(runner, run);
+        if let Some(generator) = self
+            .comparison_generators
+            .0
+            .iter_mut()
+            .find(|g| g.name() == race.name())
+        {
+            race.generate(&mut self.segments, &self.attempt_history);
+            *generator = Box::new(race);
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Removes the race comparison against the runner provided. Returns
+    /// `false` if there is no race comparison against the runner.
+    pub fn remove_race_comparison(&mut self, runner: &str) -> bool {
+        let name = race::name(runner);
+        let generators = &mut self.comparison_generators.0;
+        let len = generators.len();
+        generators.retain(|g| g.name() != name);
+        if generators.len() == len {
+            return false;
+        }
+        for segment in &mut self.segments {
+            segment.comparisons_mut().remove(&name);
+        }
+        true
+    }
+
     /// Returns a file name (without the extension) suitable for this Run that
     /// is built the following way:
     ///
@@ -527,6 +665,7 @@ impl Run {
         self.remove_duplicates();
         self.remove_none_values();
         self.reattach_unattached_segment_history_elements();
+        self.segment_groups.fix(self.segments.len());
     }
 
     /// Clears out the Attempt History and the Segment Histories of all the segments.
@@ -600,7 +739,7 @@ impl Run {
             for run_index in min_index..max_index {
                 for index in 0..self.len() {
                     if let Some(element) = self.segments[index].segment_history().get(run_index) {
-                        if element.real_time.is_none() && element.game_time.is_none() {
+                        if element.is_empty() {
                             cache.push(run_index);
                         } else {
                             cache.clear();
@@ -617,21 +756,20 @@ impl Run {
     }
 
     fn remove_duplicates(&mut self) {
-        let mut rta_set = HashSet::<OrderedFloat<_>>::new();
-        let mut igt_set = HashSet::<OrderedFloat<_>>::new();
+        let mut sets = TimingMethod::all().map(|_| HashSet::<OrderedFloat<_>>::new());
 
         for segment in self.segments_mut() {
             let history = segment.segment_history_mut();
 
-            rta_set.clear();
-            igt_set.clear();
+            for set in &mut sets {
+                set.clear();
+            }
 
             for &(_, time) in history.iter_actual_runs() {
-                if let Some(time) = time.real_time {
-                    rta_set.insert(time.total_milliseconds().into());
-                }
-                if let Some(time) = time.game_time {
-                    igt_set.insert(time.total_milliseconds().into());
+                for (set, &method) in sets.iter_mut().zip(&TimingMethod::all()) {
+                    if let Some(time) = time[method] {
+                        set.insert(time.total_milliseconds().into());
+                    }
                 }
             }
 
@@ -641,14 +779,11 @@ impl Run {
                 }
 
                 let (mut is_none, mut is_unique) = (true, false);
-                if let Some(time) = time.real_time {
-                    is_unique |= rta_set.insert(time.total_milliseconds().into());
-                    is_none = false;
-                }
-
-                if let Some(time) = time.game_time {
-                    is_unique |= igt_set.insert(time.total_milliseconds().into());
-                    is_none = false;
+                for (set, &method) in sets.iter_mut().zip(&TimingMethod::all()) {
+                    if let Some(time) = time[method] {
+                        is_unique |= set.insert(time.total_milliseconds().into());
+                        is_none = false;
+                    }
                 }
 
                 is_none || is_unique
@@ -677,6 +812,16 @@ impl Run {
     pub fn import_pb_into_segment_history(&mut self) {
         if let Some(mut index) = self.min_segment_history_index() {
             for &timing_method in &TimingMethod::all() {
+                // Custom timing methods that aren't in use don't need any
+                // additional entries.
+                if timing_method.is_custom()
+                    && self
+                        .segments
+                        .iter()
+                        .all(|s| s.personal_best_split_time()[timing_method].is_none())
+                {
+                    continue;
+                }
                 index -= 1;
                 let mut prev_time = TimeSpan::zero();
 
@@ -703,7 +848,7 @@ impl Run {
     /// This panics if the segment index provided is out of bounds.
     pub fn import_best_segment(&mut self, segment_index: usize) {
         let best_segment_time = self.segments[segment_index].best_segment_time();
-        if best_segment_time.real_time.is_some() || best_segment_time.game_time.is_some() {
+        if !best_segment_time.is_empty() {
             // We can unwrap here because due to the fact that we can access the
             // best_segment_time of some segment, at least one exists.
             let index = self.min_segment_history_index().unwrap() - 1;
@@ -733,11 +878,10 @@ impl Run {
             let split_time = segment.split_time();
             let segment_time = Time::op(split_time, last_split_time, |a, b| a - b);
             segment.segment_history_mut().insert(index, segment_time);
-            if let Some(time) = split_time.real_time {
-                last_split_time.real_time = Some(time);
-            }
-            if let Some(time) = split_time.game_time {
-                last_split_time.game_time = Some(time);
+            for &method in &TimingMethod::all() {
+                if let Some(time) = split_time[method] {
+                    last_split_time[method] = Some(time);
+                }
             }
         }
     }
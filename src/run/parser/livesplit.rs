@@ -5,7 +5,11 @@ use crate::xml_util::{
     attribute, attribute_err, end_tag, optional_attribute_err, parse_attributes, parse_base,
     parse_children, reencode_children, text, text_as_bytes_err, text_err, text_parsed,
 };
-use crate::{AtomicDateTime, Run, RunMetadata, Segment, Time, TimeSpan};
+use crate::{
+    run::{SegmentGroup, SegmentGroups},
+    timing::FrameRate,
+    AtomicDateTime, Run, RunMetadata, Segment, Time, TimeSpan, TimingMethod,
+};
 use chrono::{DateTime, TimeZone, Utc};
 use core::str;
 use quick_xml::Reader;
@@ -207,6 +211,18 @@ where
     })
 }
 
+const CUSTOM_TIME_TAGS: [&[u8]; TimingMethod::CUSTOM_COUNT] = [
+    b"CustomTime1",
+    b"CustomTime2",
+    b"CustomTime3",
+    b"CustomTime4",
+];
+
+fn custom_time_method(tag: &[u8]) -> Option<TimingMethod> {
+    let index = CUSTOM_TIME_TAGS.iter().position(|&t| t == tag)?;
+    Some(TimingMethod::custom_methods()[index])
+}
+
 fn time<R, F>(reader: &mut Reader<R>, buf: &mut Vec<u8>, f: F) -> Result<()>
 where
     R: BufRead,
@@ -223,6 +239,10 @@ where
             time_span_opt(reader, tag.into_buf(), |t| {
                 time.game_time = t;
             })
+        } else if let Some(method) = custom_time_method(tag.name()) {
+            time_span_opt(reader, tag.into_buf(), |t| {
+                time[method] = t;
+            })
         } else {
             end_tag(reader, tag.into_buf())
         }
@@ -268,6 +288,10 @@ fn parse_metadata<R: BufRead>(
                 text(reader, tag.into_buf(), |t| metadata.set_platform_name(t))
             } else if tag.name() == b"Region" {
                 text(reader, tag.into_buf(), |t| metadata.set_region_name(t))
+            } else if tag.name() == b"FrameRate" {
+                text_parsed(reader, tag.into_buf(), |t| {
+                    metadata.set_frame_rate(FrameRate::new(t))
+                })
             } else if tag.name() == b"Variables" || tag.name() == b"SpeedrunComVariables" {
                 parse_children(reader, tag.into_buf(), |reader, tag| {
                     let mut name = String::new();
@@ -389,6 +413,72 @@ fn parse_segment<R: BufRead>(
     Ok(segment)
 }
 
+fn parse_segment_groups<R: BufRead>(
+    reader: &mut Reader<R>,
+    buf: &mut Vec<u8>,
+    groups: &mut SegmentGroups,
+) -> Result<()> {
+    parse_children(reader, buf, |reader, tag| {
+        if tag.name() == b"SegmentGroup" {
+            let (mut start, mut end, mut collapsed) = (0, 0, false);
+            type_hint(parse_attributes(&tag, |k, v| {
+                if k == b"start" {
+                    start = v.get::<Error>()?.parse()?;
+                } else if k == b"end" {
+                    end = v.get::<Error>()?.parse()?;
+                } else if k == b"collapsed" {
+                    collapsed = parse_bool(v.get_raw())?;
+                }
+                Ok(true)
+            }))?;
+
+            let mut group = SegmentGroup::new(start, end);
+            group.set_collapsed(collapsed);
+
+            type_hint(text(reader, tag.into_buf(), |t| {
+                if !t.is_empty() {
+                    group.set_name(Some(t.into_owned()));
+                }
+            }))?;
+
+            // Invalid groups are simply dropped.
+            let _ = groups.insert(group);
+
+            Ok(())
+        } else {
+            end_tag(reader, tag.into_buf())
+        }
+    })
+}
+
+fn parse_custom_timing_methods<R: BufRead>(
+    reader: &mut Reader<R>,
+    buf: &mut Vec<u8>,
+    run: &mut Run,
+) -> Result<()> {
+    parse_children(reader, buf, |reader, tag| {
+        if tag.name() == b"CustomTimingMethod" {
+            let mut method = None;
+            type_hint(parse_attributes(&tag, |k, v| {
+                if k == b"id" {
+                    let id: usize = v.get::<Error>()?.parse()?;
+                    method = id.checked_sub(1).and_then(TimingMethod::custom);
+                }
+                Ok(true)
+            }))?;
+
+            type_hint(text(reader, tag.into_buf(), |t| {
+                // Unknown custom timing methods are simply dropped.
+                if let Some(method) = method {
+                    run.set_custom_timing_method_name(method, t);
+                }
+            }))
+        } else {
+            end_tag(reader, tag.into_buf())
+        }
+    })
+}
+
 fn parse_run_history<R: BufRead>(
     version: Version,
     reader: &mut Reader<R>,
@@ -464,6 +554,10 @@ fn parse_attempt_history<R: BufRead>(
                     time_span_opt(reader, tag.into_buf(), |t| {
                         time.game_time = t;
                     })
+                } else if let Some(method) = custom_time_method(tag.name()) {
+                    time_span_opt(reader, tag.into_buf(), |t| {
+                        time[method] = t;
+                    })
                 } else if tag.name() == b"PauseTime" {
                     time_span_opt(reader, tag.into_buf(), |t| {
                         pause_time = t;
@@ -503,6 +597,7 @@ pub fn parse<R: BufRead>(source: R, path: Option<PathBuf>) -> Result<Run> {
     let mut buf2 = Vec::with_capacity(4096);
 
     let mut run = Run::new();
+    let mut segment_groups = None;
 
     let mut required_flags = 0u8;
 
@@ -547,6 +642,14 @@ pub fn parse<R: BufRead>(source: R, path: Option<PathBuf>) -> Result<Run> {
                         end_tag(reader, tag.into_buf())
                     }
                 })
+            } else if tag.name() == b"SegmentGroups" {
+                parse_segment_groups(
+                    reader,
+                    tag.into_buf(),
+                    segment_groups.get_or_insert_with(SegmentGroups::new),
+                )
+            } else if tag.name() == b"CustomTimingMethods" {
+                parse_custom_timing_methods(reader, tag.into_buf(), &mut run)
             } else if tag.name() == b"AutoSplitterSettings" {
                 let settings = run.auto_splitter_settings_mut();
                 reencode_children(reader, tag.into_buf(), settings).map_err(Into::into)
@@ -562,6 +665,19 @@ pub fn parse<R: BufRead>(source: R, path: Option<PathBuf>) -> Result<Run> {
         });
     }
 
+    // Splits files written by the original LiveSplit encode the Segment
+    // Groups in the segment names. We store them explicitly, but still
+    // encode them in the names, so the original LiveSplit can show them.
+    let segment_groups = match segment_groups {
+        Some(mut groups) => {
+            groups.fix(run.len());
+            groups.strip_encoding_from_names(run.segments_mut());
+            groups
+        }
+        None => SegmentGroups::decode_from_names(run.segments_mut()),
+    };
+    *run.segment_groups_mut() = segment_groups;
+
     run.set_path(path);
 
     Ok(run)
@@ -130,11 +130,15 @@ where
                 .and_then(|b| b.checked_mul(4))
                 .context(LengthOutOfBounds)?;
 
-            if buf.len() < 0xFE + len {
+            if buf.len() < 0xFD + len {
                 return Err(Error::Image);
             }
 
-            let buf = &buf[0xFE..][..len];
+            // The pixels are stored as ARGB.
+            let buf = &mut buf[0xFD..][..len];
+            for pixel in buf.chunks_exact_mut(4) {
+                pixel.rotate_left(1);
+            }
             let image = ImageBuffer::<Rgba<u8>, _>::from_raw(width, height, buf).context(Image)?;
 
             Ok((width, height, image))
@@ -142,10 +142,9 @@ fn parse_split_time(
         None
     };
 
-    Time {
-        real_time,
-        game_time,
-    }
+    Time::new()
+        .with_real_time(real_time)
+        .with_game_time(game_time)
 }
 
 /// Attempts to parse a Splitterino splits file.
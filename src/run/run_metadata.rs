@@ -1,5 +1,6 @@
 use crate::indexmap::map::{IndexMap, Iter};
 use crate::platform::prelude::*;
+use crate::timing::FrameRate;
 use serde::{Deserialize, Serialize};
 
 /// A custom variable is a key value pair storing additional information about a
@@ -73,6 +74,10 @@ pub struct RunMetadata {
     /// the runner. Additionally auto splitters or other sources may provide
     /// temporary custom variables that are not stored in the splits files.
     pub custom_variables: IndexMap<String, CustomVariable>,
+    /// The frame rate the game runs at. If it is specified, the times of the
+    /// run are meant to be entered, shown and rounded as frames at this frame
+    /// rate. This is commonly the case for retro games.
+    pub frame_rate: Option<FrameRate>,
 }
 
 impl RunMetadata {
@@ -153,6 +158,22 @@ impl RunMetadata {
         self.region_name.push_str(region_name.as_ref());
     }
 
+    /// Accesses the frame rate the game runs at. If it is specified, the times
+    /// of the run are meant to be entered, shown and rounded as frames at this
+    /// frame rate.
+    #[inline]
+    pub const fn frame_rate(&self) -> Option<FrameRate> {
+        self.frame_rate
+    }
+
+    /// Sets the frame rate the game runs at. Specifying a frame rate causes
+    /// the times of the run to be entered, shown and rounded as frames at this
+    /// frame rate.
+    #[inline]
+    pub const fn set_frame_rate(&mut self, frame_rate: Option<FrameRate>) {
+        self.frame_rate = frame_rate;
+    }
+
     /// Sets the speedrun.com variable with the name specified to the value
     /// specified. A speedrun.com variable is an arbitrary key value pair
     /// storing additional information about the category. An example of this
@@ -230,5 +251,6 @@ impl RunMetadata {
         self.uses_emulator = false;
         self.speedrun_com_variables.clear();
         self.custom_variables.clear();
+        self.frame_rate = None;
     }
 }
@@ -0,0 +1,309 @@
+//! The History Saver exports the Attempt History and the Segment Histories of
+//! Runs, so they can be analyzed with other tools, like spreadsheets or
+//! notebooks. Both CSV and JSON are supported. Times are stored as seconds and
+//! the points in time attempts started and ended at are stored as RFC 3339
+//! date times.
+//!
+//! The CSV exports come as two separate tables. The attempts table has a row
+//! for every attempt, while the segment history table is a matrix with a row
+//! for every segment and a column for every attempt. The JSON export contains
+//! all of this information in a single document. Its schema is versioned, so
+//! any future changes to it are reflected in its `version` field.
+//!
+//! # Examples
+//!
+//! Exporting the histories of a Run as CSV and JSON files.
+//!
+//! ```no_run
+//! use livesplit_core::run::saver::history;
+//! use livesplit_core::{Run, Segment, TimingMethod};
+//! use std::fs::File;
+//! use std::io::BufWriter;
+//!
+//! // Create a run object that we can use.
+//! let mut run = Run::new();
+//! run.set_game_name("Super Mario Odyssey");
+//! run.set_category_name("Any%");
+//! run.push_segment(Segment::new("Cap Kingdom"));
+//!
+//! let file = File::create("path/to/attempts.csv");
+//! let writer = BufWriter::new(file.expect("Failed creating the file"));
+//! history::save_attempts_csv(&run, writer).expect("Couldn't save the attempts");
+//!
+//! let file = File::create("path/to/segments.csv");
+//! let writer = BufWriter::new(file.expect("Failed creating the file"));
+//! history::save_segment_history_csv(&run, TimingMethod::RealTime, writer)
+//!     .expect("Couldn't save the segment history");
+//!
+//! let file = File::create("path/to/history.json");
+//! let writer = BufWriter::new(file.expect("Failed creating the file"));
+//! history::save_json(&run, writer).expect("Couldn't save the history");
+//! ```
+
+use crate::{AtomicDateTime, Run, Time, TimeSpan, TimingMethod};
+use core::result::Result as StdResult;
+use serde::{ser::SerializeMap, Serialize, Serializer};
+use serde_json::Error as JsonError;
+use snafu::ResultExt;
+use std::io::{self, Write};
+
+/// The version of the schema of the JSON export. It is increased whenever the
+/// schema changes.
+pub const JSON_VERSION: u32 = 1;
+
+#[derive(Debug, snafu::Snafu)]
+/// The Error type for histories that couldn't be saved by the History Saver.
+pub enum Error {
+    /// Failed writing the CSV.
+    Io {
+        /// The underlying error.
+        source: io::Error,
+    },
+    /// Failed writing the JSON.
+    Json {
+        /// The underlying error.
+        source: JsonError,
+    },
+}
+
+/// The Result type for the History Saver.
+pub type Result<T> = StdResult<T, Error>;
+
+/// Saves the Attempt History of the Run as CSV. There is a row for every
+/// attempt, containing its index, when it started and ended, how long it was
+/// paused for and its final time for every timing method. The columns of the
+/// timing methods always use their default names, regardless of how the Run
+/// names its custom timing methods. Unknown values are left empty.
+pub fn save_attempts_csv<W: Write>(run: &Run, writer: W) -> Result<()> {
+    write_attempts_csv(run, writer).context(Io)
+}
+
+fn write_attempts_csv<W: Write>(run: &Run, mut writer: W) -> io::Result<()> {
+    write!(
+        writer,
+        "Attempt,Started,Started Synced,Ended,Ended Synced,Pause Time"
+    )?;
+    for method in &TimingMethod::all() {
+        write!(writer, ",{}", method.default_name())?;
+    }
+    writer.write_all(b"\r\n")?;
+
+    for attempt in run.attempt_history() {
+        write!(writer, "{}", attempt.index())?;
+        for date_time in &[attempt.started(), attempt.ended()] {
+            if let Some(date_time) = date_time {
+                write!(
+                    writer,
+                    ",{},{}",
+                    date_time.time.to_rfc3339(),
+                    date_time.synced_with_atomic_clock
+                )?;
+            } else {
+                writer.write_all(b",,")?;
+            }
+        }
+        write_time(&mut writer, attempt.pause_time())?;
+        for &method in &TimingMethod::all() {
+            write_time(&mut writer, attempt.time()[method])?;
+        }
+        writer.write_all(b"\r\n")?;
+    }
+
+    Ok(())
+}
+
+/// Saves the Segment Histories of the Run as a CSV matrix for the timing
+/// method provided. There is a row for every segment and a column for every
+/// attempt in the Attempt History. Each cell contains the segment time of the
+/// attempt. Cells are left empty if the attempt didn't reach the segment or
+/// the segment got skipped.
+pub fn save_segment_history_csv<W: Write>(
+    run: &Run,
+    method: TimingMethod,
+    writer: W,
+) -> Result<()> {
+    write_segment_history_csv(run, method, writer).context(Io)
+}
+
+fn write_segment_history_csv<W: Write>(
+    run: &Run,
+    method: TimingMethod,
+    mut writer: W,
+) -> io::Result<()> {
+    writer.write_all(b"Segment")?;
+    for attempt in run.attempt_history() {
+        write!(writer, ",{}", attempt.index())?;
+    }
+    writer.write_all(b"\r\n")?;
+
+    for segment in run.segments() {
+        write_field(&mut writer, segment.name())?;
+        let history = segment.segment_history();
+        for attempt in run.attempt_history() {
+            write_time(
+                &mut writer,
+                history.get(attempt.index()).and_then(|t| t[method]),
+            )?;
+        }
+        writer.write_all(b"\r\n")?;
+    }
+
+    Ok(())
+}
+
+/// Saves the Attempt History and the Segment Histories of the Run as JSON.
+/// Times always contain the `real_time` and the `game_time`. The times of the
+/// custom timing methods are stored separately in `custom`, using the names
+/// `Custom1` to `Custom4`. The names the Run gives the custom timing methods
+/// are listed in `custom_timing_methods`.
+pub fn save_json<W: Write>(run: &Run, writer: W) -> Result<()> {
+    let history = History {
+        version: JSON_VERSION,
+        game: run.game_name(),
+        category: run.category_name(),
+        custom_timing_methods: TimingMethod::custom_methods()
+            .iter()
+            .map(|&method| CustomTimingMethodJson {
+                id: method,
+                name: run.custom_timing_method_name(method),
+            })
+            .collect(),
+        attempts: run
+            .attempt_history()
+            .iter()
+            .map(|attempt| AttemptJson {
+                index: attempt.index(),
+                started: attempt.started().map(DateTimeJson::from),
+                ended: attempt.ended().map(DateTimeJson::from),
+                pause_time: attempt.pause_time().map(seconds),
+                times: attempt.time().into(),
+            })
+            .collect(),
+        segments: run
+            .segments()
+            .iter()
+            .map(|segment| SegmentJson {
+                name: segment.name(),
+                personal_best_split_time: segment.personal_best_split_time().into(),
+                best_segment_time: segment.best_segment_time().into(),
+                history: segment
+                    .segment_history()
+                    .iter_actual_runs()
+                    .map(|&(attempt, time)| SegmentHistoryElementJson {
+                        attempt,
+                        times: time.into(),
+                    })
+                    .collect(),
+            })
+            .collect(),
+    };
+
+    serde_json::to_writer(writer, &history).context(Json)
+}
+
+#[derive(Serialize)]
+struct History<'a> {
+    version: u32,
+    game: &'a str,
+    category: &'a str,
+    custom_timing_methods: Vec<CustomTimingMethodJson<'a>>,
+    attempts: Vec<AttemptJson>,
+    segments: Vec<SegmentJson<'a>>,
+}
+
+#[derive(Serialize)]
+struct CustomTimingMethodJson<'a> {
+    id: TimingMethod,
+    name: Option<&'a str>,
+}
+
+#[derive(Serialize)]
+struct AttemptJson {
+    index: i32,
+    started: Option<DateTimeJson>,
+    ended: Option<DateTimeJson>,
+    pause_time: Option<f64>,
+    times: TimesJson,
+}
+
+#[derive(Serialize)]
+struct DateTimeJson {
+    time: String,
+    synced_with_atomic_clock: bool,
+}
+
+impl From<AtomicDateTime> for DateTimeJson {
+    fn from(date_time: AtomicDateTime) -> Self {
+        Self {
+            time: date_time.time.to_rfc3339(),
+            synced_with_atomic_clock: date_time.synced_with_atomic_clock,
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct SegmentJson<'a> {
+    name: &'a str,
+    personal_best_split_time: TimesJson,
+    best_segment_time: TimesJson,
+    history: Vec<SegmentHistoryElementJson>,
+}
+
+#[derive(Serialize)]
+struct SegmentHistoryElementJson {
+    attempt: i32,
+    times: TimesJson,
+}
+
+/// The times of all the timing methods in seconds.
+#[derive(Serialize)]
+struct TimesJson {
+    real_time: Option<f64>,
+    game_time: Option<f64>,
+    custom: CustomTimesJson,
+}
+
+impl From<Time> for TimesJson {
+    fn from(time: Time) -> Self {
+        Self {
+            real_time: time.real_time.map(seconds),
+            game_time: time.game_time.map(seconds),
+            custom: CustomTimesJson(time),
+        }
+    }
+}
+
+/// Serializes the times of the custom timing methods as a map from their names
+/// to their times in seconds.
+struct CustomTimesJson(Time);
+
+impl Serialize for CustomTimesJson {
+    fn serialize<S: Serializer>(&self, serializer: S) -> StdResult<S::Ok, S::Error> {
+        let methods = TimingMethod::custom_methods();
+        let mut map = serializer.serialize_map(Some(methods.len()))?;
+        for &method in &methods {
+            map.serialize_entry(&method, &self.0[method].map(seconds))?;
+        }
+        map.end()
+    }
+}
+
+fn seconds(time: TimeSpan) -> f64 {
+    time.total_seconds()
+}
+
+fn write_time<W: Write>(writer: &mut W, time: Option<TimeSpan>) -> io::Result<()> {
+    match time {
+        Some(time) => write!(writer, ",{}", seconds(time)),
+        None => writer.write_all(b","),
+    }
+}
+
+/// Writes a CSV field, quoting it if necessary.
+fn write_field<W: Write>(writer: &mut W, field: &str) -> io::Result<()> {
+    if field.contains(&[',', '"', '\r', '\n'][..]) {
+        write!(writer, "\"{}\"", field.replace('"', "\"\""))
+    } else {
+        writer.write_all(field.as_bytes())
+    }
+}
@@ -25,19 +25,28 @@
 //! ```
 
 use crate::timing::formatter::{Complete, TimeFormatter};
-use crate::{settings::Image, Run, Time, TimeSpan, Timer, TimerPhase};
+use crate::xml_util::writer::{
+    fmt_buf, new_tag, scoped, scoped_iter, text, vec_as_string, write_display, write_end,
+    write_start,
+};
+use crate::{settings::Image, Run, Time, TimeSpan, Timer, TimerPhase, TimingMethod};
 use alloc::borrow::Cow;
 use byteorder::{WriteBytesExt, LE};
 use chrono::{DateTime, Utc};
-use core::fmt::Display;
-use core::mem::replace;
 use core::result::Result as StdResult;
-use quick_xml::events::{BytesDecl, BytesEnd, BytesStart, BytesText, Event};
-use quick_xml::{Error as XmlError, Writer};
+use quick_xml::events::{BytesDecl, BytesStart, BytesText, Event};
+use quick_xml::{Error as XmlError, Result as XmlResult, Writer};
 use std::io::Write;
 
 static LSS_IMAGE_HEADER: &[u8; 156] = include_bytes!("lss_image_header.bin");
 
+const CUSTOM_TIME_TAGS: [&[u8]; TimingMethod::CUSTOM_COUNT] = [
+    b"CustomTime1",
+    b"CustomTime2",
+    b"CustomTime3",
+    b"CustomTime4",
+];
+
 #[derive(Debug, snafu::Snafu)]
 /// The Error type for splits files that couldn't be saved by the LiveSplit
 /// Saver.
@@ -59,27 +68,6 @@ impl From<XmlError> for Error {
 /// The Result type for the LiveSplit Saver.
 pub type Result<T> = StdResult<T, Error>;
 
-fn new_tag(name: &[u8]) -> BytesStart<'_> {
-    BytesStart::borrowed(name, name.len())
-}
-
-fn write_start<W: Write>(writer: &mut Writer<W>, tag: BytesStart<'_>) -> Result<()> {
-    writer.write_event(Event::Start(tag))?;
-    Ok(())
-}
-
-fn write_end<W: Write>(writer: &mut Writer<W>, tag: &[u8]) -> Result<()> {
-    writer.write_event(Event::End(BytesEnd::borrowed(tag)))?;
-    Ok(())
-}
-
-fn split_tag<'a>(tag: &'a BytesStart<'a>) -> (BytesStart<'a>, BytesEnd<'a>) {
-    (
-        BytesStart::borrowed(&tag, tag.name().len()),
-        BytesEnd::borrowed(tag.name()),
-    )
-}
-
 const fn bool(value: bool) -> &'static [u8] {
     if value {
         b"True"
@@ -88,72 +76,13 @@ const fn bool(value: bool) -> &'static [u8] {
     }
 }
 
-fn scoped<W, F>(writer: &mut Writer<W>, tag: BytesStart<'_>, is_empty: bool, scope: F) -> Result<()>
-where
-    W: Write,
-    F: FnOnce(&mut Writer<W>) -> Result<()>,
-{
-    if is_empty {
-        writer.write_event(Event::Empty(tag))?;
-    } else {
-        let (start, end) = split_tag(&tag);
-        writer.write_event(Event::Start(start))?;
-        scope(writer)?;
-        writer.write_event(Event::End(end))?;
-    }
-    Ok(())
-}
-
-fn scoped_iter<W, F, I>(
-    writer: &mut Writer<W>,
-    tag: BytesStart<'_>,
-    iter: I,
-    mut scope: F,
-) -> Result<()>
-where
-    W: Write,
-    I: IntoIterator,
-    F: FnMut(&mut Writer<W>, <I as IntoIterator>::Item) -> Result<()>,
-{
-    let mut iter = iter.into_iter().peekable();
-    scoped(writer, tag, iter.peek().is_none(), |writer| {
-        for item in iter {
-            scope(writer, item)?;
-        }
-        Ok(())
-    })
-}
-
-fn text<W: Write, T: AsRef<[u8]>>(
-    writer: &mut Writer<W>,
-    tag: BytesStart<'_>,
-    text: T,
-) -> Result<()> {
-    let text = text.as_ref();
-    scoped(writer, tag, text.is_empty(), |writer| {
-        writer.write_event(Event::Text(BytesText::from_plain(text)))?;
-        Ok(())
-    })
-}
-
-fn vec_as_string<F, R>(vec: &mut Vec<u8>, f: F) -> R
-where
-    F: FnOnce(&mut String) -> R,
-{
-    let taken = replace(vec, Vec::new());
-    let mut string = String::from_utf8(taken).unwrap();
-    let result = f(&mut string);
-    *vec = string.into_bytes();
-    result
-}
-
 fn image<W: Write>(
     writer: &mut Writer<W>,
     tag: BytesStart<'_>,
     image: &Image,
     buf: &mut Vec<u8>,
     image_buf: &mut Cow<'_, [u8]>,
-) -> Result<()> {
+) -> XmlResult<()> {
     let image_data = image.data();
     if !image_data.is_empty() {
         let len = image_data.len();
@@ -181,31 +110,16 @@ fn fmt_date(date: DateTime<Utc>, buf: &mut Vec<u8>) -> &[u8] {
     fmt_buf(date.format("%m/%d/%Y %T"), buf)
 }
 
-fn fmt_buf<D: Display>(value: D, buf: &mut Vec<u8>) -> &[u8] {
-    buf.clear();
-    write!(buf, "{}", value).unwrap();
-    buf
-}
-
-fn write_display<W: Write, D: Display>(
-    writer: &mut Writer<W>,
-    tag: BytesStart<'_>,
-    value: D,
-    buf: &mut Vec<u8>,
-) -> Result<()> {
-    text(writer, tag, fmt_buf(value, buf))
-}
-
 fn time_span<W: Write>(
     writer: &mut Writer<W>,
     tag: BytesStart<'_>,
     time: TimeSpan,
     buf: &mut Vec<u8>,
-) -> Result<()> {
+) -> XmlResult<()> {
     write_display(writer, tag, Complete.format(time), buf)
 }
 
-fn time_inner<W: Write>(writer: &mut Writer<W>, time: Time, buf: &mut Vec<u8>) -> Result<()> {
+fn time_inner<W: Write>(writer: &mut Writer<W>, time: Time, buf: &mut Vec<u8>) -> XmlResult<()> {
     if let Some(time) = time.real_time {
         time_span(writer, new_tag(b"RealTime"), time, buf)?;
     }
@@ -214,6 +128,12 @@ fn time_inner<W: Write>(writer: &mut Writer<W>, time: Time, buf: &mut Vec<u8>) -
         time_span(writer, new_tag(b"GameTime"), time, buf)?;
     }
 
+    for (&tag, &method) in CUSTOM_TIME_TAGS.iter().zip(&TimingMethod::custom_methods()) {
+        if let Some(time) = time[method] {
+            time_span(writer, new_tag(tag), time, buf)?;
+        }
+    }
+
     Ok(())
 }
 
@@ -222,13 +142,10 @@ fn time<W: Write>(
     tag: BytesStart<'_>,
     time: Time,
     buf: &mut Vec<u8>,
-) -> Result<()> {
-    scoped(
-        writer,
-        tag,
-        time.real_time.is_none() && time.game_time.is_none(),
-        |writer| time_inner(writer, time, buf),
-    )
+) -> XmlResult<()> {
+    scoped(writer, tag, time.is_empty(), |writer| {
+        time_inner(writer, time, buf)
+    })
 }
 
 /// Saves the Run in use by the Timer provided as a LiveSplit splits file
@@ -282,6 +199,15 @@ pub fn save_run<W: Write>(run: &Run, writer: W) -> Result<()> {
 
     text(writer, new_tag(b"Region"), metadata.region_name())?;
 
+    if let Some(frame_rate) = metadata.frame_rate() {
+        write_display(
+            writer,
+            new_tag(b"FrameRate"),
+            frame_rate.frames_per_second(),
+            buf,
+        )?;
+    }
+
     scoped_iter(
         writer,
         new_tag(b"SpeedrunComVariables"),
@@ -332,9 +258,7 @@ pub fn save_run<W: Write>(run: &Run, writer: W) -> Result<()> {
                 tag.push_attribute((&b"isEndedSynced"[..], bool(ended.synced_with_atomic_clock)));
             }
 
-            let is_empty = attempt.time().real_time.is_none()
-                && attempt.time().game_time.is_none()
-                && attempt.pause_time().is_none();
+            let is_empty = attempt.time().is_empty() && attempt.pause_time().is_none();
 
             scoped(writer, tag, is_empty, |writer| {
                 time_inner(writer, attempt.time(), buf)?;
@@ -351,11 +275,17 @@ pub fn save_run<W: Write>(run: &Run, writer: W) -> Result<()> {
     scoped_iter(
         writer,
         new_tag(b"Segments"),
-        run.segments(),
-        |writer, segment| {
+        run.segments().iter().enumerate(),
+        |writer, (segment_index, segment)| {
             write_start(writer, new_tag(b"Segment"))?;
 
-            text(writer, new_tag(b"Name"), segment.name())?;
+            text(
+                writer,
+                new_tag(b"Name"),
+                &*run
+                    .segment_groups()
+                    .encoded_name(segment_index, segment.name()),
+            )?;
             image(writer, new_tag(b"Icon"), segment.icon(), buf, image_buf)?;
 
             scoped_iter(
@@ -391,6 +321,37 @@ pub fn save_run<W: Write>(run: &Run, writer: W) -> Result<()> {
         },
     )?;
 
+    if !run.segment_groups().is_empty() {
+        scoped_iter(
+            writer,
+            new_tag(b"SegmentGroups"),
+            run.segment_groups(),
+            |writer, group| {
+                let mut tag = new_tag(b"SegmentGroup");
+                tag.push_attribute((&b"start"[..], fmt_buf(group.start(), buf)));
+                tag.push_attribute((&b"end"[..], fmt_buf(group.end(), buf)));
+                tag.push_attribute((&b"collapsed"[..], bool(group.is_collapsed())));
+                text(writer, tag, group.name().unwrap_or_default())
+            },
+        )?;
+    }
+
+    if run.timing_methods().any(TimingMethod::is_custom) {
+        scoped_iter(
+            writer,
+            new_tag(b"CustomTimingMethods"),
+            TimingMethod::custom_methods()
+                .iter()
+                .zip(1..)
+                .filter_map(|(&method, id)| Some((run.custom_timing_method_name(method)?, id))),
+            |writer, (name, id)| {
+                let mut tag = new_tag(b"CustomTimingMethod");
+                tag.push_attribute((&b"id"[..], fmt_buf(id, buf)));
+                text(writer, tag, name)
+            },
+        )?;
+    }
+
     scoped(
         writer,
         new_tag(b"AutoSplitterSettings"),
@@ -0,0 +1,255 @@
+//! The Llanfair (Gered) Saver saves Runs as splits files used by Gered's
+//! Llanfair fork (*.lfs). These files store the game and category names, the
+//! real time personal best, the best segments and the segment icons. The
+//! icons are stored as serialized Java objects, so icons that can't be decoded
+//! are lost. Everything that is lost is reported in the returned Loss Report.
+//!
+//! # Examples
+//!
+//! Using the Llanfair (Gered) Saver to save a Run as a Llanfair (Gered) splits
+//! file.
+//!
+//! ```no_run
+//! use livesplit_core::run::saver::llanfair_gered;
+//! use livesplit_core::{Run, Segment};
+//! use std::fs::File;
+//! use std::io::BufWriter;
+//!
+//! // Create a run object that we can use.
+//! let mut run = Run::new();
+//! run.set_game_name("Super Mario Odyssey");
+//! run.set_category_name("Any%");
+//! run.push_segment(Segment::new("Cap Kingdom"));
+//!
+//! // Create the splits file.
+//! let file = File::create("path/to/splits_file.lfs");
+//! let writer = BufWriter::new(file.expect("Failed creating the file"));
+//!
+//! // Save the splits file as a Llanfair (Gered) splits file.
+//! let report = llanfair_gered::save_run(&run, writer).expect("Couldn't save the splits file");
+//! assert!(report.is_lossless());
+//! ```
+
+use super::LossReport;
+use crate::{
+    settings::Image,
+    xml_util::writer::{new_tag, scoped, text, write_display},
+    Run, TimeSpan,
+};
+use base64::STANDARD;
+use core::result::Result as StdResult;
+use quick_xml::events::BytesStart;
+use quick_xml::{Error as XmlError, Result as XmlResult, Writer};
+use std::io::Write;
+
+#[derive(Debug, snafu::Snafu)]
+/// The Error type for splits files that couldn't be saved by the Llanfair
+/// (Gered) Saver.
+pub enum Error {
+    /// Failed writing the XML.
+    #[snafu(display("{}", error))]
+    Xml {
+        /// The underlying error.
+        error: XmlError,
+    },
+}
+
+impl From<XmlError> for Error {
+    fn from(error: XmlError) -> Self {
+        Error::Xml { error }
+    }
+}
+
+/// The Result type for the Llanfair (Gered) Saver.
+pub type Result<T> = StdResult<T, Error>;
+
+fn custom_tag(name: &'static [u8]) -> BytesStart<'static> {
+    BytesStart::borrowed_name(name).with_attributes(Some(("serialization", "custom")))
+}
+
+/// The serialized `javax.swing.ImageIcon` class description, which is followed
+/// by the values of its fields.
+const IMAGE_ICON_CLASS: &[u8] = b"\xac\xed\x00\x05sr\x00\x15javax.swing.ImageIcon\
+    \xf2\xa65n\xde\x0c\x0e2\x03\x00\x05I\x00\x06heightI\x00\x05widthL\x00\x11\
+    accessibleContextt\x00+Ljavax/swing/ImageIcon$AccessibleImageIcon;L\x00\x0b\
+    descriptiont\x00\x12Ljava/lang/String;L\x00\x0dimageObservert\x00\x1e\
+    Ljava/awt/image/ImageObserver;xp";
+
+/// The serialized `int[]` class description, which is followed by the length
+/// of the array and its elements.
+const INT_ARRAY_CLASS: &[u8] = b"ur\x00\x02[IM\xba`&v\xea\xb2\xa5\x02\x00\x00xp";
+
+const TC_NULL: u8 = 0x70;
+const TC_BLOCKDATA: u8 = 0x77;
+const TC_ENDBLOCKDATA: u8 = 0x78;
+
+/// Serializes the icon as a `javax.swing.ImageIcon` Java object. Returns
+/// `false` if the icon couldn't be decoded.
+fn serialize_icon(icon: &Image, buf: &mut Vec<u8>) -> bool {
+    let image = match image::load_from_memory(icon.data()) {
+        Ok(image) => image.to_rgba8(),
+        Err(_) => return false,
+    };
+    let (width, height) = image.dimensions();
+    let len = match width.checked_mul(height) {
+        Some(len) => len,
+        None => return false,
+    };
+
+    buf.clear();
+    buf.extend_from_slice(IMAGE_ICON_CLASS);
+    buf.extend_from_slice(&height.to_be_bytes());
+    buf.extend_from_slice(&width.to_be_bytes());
+    // The accessible context, the description and the image observer.
+    buf.extend_from_slice(&[TC_NULL; 3]);
+
+    // The image icon writes the dimensions and the pixels itself.
+    buf.extend_from_slice(&[TC_BLOCKDATA, 8]);
+    buf.extend_from_slice(&width.to_be_bytes());
+    buf.extend_from_slice(&height.to_be_bytes());
+    buf.extend_from_slice(INT_ARRAY_CLASS);
+    buf.extend_from_slice(&len.to_be_bytes());
+    for pixel in image.pixels() {
+        let [r, g, b, a] = pixel.0;
+        buf.extend_from_slice(&[a, r, g, b]);
+    }
+    buf.push(TC_ENDBLOCKDATA);
+
+    true
+}
+
+/// Writes the icon, unless it's empty. Returns `false` if the icon had to be
+/// dropped.
+fn write_icon<W: Write>(
+    writer: &mut Writer<W>,
+    icon: &Image,
+    buf: &mut Vec<u8>,
+    text_buf: &mut String,
+) -> XmlResult<bool> {
+    if icon.is_empty() {
+        return Ok(true);
+    }
+    if !serialize_icon(icon, buf) {
+        return Ok(false);
+    }
+    text_buf.clear();
+    base64::encode_config_buf(&*buf, STANDARD, text_buf);
+    scoped(writer, new_tag(b"icon"), false, |writer| {
+        text(writer, new_tag(b"ImageIcon"), &*text_buf)
+    })?;
+    Ok(true)
+}
+
+fn milliseconds(time: TimeSpan) -> i64 {
+    time.total_milliseconds().round() as i64
+}
+
+fn time<W: Write>(
+    writer: &mut Writer<W>,
+    tag: BytesStart<'_>,
+    milliseconds: i64,
+    buf: &mut Vec<u8>,
+) -> XmlResult<()> {
+    scoped(writer, tag, false, |writer| {
+        write_display(writer, new_tag(b"milliseconds"), milliseconds, buf)
+    })
+}
+
+/// Saves a Run as a splits file used by Gered's Llanfair fork. Returns a Loss
+/// Report describing everything that couldn't be stored in the file.
+pub fn save_run<W: Write>(run: &Run, writer: W) -> Result<LossReport> {
+    let writer = &mut Writer::new_with_indent(writer, b' ', 2);
+    let buf = &mut Vec::new();
+    let icon_buf = &mut Vec::new();
+    let icon_text = &mut String::new();
+    let mut lost_icons = 0;
+
+    scoped(writer, custom_tag(b"Run"), false, |writer| {
+        scoped(writer, new_tag(b"Run"), false, |writer| {
+            scoped(writer, new_tag(b"default"), false, |writer| {
+                write_display(
+                    writer,
+                    new_tag(b"delayedStart"),
+                    milliseconds(TimeSpan::zero() - run.offset()),
+                    buf,
+                )?;
+                write_display(
+                    writer,
+                    new_tag(b"numberOfAttempts"),
+                    run.attempt_count(),
+                    buf,
+                )?;
+                write_display(
+                    writer,
+                    new_tag(b"numberOfCompletedAttempts"),
+                    run.attempt_history()
+                        .iter()
+                        .filter(|a| a.time().real_time.is_some())
+                        .count(),
+                    buf,
+                )?;
+                text(writer, new_tag(b"segmented"), "false")?;
+                text(writer, new_tag(b"name"), run.game_name())?;
+
+                // Llanfair stores the personal best as segment times. The
+                // time of segments without a split time is part of the next
+                // segment's time. The split times are rounded before
+                // calculating the segment times, so no rounding errors
+                // accumulate.
+                let mut previous_split_time = 0;
+                let segments = run.segments();
+
+                scoped(
+                    writer,
+                    new_tag(b"segments"),
+                    segments.is_empty(),
+                    |writer| {
+                        for segment in segments {
+                            scoped(writer, custom_tag(b"Segment"), false, |writer| {
+                                scoped(writer, new_tag(b"Segment"), false, |writer| {
+                                    scoped(writer, new_tag(b"default"), false, |writer| {
+                                        if let Some(best) = segment.best_segment_time().real_time {
+                                            time(
+                                                writer,
+                                                new_tag(b"bestTime"),
+                                                milliseconds(best),
+                                                buf,
+                                            )?;
+                                        }
+                                        if !write_icon(writer, segment.icon(), icon_buf, icon_text)?
+                                        {
+                                            lost_icons += 1;
+                                        }
+                                        text(writer, new_tag(b"name"), segment.name())?;
+                                        if let Some(split_time) =
+                                            segment.personal_best_split_time().real_time
+                                        {
+                                            let split_time = milliseconds(split_time);
+                                            time(
+                                                writer,
+                                                new_tag(b"runTime"),
+                                                split_time - previous_split_time,
+                                                buf,
+                                            )?;
+                                            previous_split_time = split_time;
+                                        }
+                                        Ok(())
+                                    })
+                                })
+                            })?;
+                        }
+                        Ok(())
+                    },
+                )?;
+
+                text(writer, new_tag(b"subTitle"), run.category_name())
+            })
+        })
+    })?;
+
+    Ok(LossReport {
+        game_name: false,
+        segment_icons: lost_icons,
+        ..LossReport::new(run)
+    })
+}
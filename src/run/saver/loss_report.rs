@@ -0,0 +1,106 @@
+use crate::{comparison::personal_best, Run, RunMetadata, Time, TimingMethod};
+
+/// Most splits file formats other than LiveSplit's own can't store everything
+/// a Run can hold. A Loss Report is returned by the savers for these formats
+/// and describes everything that couldn't be stored in the file.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct LossReport {
+    /// Whether the name of the game couldn't be stored.
+    pub game_name: bool,
+    /// Whether the game icon couldn't be stored.
+    pub game_icon: bool,
+    /// The amount of segment icons that couldn't be stored.
+    pub segment_icons: usize,
+    /// The amount of attempts in the attempt history that couldn't be stored.
+    pub attempt_history: usize,
+    /// The amount of segment times in the segment histories that couldn't be
+    /// stored.
+    pub segment_history: usize,
+    /// Whether any of the game times couldn't be stored.
+    pub game_time: bool,
+    /// The custom timing methods whose names or times couldn't be stored.
+    pub custom_timing_methods: Vec<TimingMethod>,
+    /// The names of the custom comparisons that couldn't be stored.
+    pub custom_comparisons: Vec<String>,
+    /// Whether any of the run's metadata, like the speedrun.com variables or
+    /// the permanent custom variables, couldn't be stored.
+    pub metadata: bool,
+    /// The amount of segment groups that couldn't be stored.
+    pub segment_groups: usize,
+    /// Whether the auto splitter settings couldn't be stored.
+    pub auto_splitter_settings: bool,
+}
+
+impl LossReport {
+    /// Creates a Loss Report for a format that stores none of the information
+    /// tracked by the report. The savers clear everything their format is able
+    /// to store.
+    pub(super) fn new(run: &Run) -> Self {
+        let segments = run.segments();
+        Self {
+            game_name: !run.game_name().is_empty(),
+            game_icon: !run.game_icon().is_empty(),
+            segment_icons: segments.iter().filter(|s| !s.icon().is_empty()).count(),
+            attempt_history: run.attempt_history().len(),
+            segment_history: segments
+                .iter()
+                .map(|s| s.segment_history().iter().count())
+                .sum(),
+            game_time: has_times(run, TimingMethod::GameTime),
+            custom_timing_methods: TimingMethod::custom_methods()
+                .iter()
+                .copied()
+                .filter(|&method| {
+                    run.custom_timing_method_name(method).is_some() || has_times(run, method)
+                })
+                .collect(),
+            custom_comparisons: run
+                .custom_comparisons()
+                .iter()
+                .filter(|c| *c != personal_best::NAME)
+                .cloned()
+                .collect(),
+            metadata: has_metadata(run.metadata(), true, None),
+            segment_groups: run.segment_groups().len(),
+            auto_splitter_settings: !run.auto_splitter_settings().is_empty(),
+        }
+    }
+
+    /// Returns `true` if nothing was lost while saving the Run.
+    pub fn is_lossless(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+fn has_times(run: &Run, method: TimingMethod) -> bool {
+    let has = |time: Time| time[method].is_some();
+    run.attempt_history().iter().any(|a| has(a.time()))
+        || run.segments().iter().any(|s| {
+            has(s.personal_best_split_time())
+                || has(s.best_segment_time())
+                || run
+                    .custom_comparisons()
+                    .iter()
+                    .any(|c| has(s.comparison(c)))
+                || s.segment_history().iter().any(|&(_, t)| has(t))
+        })
+}
+
+/// Checks whether the metadata contains any information. The platform and
+/// region can be excluded from the check and so can a single custom variable,
+/// for formats that are able to store them.
+pub(super) fn has_metadata(
+    metadata: &RunMetadata,
+    include_platform_and_region: bool,
+    stored_variable: Option<&str>,
+) -> bool {
+    !metadata.run_id().is_empty()
+        || metadata.uses_emulator()
+        || (include_platform_and_region
+            && (!metadata.platform_name().is_empty() || !metadata.region_name().is_empty()))
+        || metadata.frame_rate().is_some()
+        || metadata.speedrun_com_variables().next().is_some()
+        || metadata
+            .custom_variables()
+            .any(|(name, var)| var.is_permanent && Some(name.as_str()) != stored_variable)
+}
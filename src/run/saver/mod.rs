@@ -24,5 +24,17 @@
 //! // Save the splits file as a LiveSplit splits file.
 //! livesplit::save_run(&run, writer).expect("Couldn't save the splits file");
 //! ```
+//!
+//! The savers for other timers' formats return a [`LossReport`] describing
+//! everything the format isn't able to store.
+
+mod loss_report;
 
+pub mod history;
 pub mod livesplit;
+pub mod llanfair_gered;
+pub mod splitterino;
+pub mod urn;
+pub mod wsplit;
+
+pub use self::loss_report::LossReport;
@@ -0,0 +1,194 @@
+//! The Splitterino Saver saves Runs as Splitterino splits files (*.splits).
+//! Splitterino stores the game and category names, the platform and region,
+//! the personal best and the best segments for both real time and game time.
+//! Everything else is reported as lost in the returned Loss Report.
+//!
+//! # Examples
+//!
+//! Using the Splitterino Saver to save a Run as a Splitterino splits file.
+//!
+//! ```no_run
+//! use livesplit_core::run::saver::splitterino;
+//! use livesplit_core::{Run, Segment};
+//! use std::fs::File;
+//! use std::io::BufWriter;
+//!
+//! // Create a run object that we can use.
+//! let mut run = Run::new();
+//! run.set_game_name("Super Mario Odyssey");
+//! run.set_category_name("Any%");
+//! run.push_segment(Segment::new("Cap Kingdom"));
+//!
+//! // Create the splits file.
+//! let file = File::create("path/to/splits_file.splits");
+//! let writer = BufWriter::new(file.expect("Failed creating the file"));
+//!
+//! // Save the splits file as a Splitterino splits file.
+//! let report = splitterino::save_run(&run, writer).expect("Couldn't save the splits file");
+//! assert!(report.is_lossless());
+//! ```
+
+use super::{loss_report::has_metadata, LossReport};
+use crate::{Run, TimeSpan, TimingMethod};
+use core::result::Result as StdResult;
+use serde::Serialize;
+use serde_json::Error as JsonError;
+use std::io::Write;
+
+#[derive(Debug, snafu::Snafu)]
+/// The Error type for splits files that couldn't be saved by the Splitterino
+/// Saver.
+pub enum Error {
+    /// Failed writing the JSON.
+    #[snafu(display("{}", error))]
+    Json {
+        /// The underlying error.
+        error: JsonError,
+    },
+}
+
+impl From<JsonError> for Error {
+    fn from(error: JsonError) -> Self {
+        Error::Json { error }
+    }
+}
+
+/// The Result type for the Splitterino Saver.
+pub type Result<T> = StdResult<T, Error>;
+
+const VERSION: &str = "0.1";
+
+#[derive(Serialize)]
+struct SplitsFormat<'a> {
+    version: &'static str,
+    splits: Splits<'a>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Splits<'a> {
+    game: GameInfo<'a>,
+    start_delay: i64,
+    segments: Vec<SplitterinoSegment<'a>>,
+    timing: &'static str,
+}
+
+#[derive(Serialize)]
+struct GameInfo<'a> {
+    name: &'a str,
+    category: &'a str,
+    platform: &'a str,
+    region: &'a str,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SplitterinoSegment<'a> {
+    id: String,
+    name: &'a str,
+    passed: bool,
+    skipped: bool,
+    personal_best: SegmentTime,
+    overall_best: SegmentTime,
+}
+
+#[derive(Serialize)]
+struct SegmentTime {
+    igt: DetailedTime,
+    rta: DetailedTime,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct DetailedTime {
+    raw_time: u64,
+    pause_time: u64,
+}
+
+/// Splitterino stores times in milliseconds, with zero meaning that there is
+/// no time.
+fn milliseconds(time: Option<TimeSpan>) -> u64 {
+    time.map_or(0, |t| t.total_milliseconds().round().max(0.0) as u64)
+}
+
+const fn detailed_time(raw_time: u64) -> DetailedTime {
+    DetailedTime {
+        raw_time,
+        pause_time: 0,
+    }
+}
+
+/// Saves a Run as a Splitterino splits file. Returns a Loss Report describing
+/// everything that couldn't be stored in the file.
+pub fn save_run<W: Write>(run: &Run, writer: W) -> Result<LossReport> {
+    // Splitterino stores the personal best as segment times. Skipped segments
+    // have no time, so the time they took is part of the next segment's time.
+    // The split times are rounded before calculating the segment times, so no
+    // rounding errors accumulate.
+    let mut previous_split_time = [0; 2];
+
+    let segments = run
+        .segments()
+        .iter()
+        .enumerate()
+        .map(|(index, segment)| {
+            let split_time = segment.personal_best_split_time();
+            let mut segment_time = |method: TimingMethod| {
+                let time = milliseconds(split_time[method]);
+                if time == 0 {
+                    return 0;
+                }
+                let previous = &mut previous_split_time[method as usize];
+                let segment_time = time.saturating_sub(*previous);
+                *previous = time;
+                segment_time
+            };
+            let rta = segment_time(TimingMethod::RealTime);
+            let igt = segment_time(TimingMethod::GameTime);
+            let best_segment_time = segment.best_segment_time();
+
+            let skipped = split_time.real_time.is_none() && split_time.game_time.is_none();
+
+            SplitterinoSegment {
+                id: format!("00000000-0000-4000-8000-{:012x}", index),
+                name: segment.name(),
+                passed: false,
+                skipped,
+                personal_best: SegmentTime {
+                    igt: detailed_time(igt),
+                    rta: detailed_time(rta),
+                },
+                overall_best: SegmentTime {
+                    igt: detailed_time(milliseconds(best_segment_time.game_time)),
+                    rta: detailed_time(milliseconds(best_segment_time.real_time)),
+                },
+            }
+        })
+        .collect();
+
+    let metadata = run.metadata();
+
+    let splits = SplitsFormat {
+        version: VERSION,
+        splits: Splits {
+            game: GameInfo {
+                name: run.game_name(),
+                category: run.category_name(),
+                platform: metadata.platform_name(),
+                region: metadata.region_name(),
+            },
+            start_delay: -run.offset().total_milliseconds().round() as i64,
+            segments,
+            timing: "rta",
+        },
+    };
+
+    serde_json::to_writer_pretty(writer, &splits)?;
+
+    Ok(LossReport {
+        game_name: false,
+        game_time: false,
+        metadata: has_metadata(metadata, false, None),
+        ..LossReport::new(run)
+    })
+}
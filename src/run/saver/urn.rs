@@ -0,0 +1,158 @@
+//! The Urn Saver saves Runs as Urn splits files (*.json). Urn only stores a
+//! single name for the run, the real time personal best, the best segments and
+//! the best split times. The best split times are derived from the attempt
+//! history. Everything else is reported as lost in the returned Loss Report.
+//!
+//! # Examples
+//!
+//! Using the Urn Saver to save a Run as an Urn splits file.
+//!
+//! ```no_run
+//! use livesplit_core::run::saver::urn;
+//! use livesplit_core::{Run, Segment};
+//! use std::fs::File;
+//! use std::io::BufWriter;
+//!
+//! // Create a run object that we can use.
+//! let mut run = Run::new();
+//! run.set_game_name("Super Mario Odyssey");
+//! run.set_category_name("Any%");
+//! run.push_segment(Segment::new("Cap Kingdom"));
+//!
+//! // Create the splits file.
+//! let file = File::create("path/to/splits_file.json");
+//! let writer = BufWriter::new(file.expect("Failed creating the file"));
+//!
+//! // Save the splits file as an Urn splits file.
+//! let report = urn::save_run(&run, writer).expect("Couldn't save the splits file");
+//! assert!(report.game_name);
+//! ```
+
+use super::LossReport;
+use crate::{Run, TimeSpan};
+use core::result::Result as StdResult;
+use serde::Serialize;
+use serde_json::Error as JsonError;
+use std::io::Write;
+
+#[derive(Debug, snafu::Snafu)]
+/// The Error type for splits files that couldn't be saved by the Urn Saver.
+pub enum Error {
+    /// Failed writing the JSON.
+    #[snafu(display("{}", error))]
+    Json {
+        /// The underlying error.
+        error: JsonError,
+    },
+}
+
+impl From<JsonError> for Error {
+    fn from(error: JsonError) -> Self {
+        Error::Json { error }
+    }
+}
+
+/// The Result type for the Urn Saver.
+pub type Result<T> = StdResult<T, Error>;
+
+#[derive(Serialize)]
+struct Splits<'a> {
+    title: &'a str,
+    attempt_count: u32,
+    start_delay: String,
+    splits: Vec<Split<'a>>,
+}
+
+#[derive(Serialize)]
+struct Split<'a> {
+    title: &'a str,
+    time: String,
+    best_time: String,
+    best_segment: String,
+}
+
+/// Formats the time the way Urn does, leaving out the hours and minutes if
+/// they are zero. Empty times are stored as zero.
+fn fmt_time(time: Option<TimeSpan>) -> String {
+    let micros = time.map_or(0, |t| {
+        t.to_duration().num_microseconds().unwrap_or_default()
+    });
+    let sign = if micros < 0 { "-" } else { "" };
+    let micros = micros.abs();
+    let (seconds, fraction) = (micros / 1_000_000, micros % 1_000_000);
+    let (minutes, seconds) = (seconds / 60, seconds % 60);
+    let (hours, minutes) = (minutes / 60, minutes % 60);
+    if hours > 0 {
+        format!(
+            "{}{}:{:02}:{:02}.{:06}",
+            sign, hours, minutes, seconds, fraction
+        )
+    } else if minutes > 0 {
+        format!("{}{}:{:02}.{:06}", sign, minutes, seconds, fraction)
+    } else {
+        format!("{}{}.{:06}", sign, seconds, fraction)
+    }
+}
+
+/// Calculates the best split time of every segment the same way the Best
+/// Split Times comparison does. It's not guaranteed that the comparison is
+/// generated for the Run, so it's calculated here.
+fn best_split_times(run: &Run) -> Vec<Option<TimeSpan>> {
+    let segments = run.segments();
+    let mut best_split_times: Vec<Option<TimeSpan>> = segments
+        .iter()
+        .enumerate()
+        .map(|(i, s)| {
+            if i == 0 {
+                s.best_segment_time().real_time
+            } else {
+                s.personal_best_split_time().real_time
+            }
+        })
+        .collect();
+
+    for attempt in run.attempt_history() {
+        let id = attempt.index();
+        let mut total_time = TimeSpan::zero();
+
+        for (segment, best_split_time) in segments.iter().zip(&mut best_split_times) {
+            if let Some(time) = segment.segment_history().get(id) {
+                if let Some(time) = time.real_time {
+                    total_time += time;
+                    if best_split_time.map_or(true, |b| total_time < b) {
+                        *best_split_time = Some(total_time);
+                    }
+                }
+            } else {
+                break;
+            }
+        }
+    }
+
+    best_split_times
+}
+
+/// Saves a Run as an Urn splits file. Returns a Loss Report describing
+/// everything that couldn't be stored in the file.
+pub fn save_run<W: Write>(run: &Run, writer: W) -> Result<LossReport> {
+    let splits = Splits {
+        title: run.category_name(),
+        attempt_count: run.attempt_count(),
+        start_delay: fmt_time(Some(-run.offset())),
+        splits: run
+            .segments()
+            .iter()
+            .zip(best_split_times(run))
+            .map(|(segment, best_time)| Split {
+                title: segment.name(),
+                time: fmt_time(segment.personal_best_split_time().real_time),
+                best_time: fmt_time(best_time),
+                best_segment: fmt_time(segment.best_segment_time().real_time),
+            })
+            .collect(),
+    };
+
+    serde_json::to_writer_pretty(writer, &splits)?;
+
+    Ok(LossReport::new(run))
+}
@@ -0,0 +1,114 @@
+//! The WSplit Saver saves Runs as WSplit splits files. WSplit stores a single
+//! name for the run, the real time personal best, the best segments and the
+//! times of an `Old Run` comparison. A `Goal` custom variable is stored as
+//! well. WSplit stores icons as paths to image files, which a Run doesn't
+//! know about, so icons are always lost. Everything else that is lost is
+//! reported in the returned Loss Report.
+//!
+//! WSplit separates the values of a segment with commas, without any way to
+//! escape them. Commas in segment names are therefore replaced by spaces.
+//!
+//! # Examples
+//!
+//! Using the WSplit Saver to save a Run as a WSplit splits file.
+//!
+//! ```no_run
+//! use livesplit_core::run::saver::wsplit;
+//! use livesplit_core::{Run, Segment};
+//! use std::fs::File;
+//! use std::io::BufWriter;
+//!
+//! // Create a run object that we can use.
+//! let mut run = Run::new();
+//! run.set_game_name("Super Mario Odyssey");
+//! run.set_category_name("Any%");
+//! run.push_segment(Segment::new("Cap Kingdom"));
+//!
+//! // Create the splits file.
+//! let file = File::create("path/to/splits_file.txt");
+//! let writer = BufWriter::new(file.expect("Failed creating the file"));
+//!
+//! // Save the splits file as a WSplit splits file.
+//! let report = wsplit::save_run(&run, writer).expect("Couldn't save the splits file");
+//! assert!(report.game_name);
+//! ```
+
+use super::{loss_report::has_metadata, LossReport};
+use crate::{Run, TimeSpan};
+use core::result::Result as StdResult;
+use snafu::ResultExt;
+use std::io::{self, Write};
+
+#[derive(Debug, snafu::Snafu)]
+/// The Error type for splits files that couldn't be saved by the WSplit Saver.
+pub enum Error {
+    /// Failed writing a line.
+    Line {
+        /// The underlying error.
+        source: io::Error,
+    },
+}
+
+/// The Result type for the WSplit Saver.
+pub type Result<T> = StdResult<T, Error>;
+
+const OLD_RUN: &str = "Old Run";
+const GOAL: &str = "Goal";
+
+/// WSplit stores times in seconds, with zero meaning that there is no time.
+/// The times are rounded to milliseconds, as WSplit itself doesn't store them
+/// any more precisely.
+fn seconds(time: Option<TimeSpan>) -> f64 {
+    time.map_or(0.0, |t| t.total_milliseconds().round() / 1000.0)
+}
+
+/// Saves a Run as a WSplit splits file. Returns a Loss Report describing
+/// everything that couldn't be stored in the file.
+pub fn save_run<W: Write>(run: &Run, mut writer: W) -> Result<LossReport> {
+    let category_name = run.category_name();
+    writeln!(writer, "Title={}", category_name).context(Line)?;
+    writeln!(writer, "Attempts={}", run.attempt_count()).context(Line)?;
+    writeln!(
+        writer,
+        "Offset={}",
+        (TimeSpan::zero() - run.offset())
+            .total_milliseconds()
+            .round()
+    )
+    .context(Line)?;
+
+    // The Goal would be used as the category name if there's none.
+    let goal = run
+        .metadata()
+        .custom_variable(GOAL)
+        .filter(|goal| goal.is_permanent && !goal.value.is_empty() && !category_name.is_empty())
+        .map(|goal| goal.value.as_str());
+    if let Some(goal) = goal {
+        writeln!(writer, "Goal={}", goal).context(Line)?;
+    }
+
+    let has_old_run = run.custom_comparisons().iter().any(|c| c == OLD_RUN);
+
+    for segment in run.segments() {
+        let old_time = if has_old_run {
+            segment.comparison(OLD_RUN).real_time
+        } else {
+            None
+        };
+        writeln!(
+            writer,
+            "{},{},{},{}",
+            segment.name().replace(',', " "),
+            seconds(old_time),
+            seconds(segment.personal_best_split_time().real_time),
+            seconds(segment.best_segment_time().real_time),
+        )
+        .context(Line)?;
+    }
+
+    let mut report = LossReport::new(run);
+    report.custom_comparisons.retain(|c| c != OLD_RUN);
+    report.metadata = has_metadata(run.metadata(), true, goal.map(|_| GOAL));
+
+    Ok(report)
+}
@@ -0,0 +1,408 @@
+use crate::{platform::prelude::*, Segment};
+use alloc::borrow::Cow;
+use core::ops::Range;
+use core::slice::Iter;
+
+/// A Segment Group combines a range of consecutive segments into a single
+/// unit, which is commonly known as subsplits. The segments of a group can be
+/// collapsed, so only a single row is shown for the whole group, which keeps
+/// long routes readable.
+///
+/// # Examples
+///
+/// ```
+/// use livesplit_core::run::SegmentGroup;
+///
+/// let group = SegmentGroup::new(2, 5).with_name("Forsaken City");
+/// assert!(group.contains(4));
+/// assert!(!group.contains(5));
+/// assert_eq!(group.name(), Some("Forsaken City"));
+/// ```
+#[derive(Clone, Default, Debug, PartialEq, Eq, Hash)]
+pub struct SegmentGroup {
+    start: usize,
+    end: usize,
+    name: Option<String>,
+    collapsed: bool,
+}
+
+/// Describes an Error that occurred while adding a Segment Group.
+#[derive(PartialEq, Debug, snafu::Snafu)]
+pub enum SegmentGroupError {
+    /// The Segment Group doesn't contain any segments.
+    EmptyGroup,
+    /// The Segment Group overlaps with a Segment Group that already exists.
+    Overlapping,
+}
+
+impl SegmentGroup {
+    /// Creates a new Segment Group spanning the segments from `start` up to
+    /// but excluding `end`.
+    pub const fn new(start: usize, end: usize) -> Self {
+        Self {
+            start,
+            end,
+            name: None,
+            collapsed: false,
+        }
+    }
+
+    /// Returns the Segment Group with the name provided.
+    pub fn with_name<S: Into<String>>(mut self, name: S) -> Self {
+        self.name = Some(name.into());
+        self
+    }
+
+    /// Accesses the index of the first segment of the group.
+    #[inline]
+    pub const fn start(&self) -> usize {
+        self.start
+    }
+
+    /// Accesses the index of the segment right after the last segment of the
+    /// group.
+    #[inline]
+    pub const fn end(&self) -> usize {
+        self.end
+    }
+
+    /// Accesses the index of the last segment of the group. This is the
+    /// segment that ends the whole group, so its split time is the group's
+    /// split time.
+    #[inline]
+    pub const fn last(&self) -> usize {
+        self.end - 1
+    }
+
+    /// Returns the range of segment indices that belong to the group.
+    #[inline]
+    pub const fn range(&self) -> Range<usize> {
+        self.start..self.end
+    }
+
+    /// Returns the amount of segments in the group.
+    #[inline]
+    pub const fn len(&self) -> usize {
+        self.end - self.start
+    }
+
+    /// Returns `true` if the group doesn't contain any segments.
+    #[inline]
+    pub const fn is_empty(&self) -> bool {
+        self.end <= self.start
+    }
+
+    /// Checks whether the name of the group's last segment has the group name
+    /// encoded in curly braces. A group consisting of a single segment has no
+    /// subsplits marking it, so the braces are needed even without a name.
+    const fn has_encoded_name(&self) -> bool {
+        self.name.is_some() || self.len() == 1
+    }
+
+    /// Checks whether the segment with the given index is part of the group.
+    #[inline]
+    pub const fn contains(&self, segment_index: usize) -> bool {
+        self.start <= segment_index && segment_index < self.end
+    }
+
+    /// Accesses the name of the group. A group doesn't need to have a name, in
+    /// which case the name of its last segment is usually shown instead.
+    #[inline]
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    /// Sets the name of the group.
+    #[inline]
+    pub fn set_name(&mut self, name: Option<String>) {
+        self.name = name;
+    }
+
+    /// Returns the name of the group or, if it doesn't have one, the name of
+    /// its last segment.
+    ///
+    /// # Panics
+    ///
+    /// This panics if the group is out of bounds of the segments provided.
+    pub fn name_or_default<'a>(&'a self, segments: &'a [Segment]) -> &'a str {
+        match &self.name {
+            Some(name) => name,
+            None => segments[self.last()].name(),
+        }
+    }
+
+    /// Returns whether the group is collapsed, meaning that only a single row
+    /// should be shown for the whole group, unless the current attempt is
+    /// within the group.
+    #[inline]
+    pub const fn is_collapsed(&self) -> bool {
+        self.collapsed
+    }
+
+    /// Sets whether the group is collapsed.
+    #[inline]
+    pub const fn set_collapsed(&mut self, collapsed: bool) {
+        self.collapsed = collapsed;
+    }
+}
+
+/// Stores all the Segment Groups of a Run. The groups are ordered by the
+/// segments they contain and never overlap.
+#[derive(Clone, Default, Debug, PartialEq)]
+pub struct SegmentGroups(Vec<SegmentGroup>);
+
+impl SegmentGroups {
+    /// Creates a new empty list of Segment Groups.
+    #[inline]
+    pub const fn new() -> Self {
+        Self(Vec::new())
+    }
+
+    /// Returns the amount of Segment Groups.
+    #[inline]
+    pub const fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Returns `true` if there are no Segment Groups.
+    #[inline]
+    pub const fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Iterates over all the Segment Groups in the order of their segments.
+    #[inline]
+    pub fn iter(&self) -> Iter<'_, SegmentGroup> {
+        self.0.iter()
+    }
+
+    /// Accesses the Segment Group with the given index. If there's no such
+    /// group, `None` is returned instead.
+    #[inline]
+    pub fn get(&self, group_index: usize) -> Option<&SegmentGroup> {
+        self.0.get(group_index)
+    }
+
+    /// Grants mutable access to the Segment Group with the given index. If
+    /// there's no such group, `None` is returned instead.
+    #[inline]
+    pub fn get_mut(&mut self, group_index: usize) -> Option<&mut SegmentGroup> {
+        self.0.get_mut(group_index)
+    }
+
+    /// Returns the index of the Segment Group that contains the segment with
+    /// the given index. If the segment is not part of any group, `None` is
+    /// returned.
+    pub fn group_index(&self, segment_index: usize) -> Option<usize> {
+        let pos = match self.0.binary_search_by_key(&segment_index, |g| g.start) {
+            Ok(pos) => pos,
+            Err(pos) => pos.checked_sub(1)?,
+        };
+        if self.0[pos].contains(segment_index) {
+            Some(pos)
+        } else {
+            None
+        }
+    }
+
+    /// Accesses the Segment Group that contains the segment with the given
+    /// index. If the segment is not part of any group, `None` is returned.
+    #[inline]
+    pub fn group_of(&self, segment_index: usize) -> Option<&SegmentGroup> {
+        self.group_index(segment_index).map(|i| &self.0[i])
+    }
+
+    /// Inserts a new Segment Group. The group may not be empty and may not
+    /// overlap with any of the existing groups. The index of the group is
+    /// returned.
+    pub fn insert(&mut self, group: SegmentGroup) -> Result<usize, SegmentGroupError> {
+        if group.is_empty() {
+            return Err(SegmentGroupError::EmptyGroup);
+        }
+        let pos = self.0.partition_point(|g| g.start < group.start);
+        let overlaps_previous = pos
+            .checked_sub(1)
+            .map_or(false, |p| self.0[p].end > group.start);
+        let overlaps_next = self.0.get(pos).map_or(false, |n| n.start < group.end);
+        if overlaps_previous || overlaps_next {
+            return Err(SegmentGroupError::Overlapping);
+        }
+        self.0.insert(pos, group);
+        Ok(pos)
+    }
+
+    /// Removes the Segment Group with the given index. The segments themselves
+    /// are kept. If there's no such group, `None` is returned.
+    pub fn remove(&mut self, group_index: usize) -> Option<SegmentGroup> {
+        if group_index < self.0.len() {
+            Some(self.0.remove(group_index))
+        } else {
+            None
+        }
+    }
+
+    /// Removes all the Segment Groups where the given closure returns `false`.
+    #[inline]
+    pub fn retain<F>(&mut self, f: F)
+    where
+        F: FnMut(&SegmentGroup) -> bool,
+    {
+        self.0.retain(f);
+    }
+
+    /// Removes all the Segment Groups.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.0.clear();
+    }
+
+    /// Adjusts the groups for a segment that got inserted at the given index.
+    /// A segment inserted in between the segments of a group becomes part of
+    /// that group.
+    pub(crate) fn insert_segment(&mut self, segment_index: usize) {
+        for group in &mut self.0 {
+            if group.start >= segment_index {
+                group.start += 1;
+                group.end += 1;
+            } else if group.end > segment_index {
+                group.end += 1;
+            }
+        }
+    }
+
+    /// Adjusts the groups for the segment that got removed at the given index.
+    /// Groups that don't contain any segments anymore are removed.
+    pub(crate) fn remove_segment(&mut self, segment_index: usize) {
+        for group in &mut self.0 {
+            if group.start > segment_index {
+                group.start -= 1;
+                group.end -= 1;
+            } else if group.end > segment_index {
+                group.end -= 1;
+            }
+        }
+        self.0.retain(|g| !g.is_empty());
+    }
+
+    /// Adjusts the groups for segments that got reordered. The new position of
+    /// each segment is provided by the index of its old position. Groups whose
+    /// segments don't stay consecutive and in order are removed.
+    pub(crate) fn reorder_segments(&mut self, new_positions: &[usize]) {
+        self.0.retain(|g| {
+            new_positions[g.start..g.end]
+                .windows(2)
+                .all(|w| w[0] + 1 == w[1])
+        });
+        for group in &mut self.0 {
+            let start = new_positions[group.start];
+            group.end = start + group.len();
+            group.start = start;
+        }
+        self.0.sort_by_key(|g| g.start);
+    }
+
+    /// Removes or shrinks the groups that reach beyond the amount of segments
+    /// provided.
+    pub(crate) fn fix(&mut self, segment_count: usize) {
+        for group in &mut self.0 {
+            group.end = group.end.min(segment_count);
+        }
+        self.0.retain(|g| !g.is_empty());
+    }
+
+    #[cfg(feature = "std")]
+    /// Determines the Segment Groups from the segment names, the way the
+    /// original LiveSplit encodes them. Each segment whose name starts with a
+    /// `-` is a subsplit that belongs to the group ended by the next segment
+    /// without a `-`. That final segment may specify the name of the group in
+    /// curly braces in front of its own name, like `{Group} Segment`. The
+    /// encoding is removed from the segment names.
+    pub(crate) fn decode_from_names(segments: &mut [Segment]) -> Self {
+        let mut groups = Vec::new();
+        let mut start = None;
+
+        for (index, segment) in segments.iter_mut().enumerate() {
+            if let Some(name) = segment.name().strip_prefix('-') {
+                let name = name.to_string();
+                segment.set_name(name);
+                start.get_or_insert(index);
+            } else {
+                let group_name = split_group_name(segment.name())
+                    .map(|(group_name, name)| (group_name.to_string(), name.to_string()));
+
+                if start.is_some() || group_name.is_some() {
+                    let mut group = SegmentGroup::new(start.unwrap_or(index), index + 1);
+                    if let Some((group_name, name)) = group_name {
+                        // Empty braces mark a group without a name.
+                        group.name = Some(group_name).filter(|n| !n.is_empty());
+                        segment.set_name(name);
+                    }
+                    groups.push(group);
+                }
+
+                start = None;
+            }
+        }
+
+        if let Some(start) = start {
+            groups.push(SegmentGroup::new(start, segments.len()));
+        }
+
+        Self(groups)
+    }
+
+    #[cfg(feature = "std")]
+    /// Removes the encoding of the groups from the names of the segments that
+    /// are part of a group. This is the inverse of `encoded_name`.
+    pub(crate) fn strip_encoding_from_names(&self, segments: &mut [Segment]) {
+        for group in &self.0 {
+            for index in group.range() {
+                let segment = &mut segments[index];
+                let name = if index == group.last() {
+                    Some(group)
+                        .filter(|g| g.has_encoded_name())
+                        .and_then(|_| split_group_name(segment.name()))
+                        .map(|(_, name)| name.to_string())
+                } else {
+                    segment.name().strip_prefix('-').map(str::to_string)
+                };
+                if let Some(name) = name {
+                    segment.set_name(name);
+                }
+            }
+        }
+    }
+
+    /// Returns the name of the segment with the group encoded into it, the way
+    /// the original LiveSplit expects it.
+    pub(crate) fn encoded_name<'a>(&self, segment_index: usize, name: &'a str) -> Cow<'a, str> {
+        match self.group_of(segment_index) {
+            Some(group) if segment_index != group.last() => Cow::Owned(format!("-{}", name)),
+            Some(group) if group.has_encoded_name() => Cow::Owned(format!(
+                "{{{}}} {}",
+                group.name.as_deref().unwrap_or_default(),
+                name
+            )),
+            _ => Cow::Borrowed(name),
+        }
+    }
+}
+
+/// Splits the group name off the name of the final segment of a group, which
+/// is encoded as `{Group} Name`. This is the inverse of `encoded_name`.
+#[cfg(feature = "std")]
+fn split_group_name(name: &str) -> Option<(&str, &str)> {
+    let rest = name.strip_prefix('{')?;
+    let end = rest.find('}')?;
+    let name = &rest[end + 1..];
+    Some((&rest[..end], name.strip_prefix(' ').unwrap_or(name)))
+}
+
+impl<'a> IntoIterator for &'a SegmentGroups {
+    type Item = &'a SegmentGroup;
+    type IntoIter = Iter<'a, SegmentGroup>;
+
+    fn into_iter(self) -> Iter<'a, SegmentGroup> {
+        self.0.iter()
+    }
+}
@@ -0,0 +1,181 @@
+use crate::{
+    comparison::personal_best,
+    run::{MergeConflict, MergeError},
+    tests_helper::{create_run, segment_history, span},
+    AtomicDateTime, Run, Time, TimeSpan, TimingMethod,
+};
+
+fn time(seconds: f64) -> Time {
+    Time::new().with_real_time(Some(span(seconds)))
+}
+
+fn add_attempt(run: &mut Run, index: i32, started: AtomicDateTime, segments: &[f64]) {
+    let mut total = 0.0;
+    for (segment, &segment_time) in run.segments_mut().iter_mut().zip(segments) {
+        segment
+            .segment_history_mut()
+            .insert(index, time(segment_time));
+        total += segment_time;
+    }
+    let final_time = if segments.len() == run.len() {
+        time(total)
+    } else {
+        Time::new()
+    };
+    let ended = started + TimeSpan::from_seconds(total);
+    run.add_attempt_with_index(final_time, index, Some(started), Some(ended), None);
+}
+
+fn set_personal_best(run: &mut Run, segments: &[f64]) {
+    let mut total = 0.0;
+    for (segment, &segment_time) in run.segments_mut().iter_mut().zip(segments) {
+        total += segment_time;
+        segment.set_personal_best_split_time(time(total));
+        segment.set_best_segment_time(time(segment_time));
+    }
+}
+
+#[test]
+fn merges_diverged_histories() {
+    let start = AtomicDateTime::now();
+    let hour = TimeSpan::from_seconds(3600.0);
+
+    let mut base = create_run(&["A", "B"]);
+    add_attempt(&mut base, 1, start, &[10.0, 20.0]);
+    set_personal_best(&mut base, &[10.0, 20.0]);
+    base.set_attempt_count(1);
+
+    let mut ours = base.clone();
+    add_attempt(&mut ours, 2, start + hour + hour, &[12.0]);
+    ours.set_attempt_count(2);
+
+    let mut theirs = base;
+    add_attempt(&mut theirs, 2, start + hour, &[9.0, 19.0]);
+    set_personal_best(&mut theirs, &[9.0, 19.0]);
+    theirs.set_attempt_count(2);
+    theirs.add_custom_comparison("Theirs").unwrap();
+
+    let report = ours.merge(&theirs).unwrap();
+    assert!(report.is_conflict_free());
+    assert_eq!(report.added_attempts, 1);
+    assert_eq!(report.duplicate_attempts, 1);
+
+    assert_eq!(ours.attempt_count(), 3);
+    let attempts = ours.attempt_history();
+    assert_eq!(
+        attempts.iter().map(|a| a.index()).collect::<Vec<_>>(),
+        [1, 2, 3]
+    );
+    assert_eq!(attempts[1].started(), Some(start + hour));
+    assert_eq!(attempts[2].started(), Some(start + hour + hour));
+
+    assert_eq!(
+        segment_history(&ours, 0, TimingMethod::RealTime),
+        [
+            (1, Some(span(10.0))),
+            (2, Some(span(9.0))),
+            (3, Some(span(12.0)))
+        ]
+    );
+    assert_eq!(
+        segment_history(&ours, 1, TimingMethod::RealTime),
+        [(1, Some(span(20.0))), (2, Some(span(19.0)))]
+    );
+
+    assert_eq!(
+        ours.segment(0).best_segment_time().real_time,
+        Some(span(9.0))
+    );
+    assert_eq!(
+        ours.segment(1).comparison(personal_best::NAME).real_time,
+        Some(span(28.0))
+    );
+    assert!(ours.custom_comparisons().iter().any(|c| c == "Theirs"));
+    assert!(ours.has_been_modified());
+}
+
+#[test]
+fn keeps_shared_unattached_history_elements_once() {
+    let start = AtomicDateTime::now();
+
+    // An imported Personal Best is stored at index 0 without an attempt.
+    let mut base = create_run(&["A", "B"]);
+    for (segment, &segment_time) in base.segments_mut().iter_mut().zip(&[8.0, 16.0]) {
+        segment.segment_history_mut().insert(0, time(segment_time));
+    }
+    set_personal_best(&mut base, &[8.0, 16.0]);
+    add_attempt(&mut base, 1, start, &[10.0, 20.0]);
+
+    let mut ours = base.clone();
+    let mut theirs = base;
+    ours.segment_mut(1)
+        .segment_history_mut()
+        .insert(-1, time(30.0));
+    theirs
+        .segment_mut(1)
+        .segment_history_mut()
+        .insert(-1, time(40.0));
+
+    ours.merge(&theirs).unwrap();
+
+    assert_eq!(
+        segment_history(&ours, 0, TimingMethod::RealTime),
+        [(-2, Some(span(8.0))), (1, Some(span(10.0)))]
+    );
+    assert_eq!(
+        segment_history(&ours, 1, TimingMethod::RealTime),
+        [
+            (-4, Some(span(40.0))),
+            (-3, Some(span(30.0))),
+            (-2, Some(span(16.0))),
+            (1, Some(span(20.0)))
+        ]
+    );
+}
+
+#[test]
+fn keeps_our_personal_best_if_it_is_faster() {
+    let mut ours = create_run(&["A", "B"]);
+    set_personal_best(&mut ours, &[5.0, 5.0]);
+    let mut theirs = create_run(&["A", "B"]);
+    set_personal_best(&mut theirs, &[4.0, 7.0]);
+
+    ours.merge(&theirs).unwrap();
+
+    assert_eq!(
+        ours.segment(1).personal_best_split_time().real_time,
+        Some(span(10.0))
+    );
+    assert_eq!(
+        ours.segment(0).best_segment_time().real_time,
+        Some(span(4.0))
+    );
+}
+
+#[test]
+fn reports_conflicts() {
+    let mut ours = create_run(&["A", "B"]);
+    ours.set_game_name("Game");
+    let mut theirs = create_run(&["A", "C"]);
+    theirs.set_game_name("Other Game");
+
+    let report = ours.merge(&theirs).unwrap();
+    assert_eq!(
+        report.conflicts,
+        [
+            MergeConflict::GameName {
+                theirs: "Other Game".into()
+            },
+            MergeConflict::SegmentName {
+                index: 1,
+                theirs: "C".into()
+            },
+        ]
+    );
+    assert_eq!(ours.segment(1).name(), "B");
+
+    assert_eq!(
+        ours.merge(&create_run(&["A"])),
+        Err(MergeError::SegmentCountMismatch { ours: 2, theirs: 1 })
+    );
+}
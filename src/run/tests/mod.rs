@@ -2,4 +2,7 @@ mod comparison;
 mod empty_run;
 mod extended_category_name;
 mod fixing;
+mod merge;
 mod metadata;
+mod segment_groups;
+mod timing_methods;
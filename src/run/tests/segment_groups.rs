@@ -0,0 +1,155 @@
+use crate::run::{SegmentGroup, SegmentGroupError, SegmentGroups};
+use crate::{Run, Segment};
+
+fn run_with_segments(names: &[&str]) -> Run {
+    let mut run = Run::new();
+    for name in names {
+        run.push_segment(Segment::new(*name));
+    }
+    run
+}
+
+#[test]
+fn groups_stay_sorted() {
+    let mut groups = SegmentGroups::new();
+    assert_eq!(groups.insert(SegmentGroup::new(4, 6)), Ok(0));
+    assert_eq!(groups.insert(SegmentGroup::new(0, 2)), Ok(0));
+    assert_eq!(groups.insert(SegmentGroup::new(2, 4)), Ok(1));
+
+    let starts = groups.iter().map(|g| g.start()).collect::<Vec<_>>();
+    assert_eq!(starts, [0, 2, 4]);
+}
+
+#[test]
+fn rejects_invalid_groups() {
+    let mut groups = SegmentGroups::new();
+    groups.insert(SegmentGroup::new(2, 5)).unwrap();
+
+    assert_eq!(
+        groups.insert(SegmentGroup::new(3, 3)),
+        Err(SegmentGroupError::EmptyGroup)
+    );
+    assert_eq!(
+        groups.insert(SegmentGroup::new(4, 7)),
+        Err(SegmentGroupError::Overlapping)
+    );
+    assert_eq!(
+        groups.insert(SegmentGroup::new(0, 3)),
+        Err(SegmentGroupError::Overlapping)
+    );
+    assert_eq!(groups.len(), 1);
+}
+
+#[test]
+fn finds_group_of_segment() {
+    let mut groups = SegmentGroups::new();
+    groups.insert(SegmentGroup::new(1, 3)).unwrap();
+    groups.insert(SegmentGroup::new(5, 6)).unwrap();
+
+    assert_eq!(groups.group_index(0), None);
+    assert_eq!(groups.group_index(1), Some(0));
+    assert_eq!(groups.group_index(2), Some(0));
+    assert_eq!(groups.group_index(3), None);
+    assert_eq!(groups.group_index(5), Some(1));
+    assert_eq!(groups.group_index(6), None);
+}
+
+#[test]
+fn adjusts_to_inserted_and_removed_segments() {
+    let mut groups = SegmentGroups::new();
+    groups.insert(SegmentGroup::new(1, 3)).unwrap();
+    groups.insert(SegmentGroup::new(4, 5)).unwrap();
+
+    groups.insert_segment(2);
+    assert_eq!(groups.get(0).unwrap().range(), 1..4);
+    assert_eq!(groups.get(1).unwrap().range(), 5..6);
+
+    groups.insert_segment(1);
+    assert_eq!(groups.get(0).unwrap().range(), 2..5);
+
+    groups.remove_segment(6);
+    assert!(groups.get(1).is_none());
+
+    groups.remove_segment(0);
+    assert_eq!(groups.get(0).unwrap().range(), 1..4);
+}
+
+#[test]
+fn fixing_shrinks_groups() {
+    let mut run = run_with_segments(&["A", "B", "C"]);
+    run.segment_groups_mut()
+        .insert(SegmentGroup::new(1, 5))
+        .unwrap();
+    run.fix_splits();
+    assert_eq!(run.segment_groups().get(0).unwrap().range(), 1..3);
+}
+
+#[test]
+fn add_segment_group_clamps_to_segments() {
+    let mut run = run_with_segments(&["A", "B", "C"]);
+    assert_eq!(run.add_segment_group(1, 10), Ok(0));
+    assert_eq!(run.segment_groups().get(0).unwrap().range(), 1..3);
+    assert_eq!(
+        run.add_segment_group(3, 4),
+        Err(SegmentGroupError::EmptyGroup)
+    );
+}
+
+#[test]
+fn decodes_group_names() {
+    let mut run = run_with_segments(&["-A", "-B", "{Chapter 1}C", "D", "-E", "F", "{Solo} G"]);
+    let groups = SegmentGroups::decode_from_names(run.segments_mut());
+
+    let groups = groups.iter().collect::<Vec<_>>();
+    assert_eq!(groups.len(), 3);
+    assert_eq!(groups[0].range(), 0..3);
+    assert_eq!(groups[0].name(), Some("Chapter 1"));
+    assert_eq!(groups[1].range(), 4..6);
+    assert_eq!(groups[1].name(), None);
+    assert_eq!(groups[2].range(), 6..7);
+    assert_eq!(groups[2].name(), Some("Solo"));
+
+    let names = run.segments().iter().map(|s| s.name()).collect::<Vec<_>>();
+    assert_eq!(names, ["A", "B", "C", "D", "E", "F", "G"]);
+}
+
+#[test]
+fn decoding_keeps_the_rest_of_the_name() {
+    let mut run = run_with_segments(&["{Group}  A", "{} B"]);
+    let groups = SegmentGroups::decode_from_names(run.segments_mut());
+
+    let groups = groups.iter().collect::<Vec<_>>();
+    assert_eq!(groups.len(), 2);
+    assert_eq!(groups[0].name(), Some("Group"));
+    assert_eq!(groups[1].range(), 1..2);
+    assert_eq!(groups[1].name(), None);
+
+    let names = run.segments().iter().map(|s| s.name()).collect::<Vec<_>>();
+    assert_eq!(names, [" A", "B"]);
+}
+
+#[test]
+fn encoding_round_trips() {
+    let mut run = run_with_segments(&["A", "B", "C", "D"]);
+    run.segment_groups_mut()
+        .insert(SegmentGroup::new(0, 3).with_name("Group"))
+        .unwrap();
+
+    let groups = run.segment_groups().clone();
+    let encoded = run
+        .segments()
+        .iter()
+        .enumerate()
+        .map(|(i, s)| groups.encoded_name(i, s.name()).into_owned())
+        .collect::<Vec<_>>();
+    assert_eq!(encoded, ["-A", "-B", "{Group} C", "D"]);
+
+    let mut decoded = run_with_segments(&encoded.iter().map(|s| &**s).collect::<Vec<_>>());
+    groups.strip_encoding_from_names(decoded.segments_mut());
+    let names = decoded
+        .segments()
+        .iter()
+        .map(|s| s.name())
+        .collect::<Vec<_>>();
+    assert_eq!(names, ["A", "B", "C", "D"]);
+}
@@ -0,0 +1,31 @@
+use crate::{Run, TimingMethod};
+
+#[test]
+fn custom_timing_method_names() {
+    let mut run = Run::new();
+    assert_eq!(run.custom_timing_method_name(TimingMethod::Custom1), None);
+    assert_eq!(
+        run.timing_method_name(TimingMethod::Custom1),
+        "Custom Timing Method 1"
+    );
+    assert_eq!(
+        run.timing_methods().collect::<Vec<_>>(),
+        [TimingMethod::RealTime, TimingMethod::GameTime]
+    );
+
+    run.set_custom_timing_method_name(TimingMethod::Custom3, "Frames");
+    run.set_custom_timing_method_name(TimingMethod::GameTime, "Ignored");
+    assert_eq!(run.timing_method_name(TimingMethod::Custom3), "Frames");
+    assert_eq!(run.timing_method_name(TimingMethod::GameTime), "Game Time");
+    assert_eq!(
+        run.timing_methods().collect::<Vec<_>>(),
+        [
+            TimingMethod::RealTime,
+            TimingMethod::GameTime,
+            TimingMethod::Custom3
+        ]
+    );
+
+    run.set_custom_timing_method_name(TimingMethod::Custom3, "");
+    assert_eq!(run.custom_timing_method_name(TimingMethod::Custom3), None);
+}
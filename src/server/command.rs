@@ -0,0 +1,282 @@
+use crate::{
+    analysis::{self, current_pace},
+    comparison::best_segments,
+    timing::{
+        formatter::{Accuracy, Delta, Regular, TimeFormatter},
+        ParseError as TimeParseError,
+    },
+    TimeSpan, Timer, TimerPhase, TimingMethod,
+};
+use snafu::ResultExt;
+
+/// The Error type for commands that couldn't be parsed.
+#[derive(Debug, snafu::Snafu)]
+pub enum ParseError {
+    /// The command is not known.
+    #[snafu(display("The command `{}` is not known.", name))]
+    UnknownCommand {
+        /// The name of the command.
+        name: String,
+    },
+    /// The command requires an argument, but none was provided.
+    MissingArgument,
+    /// The time provided as the argument couldn't be parsed.
+    InvalidTime {
+        /// The underlying error.
+        source: TimeParseError,
+    },
+    /// The timing method provided as the argument is not known.
+    InvalidTimingMethod,
+}
+
+/// A command of the LiveSplit Server protocol. Each command consists of the
+/// name of the command, optionally followed by a space and an argument.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Command<'a> {
+    /// Starts the timer.
+    StartTimer,
+    /// Starts the timer or splits if an attempt is in progress.
+    StartOrSplit,
+    /// Splits.
+    Split,
+    /// Undoes the most recent split.
+    Unsplit,
+    /// Skips the current split.
+    SkipSplit,
+    /// Pauses the timer.
+    Pause,
+    /// Resumes the timer.
+    Resume,
+    /// Resets the current attempt, storing it in the history.
+    Reset,
+    /// Initializes Game Time for the current attempt.
+    InitGameTime,
+    /// Sets the Game Time to the time provided.
+    SetGameTime(TimeSpan),
+    /// Sets the loading times to the time provided.
+    SetLoadingTimes(TimeSpan),
+    /// Pauses the Game Time.
+    PauseGameTime,
+    /// Resumes the Game Time.
+    UnpauseGameTime,
+    /// Switches to the comparison provided.
+    SetComparison(&'a str),
+    /// Switches to the timing method provided. Either `realtime` or
+    /// `gametime`.
+    SwitchTo(TimingMethod),
+    /// Returns the delta of the most recent split compared to the comparison
+    /// provided or the current comparison.
+    GetDelta(Option<&'a str>),
+    /// Returns the split time of the most recent split.
+    GetLastSplitTime,
+    /// Returns the current comparison's split time of the current split.
+    GetComparisonSplitTime,
+    /// Returns the current time.
+    GetCurrentTime,
+    /// Returns the final time of the attempt if it is finished. Otherwise the
+    /// final split time of the comparison provided or the current comparison
+    /// is returned.
+    GetFinalTime(Option<&'a str>),
+    /// Returns the final time that is predicted for the attempt based on the
+    /// comparison provided or the current comparison.
+    GetPredictedTime(Option<&'a str>),
+    /// Returns the best possible final time of the attempt.
+    GetBestPossibleTime,
+    /// Returns the index of the current split or `-1` if there's no attempt in
+    /// progress.
+    GetSplitIndex,
+    /// Returns the name of the current split.
+    GetCurrentSplitName,
+    /// Returns the name of the previous split.
+    GetPreviousSplitName,
+    /// Returns the current Timer Phase.
+    GetCurrentTimerPhase,
+    /// Returns the amount of attempts.
+    GetAttemptCount,
+    /// Returns the amount of finished attempts.
+    GetCompletedCount,
+    /// Returns `pong`.
+    Ping,
+}
+
+impl<'a> Command<'a> {
+    /// Parses a single command. Surrounding whitespace is ignored.
+    pub fn parse(message: &'a str) -> Result<Self, ParseError> {
+        let message = message.trim();
+        let (name, argument) = match message.find(' ') {
+            Some(index) => (&message[..index], Some(message[index + 1..].trim())),
+            None => (message, None),
+        };
+        let argument = argument.filter(|a| !a.is_empty());
+        let required = || argument.ok_or(ParseError::MissingArgument);
+        let time = || -> Result<TimeSpan, ParseError> { required()?.parse().context(InvalidTime) };
+
+        Ok(match name {
+            "starttimer" => Command::StartTimer,
+            "startorsplit" => Command::StartOrSplit,
+            "split" => Command::Split,
+            "unsplit" => Command::Unsplit,
+            "skipsplit" => Command::SkipSplit,
+            "pause" => Command::Pause,
+            "resume" => Command::Resume,
+            "reset" => Command::Reset,
+            "initgametime" => Command::InitGameTime,
+            "setgametime" => Command::SetGameTime(time()?),
+            "setloadingtimes" => Command::SetLoadingTimes(time()?),
+            "pausegametime" => Command::PauseGameTime,
+            "unpausegametime" => Command::UnpauseGameTime,
+            "setcomparison" => Command::SetComparison(required()?),
+            "switchto" => Command::SwitchTo(match required()? {
+                "realtime" => TimingMethod::RealTime,
+                "gametime" => TimingMethod::GameTime,
+                _ => return Err(ParseError::InvalidTimingMethod),
+            }),
+            "getdelta" => Command::GetDelta(argument),
+            "getlastsplittime" => Command::GetLastSplitTime,
+            "getcomparisonsplittime" => Command::GetComparisonSplitTime,
+            "getcurrenttime" => Command::GetCurrentTime,
+            "getfinaltime" | "getfinalsplittime" => Command::GetFinalTime(argument),
+            "getpredictedtime" => Command::GetPredictedTime(argument),
+            "getbestpossibletime" => Command::GetBestPossibleTime,
+            "getsplitindex" => Command::GetSplitIndex,
+            "getcurrentsplitname" => Command::GetCurrentSplitName,
+            "getprevioussplitname" | "getlastsplitname" => Command::GetPreviousSplitName,
+            "getcurrenttimerphase" => Command::GetCurrentTimerPhase,
+            "getattemptcount" => Command::GetAttemptCount,
+            "getcompletedcount" => Command::GetCompletedCount,
+            "ping" => Command::Ping,
+            _ => {
+                return Err(ParseError::UnknownCommand {
+                    name: name.to_owned(),
+                })
+            }
+        })
+    }
+
+    /// Executes the command on the timer provided. Commands that query
+    /// information return a response, while all other commands return `None`.
+    /// Times that are not available are returned as `-`.
+    pub fn execute(self, timer: &mut Timer) -> Option<String> {
+        let method = timer.current_timing_method();
+
+        match self {
+            Command::StartTimer => timer.start(),
+            Command::StartOrSplit => timer.split_or_start(),
+            Command::Split => timer.split(),
+            Command::Unsplit => timer.undo_split(),
+            Command::SkipSplit => timer.skip_split(),
+            Command::Pause => timer.pause(),
+            Command::Resume => timer.resume(),
+            Command::Reset => timer.reset(true),
+            Command::InitGameTime => timer.initialize_game_time(),
+            Command::SetGameTime(time) => timer.set_game_time(time),
+            Command::SetLoadingTimes(time) => timer.set_loading_times(time),
+            Command::PauseGameTime => timer.pause_game_time(),
+            Command::UnpauseGameTime => timer.resume_game_time(),
+            Command::SetComparison(comparison) => {
+                // Unknown comparisons are ignored, just like in LiveSplit.
+                let _ = timer.set_current_comparison(comparison);
+            }
+            Command::SwitchTo(method) => timer.set_current_timing_method(method),
+            Command::GetDelta(comparison) => {
+                let comparison = comparison.unwrap_or_else(|| timer.current_comparison());
+                let delta = catch! {
+                    let index = timer.current_split_index()?.min(timer.run().len() - 1);
+                    analysis::last_delta(timer.run(), index, comparison, method)?
+                };
+                return Some(format_time(
+                    &Delta::custom(false, Accuracy::Hundredths),
+                    delta,
+                ));
+            }
+            Command::GetLastSplitTime => {
+                let time = catch! {
+                    let index = timer.current_split_index()?.checked_sub(1)?;
+                    timer.run().segment(index).split_time()[method]?
+                };
+                return Some(format_time(&regular(), time));
+            }
+            Command::GetComparisonSplitTime => {
+                let time = catch! {
+                    timer.current_split()?.comparison(timer.current_comparison())[method]?
+                };
+                return Some(format_time(&regular(), time));
+            }
+            Command::GetCurrentTime => {
+                return Some(format_time(
+                    &regular(),
+                    timer.snapshot().current_time()[method],
+                ));
+            }
+            Command::GetFinalTime(comparison) => {
+                let comparison = comparison.unwrap_or_else(|| timer.current_comparison());
+                let time = catch! {
+                    let last_segment = timer.run().segments().last()?;
+                    if timer.current_phase() == TimerPhase::Ended {
+                        last_segment.split_time()[method]?
+                    } else {
+                        last_segment.comparison(comparison)[method]?
+                    }
+                };
+                return Some(format_time(&regular(), time));
+            }
+            Command::GetPredictedTime(comparison) => {
+                let comparison = comparison.unwrap_or_else(|| timer.current_comparison());
+                let (time, _) = current_pace::calculate(&timer.snapshot(), comparison);
+                return Some(format_time(&regular(), time));
+            }
+            Command::GetBestPossibleTime => {
+                let (time, _) = current_pace::calculate(&timer.snapshot(), best_segments::NAME);
+                return Some(format_time(&regular(), time));
+            }
+            Command::GetSplitIndex => {
+                let index = timer.current_split_index().map_or(-1, |i| i as isize);
+                return Some(index.to_string());
+            }
+            Command::GetCurrentSplitName => {
+                let name = timer.current_split().map_or("-", |s| s.name());
+                return Some(name.to_owned());
+            }
+            Command::GetPreviousSplitName => {
+                let name = catch! {
+                    let index = timer.current_split_index()?.checked_sub(1)?;
+                    timer.run().segment(index).name()
+                };
+                return Some(name.unwrap_or("-").to_owned());
+            }
+            Command::GetCurrentTimerPhase => {
+                let phase = match timer.current_phase() {
+                    TimerPhase::NotRunning => "NotRunning",
+                    TimerPhase::Running => "Running",
+                    TimerPhase::Ended => "Ended",
+                    TimerPhase::Paused => "Paused",
+                };
+                return Some(phase.to_owned());
+            }
+            Command::GetAttemptCount => return Some(timer.run().attempt_count().to_string()),
+            Command::GetCompletedCount => {
+                let count = timer
+                    .run()
+                    .attempt_history()
+                    .iter()
+                    .filter(|a| a.time()[method].is_some())
+                    .count();
+                return Some(count.to_string());
+            }
+            Command::Ping => return Some("pong".to_owned()),
+        }
+
+        None
+    }
+}
+
+const fn regular() -> Regular {
+    Regular::with_accuracy(Accuracy::Hundredths)
+}
+
+fn format_time<'a, F: TimeFormatter<'a>>(formatter: &'a F, time: Option<TimeSpan>) -> String {
+    match time {
+        Some(time) => formatter.format(time).to_string(),
+        None => "-".to_owned(),
+    }
+}
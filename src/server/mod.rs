@@ -0,0 +1,154 @@
+//! The server module provides a server that allows controlling and querying a
+//! Timer remotely through the protocol of the LiveSplit Server. Every message
+//! is a single command, such as `split` or `getcurrenttime`. Commands that
+//! query information are answered with a single message. The server can be
+//! reached through plain TCP, where each message is a line of text, or through
+//! WebSockets, where each message is a text frame. The module is optional and
+//! is not compiled in by default.
+
+mod command;
+mod websocket;
+
+pub use self::command::{Command, ParseError};
+
+use crate::SharedTimer;
+use snafu::ResultExt;
+use std::{
+    io::{self, BufRead, BufReader, Read, Write},
+    net::{TcpListener, TcpStream},
+    thread,
+};
+
+/// The maximum length of a single message in bytes. Connections that send
+/// longer messages are closed.
+const MAX_MESSAGE_LEN: usize = 64 << 10;
+
+/// The Error type for connections that couldn't be served.
+#[derive(Debug, snafu::Snafu)]
+pub enum Error {
+    /// Failed to read from or write to the connection.
+    Io {
+        /// The underlying error.
+        source: io::Error,
+    },
+    /// The client didn't send a valid WebSocket handshake.
+    Handshake,
+    /// The WebSocket handshake came from a website that isn't hosted locally.
+    ForbiddenOrigin,
+    /// The client sent a WebSocket frame that wasn't masked.
+    UnmaskedFrame,
+    /// The client sent a message that is too large.
+    MessageTooLarge,
+}
+
+/// The Result type for connections that couldn't be served.
+pub type Result<T = ()> = std::result::Result<T, Error>;
+
+/// A server that executes the commands it receives on a shared Timer. The
+/// server can be cloned cheaply to serve multiple connections at once.
+#[derive(Debug, Clone)]
+pub struct Server {
+    timer: SharedTimer,
+    allow_remote_origins: bool,
+}
+
+impl Server {
+    /// Creates a new server that controls the Timer provided. WebSocket
+    /// connections are only accepted from websites hosted locally.
+    pub const fn new(timer: SharedTimer) -> Self {
+        Self {
+            timer,
+            allow_remote_origins: false,
+        }
+    }
+
+    /// Accesses the Timer controlled by the server.
+    pub const fn timer(&self) -> &SharedTimer {
+        &self.timer
+    }
+
+    /// Sets whether WebSocket connections are accepted from websites that
+    /// aren't hosted locally. Browsers allow any website to connect to a
+    /// WebSocket server, so this allows any website that is opened to control
+    /// the Timer. Clients that aren't browsers don't send an origin and are
+    /// always accepted.
+    pub fn set_allow_remote_origins(&mut self, allow: bool) {
+        self.allow_remote_origins = allow;
+    }
+
+    /// Parses and executes a single message and returns the response, if the
+    /// command has one. Just like in LiveSplit, messages that can't be parsed
+    /// are ignored.
+    pub fn execute(&self, message: &str) -> Option<String> {
+        let command = Command::parse(message).ok()?;
+        command.execute(&mut self.timer.write())
+    }
+
+    /// Accepts TCP connections on the listener provided and serves each of
+    /// them on its own thread. This only returns if accepting a connection
+    /// fails.
+    pub fn serve_tcp(&self, listener: &TcpListener) -> Result {
+        loop {
+            let (stream, _) = listener.accept().context(Io)?;
+            let server = self.clone();
+            thread::spawn(move || server.handle_tcp_connection(stream));
+        }
+    }
+
+    /// Accepts WebSocket connections on the listener provided and serves each
+    /// of them on its own thread. This only returns if accepting a connection
+    /// fails.
+    pub fn serve_websocket(&self, listener: &TcpListener) -> Result {
+        loop {
+            let (stream, _) = listener.accept().context(Io)?;
+            let server = self.clone();
+            thread::spawn(move || server.handle_websocket_connection(stream));
+        }
+    }
+
+    /// Serves a single TCP connection until the client disconnects. Each line
+    /// received is a command and each response is sent as a line terminated
+    /// by `\r\n`.
+    pub fn handle_tcp_connection(&self, stream: TcpStream) -> Result {
+        let mut writer = stream.try_clone().context(Io)?;
+        let mut reader = BufReader::new(stream);
+        let mut line = Vec::new();
+        loop {
+            line.clear();
+            let len = reader
+                .by_ref()
+                .take(MAX_MESSAGE_LEN as u64 + 1)
+                .read_until(b'\n', &mut line)
+                .context(Io)?;
+            if len == 0 {
+                return Ok(());
+            }
+            if line.last() == Some(&b'\n') {
+                line.pop();
+                if line.last() == Some(&b'\r') {
+                    line.pop();
+                }
+            } else if line.len() > MAX_MESSAGE_LEN {
+                return MessageTooLarge.fail();
+            }
+            if let Some(response) = self.execute(&String::from_utf8_lossy(&line)) {
+                write!(writer, "{}\r\n", response).context(Io)?;
+            }
+        }
+    }
+
+    /// Serves a single WebSocket connection until the client disconnects.
+    /// Each text message received is a command and each response is sent as a
+    /// text message.
+    pub fn handle_websocket_connection(&self, stream: TcpStream) -> Result {
+        let mut writer = stream.try_clone().context(Io)?;
+        let mut reader = BufReader::new(stream);
+        websocket::handshake(&mut reader, &mut writer, self.allow_remote_origins)?;
+        while let Some(message) = websocket::read_message(&mut reader, &mut writer)? {
+            if let Some(response) = self.execute(&message) {
+                websocket::write_text(&mut writer, &response).context(Io)?;
+            }
+        }
+        Ok(())
+    }
+}
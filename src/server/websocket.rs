@@ -0,0 +1,239 @@
+//! A minimal implementation of the server side of the WebSocket protocol as
+//! specified in RFC 6455. Only what is needed to exchange text messages is
+//! supported.
+
+use super::{
+    ForbiddenOrigin, Handshake, Io, MessageTooLarge, Result, UnmaskedFrame, MAX_MESSAGE_LEN,
+};
+use snafu::{OptionExt, ResultExt};
+use std::io::{self, BufRead, Read, Write};
+
+const GUID: &str = "258EAFA5-E914-47DA-95CA-C5AB0DC85B11";
+
+const OP_CONTINUATION: u8 = 0x0;
+const OP_TEXT: u8 = 0x1;
+const OP_BINARY: u8 = 0x2;
+const OP_CLOSE: u8 = 0x8;
+const OP_PING: u8 = 0x9;
+const OP_PONG: u8 = 0xA;
+
+/// Calculates the value of the `Sec-WebSocket-Accept` header for the key that
+/// the client sent.
+fn accept_key(key: &str) -> String {
+    let mut hasher = sha1_smol::Sha1::new();
+    hasher.update(key.as_bytes());
+    hasher.update(GUID.as_bytes());
+    base64::encode(hasher.digest().bytes())
+}
+
+/// Checks whether the origin of a website is hosted on this machine.
+fn is_local_origin(origin: &str) -> bool {
+    let host = origin
+        .find("://")
+        .map_or(origin, |index| &origin[index + 3..]);
+    let host = host.split('/').next().unwrap_or_default();
+    let host = if host.starts_with('[') {
+        host.find(']').map_or(host, |index| &host[..=index])
+    } else {
+        host.split(':').next().unwrap_or_default()
+    };
+    host.eq_ignore_ascii_case("localhost") || host == "127.0.0.1" || host == "[::1]"
+}
+
+/// Reads the client's opening handshake and responds to it. Unless remote
+/// origins are allowed, handshakes of websites that aren't hosted locally are
+/// rejected.
+pub fn handshake(
+    reader: &mut impl BufRead,
+    writer: &mut impl Write,
+    allow_remote_origins: bool,
+) -> Result {
+    let mut key = None;
+    let mut origin = None;
+    let mut line = String::new();
+    loop {
+        line.clear();
+        if reader.read_line(&mut line).context(Io)? == 0 {
+            return Handshake.fail();
+        }
+        let line = line.trim_end();
+        if line.is_empty() {
+            break;
+        }
+        if let Some(index) = line.find(':') {
+            if line[..index]
+                .trim()
+                .eq_ignore_ascii_case("Sec-WebSocket-Key")
+            {
+                key = Some(line[index + 1..].trim().to_owned());
+            } else if line[..index].trim().eq_ignore_ascii_case("Origin") {
+                origin = Some(line[index + 1..].trim().to_owned());
+            }
+        }
+    }
+    let key = key.context(Handshake)?;
+
+    if let Some(origin) = origin {
+        if !allow_remote_origins && !is_local_origin(&origin) {
+            write!(writer, "HTTP/1.1 403 Forbidden\r\n\r\n").context(Io)?;
+            return ForbiddenOrigin.fail();
+        }
+    }
+
+    write!(
+        writer,
+        "HTTP/1.1 101 Switching Protocols\r\n\
+         Upgrade: websocket\r\n\
+         Connection: Upgrade\r\n\
+         Sec-WebSocket-Accept: {}\r\n\r\n",
+        accept_key(&key),
+    )
+    .context(Io)
+}
+
+/// Reads the next text message. Control frames are handled transparently.
+/// Returns `None` once the client closed the connection.
+pub fn read_message(reader: &mut impl Read, writer: &mut impl Write) -> Result<Option<String>> {
+    let mut message = Vec::new();
+    loop {
+        let mut header = [0; 2];
+        match reader.read_exact(&mut header) {
+            Err(e) if e.kind() == io::ErrorKind::UnexpectedEof => return Ok(None),
+            result => result.context(Io)?,
+        }
+        let is_final = header[0] & 0x80 != 0;
+        let opcode = header[0] & 0x0F;
+        if header[1] & 0x80 == 0 {
+            return UnmaskedFrame.fail();
+        }
+
+        let len = match header[1] & 0x7F {
+            126 => {
+                let mut len = [0; 2];
+                reader.read_exact(&mut len).context(Io)?;
+                u16::from_be_bytes(len) as u64
+            }
+            127 => {
+                let mut len = [0; 8];
+                reader.read_exact(&mut len).context(Io)?;
+                u64::from_be_bytes(len)
+            }
+            len => len as u64,
+        };
+        // The message never exceeds the limit, so this can't underflow, while
+        // adding the length sent by the client could overflow.
+        if len > (MAX_MESSAGE_LEN - message.len()) as u64 {
+            return MessageTooLarge.fail();
+        }
+
+        let mut mask = [0; 4];
+        reader.read_exact(&mut mask).context(Io)?;
+        let mut payload = vec![0; len as usize];
+        reader.read_exact(&mut payload).context(Io)?;
+        for (i, byte) in payload.iter_mut().enumerate() {
+            *byte ^= mask[i % 4];
+        }
+
+        match opcode {
+            OP_CLOSE => {
+                write_frame(writer, OP_CLOSE, &payload).context(Io)?;
+                return Ok(None);
+            }
+            OP_PING => write_frame(writer, OP_PONG, &payload).context(Io)?,
+            OP_TEXT | OP_BINARY | OP_CONTINUATION => {
+                message.extend_from_slice(&payload);
+                if is_final {
+                    return Ok(Some(String::from_utf8_lossy(&message).into_owned()));
+                }
+            }
+            // Pongs and unknown frames are ignored.
+            _ => {}
+        }
+    }
+}
+
+/// Sends a text message to the client.
+pub fn write_text(writer: &mut impl Write, text: &str) -> io::Result<()> {
+    write_frame(writer, OP_TEXT, text.as_bytes())
+}
+
+fn write_frame(writer: &mut impl Write, opcode: u8, payload: &[u8]) -> io::Result<()> {
+    let mut frame = Vec::with_capacity(payload.len() + 10);
+    frame.push(0x80 | opcode);
+    let len = payload.len();
+    if len < 126 {
+        frame.push(len as u8);
+    } else if len <= u16::MAX as usize {
+        frame.push(126);
+        frame.extend_from_slice(&(len as u16).to_be_bytes());
+    } else {
+        frame.push(127);
+        frame.extend_from_slice(&(len as u64).to_be_bytes());
+    }
+    frame.extend_from_slice(payload);
+    writer.write_all(&frame)?;
+    writer.flush()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{accept_key, is_local_origin, read_message, MAX_MESSAGE_LEN};
+    use crate::server::Error;
+
+    #[test]
+    fn accept_key_from_rfc() {
+        assert_eq!(
+            accept_key("dGhlIHNhbXBsZSBub25jZQ=="),
+            "s3pPLMBiTxaQ9kYGzzhZRbK+xOo="
+        );
+    }
+
+    #[test]
+    fn local_origins() {
+        assert!(is_local_origin("http://localhost:8080"));
+        assert!(is_local_origin("https://127.0.0.1"));
+        assert!(is_local_origin("http://[::1]:80/"));
+        assert!(!is_local_origin("https://example.com"));
+        assert!(!is_local_origin("http://localhost.example.com"));
+        assert!(!is_local_origin("null"));
+    }
+
+    /// Builds a masked frame with an all zero mask, so the payload stays the
+    /// same.
+    fn frame(first_byte: u8, len: u64, payload: &[u8]) -> Vec<u8> {
+        let mut frame = vec![first_byte];
+        if len < 126 {
+            frame.push(0x80 | len as u8);
+        } else {
+            frame.push(0x80 | 127);
+            frame.extend_from_slice(&len.to_be_bytes());
+        }
+        frame.extend_from_slice(&[0; 4]);
+        frame.extend_from_slice(payload);
+        frame
+    }
+
+    #[test]
+    fn fragmented_messages() {
+        let mut data = frame(0x01, 3, b"spl");
+        data.extend(frame(0x80, 2, b"it"));
+        let message = read_message(&mut &data[..], &mut Vec::new()).unwrap();
+        assert_eq!(message.as_deref(), Some("split"));
+    }
+
+    #[test]
+    fn oversized_frames_after_a_fragment() {
+        let mut data = frame(0x01, 3, b"spl");
+        data.extend(frame(0x80, u64::MAX, b""));
+        assert!(matches!(
+            read_message(&mut &data[..], &mut Vec::new()),
+            Err(Error::MessageTooLarge)
+        ));
+
+        let data = frame(0x81, MAX_MESSAGE_LEN as u64 + 1, b"");
+        assert!(matches!(
+            read_message(&mut &data[..], &mut Vec::new()),
+            Err(Error::MessageTooLarge)
+        ));
+    }
+}
@@ -1,6 +1,10 @@
 use crate::{
-    component::splits::{ColumnStartWith, ColumnUpdateTrigger, ColumnUpdateWith},
-    hotkey::KeyCode,
+    component::{
+        run_history::HistoryAxis,
+        splits::{ColumnStartWith, ColumnUpdateTrigger, ColumnUpdateWith, StyleCondition},
+        statistic::Statistic,
+    },
+    hotkey::Hotkey,
     layout::LayoutDirection,
     platform::prelude::*,
     settings::{Alignment, Color, Font, Gradient, ListGradient},
@@ -51,12 +55,20 @@ pub enum Value {
     /// A value describing when to update a column of the Splits Component.
     ColumnUpdateTrigger(ColumnUpdateTrigger),
     /// A value describing what hotkey to press to trigger a certain action.
-    Hotkey(Option<KeyCode>),
+    Hotkey(Option<Hotkey>),
     /// A value describing the direction of a layout.
     LayoutDirection(LayoutDirection),
     /// A value describing a font to use. `None` if a default font should be
     /// used.
     Font(Option<Font>),
+    /// A value describing which statistic to show.
+    Statistic(Statistic),
+    /// A value describing what the horizontal axis of the Run History
+    /// Component is based on.
+    HistoryAxis(HistoryAxis),
+    /// A value describing the condition a row of the Splits Component needs
+    /// to meet for a Style Rule to be applied.
+    StyleCondition(StyleCondition),
 }
 
 impl From<bool> for Value {
@@ -155,8 +167,8 @@ impl From<ColumnUpdateTrigger> for Value {
     }
 }
 
-impl From<Option<KeyCode>> for Value {
-    fn from(x: Option<KeyCode>) -> Self {
+impl From<Option<Hotkey>> for Value {
+    fn from(x: Option<Hotkey>) -> Self {
         Value::Hotkey(x)
     }
 }
@@ -173,6 +185,24 @@ impl From<Option<Font>> for Value {
     }
 }
 
+impl From<Statistic> for Value {
+    fn from(x: Statistic) -> Self {
+        Value::Statistic(x)
+    }
+}
+
+impl From<HistoryAxis> for Value {
+    fn from(x: HistoryAxis) -> Self {
+        Value::HistoryAxis(x)
+    }
+}
+
+impl From<StyleCondition> for Value {
+    fn from(x: StyleCondition) -> Self {
+        Value::StyleCondition(x)
+    }
+}
+
 /// The Error type for values that couldn't be converted.
 #[derive(Debug, snafu::Snafu)]
 pub enum Error {
@@ -319,7 +349,7 @@ impl Value {
     }
 
     /// Tries to convert the value into a hotkey.
-    pub fn into_hotkey(self) -> Result<Option<KeyCode>> {
+    pub fn into_hotkey(self) -> Result<Option<Hotkey>> {
         match self {
             Value::Hotkey(v) => Ok(v),
             Value::String(v) | Value::OptionalString(Some(v)) => {
@@ -345,6 +375,30 @@ impl Value {
             _ => Err(Error::WrongType),
         }
     }
+
+    /// Tries to convert the value into a statistic.
+    pub fn into_statistic(self) -> Result<Statistic> {
+        match self {
+            Value::Statistic(v) => Ok(v),
+            _ => Err(Error::WrongType),
+        }
+    }
+
+    /// Tries to convert the value into a history axis.
+    pub fn into_history_axis(self) -> Result<HistoryAxis> {
+        match self {
+            Value::HistoryAxis(v) => Ok(v),
+            _ => Err(Error::WrongType),
+        }
+    }
+
+    /// Tries to convert the value into a style condition.
+    pub fn into_style_condition(self) -> Result<StyleCondition> {
+        match self {
+            Value::StyleCondition(v) => Ok(v),
+            _ => Err(Error::WrongType),
+        }
+    }
 }
 
 impl From<Value> for bool {
@@ -443,7 +497,7 @@ impl From<Value> for ColumnUpdateTrigger {
     }
 }
 
-impl From<Value> for Option<KeyCode> {
+impl From<Value> for Option<Hotkey> {
     fn from(value: Value) -> Self {
         value.into_hotkey().unwrap()
     }
@@ -460,3 +514,21 @@ impl From<Value> for Option<Font> {
         value.into_font().unwrap()
     }
 }
+
+impl From<Value> for Statistic {
+    fn from(value: Value) -> Self {
+        value.into_statistic().unwrap()
+    }
+}
+
+impl From<Value> for HistoryAxis {
+    fn from(value: Value) -> Self {
+        value.into_history_axis().unwrap()
+    }
+}
+
+impl From<Value> for StyleCondition {
+    fn from(value: Value) -> Self {
+        value.into_style_condition().unwrap()
+    }
+}
@@ -46,12 +46,34 @@ pub fn make_progress_run_with_splits_opt(timer: &mut Timer, splits: &[Option<f64
     }
 }
 
+/// Finishes the two attempts that many tests use as the history of their run.
+/// The first attempt splits at 10, 30 and 60 seconds, the second one is
+/// slower on every segment and splits at 12, 34 and 70 seconds.
+pub fn run_sample_attempts(timer: &mut Timer) {
+    run_with_splits(timer, &[10.0, 30.0, 60.0]);
+    run_with_splits(timer, &[12.0, 34.0, 70.0]);
+}
+
 pub fn run_with_splits_opt(timer: &mut Timer, splits: &[Option<f64>]) {
     start_run(timer);
     make_progress_run_with_splits_opt(timer, splits);
     timer.reset(true);
 }
 
+/// Collects the segment history of a segment with the times of the timing
+/// method provided.
+pub fn segment_history(
+    run: &Run,
+    segment: usize,
+    method: TimingMethod,
+) -> Vec<(i32, Option<TimeSpan>)> {
+    run.segment(segment)
+        .segment_history()
+        .iter()
+        .map(|(index, time)| (*index, time[method]))
+        .collect()
+}
+
 pub fn span(seconds: f64) -> TimeSpan {
     TimeSpan::from_seconds(seconds)
 }
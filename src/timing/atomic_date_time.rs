@@ -2,7 +2,7 @@ use crate::{
     platform::{utc_now, DateTime, Utc},
     TimeSpan,
 };
-use core::ops::Sub;
+use core::ops::{Add, Sub};
 
 /// An Atomic Date Time represents a UTC Date Time that tries to be as close to
 /// an atomic clock as possible.
@@ -59,3 +59,14 @@ impl Sub<DateTime<Utc>> for AtomicDateTime {
         self.time.signed_duration_since(rhs).into()
     }
 }
+
+impl Add<TimeSpan> for AtomicDateTime {
+    type Output = AtomicDateTime;
+
+    fn add(self, rhs: TimeSpan) -> AtomicDateTime {
+        AtomicDateTime {
+            time: self.time + rhs.to_duration(),
+            synced_with_atomic_clock: self.synced_with_atomic_clock,
+        }
+    }
+}
@@ -0,0 +1,245 @@
+use crate::{
+    platform::{math::f64::floor, prelude::*, Duration},
+    AtomicDateTime, TimeSpan, TimeStamp,
+};
+use core::fmt::Debug;
+
+/// A clock is the source of the Time Stamps that a [`Timer`](crate::Timer)
+/// measures its times with. By default the timer uses the [`SystemClock`], but
+/// a different clock can be provided to drive the timer deterministically,
+/// such as when replaying an attempt, or to synchronize it with a different
+/// source of time, such as the clock of a race server. The timer owns its
+/// clock, which can be accessed through
+/// [`Timer::clock_mut`](crate::Timer::clock_mut). Clocks need to implement
+/// [`Clone`], so that the timer can be cloned.
+pub trait Clock: Debug + Send + Sync + AnyClock + 'static {
+    /// Returns a Time Stamp representing the current point in time.
+    fn now(&self) -> TimeStamp;
+
+    /// Returns the current date and time of day. The timer uses this to
+    /// determine when attempts are started and ended. By default this reads
+    /// the date and time from the system.
+    fn date_now(&self) -> AtomicDateTime {
+        AtomicDateTime::now()
+    }
+}
+
+mod private {
+    use super::Clock;
+    use crate::platform::prelude::*;
+    use core::any::Any;
+
+    /// Allows cloning and downcasting clocks that are stored as trait objects.
+    /// This is implemented for every clock that implements [`Clone`].
+    pub trait AnyClock {
+        fn clone_box(&self) -> Box<dyn Clock>;
+        fn as_any(&self) -> &dyn Any;
+        fn as_any_mut(&mut self) -> &mut dyn Any;
+    }
+
+    impl<C: Clock + Clone> AnyClock for C {
+        fn clone_box(&self) -> Box<dyn Clock> {
+            Box::new(self.clone())
+        }
+
+        fn as_any(&self) -> &dyn Any {
+            self
+        }
+
+        fn as_any_mut(&mut self) -> &mut dyn Any {
+            self
+        }
+    }
+}
+
+use self::private::AnyClock;
+
+impl Clone for Box<dyn Clock> {
+    fn clone(&self) -> Self {
+        (**self).clone_box()
+    }
+}
+
+impl dyn Clock {
+    /// Returns the clock as the type of clock provided, if it is of that type.
+    pub fn downcast_ref<C: Clock>(&self) -> Option<&C> {
+        self.as_any().downcast_ref()
+    }
+
+    /// Returns the clock mutably as the type of clock provided, if it is of
+    /// that type.
+    pub fn downcast_mut<C: Clock>(&mut self) -> Option<&mut C> {
+        self.as_any_mut().downcast_mut()
+    }
+}
+
+/// The system clock reads the current point in time from the high precision
+/// clock of the operating system. This is the default clock of a
+/// [`Timer`](crate::Timer).
+#[derive(Debug, Default, Copy, Clone)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> TimeStamp {
+        TimeStamp::now()
+    }
+}
+
+/// A manual clock only moves forward when it is told to. The date and time of
+/// day start out at the moment the clock got created and move along with the
+/// elapsed time.
+#[derive(Debug, Clone)]
+pub struct ManualClock {
+    origin: TimeStamp,
+    date_origin: AtomicDateTime,
+    elapsed: TimeSpan,
+}
+
+impl Default for ManualClock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ManualClock {
+    /// Creates a new manual clock that starts out at an elapsed time of zero.
+    pub fn new() -> Self {
+        Self::with_date(AtomicDateTime::now())
+    }
+
+    /// Creates a new manual clock that starts out at an elapsed time of zero
+    /// and at the date and time provided. This makes the dates of the attempts
+    /// deterministic as well.
+    pub fn with_date(date: AtomicDateTime) -> Self {
+        Self {
+            origin: TimeStamp::now(),
+            date_origin: date,
+            elapsed: TimeSpan::zero(),
+        }
+    }
+
+    /// Returns the amount of time that has elapsed since the clock was
+    /// created.
+    pub const fn elapsed(&self) -> TimeSpan {
+        self.elapsed
+    }
+
+    /// Sets the amount of time that has elapsed since the clock was created.
+    /// The time is allowed to go backwards.
+    pub const fn set_elapsed(&mut self, elapsed: TimeSpan) {
+        self.elapsed = elapsed;
+    }
+
+    /// Moves the clock forward by the amount of time provided.
+    pub fn advance(&mut self, time: TimeSpan) {
+        self.elapsed += time;
+    }
+}
+
+impl Clock for ManualClock {
+    fn now(&self) -> TimeStamp {
+        self.origin + self.elapsed
+    }
+
+    fn date_now(&self) -> AtomicDateTime {
+        self.date_origin + self.elapsed
+    }
+}
+
+/// An offset clock is ahead of another clock by a fixed amount of time. A
+/// negative offset puts it behind the other clock instead. The offset can be
+/// changed at any point, which allows keeping a timer in sync with a remote
+/// clock, such as the one of a race server.
+#[derive(Debug, Clone)]
+pub struct OffsetClock<C> {
+    clock: C,
+    offset: TimeSpan,
+}
+
+impl<C: Clock> OffsetClock<C> {
+    /// Creates a new offset clock that is ahead of the clock provided by the
+    /// offset provided.
+    pub const fn new(clock: C, offset: TimeSpan) -> Self {
+        Self { clock, offset }
+    }
+
+    /// Grants mutable access to the underlying clock.
+    pub const fn clock_mut(&mut self) -> &mut C {
+        &mut self.clock
+    }
+
+    /// Returns the amount of time this clock is ahead of the underlying clock.
+    pub const fn offset(&self) -> TimeSpan {
+        self.offset
+    }
+
+    /// Sets the amount of time this clock is ahead of the underlying clock.
+    pub const fn set_offset(&mut self, offset: TimeSpan) {
+        self.offset = offset;
+    }
+}
+
+impl<C: Clock + Clone> Clock for OffsetClock<C> {
+    fn now(&self) -> TimeStamp {
+        self.clock.now() + self.offset
+    }
+
+    fn date_now(&self) -> AtomicDateTime {
+        self.clock.date_now() + self.offset
+    }
+}
+
+/// A skewed clock runs faster or slower than another clock. A rate of `2.0`
+/// means that two seconds pass on the skewed clock for every second that
+/// passes on the other clock. The clocks agree on the moment the skewed clock
+/// got created. This is useful for simulating clocks that drift apart.
+#[derive(Debug, Clone)]
+pub struct SkewedClock<C> {
+    clock: C,
+    origin: TimeStamp,
+    date_origin: AtomicDateTime,
+    rate: f64,
+}
+
+impl<C: Clock> SkewedClock<C> {
+    /// Creates a new skewed clock that runs at the rate provided relative to
+    /// the clock provided.
+    pub fn new(clock: C, rate: f64) -> Self {
+        Self {
+            origin: clock.now(),
+            date_origin: clock.date_now(),
+            clock,
+            rate,
+        }
+    }
+
+    /// Grants mutable access to the underlying clock.
+    pub const fn clock_mut(&mut self) -> &mut C {
+        &mut self.clock
+    }
+
+    /// Returns the rate at which this clock runs relative to the underlying
+    /// clock.
+    pub const fn rate(&self) -> f64 {
+        self.rate
+    }
+
+    fn skewed_elapsed(&self) -> TimeSpan {
+        let elapsed = microseconds(self.clock.now() - self.origin) as f64;
+        Duration::microseconds(floor(elapsed * self.rate + 0.5) as i64).into()
+    }
+}
+
+impl<C: Clock + Clone> Clock for SkewedClock<C> {
+    fn now(&self) -> TimeStamp {
+        self.origin + self.skewed_elapsed()
+    }
+
+    fn date_now(&self) -> AtomicDateTime {
+        self.date_origin + self.skewed_elapsed()
+    }
+}
+
+fn microseconds(time: TimeSpan) -> i64 {
+    time.to_duration().num_microseconds().unwrap_or_default()
+}
@@ -0,0 +1,146 @@
+use super::{Accuracy, Short, TimeFormatter, MINUS};
+use crate::{timing::FrameRate, TimeSpan};
+use core::fmt::{Display, Formatter, Result};
+
+pub struct Inner {
+    time: Option<TimeSpan>,
+    frame_rate: FrameRate,
+    accuracy: Option<Accuracy>,
+}
+
+/// The Frames Time Formatter is suitable for categories that are timed in
+/// frames rather than in seconds. By default it shows the amount of frames a
+/// Time Span takes up at the configured frame rate. Alternatively it can be
+/// configured with an accuracy, in which case the Time Span gets snapped to the
+/// closest frame boundary and then shown the same way the [`Short`] Time
+/// Formatter would show it.
+///
+/// # Example Formatting
+///
+/// * Empty Time `0f`
+/// * Frames `1234f`
+/// * Negative Frames `−12f`
+/// * Snapped to Frames with Milliseconds `20.533`
+pub struct Frames {
+    frame_rate: FrameRate,
+    accuracy: Option<Accuracy>,
+}
+
+impl Frames {
+    /// Creates a new Frames Time Formatter that shows the amount of frames at
+    /// the frame rate provided.
+    pub const fn new(frame_rate: FrameRate) -> Self {
+        Frames {
+            frame_rate,
+            accuracy: None,
+        }
+    }
+
+    /// Creates a new Frames Time Formatter that snaps the Time Spans to the
+    /// frame boundaries of the frame rate provided and shows them with the
+    /// accuracy provided.
+    pub const fn with_accuracy(frame_rate: FrameRate, accuracy: Accuracy) -> Self {
+        Frames {
+            frame_rate,
+            accuracy: Some(accuracy),
+        }
+    }
+}
+
+/// A Frame Wrapper wraps a Time Formatter and snaps the Time Spans to the
+/// closest frame boundary of a frame rate before they get formatted. Without a
+/// frame rate the Time Spans are passed on unchanged, so the wrapper can be
+/// used regardless of whether a run is timed in frames.
+pub struct FrameWrapper<F> {
+    frame_rate: Option<FrameRate>,
+    inner: F,
+}
+
+impl<F> FrameWrapper<F> {
+    /// Creates a new Frame Wrapper that wraps around the Time Formatter
+    /// provided and snaps the Time Spans to the frame rate provided, if there
+    /// is one.
+    pub const fn new(frame_rate: Option<FrameRate>, inner: F) -> Self {
+        FrameWrapper { frame_rate, inner }
+    }
+}
+
+impl TimeFormatter<'_> for Frames {
+    type Inner = Inner;
+
+    fn format<T>(&self, time: T) -> Self::Inner
+    where
+        T: Into<Option<TimeSpan>>,
+    {
+        Inner {
+            time: time.into(),
+            frame_rate: self.frame_rate,
+            accuracy: self.accuracy,
+        }
+    }
+}
+
+impl<'a, F: TimeFormatter<'a>> TimeFormatter<'a> for FrameWrapper<F> {
+    type Inner = F::Inner;
+
+    fn format<T>(&'a self, time: T) -> Self::Inner
+    where
+        T: Into<Option<TimeSpan>>,
+    {
+        let frame_rate = self.frame_rate;
+        self.inner
+            .format(time.into().map(|t| frame_rate.map_or(t, |f| f.snap(t))))
+    }
+}
+
+impl Display for Inner {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        if let Some(accuracy) = self.accuracy {
+            let time = self.time.map(|t| self.frame_rate.snap(t));
+            write!(f, "{}", Short::with_accuracy(accuracy).format(time))
+        } else if let Some(time) = self.time {
+            let frames = self.frame_rate.frames(time);
+            if frames < 0 {
+                write!(f, "{}{}f", MINUS, -frames)
+            } else {
+                write!(f, "{}f", frames)
+            }
+        } else {
+            write!(f, "0f")
+        }
+    }
+}
+
+#[test]
+fn frame_counts() {
+    let frame_rate = FrameRate::new(60.0988).unwrap();
+    let formatter = Frames::new(frame_rate);
+    let time = frame_rate.time_span(1234);
+    assert_eq!(formatter.format(time).to_string(), "1234f");
+    assert_eq!(formatter.format(-time).to_string(), "−1234f");
+    assert_eq!(formatter.format(None).to_string(), "0f");
+}
+
+#[test]
+fn wrapped_formatters() {
+    use super::Delta;
+
+    let frame_rate = FrameRate::new(60.0).unwrap();
+    let time = TimeSpan::from_seconds(20.54);
+    let formatter = FrameWrapper::new(
+        Some(frame_rate),
+        Delta::custom(false, Accuracy::Milliseconds),
+    );
+    assert_eq!(formatter.format(time).to_string(), "+20.533");
+    let formatter = FrameWrapper::new(None, Delta::custom(false, Accuracy::Milliseconds));
+    assert_eq!(formatter.format(time).to_string(), "+20.540");
+}
+
+#[test]
+fn snapped_to_frames() {
+    let frame_rate = FrameRate::new(60.0).unwrap();
+    let formatter = Frames::with_accuracy(frame_rate, Accuracy::Milliseconds);
+    let time = TimeSpan::from_seconds(20.54);
+    assert_eq!(formatter.format(time).to_string(), "20.533");
+    assert_eq!(formatter.format(None).to_string(), "0.000");
+}
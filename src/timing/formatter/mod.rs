@@ -25,6 +25,7 @@ mod complete;
 mod days;
 mod delta;
 mod digits_format;
+mod frames;
 pub mod none_wrapper;
 mod regular;
 mod segment_time;
@@ -36,6 +37,7 @@ pub use self::complete::Complete;
 pub use self::days::Days;
 pub use self::delta::Delta;
 pub use self::digits_format::DigitsFormat;
+pub use self::frames::{FrameWrapper, Frames};
 pub use self::regular::Regular;
 pub use self::segment_time::SegmentTime;
 pub use self::short::Short;
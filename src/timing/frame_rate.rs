@@ -0,0 +1,68 @@
+use crate::{
+    platform::{math::f64::floor, Duration},
+    TimeSpan,
+};
+use serde::{Deserialize, Serialize};
+
+/// A Frame Rate describes how many frames a game renders per second. Many
+/// categories, especially for retro games, are timed in frames rather than in
+/// seconds. A Frame Rate allows converting between Time Spans and frame counts
+/// and snapping Time Spans to the closest frame boundary.
+///
+/// # Examples
+///
+/// ```
+/// use livesplit_core::{timing::FrameRate, TimeSpan};
+///
+/// // The NES runs at roughly 60.0988 frames per second on NTSC consoles.
+/// let frame_rate = FrameRate::new(60.0988).unwrap();
+///
+/// let time = frame_rate.time_span(601);
+/// assert_eq!(frame_rate.frames(time), 601);
+/// assert_eq!(frame_rate.snap(time + TimeSpan::from_milliseconds(3.0)), time);
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq, PartialOrd, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct FrameRate(f64);
+
+impl FrameRate {
+    /// Creates a new Frame Rate from the amount of frames per second. `None`
+    /// is returned if the amount of frames per second is not a positive finite
+    /// number.
+    pub fn new(frames_per_second: f64) -> Option<Self> {
+        if frames_per_second.is_finite() && frames_per_second > 0.0 {
+            Some(FrameRate(frames_per_second))
+        } else {
+            None
+        }
+    }
+
+    /// Returns the amount of frames per second.
+    pub const fn frames_per_second(self) -> f64 {
+        self.0
+    }
+
+    /// Returns the duration of a single frame.
+    pub fn frame_duration(self) -> TimeSpan {
+        self.time_span(1)
+    }
+
+    /// Returns the amount of frames that fit into the Time Span provided. The
+    /// Time Span is rounded to the closest frame boundary, so that times that
+    /// were rounded when they got entered or stored still map to the frame
+    /// they came from.
+    pub fn frames(self, time: TimeSpan) -> i64 {
+        floor(time.total_seconds() * self.0 + 0.5) as i64
+    }
+
+    /// Returns the Time Span that the amount of frames provided take up.
+    pub fn time_span(self, frames: i64) -> TimeSpan {
+        let microseconds = floor(frames as f64 * 1_000_000.0 / self.0 + 0.5);
+        Duration::microseconds(microseconds as i64).into()
+    }
+
+    /// Snaps the Time Span provided to the closest frame boundary.
+    pub fn snap(self, time: TimeSpan) -> TimeSpan {
+        self.time_span(self.frames(time))
+    }
+}
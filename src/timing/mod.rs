@@ -2,7 +2,9 @@
 //! measuring them.
 
 mod atomic_date_time;
+mod clock;
 pub mod formatter;
+mod frame_rate;
 mod time;
 mod time_span;
 mod time_stamp;
@@ -11,11 +13,16 @@ mod timer_phase;
 mod timing_method;
 
 pub use self::atomic_date_time::AtomicDateTime;
+pub use self::clock::{Clock, ManualClock, OffsetClock, SkewedClock, SystemClock};
+pub use self::frame_rate::FrameRate;
 pub use self::time::{GameTime, RealTime, Time};
 pub use self::time_span::{ParseError, TimeSpan};
 pub use self::time_stamp::TimeStamp;
 #[cfg(feature = "std")]
 pub use self::timer::SharedTimer;
-pub use self::timer::{CreationError as TimerCreationError, Snapshot, Timer};
+pub use self::timer::{
+    CreationError as TimerCreationError, Event as TimerEvent, Snapshot,
+    SubscriptionId as TimerSubscriptionId, Timer,
+};
 pub use self::timer_phase::TimerPhase;
 pub use self::timing_method::TimingMethod;
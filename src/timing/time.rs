@@ -1,14 +1,15 @@
 use crate::{TimeSpan, TimingMethod};
 use core::ops::{Add, AddAssign, Index, IndexMut, Sub, SubAssign};
 
-/// A time that can store a Real Time and a Game Time. Both of them are
-/// optional.
+/// A time that can store a Real Time, a Game Time and the times of the custom
+/// timing methods. All of them are optional.
 #[derive(Copy, Clone, Default, Debug, Eq, PartialEq)]
 pub struct Time {
     /// The Real Time value.
     pub real_time: Option<TimeSpan>,
     /// The Game Time value.
     pub game_time: Option<TimeSpan>,
+    custom: [Option<TimeSpan>; TimingMethod::CUSTOM_COUNT],
 }
 
 impl Time {
@@ -18,7 +19,7 @@ impl Time {
         Time::default()
     }
 
-    /// Creates a new Time where Real Time and Game Time are zero. Keep in mind
+    /// Creates a new Time where all the timing methods are zero. Keep in mind
     /// that a zero Time Span is not the same as a `None` Time Span as created
     /// by `Time::new()`.
     #[inline]
@@ -26,6 +27,7 @@ impl Time {
         Time {
             real_time: Some(TimeSpan::zero()),
             game_time: Some(TimeSpan::zero()),
+            custom: [Some(TimeSpan::zero()); TimingMethod::CUSTOM_COUNT],
         }
     }
 
@@ -55,16 +57,24 @@ impl Time {
         self
     }
 
-    /// Applies an operation to both Timing Methods of the two times provided
-    /// and creates a new Time from the result.
+    /// Returns whether none of the timing methods store a time.
+    pub fn is_empty(&self) -> bool {
+        TimingMethod::all()
+            .iter()
+            .all(|&method| self[method].is_none())
+    }
+
+    /// Applies an operation to all the Timing Methods of the two times
+    /// provided and creates a new Time from the result.
     pub fn op<F>(a: Time, b: Time, mut f: F) -> Time
     where
         F: FnMut(TimeSpan, TimeSpan) -> TimeSpan,
     {
-        Time {
-            real_time: catch! { f(a.real_time?, b.real_time?) },
-            game_time: catch! { f(a.game_time?, b.game_time?) },
+        let mut time = Time::new();
+        for &method in &TimingMethod::all() {
+            time[method] = catch! { f(a[method]?, b[method]?) };
         }
+        time
     }
 }
 
@@ -121,6 +131,7 @@ impl Index<TimingMethod> for Time {
         match timing_method {
             TimingMethod::RealTime => &self.real_time,
             TimingMethod::GameTime => &self.game_time,
+            method => &self.custom[method.custom_index().unwrap()],
         }
     }
 }
@@ -130,6 +141,7 @@ impl IndexMut<TimingMethod> for Time {
         match timing_method {
             TimingMethod::RealTime => &mut self.real_time,
             TimingMethod::GameTime => &mut self.game_time,
+            method => &mut self.custom[method.custom_index().unwrap()],
         }
     }
 }
@@ -1,6 +1,6 @@
 use crate::platform::Instant;
 use crate::TimeSpan;
-use core::ops::Sub;
+use core::ops::{Add, Sub};
 
 /// A Time Stamp stores a point in time, that can be used to calculate Time
 /// Spans.
@@ -29,3 +29,11 @@ impl Sub<TimeSpan> for TimeStamp {
         TimeStamp(self.0, self.1 - rhs)
     }
 }
+
+impl Add<TimeSpan> for TimeStamp {
+    type Output = TimeStamp;
+
+    fn add(self, rhs: TimeSpan) -> TimeStamp {
+        TimeStamp(self.0, self.1 + rhs)
+    }
+}
@@ -0,0 +1,107 @@
+use crate::{platform::prelude::*, Time, TimeSpan, TimingMethod};
+use core::fmt;
+
+/// An event that is emitted by the Timer whenever something happened to it.
+/// You can subscribe to these events with `Timer::subscribe` in order to react
+/// to them right away instead of polling the Timer.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Event {
+    /// A new attempt was started.
+    Started,
+    /// A split time was stored for a segment. If this was the last segment,
+    /// the attempt is finished now.
+    Split {
+        /// The index of the segment that was split.
+        segment_index: usize,
+        /// The split time that was stored for the segment.
+        split_time: Time,
+        /// The difference between the split time and the current comparison's
+        /// time for the segment, based on the current timing method. This is
+        /// `None` if either of them is empty.
+        delta: Option<TimeSpan>,
+        /// Whether the segment is a new best segment for the current timing
+        /// method.
+        is_best_segment: bool,
+    },
+    /// A segment was skipped.
+    SplitSkipped {
+        /// The index of the segment that was skipped.
+        segment_index: usize,
+    },
+    /// The split time of a segment was removed again.
+    SplitUndone {
+        /// The index of the segment whose split time was removed.
+        segment_index: usize,
+    },
+    /// The current attempt was reset.
+    Reset {
+        /// Whether the attempt's information was stored in the Run's history.
+        saved: bool,
+    },
+    /// The current attempt was paused.
+    Paused,
+    /// The current attempt was resumed.
+    Resumed,
+    /// All the pause times were removed from the current attempt.
+    PausesUndone,
+    /// The current comparison was changed.
+    ComparisonChanged {
+        /// The name of the new comparison.
+        comparison: String,
+    },
+    /// The current timing method was changed.
+    TimingMethodChanged {
+        /// The new timing method.
+        method: TimingMethod,
+    },
+    /// Game Time was initialized for the current attempt.
+    GameTimeInitialized,
+}
+
+/// Identifies a subscription to the Timer's events. It can be used to
+/// unsubscribe again. The IDs are unique for the Timer that handed them out.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct SubscriptionId(pub usize);
+
+type Listener = Box<dyn FnMut(&Event) + Send + Sync>;
+
+/// The listeners subscribed to a Timer. Listeners are not shared with clones
+/// of the Timer, as those are independent Timers.
+#[derive(Default)]
+pub(super) struct Listeners {
+    next_id: usize,
+    listeners: Vec<(SubscriptionId, Listener)>,
+}
+
+impl Listeners {
+    pub fn subscribe(&mut self, listener: Listener) -> SubscriptionId {
+        let id = SubscriptionId(self.next_id);
+        self.next_id += 1;
+        self.listeners.push((id, listener));
+        id
+    }
+
+    pub fn unsubscribe(&mut self, id: SubscriptionId) -> bool {
+        let len = self.listeners.len();
+        self.listeners.retain(|(i, _)| *i != id);
+        self.listeners.len() != len
+    }
+
+    pub fn emit(&mut self, event: &Event) {
+        for (_, listener) in &mut self.listeners {
+            listener(event);
+        }
+    }
+}
+
+impl Clone for Listeners {
+    fn clone(&self) -> Self {
+        Self::default()
+    }
+}
+
+impl fmt::Debug for Listeners {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} listeners", self.listeners.len())
+    }
+}
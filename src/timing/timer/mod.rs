@@ -1,12 +1,19 @@
+use super::{Clock, SystemClock};
 use crate::{
-    comparison::personal_best, platform::prelude::*, AtomicDateTime, Run, Segment, Time, TimeSpan,
-    TimeStamp, TimerPhase, TimerPhase::*, TimingMethod,
+    analysis::state_helper, comparison::personal_best, platform::prelude::*, run::ComparisonResult,
+    AtomicDateTime, Run, Segment, Time, TimeSpan, TimeStamp, TimerPhase, TimerPhase::*,
+    TimingMethod,
 };
 use core::{mem, ops::Deref};
 
+mod event;
+
 #[cfg(test)]
 mod tests;
 
+use self::event::Listeners;
+pub use self::event::{Event, SubscriptionId};
+
 /// A Timer provides all the capabilities necessary for doing speedrun attempts.
 ///
 /// # Examples
@@ -56,6 +63,10 @@ pub struct Timer {
     is_game_time_paused: bool,
     game_time_pause_time: Option<TimeSpan>,
     loading_times: Option<TimeSpan>,
+    custom_times: [Option<TimeSpan>; TimingMethod::CUSTOM_COUNT],
+    run_revision: u64,
+    listeners: Listeners,
+    clock: Box<dyn Clock>,
 }
 
 /// A snapshot represents a specific point in time that the timer was observed
@@ -99,14 +110,23 @@ impl Timer {
     /// that the Timer can store the final time. If a Run object with no
     /// segments is provided, the Timer creation fails.
     #[inline]
-    pub fn new(mut run: Run) -> Result<Self, CreationError> {
+    pub fn new(run: Run) -> Result<Self, CreationError> {
+        Self::with_clock(run, SystemClock)
+    }
+
+    /// Creates a new Timer based on a Run object that measures its times with
+    /// the clock provided instead of the system clock. This allows driving the
+    /// Timer deterministically, such as when replaying an attempt. The Run
+    /// object needs to have at least one segment, otherwise the Timer creation
+    /// fails.
+    pub fn with_clock(mut run: Run, clock: impl Clock) -> Result<Self, CreationError> {
         if run.is_empty() {
             return Err(CreationError::EmptyRun);
         }
 
         run.fix_splits();
         run.regenerate_comparisons();
-        let now = TimeStamp::now();
+        let now = clock.now();
 
         Ok(Timer {
             run,
@@ -123,6 +143,10 @@ impl Timer {
             is_game_time_paused: false,
             game_time_pause_time: None,
             loading_times: None,
+            custom_times: Default::default(),
+            run_revision: 0,
+            listeners: Listeners::default(),
+            clock: Box::new(clock),
         })
     }
 
@@ -156,10 +180,14 @@ impl Timer {
         self.reset(update_splits);
         if !run.comparisons().any(|c| c == self.current_comparison) {
             self.current_comparison = personal_best::NAME.to_string();
+            self.emit(Event::ComparisonChanged {
+                comparison: self.current_comparison.clone(),
+            });
         }
 
         run.fix_splits();
         run.regenerate_comparisons();
+        self.run_revision += 1;
 
         Ok(mem::replace(&mut self.run, run))
     }
@@ -178,6 +206,28 @@ impl Timer {
         &self.run
     }
 
+    /// Returns the revision of the Run's history. It changes whenever an
+    /// attempt is reset or the Run gets replaced, so anything that is
+    /// calculated from the history of the Run can be cached until it changes.
+    #[inline]
+    pub const fn run_revision(&self) -> u64 {
+        self.run_revision
+    }
+
+    /// Accesses the clock the Timer measures its times with.
+    #[inline]
+    pub fn clock(&self) -> &dyn Clock {
+        &*self.clock
+    }
+
+    /// Grants mutable access to the clock the Timer measures its times with,
+    /// if it is of the type of clock provided. This allows controlling clocks
+    /// such as the [`ManualClock`](crate::timing::ManualClock).
+    #[inline]
+    pub fn clock_mut<C: Clock>(&mut self) -> Option<&mut C> {
+        self.clock.downcast_mut()
+    }
+
     /// Marks the Run as unmodified, so that it is known that all the changes
     /// have been saved.
     #[inline]
@@ -194,7 +244,7 @@ impl Timer {
     fn current_time(&self) -> Time {
         let real_time = match self.phase {
             NotRunning => Some(self.run.offset()),
-            Running => Some(TimeStamp::now() - self.adjusted_start_time),
+            Running => Some(self.clock.now() - self.adjusted_start_time),
             Paused => Some(self.time_paused_at),
             Ended => self.run.segments().last().unwrap().split_time().real_time,
         };
@@ -213,9 +263,19 @@ impl Timer {
             }
         };
 
-        Time::new()
+        let mut time = Time::new()
             .with_real_time(real_time)
-            .with_game_time(game_time)
+            .with_game_time(game_time);
+
+        for (index, &method) in TimingMethod::custom_methods().iter().enumerate() {
+            time[method] = match self.phase {
+                NotRunning => Some(self.run.offset()),
+                Ended => self.run.segments().last().unwrap().split_time()[method],
+                _ => self.custom_times[index],
+            };
+        }
+
+        time
     }
 
     /// Creates a new snapshot of the timer at the point in time of this call.
@@ -238,16 +298,24 @@ impl Timer {
     /// Sets the current Timing Method to the Timing Method provided.
     #[inline]
     pub fn set_current_timing_method(&mut self, method: TimingMethod) {
-        self.current_timing_method = method;
+        if self.current_timing_method != method {
+            self.current_timing_method = method;
+            self.emit(Event::TimingMethodChanged { method });
+        }
     }
 
-    /// Toggles between the `Real Time` and `Game Time` timing methods.
+    /// Toggles between the `Real Time` and `Game Time` timing methods. If the
+    /// Run uses any custom timing methods, they are cycled through as well.
     #[inline]
     pub fn toggle_timing_method(&mut self) {
-        self.current_timing_method = match self.current_timing_method {
-            TimingMethod::RealTime => TimingMethod::GameTime,
-            TimingMethod::GameTime => TimingMethod::RealTime,
-        };
+        let current = self.current_timing_method;
+        let next = self
+            .run
+            .timing_methods()
+            .skip_while(|&method| method != current)
+            .nth(1)
+            .unwrap_or(TimingMethod::RealTime);
+        self.set_current_timing_method(next);
     }
 
     /// Returns the current comparison that is being compared against. This may
@@ -263,14 +331,48 @@ impl Timer {
     pub fn set_current_comparison<S: AsRef<str>>(&mut self, comparison: S) -> Result<(), ()> {
         let comparison = comparison.as_ref();
         if self.run.comparisons().any(|c| c == comparison) {
-            self.current_comparison.clear();
-            self.current_comparison.push_str(comparison);
+            if self.current_comparison != comparison {
+                self.current_comparison.clear();
+                self.current_comparison.push_str(comparison);
+                self.emit_comparison_changed();
+            }
             Ok(())
         } else {
             Err(())
         }
     }
 
+    /// Adds a race comparison against the runner provided, which compares
+    /// against the Personal Best of the run provided. The comparison is called
+    /// `[Race] <runner>` and its segments are mapped onto the segments of the
+    /// Timer's Run by their names. The comparison can't be added if one
+    /// against the same runner already exists.
+    pub fn add_race_comparison(&mut self, runner: &str, run: &Run) -> ComparisonResult<()> {
+        self.run.add_race_comparison(runner, run)
+    }
+
+    /// Updates the race comparison against the runner provided with the
+    /// Personal Best of the run provided. This can be done at any point, even
+    /// in the middle of an attempt. Returns `false` if there is no race
+    /// comparison against the runner.
+    pub fn update_race_comparison(&mut self, runner: &str, run: &Run) -> bool {
+        self.run.update_race_comparison(runner, run)
+    }
+
+    /// Removes the race comparison against the runner provided. If it is the
+    /// current comparison, the Timer switches to the Personal Best. Returns
+    /// `false` if there is no race comparison against the runner.
+    pub fn remove_race_comparison(&mut self, runner: &str) -> bool {
+        if !self.run.remove_race_comparison(runner) {
+            return false;
+        }
+        if !self.run.comparisons().any(|c| c == self.current_comparison) {
+            self.current_comparison = personal_best::NAME.to_string();
+            self.emit_comparison_changed();
+        }
+        true
+    }
+
     /// Accesses the split the attempt is currently on. If there's no attempt in
     /// progress or the run finished, `None` is returned instead.
     pub fn current_split(&self) -> Option<&Segment> {
@@ -299,22 +401,29 @@ impl Timer {
         if self.phase == NotRunning {
             self.phase = Running;
             self.current_split_index = Some(0);
-            self.attempt_started = Some(AtomicDateTime::now());
-            self.start_time = TimeStamp::now();
+            self.attempt_started = Some(self.clock.date_now());
+            self.start_time = self.clock.now();
             self.start_time_with_offset = self.start_time - self.run.offset();
             self.adjusted_start_time = self.start_time_with_offset;
             self.time_paused_at = self.run.offset();
             self.deinitialize_game_time();
+            self.custom_times = Default::default();
             self.run.start_next_run();
 
-            // FIXME: OnStart
+            self.emit(Event::Started);
         }
     }
 
     /// If an attempt is in progress, stores the current time as the time of the
-    /// current split. The attempt ends if the last split time is stored.
+    /// current split. The attempt ends if the last split time is stored. If the
+    /// run is timed in frames, the Game Time gets rounded to the closest frame.
     pub fn split(&mut self) {
-        let current_time = self.current_time();
+        let mut current_time = self.current_time();
+        // The frame rate is the one of the game, so only the Game Time is
+        // measured in frames.
+        if let Some(frame_rate) = self.run.metadata().frame_rate() {
+            current_time.game_time = current_time.game_time.map(|t| frame_rate.snap(t));
+        }
         if self.phase == Running
             && current_time
                 .real_time
@@ -323,14 +432,25 @@ impl Timer {
             self.current_split_mut()
                 .unwrap()
                 .set_split_time(current_time);
+            let segment_index = self.current_split_index.unwrap();
             *self.current_split_index.as_mut().unwrap() += 1;
             if Some(self.run.len()) == self.current_split_index {
                 self.phase = Ended;
-                self.attempt_ended = Some(AtomicDateTime::now());
+                self.attempt_ended = Some(self.clock.date_now());
             }
             self.run.mark_as_modified();
 
-            // FIXME: OnSplit
+            let method = self.current_timing_method;
+            let comparison_time = self
+                .run
+                .segment(segment_index)
+                .comparison(&self.current_comparison)[method];
+            self.emit(Event::Split {
+                segment_index,
+                split_time: current_time,
+                delta: catch! { current_time[method]? - comparison_time? },
+                is_best_segment: state_helper::check_best_segment(self, segment_index, method),
+            });
         }
     }
 
@@ -350,11 +470,12 @@ impl Timer {
         if (self.phase == Running || self.phase == Paused)
             && self.current_split_index < self.run.len().checked_sub(1)
         {
+            let segment_index = self.current_split_index.unwrap();
             self.current_split_mut().unwrap().clear_split_time();
-            self.current_split_index = self.current_split_index.map(|i| i + 1);
+            self.current_split_index = Some(segment_index + 1);
             self.run.mark_as_modified();
 
-            // FIXME: OnSkipSplit
+            self.emit(Event::SplitSkipped { segment_index });
         }
     }
 
@@ -366,11 +487,12 @@ impl Timer {
             if self.phase == Ended {
                 self.phase = Running;
             }
-            self.current_split_index = self.current_split_index.map(|i| i - 1);
+            let segment_index = self.current_split_index.unwrap() - 1;
+            self.current_split_index = Some(segment_index);
             self.current_split_mut().unwrap().clear_split_time();
             self.run.mark_as_modified();
 
-            // FIXME: OnUndoSplit
+            self.emit(Event::SplitUndone { segment_index });
         }
     }
 
@@ -381,7 +503,7 @@ impl Timer {
     pub fn reset(&mut self, update_splits: bool) {
         if self.phase != NotRunning {
             self.reset_state(update_splits);
-            self.reset_splits();
+            self.reset_splits(update_splits);
         }
     }
 
@@ -392,13 +514,13 @@ impl Timer {
         if self.phase != NotRunning {
             self.reset_state(true);
             self.set_run_as_pb();
-            self.reset_splits();
+            self.reset_splits(true);
         }
     }
 
     fn reset_state(&mut self, update_times: bool) {
         if self.phase != Ended {
-            self.attempt_ended = Some(AtomicDateTime::now());
+            self.attempt_ended = Some(self.clock.date_now());
         }
         self.resume_game_time();
         self.set_loading_times(TimeSpan::zero());
@@ -411,7 +533,7 @@ impl Timer {
         }
     }
 
-    fn reset_splits(&mut self) {
+    fn reset_splits(&mut self, saved: bool) {
         self.phase = NotRunning;
         self.current_split_index = None;
 
@@ -420,10 +542,11 @@ impl Timer {
             segment.clear_split_time();
         }
 
-        // FIXME: OnReset
-
         self.run.fix_splits();
         self.run.regenerate_comparisons();
+        self.run_revision += 1;
+
+        self.emit(Event::Reset { saved });
     }
 
     /// Pauses an active attempt that is not paused.
@@ -432,17 +555,17 @@ impl Timer {
             self.time_paused_at = self.current_time().real_time.unwrap();
             self.phase = Paused;
 
-            // FIXME: OnPause
+            self.emit(Event::Paused);
         }
     }
 
     /// Resumes an attempt that is paused.
     pub fn resume(&mut self) {
         if self.phase == Paused {
-            self.adjusted_start_time = TimeStamp::now() - self.time_paused_at;
+            self.adjusted_start_time = self.clock.now() - self.time_paused_at;
             self.phase = Running;
 
-            // FIXME: OnResume
+            self.emit(Event::Resumed);
         }
     }
 
@@ -499,7 +622,7 @@ impl Timer {
 
         self.adjusted_start_time = self.start_time_with_offset;
 
-        // FIXME: OnUndoAllPauses
+        self.emit(Event::PausesUndone);
     }
 
     /// Switches the current comparison to the next comparison in the list.
@@ -512,7 +635,7 @@ impl Timer {
         let index = (index + 1) % len;
         self.current_comparison = self.run.comparisons().nth(index).unwrap().to_owned();
 
-        // FIXME: OnNextComparison
+        self.emit_comparison_changed();
     }
 
     /// Switches the current comparison to the previous comparison in the list.
@@ -525,7 +648,7 @@ impl Timer {
         let index = (index + len - 1) % len;
         self.current_comparison = self.run.comparisons().nth(index).unwrap().to_owned();
 
-        // FIXME: OnPreviousComparison
+        self.emit_comparison_changed();
     }
 
     /// Returns the total duration of the current attempt. This is not affected
@@ -538,7 +661,7 @@ impl Timer {
     pub fn current_attempt_duration(&self) -> TimeSpan {
         match self.current_phase() {
             NotRunning => TimeSpan::zero(),
-            Paused | Running => TimeStamp::now() - self.start_time,
+            Paused | Running => self.clock.now() - self.start_time,
             Ended => self.attempt_ended.unwrap() - self.attempt_started.unwrap(),
         }
     }
@@ -547,7 +670,7 @@ impl Timer {
     /// for. None is returned if there have not been any pauses.
     pub fn get_pause_time(&self) -> Option<TimeSpan> {
         match self.current_phase() {
-            Paused => Some(TimeStamp::now() - self.start_time_with_offset - self.time_paused_at),
+            Paused => Some(self.clock.now() - self.start_time_with_offset - self.time_paused_at),
             Running | Ended if self.start_time_with_offset != self.adjusted_start_time => {
                 Some(self.adjusted_start_time - self.start_time_with_offset)
             }
@@ -566,7 +689,10 @@ impl Timer {
     /// gets uninitialized for each new attempt.
     #[inline]
     pub fn initialize_game_time(&mut self) {
-        self.loading_times = Some(self.loading_times());
+        if !self.is_game_time_initialized() {
+            self.loading_times = Some(self.loading_times());
+            self.emit(Event::GameTimeInitialized);
+        }
     }
 
     /// Deinitializes Game Time for the current attempt.
@@ -633,6 +759,17 @@ impl Timer {
         }
     }
 
+    /// Sets the time of the custom timing method provided. Custom timing
+    /// methods don't move forward on their own, so their time needs to be
+    /// updated periodically, for example by an auto splitter. A custom timing
+    /// method doesn't have a time until it is set for the first time in an
+    /// attempt. Nothing happens for Real Time and Game Time.
+    pub const fn set_custom_time(&mut self, method: TimingMethod, time: Option<TimeSpan>) {
+        if let Some(index) = method.custom_index() {
+            self.custom_times[index] = time;
+        }
+    }
+
     /// Sets the value of a custom variable with the name specified. If the
     /// variable does not exist, a temporary variable gets created that will not
     /// be stored in the splits file.
@@ -648,6 +785,35 @@ impl Timer {
         }
     }
 
+    /// Subscribes to the events emitted by the Timer. The listener provided is
+    /// called for every event right after it happened. The listener is called
+    /// while the Timer is being modified, so if the Timer is shared, the
+    /// listener must not try to lock it, as that would deadlock. Listeners are
+    /// not carried over to clones of the Timer.
+    pub fn subscribe<F>(&mut self, listener: F) -> SubscriptionId
+    where
+        F: FnMut(&Event) + Send + Sync + 'static,
+    {
+        self.listeners.subscribe(Box::new(listener))
+    }
+
+    /// Unsubscribes the listener with the subscription ID provided from the
+    /// events emitted by the Timer. Returns whether there was such a
+    /// listener.
+    pub fn unsubscribe(&mut self, id: SubscriptionId) -> bool {
+        self.listeners.unsubscribe(id)
+    }
+
+    fn emit(&mut self, event: Event) {
+        self.listeners.emit(&event);
+    }
+
+    fn emit_comparison_changed(&mut self) {
+        self.emit(Event::ComparisonChanged {
+            comparison: self.current_comparison.clone(),
+        });
+    }
+
     fn update_attempt_history(&mut self) {
         let time = if self.phase == Ended {
             self.current_time()
@@ -662,32 +828,20 @@ impl Timer {
     }
 
     fn update_best_segments(&mut self) {
-        let mut previous_split_time_rta = Some(TimeSpan::zero());
-        let mut previous_split_time_game_time = Some(TimeSpan::zero());
+        let mut previous_split_times = Time::zero();
 
         for split in self.run.segments_mut() {
             let mut new_best_segment = split.best_segment_time();
-            if let Some(split_time) = split.split_time().real_time {
-                let current_segment = previous_split_time_rta.map(|previous| split_time - previous);
-                previous_split_time_rta = Some(split_time);
-                if split
-                    .best_segment_time()
-                    .real_time
-                    .map_or(true, |b| current_segment.map_or(false, |c| c < b))
-                {
-                    new_best_segment.real_time = current_segment;
-                }
-            }
-            if let Some(split_time) = split.split_time().game_time {
-                let current_segment =
-                    previous_split_time_game_time.map(|previous| split_time - previous);
-                previous_split_time_game_time = Some(split_time);
-                if split
-                    .best_segment_time()
-                    .game_time
-                    .map_or(true, |b| current_segment.map_or(false, |c| c < b))
-                {
-                    new_best_segment.game_time = current_segment;
+            for &method in &TimingMethod::all() {
+                if let Some(split_time) = split.split_time()[method] {
+                    let current_segment =
+                        previous_split_times[method].map(|previous| split_time - previous);
+                    previous_split_times[method] = Some(split_time);
+                    if split.best_segment_time()[method]
+                        .map_or(true, |b| current_segment.map_or(false, |c| c < b))
+                    {
+                        new_best_segment[method] = current_segment;
+                    }
                 }
             }
             split.set_best_segment_time(new_best_segment);
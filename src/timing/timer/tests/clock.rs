@@ -0,0 +1,191 @@
+use super::run;
+use crate::{
+    platform::{DateTime, Utc},
+    tests_helper::span,
+    timing::{FrameRate, ManualClock, OffsetClock, SkewedClock},
+    AtomicDateTime, Timer, TimerPhase, TimingMethod,
+};
+
+fn date() -> AtomicDateTime {
+    let time = DateTime::parse_from_rfc3339("2020-01-01T12:00:00Z")
+        .unwrap()
+        .with_timezone(&Utc);
+    AtomicDateTime::new(time, false)
+}
+
+fn timer() -> Timer {
+    Timer::with_clock(run(), ManualClock::with_date(date())).unwrap()
+}
+
+fn clock(timer: &mut Timer) -> &mut ManualClock {
+    timer.clock_mut().unwrap()
+}
+
+#[test]
+fn split_times_match_the_clock() {
+    let mut timer = timer();
+
+    timer.start();
+    clock(&mut timer).set_elapsed(span(1.5));
+    timer.split();
+    clock(&mut timer).advance(span(2.25));
+    timer.split();
+    clock(&mut timer).advance(span(0.001));
+    timer.split();
+
+    assert_eq!(timer.current_phase(), TimerPhase::Ended);
+    let split_times = timer
+        .run()
+        .segments()
+        .iter()
+        .map(|s| s.split_time().real_time)
+        .collect::<Vec<_>>();
+    assert_eq!(
+        split_times,
+        [Some(span(1.5)), Some(span(3.75)), Some(span(3.751))],
+    );
+}
+
+#[test]
+fn game_time_split_times_are_rounded_to_frames() {
+    let frame_rate = FrameRate::new(60.0).unwrap();
+    let mut run = run();
+    run.metadata_mut().set_frame_rate(Some(frame_rate));
+    let mut timer = Timer::with_clock(run, ManualClock::with_date(date())).unwrap();
+
+    timer.start();
+    timer.initialize_game_time();
+    timer.pause_game_time();
+    clock(&mut timer).set_elapsed(span(1.01));
+    timer.set_game_time(span(1.01));
+    timer.split();
+
+    let split_time = timer.run().segment(0).split_time();
+    assert_eq!(split_time.game_time, Some(frame_rate.time_span(61)));
+    assert_eq!(split_time.real_time, Some(span(1.01)));
+}
+
+#[test]
+fn current_time_only_moves_with_the_clock() {
+    let mut timer = timer();
+
+    timer.start();
+    assert_eq!(timer.snapshot().current_time().real_time, Some(span(0.0)));
+
+    clock(&mut timer).advance(span(10.0));
+    let time = timer.snapshot().current_time().real_time;
+    assert_eq!(time, Some(span(10.0)));
+    assert_eq!(timer.snapshot().current_time().real_time, time);
+}
+
+#[test]
+fn pauses_are_exact() {
+    let mut timer = timer();
+
+    timer.start();
+    clock(&mut timer).set_elapsed(span(2.0));
+    timer.pause();
+    clock(&mut timer).set_elapsed(span(7.0));
+    assert_eq!(timer.snapshot().current_time().real_time, Some(span(2.0)));
+    timer.resume();
+    clock(&mut timer).set_elapsed(span(8.0));
+    timer.split();
+
+    assert_eq!(timer.get_pause_time(), Some(span(5.0)));
+    assert_eq!(
+        timer.run().segment(0).split_time().real_time,
+        Some(span(3.0)),
+    );
+}
+
+#[test]
+fn attempt_dates_come_from_the_clock() {
+    let mut timer = timer();
+
+    clock(&mut timer).set_elapsed(span(60.0));
+    timer.start();
+    clock(&mut timer).advance(span(30.0));
+    timer.split();
+    timer.split();
+    timer.split();
+    assert_eq!(timer.current_attempt_duration(), span(30.0));
+    timer.reset(true);
+
+    let attempt = &timer.run().attempt_history()[0];
+    assert_eq!(attempt.started(), Some(date() + span(60.0)));
+    assert_eq!(attempt.ended(), Some(date() + span(90.0)));
+    assert_eq!(attempt.duration(), Some(span(30.0)));
+}
+
+#[test]
+fn offset_clock() {
+    let clock = OffsetClock::new(ManualClock::with_date(date()), span(5.0));
+    let mut timer = Timer::with_clock(run(), clock).unwrap();
+
+    timer.start();
+    let offset = timer.clock_mut::<OffsetClock<ManualClock>>().unwrap();
+    offset.clock_mut().advance(span(1.0));
+    // Jumping the offset forward moves the timer along with it.
+    offset.set_offset(span(7.0));
+    timer.split();
+    assert_eq!(
+        timer.run().segment(0).split_time().real_time,
+        Some(span(3.0)),
+    );
+    let offset = timer.clock().downcast_ref::<OffsetClock<ManualClock>>();
+    assert_eq!(offset.unwrap().offset(), span(7.0));
+    assert_eq!(timer.clock().date_now(), date() + span(8.0));
+}
+
+#[test]
+fn skewed_clock() {
+    let clock = SkewedClock::new(ManualClock::with_date(date()), 1.5);
+    let mut timer = Timer::with_clock(run(), clock).unwrap();
+    let advance = |timer: &mut Timer, time| {
+        timer
+            .clock_mut::<SkewedClock<ManualClock>>()
+            .unwrap()
+            .clock_mut()
+            .advance(span(time));
+    };
+
+    timer.start();
+    advance(&mut timer, 2.0);
+    timer.split();
+    advance(&mut timer, 4.0);
+    timer.split();
+
+    let run = timer.run();
+    assert_eq!(run.segment(0).split_time().real_time, Some(span(3.0)));
+    assert_eq!(run.segment(1).split_time().real_time, Some(span(9.0)));
+    assert_eq!(timer.clock().date_now(), date() + span(9.0));
+}
+
+#[test]
+fn clones_have_their_own_clock() {
+    let mut timer = timer();
+    timer.start();
+    let clone = timer.clone();
+    clock(&mut timer).advance(span(3.0));
+
+    assert_eq!(timer.snapshot().current_time().real_time, Some(span(3.0)));
+    assert_eq!(clone.snapshot().current_time().real_time, Some(span(0.0)));
+    assert!(timer.clock_mut::<SkewedClock<ManualClock>>().is_none());
+}
+
+#[test]
+fn game_time_is_unaffected() {
+    let mut timer = timer();
+    timer.set_current_timing_method(TimingMethod::GameTime);
+
+    timer.start();
+    timer.initialize_game_time();
+    timer.pause_game_time();
+    timer.set_game_time(span(1.0));
+    clock(&mut timer).advance(span(20.0));
+    timer.split();
+
+    let split_time = timer.run().segment(0).split_time();
+    assert_eq!(split_time.real_time, Some(span(20.0)));
+    assert_eq!(split_time.game_time, Some(span(1.0)));
+}
@@ -0,0 +1,130 @@
+use super::run;
+use crate::{
+    comparison::best_segments, tests_helper::span, TimeSpan, Timer, TimerPhase, TimingMethod,
+};
+
+const IGT: TimingMethod = TimingMethod::Custom1;
+
+fn timer() -> Timer {
+    let mut run = run();
+    run.set_custom_timing_method_name(IGT, "In-Game Time");
+    Timer::new(run).unwrap()
+}
+
+fn run_with_custom_splits(timer: &mut Timer, splits: &[f64]) {
+    timer.start();
+    for &split in splits {
+        timer.set_custom_time(IGT, Some(span(split)));
+        timer.split();
+    }
+    timer.reset(true);
+}
+
+#[test]
+fn records_custom_split_times() {
+    let mut timer = timer();
+
+    timer.start();
+    assert_eq!(timer.snapshot().current_time()[IGT], None);
+    timer.set_custom_time(IGT, Some(span(1.0)));
+    assert_eq!(timer.snapshot().current_time()[IGT], Some(span(1.0)));
+    timer.split();
+    timer.set_custom_time(IGT, Some(span(2.5)));
+    timer.split();
+    timer.set_custom_time(IGT, Some(span(4.0)));
+    timer.split();
+
+    assert_eq!(timer.current_phase(), TimerPhase::Ended);
+    assert_eq!(timer.snapshot().current_time()[IGT], Some(span(4.0)));
+    let split_times = timer
+        .run()
+        .segments()
+        .iter()
+        .map(|s| s.split_time()[IGT])
+        .collect::<Vec<_>>();
+    assert_eq!(
+        split_times,
+        [Some(span(1.0)), Some(span(2.5)), Some(span(4.0))]
+    );
+
+    // Only the custom timing method that is in use gets any times.
+    assert!(timer
+        .run()
+        .segments()
+        .iter()
+        .all(|s| s.split_time()[TimingMethod::Custom2].is_none()));
+}
+
+#[test]
+fn custom_times_are_cleared_for_each_attempt() {
+    let mut timer = timer();
+
+    timer.start();
+    timer.set_custom_time(IGT, Some(span(3.0)));
+    timer.reset(false);
+
+    assert_eq!(timer.snapshot().current_time()[IGT], Some(TimeSpan::zero()));
+    timer.start();
+    assert_eq!(timer.snapshot().current_time()[IGT], None);
+}
+
+#[test]
+fn updates_personal_best_and_best_segments() {
+    let mut timer = timer();
+    timer.set_current_timing_method(IGT);
+
+    run_with_custom_splits(&mut timer, &[2.0, 5.0, 9.0]);
+    run_with_custom_splits(&mut timer, &[1.0, 6.0, 8.0]);
+
+    let run = timer.run();
+    let pb = run
+        .segments()
+        .iter()
+        .map(|s| s.personal_best_split_time()[IGT])
+        .collect::<Vec<_>>();
+    assert_eq!(pb, [Some(span(1.0)), Some(span(6.0)), Some(span(8.0))]);
+
+    let best_segments = run
+        .segments()
+        .iter()
+        .map(|s| s.best_segment_time()[IGT])
+        .collect::<Vec<_>>();
+    assert_eq!(
+        best_segments,
+        [Some(span(1.0)), Some(span(3.0)), Some(span(2.0))]
+    );
+
+    let generated = run
+        .segments()
+        .iter()
+        .map(|s| s.comparison(best_segments::NAME)[IGT])
+        .collect::<Vec<_>>();
+    assert_eq!(
+        generated,
+        [Some(span(1.0)), Some(span(4.0)), Some(span(6.0))]
+    );
+
+    let history = run.segment(1).segment_history();
+    let times = history.iter().map(|&(_, t)| t[IGT]).collect::<Vec<_>>();
+    assert_eq!(times, [Some(span(3.0)), Some(span(5.0))]);
+}
+
+#[test]
+fn toggling_cycles_through_the_timing_methods_in_use() {
+    let mut timer = timer();
+    assert_eq!(timer.current_timing_method(), TimingMethod::RealTime);
+
+    timer.toggle_timing_method();
+    assert_eq!(timer.current_timing_method(), TimingMethod::GameTime);
+    timer.toggle_timing_method();
+    assert_eq!(timer.current_timing_method(), IGT);
+    timer.toggle_timing_method();
+    assert_eq!(timer.current_timing_method(), TimingMethod::RealTime);
+
+    // Without any custom timing methods, only Real Time and Game Time get
+    // toggled.
+    let mut timer = Timer::new(run()).unwrap();
+    timer.toggle_timing_method();
+    timer.toggle_timing_method();
+    assert_eq!(timer.current_timing_method(), TimingMethod::RealTime);
+}
@@ -0,0 +1,129 @@
+use crate::comparison::{best_segments, personal_best};
+use crate::tests_helper::{create_timer, run_with_splits, span, start_run};
+use crate::{Timer, TimerEvent as Event, TimingMethod};
+use std::sync::{Arc, Mutex};
+
+fn subscribe(timer: &mut Timer) -> Arc<Mutex<Vec<Event>>> {
+    let events = Arc::new(Mutex::new(Vec::new()));
+    let sink = events.clone();
+    timer.subscribe(move |event| sink.lock().unwrap().push(event.clone()));
+    events
+}
+
+fn take(events: &Mutex<Vec<Event>>) -> Vec<Event> {
+    events.lock().unwrap().drain(..).collect()
+}
+
+#[test]
+fn attempt_events() {
+    let mut timer = create_timer(&["A", "B", "C"]);
+    run_with_splits(&mut timer, &[10.0, 20.0, 30.0]);
+    let events = subscribe(&mut timer);
+
+    start_run(&mut timer);
+    assert_eq!(take(&events), [Event::Started, Event::GameTimeInitialized]);
+
+    timer.set_game_time(span(8.0));
+    timer.split();
+    let split = take(&events);
+    match &split[..] {
+        [Event::Split {
+            segment_index: 0,
+            split_time,
+            delta,
+            is_best_segment: true,
+        }] => {
+            assert_eq!(split_time.game_time, Some(span(8.0)));
+            assert_eq!(*delta, Some(span(-2.0)));
+        }
+        _ => panic!("Unexpected events: {:?}", split),
+    }
+
+    timer.skip_split();
+    timer.set_game_time(span(35.0));
+    timer.split();
+    timer.undo_split();
+    match &take(&events)[..] {
+        [Event::SplitSkipped { segment_index: 1 }, Event::Split {
+            segment_index: 2,
+            delta,
+            is_best_segment: false,
+            ..
+        }, Event::SplitUndone { segment_index: 2 }] => {
+            assert_eq!(*delta, Some(span(5.0)));
+        }
+        events => panic!("Unexpected events: {:?}", events),
+    }
+
+    timer.reset(false);
+    assert_eq!(take(&events), [Event::Reset { saved: false }]);
+}
+
+#[test]
+fn pause_events() {
+    let mut timer = create_timer(&["A"]);
+    let events = subscribe(&mut timer);
+
+    timer.pause();
+    timer.start();
+    timer.pause();
+    timer.pause();
+    timer.resume();
+    timer.undo_all_pauses();
+    timer.reset_and_set_attempt_as_pb();
+
+    assert_eq!(
+        take(&events),
+        [
+            Event::Started,
+            Event::Paused,
+            Event::Resumed,
+            Event::PausesUndone,
+            Event::Reset { saved: true },
+        ],
+    );
+}
+
+#[test]
+fn comparison_and_timing_method_events() {
+    let mut timer = create_timer(&["A"]);
+    let events = subscribe(&mut timer);
+
+    timer.set_current_timing_method(TimingMethod::RealTime);
+    timer.toggle_timing_method();
+    timer.set_current_comparison(personal_best::NAME).unwrap();
+    timer.set_current_comparison(best_segments::NAME).unwrap();
+    timer.set_current_comparison("Missing").unwrap_err();
+    timer.switch_to_previous_comparison();
+
+    assert_eq!(
+        take(&events),
+        [
+            Event::TimingMethodChanged {
+                method: TimingMethod::GameTime,
+            },
+            Event::ComparisonChanged {
+                comparison: best_segments::NAME.to_string(),
+            },
+            Event::ComparisonChanged {
+                comparison: personal_best::NAME.to_string(),
+            },
+        ],
+    );
+}
+
+#[test]
+fn unsubscribe() {
+    let mut timer = create_timer(&["A"]);
+    let events = subscribe(&mut timer);
+    let other = timer.subscribe(|_| {});
+
+    assert!(timer.unsubscribe(other));
+    assert!(!timer.unsubscribe(other));
+    timer.start();
+    assert_eq!(take(&events), [Event::Started]);
+
+    let mut clone = timer.clone();
+    clone.reset(true);
+    assert!(take(&events).is_empty());
+}
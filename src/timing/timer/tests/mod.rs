@@ -4,6 +4,9 @@ use crate::{
     Run, Segment, TimeSpan, Timer, TimerPhase, TimingMethod,
 };
 
+mod clock;
+mod custom_timing_methods;
+mod events;
 mod mark_as_modified;
 mod variables;
 
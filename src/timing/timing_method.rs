@@ -1,7 +1,10 @@
 use serde::{Deserialize, Serialize};
 
 /// A Timing Method describes which form of timing is used. This can either be
-/// Real Time or Game Time.
+/// Real Time, Game Time or one of the custom timing methods. A Run can give
+/// the custom timing methods names, so they can be used for additional forms
+/// of timing, like an in-game time that needs to be kept apart from a load
+/// removed Real Time.
 #[derive(Copy, Clone, Debug, Eq, PartialEq, Deserialize, Serialize)]
 #[repr(u8)]
 pub enum TimingMethod {
@@ -12,11 +15,74 @@ pub enum TimingMethod {
     /// being run. This is entirely optional and may either be Real Time with
     /// loading times removed or some time provided by the game.
     GameTime = 1,
+    /// The first custom timing method.
+    Custom1 = 2,
+    /// The second custom timing method.
+    Custom2 = 3,
+    /// The third custom timing method.
+    Custom3 = 4,
+    /// The fourth custom timing method.
+    Custom4 = 5,
 }
 
 impl TimingMethod {
+    /// The amount of custom timing methods that are available.
+    pub const CUSTOM_COUNT: usize = 4;
+
     /// Returns an array of all the timing methods.
-    pub const fn all() -> [TimingMethod; 2] {
-        [TimingMethod::RealTime, TimingMethod::GameTime]
+    pub const fn all() -> [TimingMethod; 2 + Self::CUSTOM_COUNT] {
+        [
+            TimingMethod::RealTime,
+            TimingMethod::GameTime,
+            TimingMethod::Custom1,
+            TimingMethod::Custom2,
+            TimingMethod::Custom3,
+            TimingMethod::Custom4,
+        ]
+    }
+
+    /// Returns an array of all the custom timing methods.
+    pub const fn custom_methods() -> [TimingMethod; Self::CUSTOM_COUNT] {
+        [
+            TimingMethod::Custom1,
+            TimingMethod::Custom2,
+            TimingMethod::Custom3,
+            TimingMethod::Custom4,
+        ]
+    }
+
+    /// Returns the custom timing method with the index provided. The index
+    /// starts at 0 for the first custom timing method. `None` is returned if
+    /// there is no such custom timing method.
+    pub fn custom(index: usize) -> Option<TimingMethod> {
+        Self::custom_methods().get(index).copied()
+    }
+
+    /// Returns the index of the custom timing method, starting at 0 for the
+    /// first custom timing method. `None` is returned for Real Time and Game
+    /// Time.
+    pub const fn custom_index(self) -> Option<usize> {
+        match self {
+            TimingMethod::RealTime | TimingMethod::GameTime => None,
+            _ => Some(self as usize - 2),
+        }
+    }
+
+    /// Returns whether this is one of the custom timing methods.
+    pub const fn is_custom(self) -> bool {
+        self.custom_index().is_some()
+    }
+
+    /// Returns the name of the timing method that is used if the Run doesn't
+    /// provide its own name for it.
+    pub const fn default_name(self) -> &'static str {
+        match self {
+            TimingMethod::RealTime => "Real Time",
+            TimingMethod::GameTime => "Game Time",
+            TimingMethod::Custom1 => "Custom Timing Method 1",
+            TimingMethod::Custom2 => "Custom Timing Method 2",
+            TimingMethod::Custom3 => "Custom Timing Method 3",
+            TimingMethod::Custom4 => "Custom Timing Method 4",
+        }
     }
 }
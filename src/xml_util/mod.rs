@@ -12,6 +12,8 @@ use quick_xml::{
 };
 use std::io::{self, BufRead};
 
+pub mod writer;
+
 /// The Error type for XML-based splits files that couldn't be parsed.
 #[derive(Debug, snafu::Snafu)]
 pub enum Error {
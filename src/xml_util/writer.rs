@@ -0,0 +1,107 @@
+use core::{fmt::Display, mem};
+use quick_xml::{
+    events::{BytesEnd, BytesStart, BytesText, Event},
+    Result, Writer,
+};
+use std::io::Write;
+
+pub fn new_tag(name: &[u8]) -> BytesStart<'_> {
+    BytesStart::borrowed(name, name.len())
+}
+
+pub fn write_start<W: Write>(writer: &mut Writer<W>, tag: BytesStart<'_>) -> Result<()> {
+    writer.write_event(Event::Start(tag))?;
+    Ok(())
+}
+
+pub fn write_end<W: Write>(writer: &mut Writer<W>, tag: &[u8]) -> Result<()> {
+    writer.write_event(Event::End(BytesEnd::borrowed(tag)))?;
+    Ok(())
+}
+
+fn split_tag<'a>(tag: &'a BytesStart<'a>) -> (BytesStart<'a>, BytesEnd<'a>) {
+    (
+        BytesStart::borrowed(&tag, tag.name().len()),
+        BytesEnd::borrowed(tag.name()),
+    )
+}
+
+pub fn scoped<W, F>(
+    writer: &mut Writer<W>,
+    tag: BytesStart<'_>,
+    is_empty: bool,
+    scope: F,
+) -> Result<()>
+where
+    W: Write,
+    F: FnOnce(&mut Writer<W>) -> Result<()>,
+{
+    if is_empty {
+        writer.write_event(Event::Empty(tag))?;
+    } else {
+        let (start, end) = split_tag(&tag);
+        writer.write_event(Event::Start(start))?;
+        scope(writer)?;
+        writer.write_event(Event::End(end))?;
+    }
+    Ok(())
+}
+
+pub fn scoped_iter<W, F, I>(
+    writer: &mut Writer<W>,
+    tag: BytesStart<'_>,
+    iter: I,
+    mut scope: F,
+) -> Result<()>
+where
+    W: Write,
+    I: IntoIterator,
+    F: FnMut(&mut Writer<W>, <I as IntoIterator>::Item) -> Result<()>,
+{
+    let mut iter = iter.into_iter().peekable();
+    scoped(writer, tag, iter.peek().is_none(), |writer| {
+        for item in iter {
+            scope(writer, item)?;
+        }
+        Ok(())
+    })
+}
+
+pub fn text<W: Write, T: AsRef<[u8]>>(
+    writer: &mut Writer<W>,
+    tag: BytesStart<'_>,
+    text: T,
+) -> Result<()> {
+    let text = text.as_ref();
+    scoped(writer, tag, text.is_empty(), |writer| {
+        writer.write_event(Event::Text(BytesText::from_plain(text)))?;
+        Ok(())
+    })
+}
+
+pub fn fmt_buf<D: Display>(value: D, buf: &mut Vec<u8>) -> &[u8] {
+    buf.clear();
+    write!(buf, "{}", value).unwrap();
+    buf
+}
+
+pub fn write_display<W: Write, D: Display>(
+    writer: &mut Writer<W>,
+    tag: BytesStart<'_>,
+    value: D,
+    buf: &mut Vec<u8>,
+) -> Result<()> {
+    text(writer, tag, fmt_buf(value, buf))
+}
+
+/// Temporarily turns the buffer into a String, so it can be written to by APIs
+/// that only work with Strings. The buffer needs to contain valid UTF-8.
+pub fn vec_as_string<F, R>(vec: &mut Vec<u8>, f: F) -> R
+where
+    F: FnOnce(&mut String) -> R,
+{
+    let mut string = String::from_utf8(mem::take(vec)).unwrap();
+    let result = f(&mut string);
+    *vec = string.into_bytes();
+    result
+}
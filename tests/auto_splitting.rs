@@ -0,0 +1,159 @@
+#![cfg(all(feature = "auto-splitting", target_os = "linux"))]
+
+#[path = "../src/tests_helper.rs"]
+mod tests_helper;
+
+use livesplit_core::{
+    auto_splitting::{CreationError, Error, Runtime},
+    Run, Segment, TimeSpan, Timer, TimerPhase, TimingMethod,
+};
+use std::{
+    env, fs,
+    io::{self, BufRead, BufReader, Write},
+    process::{self, Command, Stdio},
+    sync::atomic::{AtomicU32, Ordering},
+    thread,
+    time::{Duration, Instant},
+};
+use tests_helper::create_timer;
+
+const HELPER_ENV: &str = "LIVESPLIT_AUTO_SPLITTING_HELPER";
+
+static VALUE: AtomicU32 = AtomicU32::new(0);
+
+/// The process whose memory the auto splitter reads. It prints the address of
+/// the value and then stores every number it receives through stdin in it.
+#[test]
+#[ignore]
+fn helper_process() {
+    if env::var_os(HELPER_ENV).is_none() {
+        return;
+    }
+    println!("address 0x{:x}", &VALUE as *const AtomicU32 as usize);
+    for line in io::stdin().lock().lines() {
+        VALUE.store(line.unwrap().parse().unwrap(), Ordering::SeqCst);
+    }
+}
+
+fn script(process_name: &str, address: &str) -> Vec<u8> {
+    format!(
+        r#"(module
+            (import "env" "timer_get_state" (func $get_state (result i32)))
+            (import "env" "timer_start" (func $start))
+            (import "env" "timer_split" (func $split))
+            (import "env" "timer_set_game_time" (func $set_game_time (param f64)))
+            (import "env" "timer_pause_game_time" (func $pause_game_time))
+            (import "env" "process_attach" (func $attach (param i32 i32) (result i64)))
+            (import "env" "process_read" (func $read (param i64 i64 i32 i32) (result i32)))
+            (import "env" "runtime_set_tick_rate" (func $set_tick_rate (param f64)))
+            (memory (export "memory") 1)
+            (data (i32.const 0) "{name}")
+            (global $process (mut i64) (i64.const 0))
+            (global $old (mut i32) (i32.const 0))
+            (func $init (call $set_tick_rate (f64.const 200)))
+            (start $init)
+            (func (export "update")
+                (local $value i32)
+                (if (i64.eqz (global.get $process))
+                    (then (global.set $process (call $attach (i32.const 0) (i32.const {len})))))
+                (if (i64.eqz (global.get $process))
+                    (then (return)))
+                (if (i32.eqz (call $read (global.get $process) (i64.const {address}) (i32.const 64) (i32.const 4)))
+                    (then (return)))
+                (local.set $value (i32.load (i32.const 64)))
+                (if (i32.eq (local.get $value) (global.get $old))
+                    (then (return)))
+                (global.set $old (local.get $value))
+                (if (i32.eqz (call $get_state))
+                    (then (call $start) (call $pause_game_time)))
+                (call $set_game_time (f64.convert_i32_u (local.get $value)))
+                (if (i32.gt_u (local.get $value) (i32.const 1))
+                    (then (call $split)))))"#,
+        name = process_name,
+        len = process_name.len(),
+        address = address,
+    )
+    .into_bytes()
+}
+
+fn wait_until(mut condition: impl FnMut() -> bool) {
+    let start = Instant::now();
+    while !condition() {
+        assert!(
+            start.elapsed() < Duration::from_secs(10),
+            "The auto splitter didn't react in time"
+        );
+        thread::sleep(Duration::from_millis(10));
+    }
+}
+
+#[test]
+fn controls_timer_by_reading_process_memory() {
+    // The helper is a copy of this test binary with a unique name, so the auto
+    // splitter doesn't attach to this process instead.
+    let process_name = format!("ashelper{}", process::id());
+    let path = env::temp_dir().join(&process_name);
+    fs::copy(env::current_exe().unwrap(), &path).unwrap();
+    let mut child = Command::new(&path)
+        .args(["helper_process", "--exact", "--ignored", "--nocapture"])
+        .env(HELPER_ENV, "1")
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .unwrap();
+    let mut stdin = child.stdin.take().unwrap();
+    let mut stdout = BufReader::new(child.stdout.take().unwrap());
+    let address = (&mut stdout)
+        .lines()
+        .map(Result::unwrap)
+        // The test harness prefixes the output with the name of the test.
+        .find_map(|line| Some(line[line.find("address ")? + 8..].to_owned()))
+        .unwrap();
+
+    let timer = create_timer(&["A", "B"]).into_shared();
+    let runtime = Runtime::new(timer.clone());
+    runtime
+        .load_script(script(&process_name, &address))
+        .unwrap();
+
+    writeln!(stdin, "1").unwrap();
+    wait_until(|| timer.read().current_phase() == TimerPhase::Running);
+
+    writeln!(stdin, "5").unwrap();
+    wait_until(|| timer.read().current_split_index() == Some(1));
+    let split_time = timer.read().run().segment(0).split_time();
+    assert_eq!(split_time.game_time, Some(TimeSpan::from_seconds(5.0)));
+
+    runtime.unload_script().unwrap();
+    writeln!(stdin, "7").unwrap();
+    thread::sleep(Duration::from_millis(100));
+    assert_eq!(timer.read().current_split_index(), Some(1));
+
+    drop(stdin);
+    child.wait().unwrap();
+    drop(stdout);
+    fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn invalid_scripts() {
+    let runtime = Runtime::new(create_timer(&["A"]).into_shared());
+    assert!(matches!(
+        runtime.load_script(b"not a module".to_vec()),
+        Err(Error::LoadScript {
+            source: CreationError::LoadModule { .. },
+        }),
+    ));
+    assert!(matches!(
+        runtime.load_script(br#"(module (memory (export "memory") 1))"#.to_vec()),
+        Err(Error::LoadScript {
+            source: CreationError::MissingUpdateFunction { .. },
+        }),
+    ));
+    assert!(matches!(
+        runtime.load_script(br#"(module (func (export "update")))"#.to_vec()),
+        Err(Error::LoadScript {
+            source: CreationError::MissingMemory,
+        }),
+    ));
+}
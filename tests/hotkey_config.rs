@@ -0,0 +1,62 @@
+#![cfg(all(feature = "std", target_os = "linux"))]
+
+use livesplit_core::{
+    hotkey::{Hotkey, KeyCode, Modifiers},
+    settings::Value,
+    HotkeyConfig,
+};
+
+#[test]
+fn parses_hotkeys_with_modifiers() {
+    let hotkey: Hotkey = "Control + Shift + Numpad1".parse().unwrap();
+    assert_eq!(hotkey.key_code, KeyCode::Numpad1);
+    assert_eq!(hotkey.modifiers, Modifiers::CONTROL | Modifiers::SHIFT);
+    assert_eq!(hotkey.to_string(), "Control + Shift + Numpad1");
+
+    let hotkey: Hotkey = "Numpad1".parse().unwrap();
+    assert_eq!(hotkey, KeyCode::Numpad1.into());
+    assert_eq!(hotkey.to_string(), "Numpad1");
+
+    assert!("Hyper + Numpad1".parse::<Hotkey>().is_err());
+    assert!("Control + ".parse::<Hotkey>().is_err());
+}
+
+#[test]
+fn loads_configurations_without_modifiers() {
+    let config = HotkeyConfig::from_json(&br#"{"split":"Numpad7","reset":null}"#[..]).unwrap();
+    assert_eq!(config.split, Some(KeyCode::Numpad7.into()));
+    assert_eq!(config.reset, None);
+    assert_eq!(config.undo, HotkeyConfig::default().undo);
+}
+
+#[test]
+fn round_trips_modifiers_through_json() {
+    let config = HotkeyConfig {
+        split: Some(Hotkey::new(KeyCode::S, Modifiers::ALT | Modifiers::META)),
+        ..Default::default()
+    };
+
+    let mut json = Vec::new();
+    config.write_json(&mut json).unwrap();
+    assert!(String::from_utf8_lossy(&json).contains(r#""split":"Alt + Meta + S""#));
+    assert_eq!(HotkeyConfig::from_json(&json[..]).unwrap(), config);
+}
+
+#[test]
+fn sets_hotkeys_from_strings() {
+    let mut config = HotkeyConfig::default();
+    config
+        .set_value(0, Value::String("Control + Numpad9".into()))
+        .unwrap();
+    assert_eq!(
+        config.split,
+        Some(Hotkey::new(KeyCode::Numpad9, Modifiers::CONTROL)),
+    );
+    // The same key with different modifiers is a different hotkey.
+    config
+        .set_value(1, Value::String("Numpad9".into()))
+        .unwrap();
+    assert!(config
+        .set_value(2, Value::String("Control + Numpad9".into()))
+        .is_err());
+}
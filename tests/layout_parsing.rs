@@ -52,3 +52,256 @@ mod parse {
         assert_eq!(columns[1].name, "+/−");
     }
 }
+
+mod save {
+    use crate::layout_files;
+    use livesplit_core::{
+        component::{splits, timer},
+        layout::{parser::parse, saver::livesplit::save_layout, Component, Layout},
+        TimingMethod,
+    };
+    use serde_json::Value;
+    use std::io::Cursor;
+
+    fn save(layout: &Layout) -> Vec<u8> {
+        let mut buf = Vec::new();
+        save_layout(layout, &mut buf).unwrap();
+        buf
+    }
+
+    fn settings_json(layout: &Layout) -> Value {
+        let mut buf = Vec::new();
+        layout.settings().write_json(&mut buf).unwrap();
+        serde_json::from_slice(&buf).unwrap()
+    }
+
+    /// The colors are stored with 8 bits per channel in the layout files and
+    /// the parser adjusts the alpha based on the lightness of the color, so
+    /// numbers only need to match up to a couple of steps of that precision.
+    fn assert_close(original: &Value, saved: &Value, path: &str) {
+        match (original, saved) {
+            (Value::Number(original), Value::Number(saved)) => {
+                let (original, saved) = (original.as_f64().unwrap(), saved.as_f64().unwrap());
+                assert!(
+                    (original - saved).abs() <= 2.0 / 255.0,
+                    "{}: {} != {}",
+                    path,
+                    original,
+                    saved
+                );
+            }
+            (Value::Array(original), Value::Array(saved)) => {
+                assert_eq!(original.len(), saved.len(), "{}", path);
+                for (i, (original, saved)) in original.iter().zip(saved).enumerate() {
+                    assert_close(original, saved, &format!("{}[{}]", path, i));
+                }
+            }
+            (Value::Object(original), Value::Object(saved)) => {
+                assert_eq!(
+                    original.keys().collect::<Vec<_>>(),
+                    saved.keys().collect::<Vec<_>>(),
+                    "{}",
+                    path
+                );
+                for (key, original) in original {
+                    assert_close(original, &saved[key], &format!("{}.{}", path, key));
+                }
+            }
+            _ => assert_eq!(original, saved, "{}", path),
+        }
+    }
+
+    fn round_trip(data: &[u8]) {
+        let original = parse(Cursor::new(data)).unwrap();
+        assert_same_layout(&original);
+    }
+
+    fn assert_same_layout(original: &Layout) {
+        let reparsed = parse(Cursor::new(save(original))).unwrap();
+        assert_close(
+            &settings_json(original),
+            &settings_json(&reparsed),
+            "layout",
+        );
+    }
+
+    #[test]
+    fn all() {
+        round_trip(layout_files::ALL);
+    }
+
+    #[test]
+    fn dark() {
+        round_trip(layout_files::DARK);
+    }
+
+    #[test]
+    fn subsplits() {
+        round_trip(layout_files::SUBSPLITS);
+    }
+
+    #[test]
+    fn wsplit() {
+        round_trip(layout_files::WSPLIT);
+    }
+
+    #[test]
+    fn with_timer_delta_background() {
+        round_trip(layout_files::WITH_TIMER_GRADIENT_BACKGROUND);
+    }
+
+    #[test]
+    fn default_layout() {
+        assert_same_layout(&Layout::default_layout());
+    }
+
+    #[test]
+    fn custom_timing_methods_fall_back_to_the_current_one() {
+        let mut timer = timer::Component::new();
+        timer.settings_mut().timing_method = Some(TimingMethod::Custom1);
+        let mut layout = Layout::new();
+        layout.push(timer);
+
+        let saved = String::from_utf8(save(&layout)).unwrap();
+        assert!(saved.contains("<TimingMethod>Current Timing Method</TimingMethod>"));
+
+        let reparsed = parse(Cursor::new(saved)).unwrap();
+        match &reparsed.components[0] {
+            Component::Timer(timer) => assert_eq!(timer.settings().timing_method, None),
+            _ => panic!("The timer component wasn't parsed"),
+        }
+    }
+
+    #[test]
+    fn style_rules() {
+        use livesplit_core::{
+            settings::{Color, Gradient},
+            TimeSpan,
+        };
+        use splits::{NamePattern, RowStyle, StyleCondition, StyleRule};
+
+        let mut layout = Layout::new();
+        layout.push(splits::Component::with_settings(splits::Settings {
+            style_rules: vec![
+                StyleRule {
+                    condition: StyleCondition::DeltaAbove(TimeSpan::from_seconds(2.5)),
+                    style: RowStyle {
+                        text_color: Some(Color::rgba(1.0, 0.0, 0.0, 1.0)),
+                        ..Default::default()
+                    },
+                },
+                StyleRule {
+                    condition: StyleCondition::BestSegment,
+                    style: RowStyle {
+                        background: Some(Gradient::Vertical(
+                            Color::rgba(0.0, 1.0, 0.0, 1.0),
+                            Color::rgba(0.0, 0.0, 1.0, 1.0),
+                        )),
+                        ..Default::default()
+                    },
+                },
+                StyleRule {
+                    condition: StyleCondition::NameMatches(NamePattern::new("^-").unwrap()),
+                    style: RowStyle {
+                        is_hidden: true,
+                        ..Default::default()
+                    },
+                },
+            ],
+            ..Default::default()
+        }));
+
+        assert_same_layout(&layout);
+    }
+
+    #[test]
+    fn columns_unknown_to_livesplit() {
+        use splits::{ColumnSettings, ColumnStartWith, ColumnUpdateTrigger, ColumnUpdateWith};
+
+        let column = |start_with, update_with, update_trigger| ColumnSettings {
+            start_with,
+            update_with,
+            update_trigger,
+            ..Default::default()
+        };
+        let mut layout = Layout::new();
+        layout.push(splits::Component::with_settings(splits::Settings {
+            columns: vec![
+                column(
+                    ColumnStartWith::MedianSegmentTime,
+                    ColumnUpdateWith::PossibleTimeSave,
+                    ColumnUpdateTrigger::OnEndingSegment,
+                ),
+                column(
+                    ColumnStartWith::GoldCount,
+                    ColumnUpdateWith::DontUpdate,
+                    ColumnUpdateTrigger::Contextual,
+                ),
+                column(
+                    ColumnStartWith::Empty,
+                    ColumnUpdateWith::Delta,
+                    ColumnUpdateTrigger::OnStartingSegment,
+                ),
+            ],
+            ..Default::default()
+        }));
+
+        assert_same_layout(&layout);
+    }
+}
+
+mod json {
+    use crate::layout_files;
+    use livesplit_core::layout::{parser::parse, LayoutSettings};
+    use serde_json::Value;
+    use std::io::Cursor;
+
+    fn to_value(settings: &LayoutSettings) -> Value {
+        let mut buf = Vec::new();
+        settings.write_json(&mut buf).unwrap();
+        serde_json::from_slice(&buf).unwrap()
+    }
+
+    #[test]
+    fn writes_version() {
+        let settings = parse(Cursor::new(layout_files::ALL)).unwrap().settings();
+        let value = to_value(&settings);
+        assert_eq!(value["version"], LayoutSettings::VERSION);
+    }
+
+    fn decode(value: &Value) -> LayoutSettings {
+        LayoutSettings::from_json(value.to_string().as_bytes()).unwrap()
+    }
+
+    #[test]
+    fn round_trip() {
+        for data in &[
+            layout_files::ALL,
+            layout_files::DARK,
+            layout_files::SUBSPLITS,
+            layout_files::WSPLIT,
+        ] {
+            let original = parse(Cursor::new(*data)).unwrap().settings();
+            let decoded = decode(&to_value(&original));
+            assert_eq!(to_value(&decoded), to_value(&original));
+        }
+    }
+
+    #[test]
+    fn migrates_unversioned() {
+        let original = parse(Cursor::new(layout_files::DARK)).unwrap().settings();
+        let mut value = to_value(&original);
+        value.as_object_mut().unwrap().remove("version");
+
+        assert_eq!(to_value(&decode(&value)), to_value(&original));
+    }
+
+    #[test]
+    fn rejects_newer_versions() {
+        let settings = parse(Cursor::new(layout_files::DARK)).unwrap().settings();
+        let mut value = to_value(&settings);
+        value["version"] = (LayoutSettings::VERSION + 1).into();
+
+        assert!(LayoutSettings::from_json(value.to_string().as_bytes()).is_err());
+    }
+}
@@ -0,0 +1,181 @@
+#![cfg(feature = "software-rendering")]
+
+use livesplit_core::{
+    rendering::recorder::{Error, Recorder},
+    timing::ManualClock,
+    Layout, Run, Segment, TimeSpan, Timer, TimerPhase,
+};
+use std::{env, fs, process};
+
+fn span(seconds: f64) -> TimeSpan {
+    TimeSpan::from_seconds(seconds)
+}
+
+/// Records an attempt with a manual clock. `None` skips a split. The attempt
+/// gets reset at the time provided.
+fn run(splits: &[Option<f64>], reset_at: f64) -> Run {
+    run_with_offset(splits, reset_at, TimeSpan::zero())
+}
+
+/// Records an attempt of a Run with the offset provided. The times are the
+/// times of the clock, so they don't include the offset.
+fn run_with_offset(splits: &[Option<f64>], reset_at: f64, offset: TimeSpan) -> Run {
+    let mut run = Run::new();
+    for name in &["A", "B", "C"] {
+        run.push_segment(Segment::new(*name));
+    }
+    run.set_offset(offset);
+    let mut timer = Timer::with_clock(run, ManualClock::new()).unwrap();
+    let set_elapsed = |timer: &mut Timer, time| {
+        timer
+            .clock_mut::<ManualClock>()
+            .unwrap()
+            .set_elapsed(span(time));
+    };
+
+    timer.start();
+    timer.initialize_game_time();
+    timer.pause_game_time();
+    for &split in splits {
+        match split {
+            Some(time) => {
+                set_elapsed(&mut timer, time);
+                timer.set_game_time(span(time / 2.0));
+                timer.split();
+            }
+            None => timer.skip_split(),
+        }
+    }
+    set_elapsed(&mut timer, reset_at);
+    timer.reset(true);
+    timer.into_run(true)
+}
+
+#[test]
+fn replays_finished_attempt() {
+    let run = run(&[Some(1.5), None, Some(4.0)], 4.0);
+    let index = run.attempt_history()[0].index();
+    let mut recorder =
+        Recorder::new(run, index, Layout::default_layout(), [60, 100], 10.0).unwrap();
+
+    // 4 seconds of the attempt, 2 seconds of holding the final state and the
+    // frame at zero.
+    assert_eq!(recorder.frame_count(), 61);
+
+    let mut frames = 0;
+    while let Some(frame) = recorder.next_frame() {
+        assert_eq!(frame.len(), 60 * 100 * 4);
+        frames += 1;
+        if frames == 20 {
+            let timer = recorder.timer();
+            assert_eq!(timer.current_split_index(), Some(1));
+            let game_time = timer.snapshot().current_time().game_time.unwrap();
+            assert!((game_time.total_seconds() - 0.95).abs() < 1e-3);
+        }
+    }
+    assert_eq!(frames, 61);
+
+    let timer = recorder.timer();
+    assert_eq!(timer.current_phase(), TimerPhase::Ended);
+    let split_times = timer
+        .run()
+        .segments()
+        .iter()
+        .map(|s| s.split_time())
+        .collect::<Vec<_>>();
+    assert_eq!(split_times[0].real_time, Some(span(1.5)));
+    assert_eq!(split_times[0].game_time, Some(span(0.75)));
+    assert_eq!(split_times[1].real_time, None);
+    assert_eq!(split_times[2].real_time, Some(span(4.0)));
+    assert_eq!(split_times[2].game_time, Some(span(2.0)));
+}
+
+#[test]
+fn replays_attempt_with_offset() {
+    for &offset in &[-2.0, 0.5] {
+        let run = run_with_offset(&[Some(3.0), None, Some(6.0)], 6.0, span(offset));
+        let index = run.attempt_history()[0].index();
+        let mut recorder = Recorder::new(
+            run.clone(),
+            index,
+            Layout::default_layout(),
+            [60, 100],
+            10.0,
+        )
+        .unwrap();
+
+        // 6 seconds of the attempt, 2 seconds of holding the final state and
+        // the frame at zero.
+        assert_eq!(recorder.frame_count(), 81);
+
+        while recorder.next_frame().is_some() {}
+
+        let timer = recorder.timer();
+        assert_eq!(timer.current_phase(), TimerPhase::Ended);
+        for (replayed, original) in timer.run().segments().iter().zip(run.segments()) {
+            assert_eq!(replayed.split_time(), original.personal_best_split_time());
+        }
+        assert_eq!(
+            timer.run().segments()[2].split_time().real_time,
+            Some(span(6.0 + offset)),
+        );
+    }
+}
+
+#[test]
+fn replays_reset_attempt_until_reset() {
+    let run = run(&[Some(1.0)], 3.0);
+    let index = run.attempt_history()[0].index();
+    let mut recorder = Recorder::new(run, index, Layout::default_layout(), [60, 100], 4.0).unwrap();
+
+    // 3 seconds until the reset, 2 seconds of holding and the frame at zero.
+    assert_eq!(recorder.frame_count(), 21);
+
+    while recorder.next_frame().is_some() {}
+
+    let timer = recorder.timer();
+    assert_eq!(timer.current_phase(), TimerPhase::Running);
+    assert_eq!(timer.current_split_index(), Some(1));
+}
+
+#[test]
+fn writes_frames() {
+    let run = run(&[Some(0.2), Some(0.3), Some(0.5)], 0.5);
+    let index = run.attempt_history()[0].index();
+    let layout = Layout::default_layout();
+
+    let mut recorder = Recorder::new(run.clone(), index, layout.clone(), [30, 50], 2.0).unwrap();
+    let mut raw = Vec::new();
+    recorder.write_raw_frames(&mut raw).unwrap();
+    assert_eq!(raw.len(), 6 * 30 * 50 * 4);
+
+    let directory = env::temp_dir().join(format!("livesplit-recorder-{}", process::id()));
+    let mut recorder = Recorder::new(run, index, layout, [30, 50], 2.0).unwrap();
+    recorder.write_png_frames(&directory).unwrap();
+    assert_eq!(fs::read_dir(&directory).unwrap().count(), 6);
+    let first = image::open(directory.join("frame_000000.png"))
+        .unwrap()
+        .to_rgba8();
+    assert_eq!(first.dimensions(), (30, 50));
+    fs::remove_dir_all(directory).unwrap();
+}
+
+#[test]
+fn unknown_attempt() {
+    let run = run(&[Some(1.0)], 1.0);
+    assert!(matches!(
+        Recorder::new(run, 1234, Layout::default_layout(), [60, 100], 10.0),
+        Err(Error::AttemptNotFound),
+    ));
+}
+
+#[test]
+fn invalid_frame_rate() {
+    for &fps in &[0.0, -10.0, f64::NAN, f64::INFINITY] {
+        let run = run(&[Some(1.0)], 1.0);
+        assert!(matches!(
+            Recorder::new(run, 1, Layout::default_layout(), [60, 100], fps),
+            Err(Error::InvalidFrameRate),
+        ));
+    }
+}
@@ -0,0 +1,225 @@
+#![cfg(feature = "server")]
+
+#[path = "../src/tests_helper.rs"]
+mod tests_helper;
+
+use livesplit_core::{
+    server::{Command, ParseError, Server},
+    Run, Segment, TimeSpan, Timer, TimerPhase, TimingMethod,
+};
+use std::{
+    io::{BufRead, BufReader, Read, Write},
+    net::{TcpListener, TcpStream},
+    thread,
+};
+use tests_helper::{create_timer, run_with_splits};
+
+fn server() -> Server {
+    let mut timer = create_timer(&["A", "B", "C"]);
+    run_with_splits(&mut timer, &[10.0, 20.0, 30.0]);
+    Server::new(timer.into_shared())
+}
+
+fn listen() -> (TcpListener, u16) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let port = listener.local_addr().unwrap().port();
+    (listener, port)
+}
+
+#[test]
+fn parse_commands() {
+    assert_eq!(Command::parse("split").unwrap(), Command::Split);
+    assert_eq!(
+        Command::parse("setgametime 1:02.5\r").unwrap(),
+        Command::SetGameTime(TimeSpan::from_seconds(62.5)),
+    );
+    assert_eq!(
+        Command::parse("switchto gametime").unwrap(),
+        Command::SwitchTo(TimingMethod::GameTime),
+    );
+    assert_eq!(
+        Command::parse("getdelta Best Segments").unwrap(),
+        Command::GetDelta(Some("Best Segments")),
+    );
+    assert_eq!(Command::parse("getdelta").unwrap(), Command::GetDelta(None));
+    assert!(matches!(
+        Command::parse("setcomparison"),
+        Err(ParseError::MissingArgument),
+    ));
+    assert!(matches!(
+        Command::parse("setloadingtimes abc"),
+        Err(ParseError::InvalidTime { .. }),
+    ));
+    assert!(matches!(
+        Command::parse("launchrockets"),
+        Err(ParseError::UnknownCommand { .. }),
+    ));
+}
+
+#[test]
+fn execute_commands() {
+    let server = server();
+
+    assert_eq!(server.execute("getsplitindex").as_deref(), Some("-1"));
+    assert_eq!(
+        server.execute("getcurrenttimerphase").as_deref(),
+        Some("NotRunning")
+    );
+    assert_eq!(server.execute("getfinaltime").as_deref(), Some("0:30.00"));
+    assert_eq!(server.execute("getattemptcount").as_deref(), Some("1"));
+    assert_eq!(server.execute("garbage"), None);
+
+    assert_eq!(server.execute("starttimer"), None);
+    assert_eq!(server.execute("initgametime"), None);
+    assert_eq!(server.execute("switchto gametime"), None);
+    assert_eq!(server.execute("pausegametime"), None);
+    assert_eq!(server.execute("setgametime 8"), None);
+    assert_eq!(server.execute("split"), None);
+
+    assert_eq!(server.execute("getsplitindex").as_deref(), Some("1"));
+    assert_eq!(server.execute("getcurrentsplitname").as_deref(), Some("B"));
+    assert_eq!(server.execute("getprevioussplitname").as_deref(), Some("A"));
+    assert_eq!(server.execute("getlastsplittime").as_deref(), Some("0:08.00"));
+    assert_eq!(
+        server.execute("getcomparisonsplittime").as_deref(),
+        Some("0:20.00")
+    );
+    assert_eq!(server.execute("getdelta").as_deref(), Some("−2.00"));
+    assert_eq!(
+        server.execute("getcurrenttimerphase").as_deref(),
+        Some("Running")
+    );
+
+    assert_eq!(server.execute("pause"), None);
+    assert_eq!(server.timer().read().current_phase(), TimerPhase::Paused);
+    assert_eq!(server.execute("reset"), None);
+    assert_eq!(server.execute("getattemptcount").as_deref(), Some("2"));
+    assert_eq!(server.execute("getcompletedcount").as_deref(), Some("1"));
+    assert_eq!(server.execute("ping").as_deref(), Some("pong"));
+}
+
+#[test]
+fn tcp_connection() {
+    let server = server();
+    let (listener, port) = listen();
+    thread::spawn({
+        let server = server.clone();
+        move || server.serve_tcp(&listener)
+    });
+
+    let mut stream = TcpStream::connect(("127.0.0.1", port)).unwrap();
+    let mut reader = BufReader::new(stream.try_clone().unwrap());
+    stream
+        .write_all(b"starttimer\r\nsplit\r\ngetsplitindex\r\n")
+        .unwrap();
+
+    let mut line = String::new();
+    reader.read_line(&mut line).unwrap();
+    assert_eq!(line, "1\r\n");
+
+    stream.write_all(b"getcurrentsplitname\n").unwrap();
+    line.clear();
+    reader.read_line(&mut line).unwrap();
+    assert_eq!(line, "B\r\n");
+    assert_eq!(server.timer().read().current_split_index(), Some(1));
+}
+
+#[test]
+fn tcp_connection_closes_on_long_lines() {
+    let server = server();
+    let (listener, port) = listen();
+    thread::spawn(move || server.serve_tcp(&listener));
+
+    let mut stream = TcpStream::connect(("127.0.0.1", port)).unwrap();
+    // The server may close the connection before everything is written.
+    let _ = stream.write_all(&vec![b'a'; 100 << 10]);
+
+    let mut rest = Vec::new();
+    let _ = stream.read_to_end(&mut rest);
+    assert!(rest.is_empty());
+}
+
+fn write_masked(stream: &mut TcpStream, opcode: u8, is_final: bool, payload: &[u8]) {
+    let mask = [0x12, 0x34, 0x56, 0x78];
+    let mut frame = vec![(is_final as u8) << 7 | opcode, 0x80 | payload.len() as u8];
+    frame.extend_from_slice(&mask);
+    frame.extend(payload.iter().enumerate().map(|(i, b)| b ^ mask[i % 4]));
+    stream.write_all(&frame).unwrap();
+}
+
+fn read_frame(stream: &mut TcpStream) -> (u8, String) {
+    let mut header = [0; 2];
+    stream.read_exact(&mut header).unwrap();
+    assert_eq!(header[1] & 0x80, 0, "Server frames must not be masked");
+    let mut payload = vec![0; (header[1] & 0x7F) as usize];
+    stream.read_exact(&mut payload).unwrap();
+    (header[0], String::from_utf8(payload).unwrap())
+}
+
+#[test]
+fn websocket_connection_rejects_remote_origins() {
+    let server = server();
+    let (listener, port) = listen();
+    thread::spawn(move || server.serve_websocket(&listener));
+
+    let mut stream = TcpStream::connect(("127.0.0.1", port)).unwrap();
+    stream
+        .write_all(
+            b"GET / HTTP/1.1\r\n\
+              Host: localhost\r\n\
+              Origin: https://example.com\r\n\
+              Upgrade: websocket\r\n\
+              Connection: Upgrade\r\n\
+              Sec-WebSocket-Key: dGhlIHNhbXBsZSBub25jZQ==\r\n\
+              Sec-WebSocket-Version: 13\r\n\r\n",
+        )
+        .unwrap();
+
+    let mut response = String::new();
+    stream.read_to_string(&mut response).unwrap();
+    assert_eq!(response, "HTTP/1.1 403 Forbidden\r\n\r\n");
+}
+
+#[test]
+fn websocket_connection() {
+    let server = server();
+    let (listener, port) = listen();
+    thread::spawn(move || server.serve_websocket(&listener));
+
+    let mut stream = TcpStream::connect(("127.0.0.1", port)).unwrap();
+    stream
+        .write_all(
+            b"GET / HTTP/1.1\r\n\
+              Host: localhost\r\n\
+              Origin: http://localhost:8080\r\n\
+              Upgrade: websocket\r\n\
+              Connection: Upgrade\r\n\
+              sec-websocket-key: dGhlIHNhbXBsZSBub25jZQ==\r\n\
+              Sec-WebSocket-Version: 13\r\n\r\n",
+        )
+        .unwrap();
+
+    let mut response = Vec::new();
+    while !response.ends_with(b"\r\n\r\n") {
+        let mut byte = [0];
+        stream.read_exact(&mut byte).unwrap();
+        response.push(byte[0]);
+    }
+    let response = String::from_utf8(response).unwrap();
+    assert!(response.starts_with("HTTP/1.1 101 Switching Protocols\r\n"));
+    assert!(response.contains("Sec-WebSocket-Accept: s3pPLMBiTxaQ9kYGzzhZRbK+xOo=\r\n"));
+
+    write_masked(&mut stream, 0x1, true, b"ping");
+    assert_eq!(read_frame(&mut stream), (0x81, "pong".to_owned()));
+
+    write_masked(&mut stream, 0x9, true, b"hi");
+    assert_eq!(read_frame(&mut stream), (0x8A, "hi".to_owned()));
+
+    // Fragmented messages are reassembled.
+    write_masked(&mut stream, 0x1, false, b"getcurrent");
+    write_masked(&mut stream, 0x0, true, b"timerphase");
+    assert_eq!(read_frame(&mut stream), (0x81, "NotRunning".to_owned()));
+
+    write_masked(&mut stream, 0x8, true, b"");
+    assert_eq!(read_frame(&mut stream), (0x88, String::new()));
+}
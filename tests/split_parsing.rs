@@ -220,3 +220,369 @@ mod parse {
         assert_eq!(run.kind, TimerKind::Flitter);
     }
 }
+
+mod save {
+    use crate::run_files;
+    use livesplit_core::{
+        run::{
+            parser::{composite, livesplit, llanfair_gered, wsplit, TimerKind},
+            saver::{self, LossReport},
+            SegmentGroup,
+        },
+        timing::FrameRate,
+        RealTime, Run, Segment, Time, TimeSpan, TimingMethod,
+    };
+    use std::io::Cursor;
+
+    fn lss(run: &Run) -> Vec<u8> {
+        let mut buf = Vec::new();
+        saver::livesplit::save_run(run, &mut buf).unwrap();
+        buf
+    }
+
+    fn round_trip<S>(data: &[u8], kind: TimerKind, save: S) -> LossReport
+    where
+        S: Fn(&Run, &mut Vec<u8>) -> LossReport,
+    {
+        let original = composite::parse(Cursor::new(data), None, false).unwrap();
+        assert_eq!(original.kind, kind);
+
+        let mut saved = Vec::new();
+        let report = save(&original.run, &mut saved);
+
+        let reparsed = composite::parse(Cursor::new(&saved), None, false).unwrap();
+        assert_eq!(reparsed.kind, kind);
+
+        let mut saved_again = Vec::new();
+        assert_eq!(save(&reparsed.run, &mut saved_again), report);
+        assert_eq!(saved, saved_again);
+
+        assert_eq!(lss(&original.run), lss(&reparsed.run));
+
+        report
+    }
+
+    #[test]
+    fn segment_groups() {
+        let mut run = Run::new();
+        for name in &["A", "B", "C", "D"] {
+            run.push_segment(Segment::new(*name));
+        }
+        run.segment_groups_mut()
+            .insert(SegmentGroup::new(0, 2).with_name("Group"))
+            .unwrap();
+        run.segment_groups_mut()
+            .insert(SegmentGroup::new(2, 3))
+            .unwrap();
+
+        let check = |saved: &[u8]| {
+            let parsed = livesplit::parse(Cursor::new(saved), None).unwrap();
+            assert_eq!(parsed.segment_groups(), run.segment_groups());
+            let names = parsed
+                .segments()
+                .iter()
+                .map(|s| s.name())
+                .collect::<Vec<_>>();
+            assert_eq!(names, ["A", "B", "C", "D"]);
+        };
+
+        let saved = String::from_utf8(lss(&run)).unwrap();
+        assert!(saved.contains("<Name>{Group} B</Name>"));
+        assert!(saved.contains("<Name>{} C</Name>"));
+        check(saved.as_bytes());
+
+        // The original LiveSplit only knows about the groups through the
+        // segment names.
+        let start = saved.find("<SegmentGroups>").unwrap();
+        let end = saved.find("</SegmentGroups>").unwrap() + "</SegmentGroups>".len();
+        let without_groups = format!("{}{}", &saved[..start], &saved[end..]);
+        check(without_groups.as_bytes());
+    }
+
+    fn celeste() -> Run {
+        livesplit::parse(Cursor::new(run_files::CELESTE), None).unwrap()
+    }
+
+    fn llanfair_gered_run(data: &[u8]) -> Run {
+        llanfair_gered::parse(Cursor::new(data)).unwrap()
+    }
+
+    #[test]
+    fn urn() {
+        let report = round_trip(run_files::URN, TimerKind::Urn, |run, buf| {
+            saver::urn::save_run(run, buf).unwrap()
+        });
+        assert!(!report.game_name);
+        assert_eq!(report.attempt_history, 4);
+    }
+
+    #[test]
+    fn splitterino() {
+        let report = round_trip(
+            run_files::SPLITTERINO,
+            TimerKind::Splitterino,
+            |run, buf| saver::splitterino::save_run(run, buf).unwrap(),
+        );
+        assert!(report.is_lossless());
+    }
+
+    #[test]
+    fn wsplit() {
+        let report = round_trip(run_files::WSPLIT, TimerKind::WSplit, |run, buf| {
+            saver::wsplit::save_run(run, buf).unwrap()
+        });
+        assert!(report.is_lossless());
+    }
+
+    #[test]
+    fn wsplit_old_run_and_goal() {
+        let mut run = Run::new();
+        run.set_category_name("Any%");
+        run.set_offset(TimeSpan::from_seconds(-1.5));
+        run.metadata_mut()
+            .custom_variable_mut("Goal")
+            .permanent()
+            .set_value("sub 2h");
+        run.add_custom_comparison("Old Run").unwrap();
+        let mut segment = Segment::new("A, B");
+        *segment.comparison_mut("Old Run") = RealTime(Some(TimeSpan::from_seconds(12.34))).into();
+        run.push_segment(segment);
+
+        let mut buf = Vec::new();
+        let report = saver::wsplit::save_run(&run, &mut buf).unwrap();
+        assert!(report.is_lossless());
+
+        let parsed = wsplit::parse(Cursor::new(buf), false).unwrap();
+        assert_eq!(parsed.offset(), run.offset());
+        assert_eq!(
+            parsed.metadata().custom_variable_value("Goal"),
+            Some("sub 2h")
+        );
+        assert_eq!(parsed.custom_comparisons(), run.custom_comparisons());
+        assert_eq!(parsed.segment(0).name(), "A  B");
+        assert_eq!(
+            parsed.segment(0).comparison("Old Run").real_time,
+            Some(TimeSpan::from_seconds(12.34)),
+        );
+    }
+
+    #[test]
+    fn livesplit_frame_rate() {
+        let mut run = Run::new();
+        run.push_segment(Segment::new("A"));
+        let frame_rate = FrameRate::new(60.0988);
+        run.metadata_mut().set_frame_rate(frame_rate);
+
+        let saved = lss(&run);
+        let parsed = livesplit::parse(Cursor::new(&saved), None).unwrap();
+        assert_eq!(parsed.metadata().frame_rate(), frame_rate);
+        assert_eq!(lss(&parsed), saved);
+
+        let report = saver::urn::save_run(&run, Vec::new()).unwrap();
+        assert!(report.metadata);
+    }
+
+    #[test]
+    fn livesplit_custom_timing_methods() {
+        let method = TimingMethod::Custom2;
+        let mut run = Run::new();
+        run.set_custom_timing_method_name(method, "In-Game Time");
+        let mut segment = Segment::new("A");
+        segment.set_personal_best_split_time(
+            Time::new().with_timing_method(method, Some(TimeSpan::from_seconds(12.5))),
+        );
+        run.push_segment(segment);
+        run.add_attempt(
+            Time::new().with_timing_method(method, Some(TimeSpan::from_seconds(12.5))),
+            None,
+            None,
+            None,
+        );
+
+        let saved = lss(&run);
+        let parsed = livesplit::parse(Cursor::new(&saved), None).unwrap();
+        assert_eq!(
+            parsed.custom_timing_method_name(method),
+            Some("In-Game Time")
+        );
+        assert_eq!(
+            parsed.segment(0).personal_best_split_time()[method],
+            Some(TimeSpan::from_seconds(12.5)),
+        );
+        assert_eq!(
+            parsed.attempt_history()[0].time()[method],
+            Some(TimeSpan::from_seconds(12.5)),
+        );
+        assert_eq!(lss(&parsed), saved);
+
+        let report = saver::urn::save_run(&run, Vec::new()).unwrap();
+        assert_eq!(report.custom_timing_methods, [method]);
+    }
+
+    #[test]
+    fn llanfair_gered() {
+        let report = round_trip(
+            run_files::LLANFAIR_GERED,
+            TimerKind::LlanfairGered,
+            |run, buf| saver::llanfair_gered::save_run(run, buf).unwrap(),
+        );
+        assert!(report.is_lossless());
+    }
+
+    #[test]
+    fn llanfair_gered_icons() {
+        let report = round_trip(
+            run_files::LLANFAIR_GERED_ICONS,
+            TimerKind::LlanfairGered,
+            |run, buf| saver::llanfair_gered::save_run(run, buf).unwrap(),
+        );
+        assert!(report.is_lossless());
+    }
+
+    #[test]
+    fn llanfair_gered_reports_lost_icons() {
+        let mut run = llanfair_gered_run(run_files::LLANFAIR_GERED);
+        run.segment_mut(0).set_icon(b"not an image");
+        let report = saver::llanfair_gered::save_run(&run, Vec::new()).unwrap();
+        assert_eq!(
+            report,
+            LossReport {
+                segment_icons: 1,
+                ..LossReport::default()
+            }
+        );
+    }
+
+    #[test]
+    fn reports_lost_data() {
+        let run = celeste();
+        let history = run.attempt_history().len();
+        assert_ne!(history, 0);
+
+        let report = saver::urn::save_run(&run, Vec::new()).unwrap();
+        assert!(report.game_name);
+        assert_eq!(report.attempt_history, history);
+        assert_ne!(report.segment_history, 0);
+        assert!(report.game_time);
+
+        let report = saver::splitterino::save_run(&run, Vec::new()).unwrap();
+        assert!(!report.game_name);
+        assert!(!report.game_time);
+        assert_eq!(report.attempt_history, history);
+
+        let report = saver::wsplit::save_run(&run, Vec::new()).unwrap();
+        assert!(report.game_name);
+        assert!(report.game_time);
+
+        let report = saver::llanfair_gered::save_run(&run, Vec::new()).unwrap();
+        assert!(!report.game_name);
+        assert!(report.game_time);
+        assert!(!report.is_lossless());
+    }
+
+    fn history_run() -> Run {
+        let mut run = Run::new();
+        run.set_game_name("Game");
+        run.push_segment(Segment::new("Level 1"));
+        run.push_segment(Segment::new("Boss, \"Final\""));
+
+        let time = |seconds| Time::new().with_real_time(Some(TimeSpan::from_seconds(seconds)));
+        run.add_attempt(time(30.5), None, None, Some(TimeSpan::from_seconds(2.0)));
+        run.add_attempt(Time::new(), None, None, None);
+        run.segments_mut()[0]
+            .segment_history_mut()
+            .insert(1, time(10.25));
+        run.segments_mut()[0]
+            .segment_history_mut()
+            .insert(2, time(11.0));
+        run.segments_mut()[1]
+            .segment_history_mut()
+            .insert(1, time(20.25));
+        run
+    }
+
+    #[test]
+    fn history_attempts_csv() {
+        let mut run = history_run();
+        run.set_custom_timing_method_name(TimingMethod::Custom1, "In-Game Time");
+
+        let mut buf = Vec::new();
+        saver::history::save_attempts_csv(&run, &mut buf).unwrap();
+        assert_eq!(
+            String::from_utf8(buf).unwrap(),
+            "Attempt,Started,Started Synced,Ended,Ended Synced,Pause Time,\
+             Real Time,Game Time,Custom Timing Method 1,Custom Timing Method 2,\
+             Custom Timing Method 3,Custom Timing Method 4\r\n\
+             1,,,,,2,30.5,,,,,\r\n\
+             2,,,,,,,,,,,\r\n",
+        );
+    }
+
+    #[test]
+    fn history_segment_history_csv() {
+        let mut buf = Vec::new();
+        saver::history::save_segment_history_csv(&history_run(), TimingMethod::RealTime, &mut buf)
+            .unwrap();
+        assert_eq!(
+            String::from_utf8(buf).unwrap(),
+            "Segment,1,2\r\n\
+             Level 1,10.25,11\r\n\
+             \"Boss, \"\"Final\"\"\",20.25,\r\n",
+        );
+    }
+
+    fn history_json_times(real_time: &str) -> String {
+        format!(
+            r#"{{"real_time":{},"game_time":null,"custom":{{"Custom1":null,"Custom2":null,"Custom3":null,"Custom4":null}}}}"#,
+            real_time,
+        )
+    }
+
+    #[test]
+    fn history_json() {
+        let mut run = history_run();
+        run.set_custom_timing_method_name(TimingMethod::Custom1, "In-Game Time");
+
+        let mut buf = Vec::new();
+        saver::history::save_json(&run, &mut buf).unwrap();
+        let empty = history_json_times("null");
+        assert_eq!(
+            String::from_utf8(buf).unwrap(),
+            r#"{"version":1,"game":"Game","category":"","#.to_owned()
+                + r#""custom_timing_methods":[{"id":"Custom1","name":"In-Game Time"},"#
+                + r#"{"id":"Custom2","name":null},{"id":"Custom3","name":null},"#
+                + r#"{"id":"Custom4","name":null}],"#
+                + r#""attempts":[{"index":1,"started":null,"ended":null,"pause_time":2.0,"#
+                + &format!(r#""times":{}}},"#, history_json_times("30.5"))
+                + r#"{"index":2,"started":null,"ended":null,"pause_time":null,"#
+                + &format!(r#""times":{}}}],"#, empty)
+                + r#""segments":[{"name":"Level 1","#
+                + &format!(r#""personal_best_split_time":{},"#, empty)
+                + &format!(r#""best_segment_time":{},"#, empty)
+                + &format!(
+                    r#""history":[{{"attempt":1,"times":{}}},"#,
+                    history_json_times("10.25")
+                )
+                + &format!(
+                    r#"{{"attempt":2,"times":{}}}]}},"#,
+                    history_json_times("11.0")
+                )
+                + r#"{"name":"Boss, \"Final\"","#
+                + &format!(r#""personal_best_split_time":{},"#, empty)
+                + &format!(r#""best_segment_time":{},"#, empty)
+                + &format!(
+                    r#""history":[{{"attempt":1,"times":{}}}]}}]}}"#,
+                    history_json_times("20.25")
+                ),
+        );
+    }
+
+    #[test]
+    fn history_json_contains_dates() {
+        let mut buf = Vec::new();
+        saver::history::save_json(&celeste(), &mut buf).unwrap();
+        let json = String::from_utf8(buf).unwrap();
+        assert!(json.contains(r#""started":{"time":""#));
+        assert!(json.contains(r#""synced_with_atomic_clock":"#));
+    }
+}
@@ -0,0 +1,111 @@
+#![cfg(feature = "svg-rendering")]
+
+mod layout_files;
+#[path = "../src/tests_helper.rs"]
+mod tests_helper;
+
+use image::{codecs::png, ColorType};
+use livesplit_core::{
+    layout::{self, Layout, LayoutState},
+    rendering::svg::Renderer,
+    Run, Segment, TimeSpan, Timer, TimingMethod,
+};
+use std::{env, fs, io::Cursor};
+
+/// Set this environment variable to overwrite the expected documents with the
+/// documents that are currently being rendered.
+const UPDATE_VAR: &str = "LIVESPLIT_UPDATE_SVG";
+
+fn lsl(data: &[u8]) -> Layout {
+    layout::parser::parse(Cursor::new(data)).unwrap()
+}
+
+fn icon() -> Vec<u8> {
+    let pixels = [
+        255, 0, 0, 255, 0, 255, 0, 255, //
+        0, 0, 255, 255, 255, 255, 255, 0,
+    ];
+    let mut data = Vec::new();
+    png::PngEncoder::new(&mut data)
+        .encode(&pixels, 2, 2, ColorType::Rgba8)
+        .unwrap();
+    data
+}
+
+#[test]
+fn default() {
+    let mut run = tests_helper::create_run(&["A", "B", "C", "D"]);
+    run.set_game_name("Some Game Name");
+    run.set_category_name("Some Category Name");
+    run.set_game_icon(icon());
+    run.set_attempt_count(1337);
+    let mut timer = Timer::new(run).unwrap();
+    let mut layout = Layout::default_layout();
+
+    tests_helper::start_run(&mut timer);
+    tests_helper::make_progress_run_with_splits_opt(&mut timer, &[Some(5.0), None, Some(10.0)]);
+
+    let state = layout.state(&timer.snapshot());
+    let svg = check(&state, [300.0, 500.0], "default");
+
+    assert!(svg.contains("data:image/png;base64,"));
+}
+
+#[test]
+fn gradients() {
+    let mut timer = tests_helper::create_timer(&["A", "B"]);
+    let mut layout = lsl(layout_files::WITH_TIMER_GRADIENT_BACKGROUND);
+
+    tests_helper::start_run(&mut timer);
+    tests_helper::make_progress_run_with_splits_opt(&mut timer, &[Some(5.0)]);
+
+    let state = layout.state(&timer.snapshot());
+    let svg = check(&state, [300.0, 300.0], "gradients");
+
+    assert!(svg.contains("<linearGradient"));
+}
+
+#[test]
+fn is_deterministic() {
+    let timer = tests_helper::create_timer(&["A", "B"]);
+    let mut layout = Layout::default_layout();
+    let state = layout.state(&timer.snapshot());
+
+    let render = || {
+        let mut svg = String::new();
+        Renderer::new()
+            .render(&mut svg, &state, [300.0, 500.0])
+            .unwrap();
+        svg
+    };
+
+    assert_eq!(render(), render());
+}
+
+#[track_caller]
+fn check(state: &LayoutState, dims: [f32; 2], name: &str) -> String {
+    let mut svg = String::new();
+    Renderer::new().render(&mut svg, state, dims).unwrap();
+
+    let expected_path = format!("tests/svg_files/{}.svg", name);
+    if env::var_os(UPDATE_VAR).is_some() {
+        fs::write(&expected_path, &svg).unwrap();
+    }
+
+    let expected = fs::read_to_string(&expected_path).unwrap_or_default();
+    if svg != expected {
+        fs::create_dir_all("target/renders").ok();
+        let actual_path = format!("target/renders/{}.svg", name);
+        fs::write(&actual_path, &svg).ok();
+
+        panic!(
+            "Render mismatch for {}
+expected: {}
+actual: {}
+Set {} to update the expected document.",
+            name, expected_path, actual_path, UPDATE_VAR,
+        );
+    }
+
+    svg
+}